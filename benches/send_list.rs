@@ -0,0 +1,73 @@
+use std::ffi::CString;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use libpd_rs::{send::send_list_to, types::Atom};
+
+/// A list of 64 atoms which alternates floats and short symbols.
+fn mixed_list() -> Vec<Atom> {
+    (0..64)
+        .map(|index| {
+            if index % 2 == 0 {
+                Atom::from(f64::from(index))
+            } else {
+                Atom::from(format!("symbol_{index}"))
+            }
+        })
+        .collect()
+}
+
+/// Sends a list the way it was sent before, collecting the atoms and their symbols to the heap.
+fn send_list_collected(receiver: &CString, list: &[Atom]) {
+    let symbols = list
+        .iter()
+        .map(|atom| match atom {
+            Atom::Symbol(symbol) => Some(CString::new(symbol.as_str()).unwrap()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let mut atoms = list
+        .iter()
+        .zip(&symbols)
+        .map(|(atom, symbol)| {
+            let mut t_atom = libpd_sys::t_atom {
+                a_type: libpd_sys::t_atomtype_A_FLOAT,
+                a_w: libpd_sys::word { w_float: 0.0 },
+            };
+            unsafe {
+                match (atom, symbol) {
+                    (_, Some(symbol)) => libpd_sys::libpd_set_symbol(&mut t_atom, symbol.as_ptr()),
+                    (atom, None) => {
+                        libpd_sys::libpd_set_double(&mut t_atom, atom.as_float().unwrap())
+                    }
+                }
+            }
+            t_atom
+        })
+        .collect::<Vec<_>>();
+    unsafe {
+        libpd_sys::libpd_list(receiver.as_ptr(), atoms.len() as i32, atoms.as_mut_ptr());
+    }
+}
+
+fn send_list(criterion: &mut Criterion) {
+    libpd_rs::init().unwrap();
+    let patch = libpd_rs::open_patch("tests/patches/echo.pd").unwrap();
+    let list = mixed_list();
+    let receiver = CString::new("list_from_rust").unwrap();
+
+    let mut group = criterion.benchmark_group("send a list of 64 mixed atoms");
+    group.bench_function("send_list_to", |bencher| {
+        bencher.iter(|| send_list_to("list_from_rust", black_box(&list)).unwrap());
+    });
+    group.bench_function("collected to the heap", |bencher| {
+        bencher.iter(|| send_list_collected(&receiver, black_box(&list)));
+    });
+    group.finish();
+
+    libpd_rs::receive::receive_messages_from_pd();
+    patch.close().unwrap();
+}
+
+criterion_group!(benches, send_list);
+criterion_main!(benches);
@@ -0,0 +1,104 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use libpd_rs::{
+    send::{send_float_to, send_float_to_c_str, send_list_to, send_message_to, send_symbol_to},
+    symbol::intern,
+    types::Atom,
+};
+
+fn send_float(criterion: &mut Criterion) {
+    libpd_rs::init().unwrap();
+    let patch = libpd_rs::open_patch("tests/patches/echo.pd").unwrap();
+    let receiver = intern("float_from_rust").unwrap();
+
+    let mut group = criterion.benchmark_group("send a float to the same receiver");
+    group.bench_function("named by a &str", |bencher| {
+        bencher.iter(|| send_float_to(black_box("float_from_rust"), 1.0).unwrap());
+    });
+    group.bench_function("named by a C string", |bencher| {
+        bencher.iter(|| send_float_to_c_str(black_box(c"float_from_rust"), 1.0).unwrap());
+    });
+    group.bench_function("interned", |bencher| {
+        bencher.iter(|| send_float_to(black_box(&receiver), 1.0).unwrap());
+    });
+    group.finish();
+
+    libpd_rs::receive::receive_messages_from_pd();
+    patch.close().unwrap();
+}
+
+fn send_symbol(criterion: &mut Criterion) {
+    // Pd is initialized by the first benchmark already.
+    let _ = libpd_rs::init();
+    let patch = libpd_rs::open_patch("tests/patches/echo.pd").unwrap();
+    let receiver = intern("symbol_from_rust").unwrap();
+    let value = intern("saw").unwrap();
+
+    let mut group = criterion.benchmark_group("send the same symbol");
+    group.bench_function("named by a &str", |bencher| {
+        bencher.iter(|| send_symbol_to(&receiver, black_box("saw")).unwrap());
+    });
+    group.bench_function("interned", |bencher| {
+        bencher.iter(|| send_symbol_to(&receiver, black_box(&value)).unwrap());
+    });
+    group.finish();
+
+    libpd_rs::receive::receive_messages_from_pd();
+    patch.close().unwrap();
+}
+
+fn send_symbols_in_a_list(criterion: &mut Criterion) {
+    // Pd is initialized by the first benchmark already.
+    let _ = libpd_rs::init();
+    let patch = libpd_rs::open_patch("tests/patches/echo.pd").unwrap();
+    let receiver = intern("list_from_rust").unwrap();
+    let named = [Atom::from("saw"), Atom::from("square"), Atom::from(1)];
+    let interned = [
+        Atom::from(intern("saw").unwrap()),
+        Atom::from(intern("square").unwrap()),
+        Atom::from(1),
+    ];
+
+    let mut group = criterion.benchmark_group("send a list of the same symbols");
+    group.bench_function("named by strings", |bencher| {
+        bencher.iter(|| send_list_to(&receiver, black_box(&named)).unwrap());
+    });
+    group.bench_function("interned", |bencher| {
+        bencher.iter(|| send_list_to(&receiver, black_box(&interned)).unwrap());
+    });
+    group.finish();
+
+    libpd_rs::receive::receive_messages_from_pd();
+    patch.close().unwrap();
+}
+
+fn send_message(criterion: &mut Criterion) {
+    // Pd is initialized by the first benchmark already.
+    let _ = libpd_rs::init();
+    let patch = libpd_rs::open_patch("tests/patches/echo.pd").unwrap();
+    let receiver = intern("list_from_rust").unwrap();
+    let selector = intern("set").unwrap();
+    let arguments = [Atom::from(1)];
+
+    let mut group = criterion.benchmark_group("send a message with the same selector");
+    group.bench_function("named by a &str", |bencher| {
+        bencher.iter(|| send_message_to(&receiver, black_box("set"), &arguments).unwrap());
+    });
+    group.bench_function("interned", |bencher| {
+        bencher.iter(|| send_message_to(&receiver, black_box(&selector), &arguments).unwrap());
+    });
+    group.finish();
+
+    libpd_rs::receive::receive_messages_from_pd();
+    patch.close().unwrap();
+}
+
+criterion_group!(
+    benches,
+    send_float,
+    send_symbol,
+    send_symbols_in_a_list,
+    send_message
+);
+criterion_main!(benches);
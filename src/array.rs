@@ -1,12 +1,16 @@
 use crate::{
-    error::{ArrayError, SizeError},
-    C_STRING_FAILURE,
+    error::{ArrayError, SizeError, SoundfileError},
+    helpers::{return_if_not_initialized, to_c_string},
+    types::{Atom, Patch},
 };
 
-use std::ffi::CString;
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
 
 /// Gets the size of an array by its name from the pd patch which is loaded.
 ///
+/// Use [`array_exists`] to only check if the array is there.
+///
 /// # Example
 /// ```no_run
 /// use libpd_rs::array::array_size;
@@ -14,35 +18,149 @@ use std::ffi::CString;
 /// let size = array_size("my_array").unwrap();
 /// ```
 ///
+/// A missing array could be told apart from other failures:
+/// ```no_run
+/// use libpd_rs::{array::array_size, error::SizeError};
+///
+/// match array_size("maybe_array") {
+///     Ok(size) => println!("The array has {size} elements"),
+///     Err(SizeError::NonExistent(name)) => println!("The patch has no array named {name}"),
+///     Err(err) => eprintln!("{err}"),
+/// }
+/// ```
+///
 /// # Errors
 ///
 /// A list of errors that can occur:
-/// - [`CouldNotDetermine`](crate::error::SizeError::CouldNotDetermine)
-pub fn array_size<T: AsRef<str>>(name: T) -> Result<i32, SizeError> {
+/// - [`NonExistent`](crate::error::SizeError::NonExistent)
+/// - [`Unexpected`](crate::error::SizeError::Unexpected)
+/// - [`InvalidString`](crate::error::SizeError::InvalidString)
+/// - [`NotInitialized`](crate::error::SizeError::NotInitialized)
+pub fn array_size<T: AsRef<str>>(name: T) -> Result<usize, SizeError> {
+    return_if_not_initialized!(SizeError::NotInitialized);
     unsafe {
-        let name = CString::new(name.as_ref()).expect(C_STRING_FAILURE);
+        let c_name = to_c_string(name.as_ref()).map_err(|input| SizeError::InvalidString {
+            context: "array name",
+            input,
+        })?;
         // Returns size or negative error code if non-existent
-        let result = libpd_sys::libpd_arraysize(name.as_ptr());
-        if result >= 0 {
-            return Ok(result);
-        }
-        Err(SizeError::CouldNotDetermine)
+        let result = libpd_sys::libpd_arraysize(c_name.as_ptr());
+        usize::try_from(result).map_err(|_| SizeError::from_code(result, name))
+    }
+}
+
+/// Checks if an array with the name exists in the pd patches which are loaded.
+///
+/// Unlike [`array_size`] it returns `false` instead of an error for a missing array,
+/// also when pd is not initialized.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::array::{array_exists, read_float_array_from};
+///
+/// if array_exists("my_array") {
+///     let mut samples = vec![0.0; 64];
+///     read_float_array_from("my_array", 0, 64, &mut samples).unwrap();
+/// }
+/// ```
+#[must_use]
+pub fn array_exists<T: AsRef<str>>(name: T) -> bool {
+    array_size(name).is_ok()
+}
+
+/// Loads a sound file to an array, resizing the array to the length of the file.
+///
+/// Pd loads sound files with a `[soundfiler]` object, so the patch needs one which receives from
+/// a receiver local to the patch named `soundfiler`:
+///
+/// ```text
+/// [r $0-soundfiler]
+/// |
+/// [soundfiler]
+/// ```
+///
+/// The function sends `read -resize <file> <array>` to it. The file path is made absolute first,
+/// so relative paths are relative to the working directory of the program rather than the directory of the patch.
+/// The file formats which pd reads are supported, e.g. WAV, AIFF and CAF.
+///
+/// Pd reads the file while it handles the message, so the array is filled when the function returns.
+/// Returns the size of the array afterwards, which is the number of frames in the file.
+/// The errors which pd prints while it reads the file tell if it fails to,
+/// they are still received with [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd) like every printed line.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::{array::load_soundfile_to_array, open_patch};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let patch = open_patch("sampler.pd").unwrap();
+/// let frames = load_soundfile_to_array(&patch, "samples/kick.wav", "kick").unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`FileNotFound`](crate::error::SoundfileError::FileNotFound)
+/// - [`MissingArray`](crate::error::SoundfileError::MissingArray)
+/// - [`Send`](crate::error::SoundfileError::Send)
+/// - [`FailedToRead`](crate::error::SoundfileError::FailedToRead)
+/// - [`NotInitialized`](crate::error::SoundfileError::NotInitialized)
+pub fn load_soundfile_to_array<P: AsRef<Path>, A: AsRef<str>>(
+    patch: &Patch,
+    file: P,
+    array: A,
+) -> Result<usize, SoundfileError> {
+    return_if_not_initialized!(SoundfileError::NotInitialized);
+    let file = std::fs::canonicalize(file.as_ref())
+        .map(crate::without_verbatim_prefix)
+        .map_err(|_| SoundfileError::FileNotFound(file.as_ref().to_string_lossy().into_owned()))?;
+    let array = array.as_ref();
+    if !array_exists(array) {
+        return Err(SoundfileError::MissingArray(array.to_owned()));
+    }
+    let (sent, printed) = crate::queue::capture_print(|| {
+        patch.send_message_to_local(
+            "soundfiler",
+            "read",
+            &[
+                Atom::from("-resize"),
+                Atom::from(file.to_string_lossy().as_ref()),
+                Atom::from(array),
+            ],
+        )
+    });
+    sent.map_err(SoundfileError::Send)?;
+    // The array is still filled when the file is only truncated to the maximum size.
+    if printed
+        .lines()
+        .any(|line| line.starts_with("error:") && !line.contains("truncated to"))
+    {
+        return Err(SoundfileError::FailedToRead {
+            path: file.to_string_lossy().into_owned(),
+            console_output: printed.lines().map(str::to_owned).collect(),
+        });
     }
+    array_size(array).map_err(|_| SoundfileError::MissingArray(array.to_owned()))
 }
 
 /// Resizes an array found by its name from the pd patch which is loaded.
 ///
-/// Sizes `<= 0` or `> size limit` are clipped to `1`
+/// Sizes `<= 0` or `> size limit` are clipped to `1` by pd,
+/// the array is resized then and a [`Clipped`](crate::error::SizeError::Clipped) error tells the size which it has.
 ///
 /// # Example
 /// ```no_run
-/// use libpd_rs::array::{array_size, resize_array};
+/// use libpd_rs::{array::{array_size, resize_array}, error::SizeError};
 ///
 /// resize_array("my_array", 1024).unwrap();
 /// let size = array_size("my_array").unwrap();
 /// assert_eq!(size, 1024);
 ///
-/// resize_array("my_array", 0).unwrap();
+/// assert!(matches!(
+///     resize_array("my_array", 0),
+///     Err(SizeError::Clipped { requested: 0, actual: 1, .. })
+/// ));
 /// let size = array_size("my_array").unwrap();
 /// assert_eq!(size, 1);
 /// ```
@@ -50,8 +168,13 @@ pub fn array_size<T: AsRef<str>>(name: T) -> Result<i32, SizeError> {
 /// # Errors
 ///
 /// A list of errors that can occur:
-/// - [`CouldNotDetermine`](crate::error::SizeError::CouldNotDetermine)
+/// - [`Clipped`](crate::error::SizeError::Clipped)
+/// - [`NonExistent`](crate::error::SizeError::NonExistent)
+/// - [`Unexpected`](crate::error::SizeError::Unexpected)
+/// - [`InvalidString`](crate::error::SizeError::InvalidString)
+/// - [`NotInitialized`](crate::error::SizeError::NotInitialized)
 pub fn resize_array<T: AsRef<str>>(name: T, size: i32) -> Result<(), SizeError> {
+    return_if_not_initialized!(SizeError::NotInitialized);
     // The size argument is a `long` but bindgen interprets it as i64
     //
     // Also libpd has this,
@@ -73,26 +196,43 @@ pub fn resize_array<T: AsRef<str>>(name: T, size: i32) -> Result<(), SizeError>
     //
     // TODO: Find the right approach here. Low-priority
     unsafe {
-        let name = CString::new(name.as_ref()).expect(C_STRING_FAILURE);
+        let c_name = to_c_string(name.as_ref()).map_err(|input| SizeError::InvalidString {
+            context: "array name",
+            input,
+        })?;
         // returns 0 on success or negative error code if non-existent
         #[cfg(target_os = "macos")]
-        match libpd_sys::libpd_resize_array(name.as_ptr(), i64::from(size)) {
-            0 => Ok(()),
-            _ => Err(SizeError::CouldNotDetermine),
+        match libpd_sys::libpd_resize_array(c_name.as_ptr(), i64::from(size)) {
+            0 => check_resized(name.as_ref(), size),
+            code => Err(SizeError::from_code(code, &name)),
         }
         #[cfg(target_os = "linux")]
-        match libpd_sys::libpd_resize_array(name.as_ptr(), i64::from(size)) {
-            0 => Ok(()),
-            _ => Err(SizeError::CouldNotDetermine),
+        match libpd_sys::libpd_resize_array(c_name.as_ptr(), i64::from(size)) {
+            0 => check_resized(name.as_ref(), size),
+            code => Err(SizeError::from_code(code, &name)),
         }
         #[cfg(target_os = "windows")]
-        match libpd_sys::libpd_resize_array(name.as_ptr(), size) {
-            0 => Ok(()),
-            _ => Err(SizeError::CouldNotDetermine),
+        match libpd_sys::libpd_resize_array(c_name.as_ptr(), size) {
+            0 => check_resized(name.as_ref(), size),
+            code => Err(SizeError::from_code(code, &name)),
         }
     }
 }
 
+/// Checks if pd resized the array to the requested size, it clips sizes which are not positive or too large.
+fn check_resized(array: &str, requested: i32) -> Result<(), SizeError> {
+    let actual = array_size(array)?;
+    if usize::try_from(requested).is_ok_and(|requested| requested == actual) {
+        Ok(())
+    } else {
+        Err(SizeError::Clipped {
+            array: array.to_owned(),
+            requested,
+            actual,
+        })
+    }
+}
+
 /// Reads a named array from pd to a mutable slice of `f32`.
 ///
 /// Reads values as much as `source_read_amount` from the array which is specified with the `source_name` argument
@@ -106,31 +246,37 @@ pub fn resize_array<T: AsRef<str>>(name: T, size: i32) -> Result<(), SizeError>
 /// read_float_array_from("my_array", 32, 32, &mut destination).unwrap();
 /// ```
 /// # Errors
-/// This function performs no bounds checking on the destination.
-///
-/// If `destination_offset` + `read_amount` is greater than the size of the `destination` or
+/// If `source_read_offset` + `source_read_amount` is greater than the size of the array,
+/// `source_read_amount` is greater than the length of `destination` or
 /// the array which we're trying to read from is not existent it will return an error.
 ///
 /// A list of errors that can occur:
 /// - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds)
+/// - [`NegativeOffset`](crate::error::ArrayError::NegativeOffset)
+/// - [`NegativeLength`](crate::error::ArrayError::NegativeLength)
+/// - [`DestinationTooSmall`](crate::error::ArrayError::DestinationTooSmall)
 /// - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+/// - [`Unexpected`](crate::error::ArrayError::Unexpected)
+/// - [`InvalidString`](crate::error::ArrayError::InvalidString)
+/// - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
 pub fn read_float_array_from<T: AsRef<str>>(
     source_name: T,
     source_read_offset: i32,
     source_read_amount: i32,
     destination: &mut [f32],
 ) -> Result<(), ArrayError> {
+    return_if_not_initialized!(ArrayError::NotInitialized);
     unsafe {
-        let name = CString::new(source_name.as_ref()).expect(C_STRING_FAILURE);
+        let name =
+            to_c_string(source_name.as_ref()).map_err(|input| ArrayError::InvalidString {
+                context: "array name",
+                input,
+            })?;
         // Returns 0 on success or a negative error code if the array is non-existent
         // or offset + n exceeds range of array
 
-        if source_read_offset + source_read_amount
-            > array_size(source_name.as_ref()).map_err(|_| ArrayError::FailedToFindArray)?
-            || source_read_amount < 0
-        {
-            return Err(ArrayError::OutOfBounds);
-        }
+        let size = check_bounds(source_name.as_ref(), source_read_offset, source_read_amount)?;
+        fits_in_destination(source_read_amount, destination.len())?;
 
         match libpd_sys::libpd_read_array(
             destination.as_mut_ptr(),
@@ -139,16 +285,149 @@ pub fn read_float_array_from<T: AsRef<str>>(
             source_read_amount,
         ) {
             0 => Ok(()),
-            _ => Err(ArrayError::FailedToFindArray),
+            code => Err(ArrayError::from_code(
+                code,
+                source_name,
+                source_read_offset,
+                source_read_amount,
+                size,
+            )),
         }
     }
 }
 
+/// Reads from a pd named array like [`read_float_array_from`] and returns the number of values which are read.
+///
+/// Reading near the end of the array reads the values up to its end instead of failing,
+/// so the count is `source_read_amount` clamped to the number of values which the array has from `source_read_offset`,
+/// `0` if the offset is at or past its end.
+/// The values of `destination` after the count are left as they are, they are not read from the array.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::array::{read_float_array_from_clamped, resize_array};
+///
+/// resize_array("my_array", 100).unwrap();
+/// let mut destination = [0.0_f32; 64];
+/// let read = read_float_array_from_clamped("my_array", 64, 64, &mut destination).unwrap();
+/// assert_eq!(read, 36);
+/// // The tail is not from the array.
+/// destination[read..].fill(0.0);
+/// ```
+/// # Errors
+/// If `source_read_offset` or `source_read_amount` is negative, `source_read_amount` is greater than the length of `destination` or
+/// the array which we're trying to read from is not existent it will return an error.
+///
+/// A list of errors that can occur:
+/// - [`NegativeOffset`](crate::error::ArrayError::NegativeOffset)
+/// - [`NegativeLength`](crate::error::ArrayError::NegativeLength)
+/// - [`DestinationTooSmall`](crate::error::ArrayError::DestinationTooSmall)
+/// - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds) if the array shrinks while it is read
+/// - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+/// - [`Unexpected`](crate::error::ArrayError::Unexpected)
+/// - [`InvalidString`](crate::error::ArrayError::InvalidString)
+/// - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
+pub fn read_float_array_from_clamped<T: AsRef<str>>(
+    source_name: T,
+    source_read_offset: i32,
+    source_read_amount: i32,
+    destination: &mut [f32],
+) -> Result<usize, ArrayError> {
+    return_if_not_initialized!(ArrayError::NotInitialized);
+    let name = source_name.as_ref();
+    if source_read_offset < 0 {
+        return Err(ArrayError::NegativeOffset(source_read_offset));
+    }
+    if source_read_amount < 0 {
+        return Err(ArrayError::NegativeLength(source_read_amount));
+    }
+    fits_in_destination(source_read_amount, destination.len())?;
+    let size = array_size(name).map_err(|error| match error {
+        SizeError::InvalidString { context, input } => ArrayError::InvalidString { context, input },
+        _ => ArrayError::FailedToFindArray(name.to_owned()),
+    })?;
+    // The offset and the amount are checked to be positive.
+    let offset = usize::try_from(source_read_offset).unwrap_or_default();
+    let amount = usize::try_from(source_read_amount).unwrap_or_default();
+    let count = amount.min(size.saturating_sub(offset));
+    if count == 0 {
+        return Ok(0);
+    }
+    // The count is at most the amount, so it fits in an `i32`.
+    let count_to_read = i32::try_from(count).unwrap_or(source_read_amount);
+    read_float_array_from(name, source_read_offset, count_to_read, destination)?;
+    Ok(count)
+}
+
+/// Reads a named array from pd into the producer of a [rtrb](https://crates.io/crates/rtrb) ring buffer.
+///
+/// Reads the array from its start and pushes as many of its values as there are free slots in the ring buffer,
+/// a full ring buffer takes nothing. Returns the number of values which are pushed.
+/// The values are read straight into the slots, they are visible to the consumer once all of them are read.
+///
+/// This lets a consumer on another thread, like the scope of a gui, show a pd table without locking.
+///
+/// This function is only available with the `rtrb` feature.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::array::read_float_array_into_producer;
+///
+/// let (mut producer, mut consumer) = rtrb::RingBuffer::<f32>::new(1024);
+/// let pushed = read_float_array_into_producer("scope", &mut producer).unwrap();
+/// assert_eq!(consumer.slots(), pushed);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds) if the array shrinks while it is read
+/// - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+/// - [`Unexpected`](crate::error::ArrayError::Unexpected)
+/// - [`InvalidString`](crate::error::ArrayError::InvalidString)
+/// - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
+#[cfg(feature = "rtrb")]
+pub fn read_float_array_into_producer<T: AsRef<str>>(
+    source_name: T,
+    producer: &mut rtrb::Producer<f32>,
+) -> Result<usize, ArrayError> {
+    return_if_not_initialized!(ArrayError::NotInitialized);
+    let name = source_name.as_ref();
+    let size = array_size(name).map_err(|error| match error {
+        SizeError::InvalidString { context, input } => ArrayError::InvalidString { context, input },
+        _ => ArrayError::FailedToFindArray(name.to_owned()),
+    })?;
+    let amount = size.min(producer.slots());
+    let Ok(mut chunk) = producer.write_chunk(amount) else {
+        return Ok(0);
+    };
+    // The slots could wrap around the end of the ring buffer.
+    let (first, second) = chunk.as_mut_slices();
+    let read_to_slots = |offset: usize, slots: &mut [f32]| {
+        if slots.is_empty() {
+            return Ok(());
+        }
+        // This is fine since pd arrays are indexed with `int`s, the sizes fit in an `i32`.
+        #[allow(clippy::cast_possible_wrap)]
+        #[allow(clippy::cast_possible_truncation)]
+        read_float_array_from(name, offset as i32, slots.len() as i32, slots)
+    };
+    let first_length = first.len();
+    read_to_slots(0, first)?;
+    read_to_slots(first_length, second)?;
+    chunk.commit_all();
+    Ok(amount)
+}
+
 /// Writes a slice of `f32` to a pd named array.
 ///
 /// Reads values as much as `read_amount` from the array which is given as the `source` argument
 /// and writes them to a named array in pd which is specified with `destination_name` argument starting at `destination_write_offset`.
 ///
+/// A single write never happens in the middle of an audio block, libpd locks for it.
+/// Updating an array which is played with more than one write or replacing a wavetable while it is played could still be heard,
+/// see [`WavetableSwapper`] for writing to a second array and switching to it instead.
+///
 /// # Example
 /// ```no_run
 /// use libpd_rs::array::write_float_array_to;
@@ -164,26 +443,42 @@ pub fn read_float_array_from<T: AsRef<str>>(
 ///
 /// A list of errors that can occur:
 /// - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds)
+/// - [`NegativeOffset`](crate::error::ArrayError::NegativeOffset)
+/// - [`NegativeLength`](crate::error::ArrayError::NegativeLength)
+/// - [`SourceRangeOutOfBounds`](crate::error::ArrayError::SourceRangeOutOfBounds)
 /// - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+/// - [`Unexpected`](crate::error::ArrayError::Unexpected)
+/// - [`InvalidString`](crate::error::ArrayError::InvalidString)
+/// - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
 pub fn write_float_array_to<T: AsRef<str>>(
     destination_name: T,
     destination_write_offset: i32,
     source: &[f32],
     source_read_amount: i32,
 ) -> Result<(), ArrayError> {
+    return_if_not_initialized!(ArrayError::NotInitialized);
     unsafe {
-        let name = CString::new(destination_name.as_ref()).expect(C_STRING_FAILURE);
+        let name =
+            to_c_string(destination_name.as_ref()).map_err(|input| ArrayError::InvalidString {
+                context: "array name",
+                input,
+            })?;
         // Returns 0 on success or a negative error code if the array is non-existent
         // or offset + n exceeds range of array
 
-        // We check this manually in the predicate.
+        let size = check_bounds(
+            destination_name.as_ref(),
+            destination_write_offset,
+            source_read_amount,
+        )?;
+        // The amount is checked to be positive.
         #[allow(clippy::cast_sign_loss)]
-        if destination_write_offset + source_read_amount
-            > array_size(destination_name.as_ref()).map_err(|_| ArrayError::FailedToFindArray)?
-            || source_read_amount < 0
-            || source_read_amount as usize > source.len()
-        {
-            return Err(ArrayError::OutOfBounds);
+        if source_read_amount as usize > source.len() {
+            return Err(ArrayError::SourceRangeOutOfBounds {
+                start: 0,
+                end: source_read_amount as usize,
+                length: source.len(),
+            });
         }
 
         match libpd_sys::libpd_write_array(
@@ -193,11 +488,158 @@ pub fn write_float_array_to<T: AsRef<str>>(
             source_read_amount,
         ) {
             0 => Ok(()),
-            _ => Err(ArrayError::FailedToFindArray),
+            code => Err(ArrayError::from_code(
+                code,
+                destination_name,
+                destination_write_offset,
+                source_read_amount,
+                size,
+            )),
         }
     }
 }
 
+/// Writes a slice of `f32` to a pd named array and grows the array first if the values do not fit in it.
+///
+/// Works like [`write_float_array_to`] but resizes the array to `destination_write_offset` + `source_read_amount`
+/// elements when it is smaller than that, an array which is large enough is never shrunk.
+/// Returns the size of the array after writing and whether it is resized.
+///
+/// Resizing an array reallocates it, so it is better not to do it while the array is played.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::array::{array_size, write_float_array_to_resizing};
+///
+/// let source = [1.0_f32; 256];
+/// let (size, _resized) = write_float_array_to_resizing("my_array", 0, &source, 256).unwrap();
+/// assert_eq!(size, array_size("my_array").unwrap());
+/// assert!(size >= 256);
+/// ```
+/// # Errors
+///
+/// If `destination_write_offset` or `source_read_amount` is negative, `source_read_amount` is greater than the length of `source`,
+/// the array which we're trying to write to is not existent or it can not be resized to fit the values it will return an error.
+/// Nothing is resized or written then.
+///
+/// A list of errors that can occur:
+/// - [`Resize`](crate::error::ArrayError::Resize)
+/// - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds)
+/// - [`NegativeOffset`](crate::error::ArrayError::NegativeOffset)
+/// - [`NegativeLength`](crate::error::ArrayError::NegativeLength)
+/// - [`SourceRangeOutOfBounds`](crate::error::ArrayError::SourceRangeOutOfBounds)
+/// - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+/// - [`Unexpected`](crate::error::ArrayError::Unexpected)
+/// - [`InvalidString`](crate::error::ArrayError::InvalidString)
+/// - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
+pub fn write_float_array_to_resizing<T: AsRef<str>>(
+    destination_name: T,
+    destination_write_offset: i32,
+    source: &[f32],
+    source_read_amount: i32,
+) -> Result<(usize, bool), ArrayError> {
+    return_if_not_initialized!(ArrayError::NotInitialized);
+    let name = destination_name.as_ref();
+    if destination_write_offset < 0 {
+        return Err(ArrayError::NegativeOffset(destination_write_offset));
+    }
+    if source_read_amount < 0 {
+        return Err(ArrayError::NegativeLength(source_read_amount));
+    }
+    // The amount is checked to be positive.
+    let amount = usize::try_from(source_read_amount).unwrap_or_default();
+    if amount > source.len() {
+        return Err(ArrayError::SourceRangeOutOfBounds {
+            start: 0,
+            end: amount,
+            length: source.len(),
+        });
+    }
+    let size = array_size(name).map_err(|error| match error {
+        SizeError::InvalidString { context, input } => ArrayError::InvalidString { context, input },
+        _ => ArrayError::FailedToFindArray(name.to_owned()),
+    })?;
+    // Pd arrays are indexed with `int`s, so an array can not grow past `i32::MAX` elements.
+    let Some(end) = destination_write_offset.checked_add(source_read_amount) else {
+        return Err(ArrayError::OutOfBounds {
+            array: name.to_owned(),
+            offset: destination_write_offset,
+            requested: source_read_amount,
+            array_len: size,
+        });
+    };
+    // The end is checked to be positive.
+    let needed = usize::try_from(end).unwrap_or_default();
+    let resized = needed > size;
+    if resized {
+        resize_array(name, end).map_err(ArrayError::Resize)?;
+    }
+    write_float_array_to(name, destination_write_offset, source, source_read_amount)?;
+    Ok((size.max(needed), resized))
+}
+
+/// Writes a range of a slice of `f32` to a pd named array.
+///
+/// Writes the values of `source` which are in `source_range` to a named array in pd which is specified with
+/// `destination_name` argument starting at `destination_write_offset`.
+/// This saves sub-slicing a larger buffer manually before calling [`write_float_array_to`].
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::array::write_float_array_range;
+///
+/// let source = [1.0_f32; 64];
+/// // Writes the second half of the source to the start of the array.
+/// write_float_array_range("my_array", 0, &source, 32..).unwrap();
+/// ```
+/// # Errors
+///
+/// If `source_range` does not fit in `source`, `destination_write_offset` is negative,
+/// `destination_write_offset` + the length of the range is greater than the size of the array or
+/// the array which we're trying to write to is not existent it will return an error.
+///
+/// A list of errors that can occur:
+/// - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds)
+/// - [`NegativeOffset`](crate::error::ArrayError::NegativeOffset)
+/// - [`SourceRangeOutOfBounds`](crate::error::ArrayError::SourceRangeOutOfBounds)
+/// - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+/// - [`Unexpected`](crate::error::ArrayError::Unexpected)
+/// - [`InvalidString`](crate::error::ArrayError::InvalidString)
+/// - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
+pub fn write_float_array_range<T: AsRef<str>, R: RangeBounds<usize>>(
+    destination_name: T,
+    destination_write_offset: i32,
+    source: &[f32],
+    source_range: R,
+) -> Result<(), ArrayError> {
+    // A range which ends after `usize::MAX` does not fit in the source anyway.
+    let start = match source_range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match source_range.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => source.len(),
+    };
+    let source = source
+        .get(start..end)
+        .ok_or(ArrayError::SourceRangeOutOfBounds {
+            start,
+            end,
+            length: source.len(),
+        })?;
+    // An array has less than `i32::MAX` elements, so a longer range is out of its bounds anyway.
+    let source_read_amount = i32::try_from(source.len()).unwrap_or(i32::MAX);
+    write_float_array_to(
+        destination_name,
+        destination_write_offset,
+        source,
+        source_read_amount,
+    )
+}
+
 /// Reads a named array from pd to a mutable slice of `f64`.
 ///
 /// Reads values as much as `source_read_amount` from the array which is specified with the `source_name` argument
@@ -211,31 +653,37 @@ pub fn write_float_array_to<T: AsRef<str>>(
 /// read_double_array_from("my_array", 32, 32, &mut destination).unwrap();
 /// ```
 /// # Errors
-/// This function performs no bounds checking on the destination.
-///
-/// If `destination_offset` + `read_amount` is greater than the size of the `destination` or
+/// If `source_read_offset` + `source_read_amount` is greater than the size of the array,
+/// `source_read_amount` is greater than the length of `destination` or
 /// the array which we're trying to read from is not existent it will return an error.
 ///
 /// A list of errors that can occur:
 /// - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds)
+/// - [`NegativeOffset`](crate::error::ArrayError::NegativeOffset)
+/// - [`NegativeLength`](crate::error::ArrayError::NegativeLength)
+/// - [`DestinationTooSmall`](crate::error::ArrayError::DestinationTooSmall)
 /// - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+/// - [`Unexpected`](crate::error::ArrayError::Unexpected)
+/// - [`InvalidString`](crate::error::ArrayError::InvalidString)
+/// - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
 pub fn read_double_array_from<T: AsRef<str>>(
     source_name: T,
     source_read_offset: i32,
     source_read_amount: i32,
     destination: &mut [f64],
 ) -> Result<(), ArrayError> {
+    return_if_not_initialized!(ArrayError::NotInitialized);
     unsafe {
-        let name = CString::new(source_name.as_ref()).expect(C_STRING_FAILURE);
+        let name =
+            to_c_string(source_name.as_ref()).map_err(|input| ArrayError::InvalidString {
+                context: "array name",
+                input,
+            })?;
         // Returns 0 on success or a negative error code if the array is non-existent
         // or offset + n exceeds range of array
 
-        if source_read_offset + source_read_amount
-            > array_size(source_name.as_ref()).map_err(|_| ArrayError::FailedToFindArray)?
-            || source_read_amount < 0
-        {
-            return Err(ArrayError::OutOfBounds);
-        }
+        let size = check_bounds(source_name.as_ref(), source_read_offset, source_read_amount)?;
+        fits_in_destination(source_read_amount, destination.len())?;
 
         match libpd_sys::libpd_read_array_double(
             destination.as_mut_ptr(),
@@ -244,7 +692,13 @@ pub fn read_double_array_from<T: AsRef<str>>(
             source_read_amount,
         ) {
             0 => Ok(()),
-            _ => Err(ArrayError::FailedToFindArray),
+            code => Err(ArrayError::from_code(
+                code,
+                source_name,
+                source_read_offset,
+                source_read_amount,
+                size,
+            )),
         }
     }
 }
@@ -269,26 +723,42 @@ pub fn read_double_array_from<T: AsRef<str>>(
 ///
 /// A list of errors that can occur:
 /// - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds)
+/// - [`NegativeOffset`](crate::error::ArrayError::NegativeOffset)
+/// - [`NegativeLength`](crate::error::ArrayError::NegativeLength)
+/// - [`SourceRangeOutOfBounds`](crate::error::ArrayError::SourceRangeOutOfBounds)
 /// - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+/// - [`Unexpected`](crate::error::ArrayError::Unexpected)
+/// - [`InvalidString`](crate::error::ArrayError::InvalidString)
+/// - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
 pub fn write_double_array_to<T: AsRef<str>>(
     destination_name: T,
     destination_write_offset: i32,
     source: &[f64],
     source_read_amount: i32,
 ) -> Result<(), ArrayError> {
+    return_if_not_initialized!(ArrayError::NotInitialized);
     unsafe {
-        let name = CString::new(destination_name.as_ref()).expect(C_STRING_FAILURE);
+        let name =
+            to_c_string(destination_name.as_ref()).map_err(|input| ArrayError::InvalidString {
+                context: "array name",
+                input,
+            })?;
         // Returns 0 on success or a negative error code if the array is non-existent
         // or offset + n exceeds range of array
 
-        // We check this manually in the predicate.
+        let size = check_bounds(
+            destination_name.as_ref(),
+            destination_write_offset,
+            source_read_amount,
+        )?;
+        // The amount is checked to be positive.
         #[allow(clippy::cast_sign_loss)]
-        if destination_write_offset + source_read_amount
-            > array_size(destination_name.as_ref()).map_err(|_| ArrayError::FailedToFindArray)?
-            || source_read_amount < 0
-            || source_read_amount as usize > source.len()
-        {
-            return Err(ArrayError::OutOfBounds);
+        if source_read_amount as usize > source.len() {
+            return Err(ArrayError::SourceRangeOutOfBounds {
+                start: 0,
+                end: source_read_amount as usize,
+                length: source.len(),
+            });
         }
 
         match libpd_sys::libpd_write_array_double(
@@ -298,7 +768,130 @@ pub fn write_double_array_to<T: AsRef<str>>(
             source_read_amount,
         ) {
             0 => Ok(()),
-            _ => Err(ArrayError::FailedToFindArray),
+            code => Err(ArrayError::from_code(
+                code,
+                destination_name,
+                destination_write_offset,
+                source_read_amount,
+                size,
+            )),
         }
     }
 }
+
+/// Updates a wavetable without glitches by writing to one of two arrays while the other one is played.
+///
+/// The patch needs two arrays of the same size and a receiver which switches the array which is played to the symbol it receives, e.g.
+/// `[r wavetable_switch]` → `[set $1(` → `[tabosc4~ wavetable_a]`.
+///
+/// Every [`write`](WavetableSwapper::write) fills the array which is not played
+/// and then sends its name to the switch receiver, the first array is assumed to be played initially.
+/// Pd handles the switch before computing the next block so the new wavetable is played from the start of a block.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::array::WavetableSwapper;
+///
+/// let mut swapper = WavetableSwapper::new("wavetable_a", "wavetable_b", "wavetable_switch");
+///
+/// let saw: Vec<f32> = (0..512).map(|i| i as f32 / 256.0 - 1.0).collect();
+/// swapper.write(&saw).unwrap();
+/// assert_eq!(swapper.active_array(), "wavetable_b");
+/// ```
+#[derive(Debug, Clone)]
+pub struct WavetableSwapper {
+    arrays: [String; 2],
+    switch_receiver: String,
+    active: usize,
+}
+
+impl WavetableSwapper {
+    /// Creates a swapper for two arrays and the receiver which switches between them.
+    ///
+    /// `first_array` is assumed to be the one which is played initially.
+    pub fn new<A: AsRef<str>, B: AsRef<str>, R: AsRef<str>>(
+        first_array: A,
+        second_array: B,
+        switch_receiver: R,
+    ) -> Self {
+        Self {
+            arrays: [
+                first_array.as_ref().to_owned(),
+                second_array.as_ref().to_owned(),
+            ],
+            switch_receiver: switch_receiver.as_ref().to_owned(),
+            active: 0,
+        }
+    }
+
+    /// The name of the array which is played.
+    #[must_use]
+    pub fn active_array(&self) -> &str {
+        &self.arrays[self.active]
+    }
+
+    /// The name of the array which is written to by the next [`write`](WavetableSwapper::write).
+    #[must_use]
+    pub fn inactive_array(&self) -> &str {
+        &self.arrays[1 - self.active]
+    }
+
+    /// Writes `source` to the start of the inactive array and switches to it.
+    ///
+    /// Nothing is switched if writing fails.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`ArrayError`](crate::error::ArrayError)
+    ///   - [`OutOfBounds`](crate::error::ArrayError::OutOfBounds)
+    ///   - [`FailedToFindArray`](crate::error::ArrayError::FailedToFindArray)
+    ///   - [`Unexpected`](crate::error::ArrayError::Unexpected)
+    ///   - [`NotInitialized`](crate::error::ArrayError::NotInitialized)
+    /// - [`SendError`](crate::error::SendError)
+    ///   - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    ///   - [`Unexpected`](crate::error::SendError::Unexpected)
+    ///
+    /// To match over these errors, you would need to downcast the returned error.
+    pub fn write(&mut self, source: &[f32]) -> Result<(), Box<dyn std::error::Error>> {
+        // An array has less than `i32::MAX` elements, so a longer source is out of its bounds anyway.
+        let length = i32::try_from(source.len()).unwrap_or(i32::MAX);
+        write_float_array_to(self.inactive_array(), 0, source, length)?;
+        crate::send::send_symbol_to(&self.switch_receiver, self.inactive_array())?;
+        self.active = 1 - self.active;
+        Ok(())
+    }
+}
+
+/// Checks if reading `amount` elements fits in a destination of `available` elements.
+fn fits_in_destination(amount: i32, available: usize) -> Result<(), ArrayError> {
+    // The amount is checked to be positive.
+    let needed = usize::try_from(amount).unwrap_or_default();
+    if needed > available {
+        return Err(ArrayError::DestinationTooSmall { needed, available });
+    }
+    Ok(())
+}
+
+/// Checks that `amount` elements from `offset` do not go past the end of an array and returns its size.
+fn check_bounds(array: &str, offset: i32, amount: i32) -> Result<usize, ArrayError> {
+    // Pd would do pointer arithmetic with negative values.
+    if offset < 0 {
+        return Err(ArrayError::NegativeOffset(offset));
+    }
+    if amount < 0 {
+        return Err(ArrayError::NegativeLength(amount));
+    }
+    let size = array_size(array).map_err(|_| ArrayError::FailedToFindArray(array.to_owned()))?;
+    let exceeds_size =
+        usize::try_from(i64::from(offset) + i64::from(amount)).is_ok_and(|end| end > size);
+    if exceeds_size {
+        return Err(ArrayError::OutOfBounds {
+            array: array.to_owned(),
+            offset,
+            requested: amount,
+            array_len: size,
+        });
+    }
+    Ok(size)
+}
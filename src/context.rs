@@ -0,0 +1,136 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+type Slot = Option<Box<dyn Any + Send>>;
+
+static CONTEXT: Mutex<Slot> = Mutex::new(None);
+
+fn slot() -> MutexGuard<'static, Slot> {
+    // Poisoning is irrelevant here since the slot is either empty or holds a whole value.
+    CONTEXT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Attaches a context to pd which the registered callbacks could borrow with [`context_mut`].
+///
+/// There is a single context since pd is initialized once in the process,
+/// setting a new one drops the previous one whatever its type is.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::context::{context_mut, set_context};
+///
+/// struct Meter {
+///     peaks: Vec<f32>,
+/// }
+///
+/// set_context(Meter { peaks: vec![] });
+///
+/// libpd_rs::receive::on_float(|_source, value| {
+///     if let Some(mut meter) = context_mut::<Meter>() {
+///         meter.peaks.push(value);
+///     }
+/// });
+/// ```
+pub fn set_context<T: Any + Send>(context: T) {
+    let previous = slot().replace(Box::new(context));
+    // Drop the previous context outside of the lock.
+    drop(previous);
+}
+
+/// Borrows the context which is attached with [`set_context`].
+///
+/// Returns `None` if there is no context or it is not a `T`.
+///
+/// The context stays locked until the returned guard is dropped and callbacks which borrow it wait until then,
+/// so the guard should not be held while pd is processing or messages are received from pd on the same thread.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::context::{context_mut, set_context};
+///
+/// set_context(0_u32);
+///
+/// *context_mut::<u32>().unwrap() += 1;
+/// assert_eq!(*context_mut::<u32>().unwrap(), 1);
+/// assert!(context_mut::<String>().is_none());
+/// ```
+#[must_use]
+pub fn context_mut<T: Any + Send>() -> Option<ContextGuard<T>> {
+    let slot = slot();
+    if slot.as_ref()?.is::<T>() {
+        Some(ContextGuard {
+            slot,
+            marker: PhantomData,
+        })
+    } else {
+        None
+    }
+}
+
+/// Detaches the context and returns it if it is a `T`.
+///
+/// The context stays attached if it is not a `T`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::context::{context_mut, set_context, take_context};
+///
+/// set_context(String::from("state"));
+///
+/// assert!(take_context::<u32>().is_none());
+/// assert_eq!(take_context::<String>().unwrap(), "state");
+/// assert!(context_mut::<String>().is_none());
+/// ```
+#[must_use]
+pub fn take_context<T: Any + Send>() -> Option<T> {
+    let mut slot = slot();
+    if slot.as_ref()?.is::<T>() {
+        slot.take()?.downcast::<T>().ok().map(|context| *context)
+    } else {
+        None
+    }
+}
+
+/// Detaches and drops the context whatever its type is.
+pub fn clear_context() {
+    let previous = slot().take();
+    drop(previous);
+}
+
+/// A borrow of the context which is attached with [`set_context`].
+///
+/// It is returned from [`context_mut`] and dereferences to the context.
+pub struct ContextGuard<T: Any + Send> {
+    slot: MutexGuard<'static, Slot>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Any + Send> Deref for ContextGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.slot
+            .as_ref()
+            .and_then(|context| context.downcast_ref::<T>())
+            // The type is checked before the guard is made and the slot can not change while it is locked.
+            .expect("The context is checked to be a `T` before it is borrowed.")
+    }
+}
+
+impl<T: Any + Send> DerefMut for ContextGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.slot
+            .as_mut()
+            .and_then(|context| context.downcast_mut::<T>())
+            // The type is checked before the guard is made and the slot can not change while it is locked.
+            .expect("The context is checked to be a `T` before it is borrowed.")
+    }
+}
+
+impl<T: Any + Send + std::fmt::Debug> std::fmt::Debug for ContextGuard<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ContextGuard").field(&**self).finish()
+    }
+}
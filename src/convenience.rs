@@ -2,11 +2,10 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tempfile::NamedTempFile;
 
 use crate::{
-    error::{InitializationError, PatchLifeCycleError},
-    types::{PatchFileHandle, ReceiverHandle},
+    error::{AudioInitializationError, InitializationError, PatchLifeCycleError},
+    types::{AudioConfig, Patch, ReceiverHandle},
 };
 
 /// Activates audio in pd.
@@ -16,8 +15,10 @@ use crate::{
 /// A list of errors that can occur:
 /// - [`SendError`](crate::error::SendError)
 ///   - [`MissingDestination`](crate::error::SendError::MissingDestination)
+///   - [`Unexpected`](crate::error::SendError::Unexpected)
 /// - [`SizeError`](crate::error::SizeError)
 ///   - [`TooLarge`](crate::error::SizeError::TooLarge)
+///   - [`Unexpected`](crate::error::SizeError::Unexpected)
 ///
 /// To match over these errors, you would need to downcast the returned error.
 pub fn dsp_on() -> Result<(), Box<dyn std::error::Error>> {
@@ -34,8 +35,10 @@ pub fn dsp_on() -> Result<(), Box<dyn std::error::Error>> {
 /// A list of errors that can occur:
 /// - [`SendError`](crate::error::SendError)
 ///   - [`MissingDestination`](crate::error::SendError::MissingDestination)
+///   - [`Unexpected`](crate::error::SendError::Unexpected)
 /// - [`SizeError`](crate::error::SizeError)
 ///   - [`TooLarge`](crate::error::SizeError::TooLarge)
+///   - [`Unexpected`](crate::error::SizeError::Unexpected)
 ///
 /// To match over these errors, you would need to downcast the returned error.
 pub fn dsp_off() -> Result<(), Box<dyn std::error::Error>> {
@@ -55,6 +58,76 @@ pub fn calculate_ticks(channels: i32, buffer_size: i32) -> i32 {
     buffer_size / (block_size * channels)
 }
 
+/// Finds the length of an interleaved output buffer which pd fills in the given number of ticks.
+///
+/// The calculation is `ticks * block_size * output_channels`.
+/// Negative arguments are treated as `0`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::convenience::output_buffer_len;
+///
+/// assert_eq!(output_buffer_len(8, 2), 1024);
+/// ```
+#[must_use]
+pub fn output_buffer_len(ticks: i32, output_channels: i32) -> usize {
+    buffer_len(ticks, output_channels)
+}
+
+/// Finds the length of an interleaved input buffer which pd reads in the given number of ticks.
+///
+/// The calculation is `ticks * block_size * input_channels`.
+/// Negative arguments are treated as `0`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::convenience::input_buffer_len;
+///
+/// assert_eq!(input_buffer_len(8, 1), 512);
+/// ```
+#[must_use]
+pub fn input_buffer_len(ticks: i32, input_channels: i32) -> usize {
+    buffer_len(ticks, input_channels)
+}
+
+fn buffer_len(ticks: i32, channels: i32) -> usize {
+    let ticks = usize::try_from(ticks).unwrap_or(0);
+    let channels = usize::try_from(channels).unwrap_or(0);
+    let block_size = usize::try_from(crate::block_size()).unwrap_or(0);
+    ticks * block_size * channels
+}
+
+/// Allocates zeroed input and output buffers for the given number of ticks.
+///
+/// The buffers are sized according to the [`audio_config`](crate::audio_config)
+/// and could be passed directly to [`process_float`](crate::process::process_float) with the same ticks.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{init, initialize_audio, convenience::allocate_io_buffers, process::process_float};
+///
+/// init().unwrap();
+/// initialize_audio(1, 2, 44100).unwrap();
+///
+/// let (input_buffer, mut output_buffer) = allocate_io_buffers(8).unwrap();
+/// assert_eq!(input_buffer.len(), 512);
+/// assert_eq!(output_buffer.len(), 1024);
+///
+/// process_float(8, &input_buffer, &mut output_buffer);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`AudioNotInitialized`](crate::error::AudioInitializationError::AudioNotInitialized)
+pub fn allocate_io_buffers(ticks: i32) -> Result<(Vec<f32>, Vec<f32>), AudioInitializationError> {
+    let config = crate::audio_config().ok_or(AudioInitializationError::AudioNotInitialized)?;
+    Ok((
+        vec![0.0; input_buffer_len(ticks, config.input_channels)],
+        vec![0.0; output_buffer_len(ticks, config.output_channels)],
+    ))
+}
+
 /// An abstraction provided for convenience to track the state of pd and execute some common functions.
 ///
 /// Pd initializes globally.
@@ -92,13 +165,15 @@ pub fn calculate_ticks(channels: i32, buffer_size: i32) -> i32 {
 /// To avoid this situation if you use [`PdGlobal`] check its member functions and only use them and **not** their bare counterparts.
 ///
 /// There are many bare functions in this crate which is not wrapped by [`PdGlobal`] and those are safe to use while using [`PdGlobal`] related functions.
+///
+/// When [`PdGlobal`] is dropped it stops listening from all sources and closes the running patch.
+/// If the internal queues of pd are released before that, dropping it will not call into pd.
 pub struct PdGlobal {
     audio_active: bool,
     input_channels: i32,
     output_channels: i32,
     sample_rate: i32,
-    running_patch: Option<PatchFileHandle>,
-    temporary_evaluated_patch: Option<NamedTempFile>,
+    running_patch: Option<Patch>,
     /// A store to keep track of subscriptions which are made to senders in pd through the app lifecycle.
     pub subscriptions: HashMap<String, ReceiverHandle>,
     /// A store to keep track of paths which are added to pd search paths through the app lifecycle.
@@ -125,9 +200,11 @@ impl PdGlobal {
     /// A list of errors that can occur:
     /// - [`InitializationError`](crate::error::InitializationError)
     ///   - [`RingBufferInitializationError`](crate::error::InitializationError::RingBufferInitializationError)
-    ///   - [`InitializationFailed`](crate::error::InitializationError::InitializationFailed)
+    ///   - [`Unexpected`](crate::error::InitializationError::Unexpected)
     /// - [`AudioInitializationError`](crate::error::AudioInitializationError)
-    ///   - [`InitializationFailed`](crate::error::AudioInitializationError::InitializationFailed)
+    ///   - [`InvalidSampleRate`](crate::error::AudioInitializationError::InvalidSampleRate)
+    ///   - [`InvalidChannelCount`](crate::error::AudioInitializationError::InvalidChannelCount)
+    ///   - [`LibpdRefused`](crate::error::AudioInitializationError::LibpdRefused)
     ///
     /// To match over these errors, you would need to downcast the returned error.
     pub fn init_and_configure(
@@ -136,7 +213,7 @@ impl PdGlobal {
         sample_rate: i32,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         match crate::init() {
-            Ok(_) => (),
+            Ok(()) => (),
             Err(err) => match err {
                 // Ignore re-initialization errors.
                 InitializationError::AlreadyInitialized => (),
@@ -150,12 +227,42 @@ impl PdGlobal {
             output_channels,
             sample_rate,
             running_patch: None,
-            temporary_evaluated_patch: None,
             subscriptions: HashMap::default(),
             search_paths: vec![],
         })
     }
 
+    /// Initializes pd globally with an [`AudioConfig`].
+    ///
+    /// Same as [`init_and_configure`](PdGlobal::init_and_configure) with the fields of the configuration.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use libpd_rs::{convenience::PdGlobal, types::AudioConfig};
+    ///
+    /// let mut pd = PdGlobal::init_with_config(AudioConfig::stereo_48k()).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`InitializationError`](crate::error::InitializationError)
+    ///   - [`RingBufferInitializationError`](crate::error::InitializationError::RingBufferInitializationError)
+    ///   - [`Unexpected`](crate::error::InitializationError::Unexpected)
+    /// - [`AudioInitializationError`](crate::error::AudioInitializationError)
+    ///   - [`InvalidSampleRate`](crate::error::AudioInitializationError::InvalidSampleRate)
+    ///   - [`InvalidChannelCount`](crate::error::AudioInitializationError::InvalidChannelCount)
+    ///   - [`LibpdRefused`](crate::error::AudioInitializationError::LibpdRefused)
+    ///
+    /// To match over these errors, you would need to downcast the returned error.
+    pub fn init_with_config(config: AudioConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::init_and_configure(
+            config.input_channels,
+            config.output_channels,
+            config.sample_rate,
+        )
+    }
+
     /// Adds a path to the list of paths where libpd searches in.
     ///
     /// Relative paths are relative to the current working directory.
@@ -224,7 +331,6 @@ impl PdGlobal {
         if let Some(handle) = self.running_patch.take() {
             crate::close_patch(handle)?;
         }
-        self.temporary_evaluated_patch.take();
         Ok(())
     }
 
@@ -251,6 +357,10 @@ impl PdGlobal {
     ///   - [`FailedToClosePatch`](crate::error::PatchLifeCycleError::FailedToClosePatch)
     ///   - [`FailedToOpenPatch`](crate::error::PatchLifeCycleError::FailedToOpenPatch)
     ///   - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist)
+    ///   - [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath)
+    ///   - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+    ///   - [`EvaluationFailed`](crate::error::PatchLifeCycleError::EvaluationFailed)
+    ///   - [`UnexpectedExtension`](crate::error::PatchLifeCycleError::UnexpectedExtension)
     ///
     /// To match over these errors, you would need to downcast the returned error.
     pub fn open_patch<T: AsRef<Path>>(
@@ -267,6 +377,7 @@ impl PdGlobal {
     /// Evaluate a string as a pd patch.
     ///
     /// This function creates a temporary file with the contents passed behind the scenes.
+    /// It is only available with the `std` feature which is enabled by default.
     /// and saves it into the [`PdGlobal`] struct holding onto it until the patch is closed or the instantiated [`PdGlobal`] is dropped.
     ///
     /// Note: The patch opened after this evaluation could be closed safely with [`close_patch`](PdGlobal::close_patch).
@@ -300,8 +411,13 @@ impl PdGlobal {
     ///   - [`FailedToClosePatch`](crate::error::PatchLifeCycleError::FailedToClosePatch)
     ///   - [`FailedToOpenPatch`](crate::error::PatchLifeCycleError::FailedToOpenPatch)
     ///   - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist)
+    ///   - [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath)
+    ///   - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+    ///   - [`EvaluationFailed`](crate::error::PatchLifeCycleError::EvaluationFailed)
+    ///   - [`UnexpectedExtension`](crate::error::PatchLifeCycleError::UnexpectedExtension)
     ///
     /// To match over these errors, you would need to downcast the returned error.
+    #[cfg(feature = "std")]
     pub fn eval_patch<T: AsRef<str>>(
         &mut self,
         contents: T,
@@ -309,19 +425,11 @@ impl PdGlobal {
         if self.running_patch.is_some() {
             self.close_patch()?;
         }
-        let temp_file =
-            NamedTempFile::new().map_err(|err| PatchLifeCycleError::FailedToEvaluateAsPatch {
-                content: contents.as_ref().to_owned(),
-                msg: err.to_string(),
-            })?;
-        std::fs::write(temp_file.path(), contents.as_ref()).map_err(|err| {
-            PatchLifeCycleError::FailedToEvaluateAsPatch {
-                content: contents.as_ref().to_owned(),
-                msg: err.to_string(),
-            }
-        })?;
-        self.running_patch = Some(crate::open_patch(temp_file.path())?);
-        self.temporary_evaluated_patch = Some(temp_file);
+        self.running_patch = Some(crate::open_patch_from_str(
+            "evaluated",
+            contents.as_ref(),
+            None,
+        )?);
         Ok(())
     }
 
@@ -343,6 +451,7 @@ impl PdGlobal {
     /// A list of errors that can occur:
     /// - [`SubscriptionError`](crate::error::SubscriptionError)
     ///   - [`FailedToSubscribeToSender`](crate::error::SubscriptionError::FailedToSubscribeToSender)
+    ///   - [`EmptySourceName`](crate::error::SubscriptionError::EmptySourceName)
     ///
     /// To match over these errors, you would need to downcast the returned error.
     pub fn subscribe_to<T: AsRef<str>>(
@@ -377,6 +486,7 @@ impl PdGlobal {
     /// A list of errors that can occur:
     /// - [`SubscriptionError`](crate::error::SubscriptionError)
     ///   - [`FailedToSubscribeToSender`](crate::error::SubscriptionError::FailedToSubscribeToSender)
+    ///   - [`EmptySourceName`](crate::error::SubscriptionError::EmptySourceName)
     ///
     /// To match over these errors, you would need to downcast the returned error.
     pub fn subscribe_to_many<T: AsRef<str>>(
@@ -469,7 +579,7 @@ impl PdGlobal {
             let dollar_zero = crate::get_dollar_zero(patch)?;
             return Ok(dollar_zero);
         }
-        Err(Box::new(PatchLifeCycleError::PatchIsNotOpen))
+        Err(Box::new(PatchLifeCycleError::PatchIsNotOpen(String::new())))
     }
 
     /// Checks if the audio is active.
@@ -490,8 +600,10 @@ impl PdGlobal {
     /// A list of errors that can occur:
     /// - [`SendError`](crate::error::SendError)
     ///   - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    ///   - [`Unexpected`](crate::error::SendError::Unexpected)
     /// - [`SizeError`](crate::error::SizeError)
     ///   - [`TooLarge`](crate::error::SizeError::TooLarge)
+    ///   - [`Unexpected`](crate::error::SizeError::Unexpected)
     ///
     /// To match over these errors, you would need to downcast the returned error.
     pub fn activate_audio(&mut self, on: bool) -> Result<(), Box<dyn std::error::Error>> {
@@ -507,6 +619,42 @@ impl PdGlobal {
         Ok(())
     }
 
+    /// Re-initializes audio with a new configuration and updates the tracked state.
+    ///
+    /// See [`reinitialize_audio`](crate::reinitialize_audio) for details.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use libpd_rs::convenience::PdGlobal;
+    ///
+    /// let mut pd = PdGlobal::init_and_configure(1, 2, 44100).unwrap();
+    /// pd.reinitialize_audio(1, 2, 48000).unwrap();
+    /// assert_eq!(pd.sample_rate(), 48000);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`AudioInitializationError`](crate::error::AudioInitializationError)
+    ///   - [`AudioNotInitialized`](crate::error::AudioInitializationError::AudioNotInitialized)
+    ///   - [`InvalidSampleRate`](crate::error::AudioInitializationError::InvalidSampleRate)
+    ///   - [`InvalidChannelCount`](crate::error::AudioInitializationError::InvalidChannelCount)
+    ///   - [`LibpdRefused`](crate::error::AudioInitializationError::LibpdRefused)
+    ///
+    /// To match over these errors, you would need to downcast the returned error.
+    pub fn reinitialize_audio(
+        &mut self,
+        input_channels: i32,
+        output_channels: i32,
+        sample_rate: i32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::reinitialize_audio(input_channels, output_channels, sample_rate)?;
+        self.input_channels = input_channels;
+        self.output_channels = output_channels;
+        self.sample_rate = sample_rate;
+        Ok(())
+    }
+
     /// Gets the sample rate which pd is configured with.
     ///
     /// The state is tracked by [`PdGlobal`].
@@ -537,3 +685,20 @@ impl PdGlobal {
         self.output_channels
     }
 }
+
+impl Drop for PdGlobal {
+    /// Stops listening from all sources and closes the running patch.
+    ///
+    /// If pd is not alive anymore (e.g. [`release_internal_queues`](crate::release_internal_queues) is called before),
+    /// the handles are forgotten without calling into pd.
+    fn drop(&mut self) {
+        if !crate::is_initialized() {
+            self.subscriptions.clear();
+            self.running_patch.take();
+            return;
+        }
+        self.unsubscribe_from_all();
+        // There is nothing meaningful to do with an error while dropping.
+        let _ = self.close_patch();
+    }
+}
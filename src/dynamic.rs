@@ -0,0 +1,306 @@
+use crate::{
+    error::DynamicPatchingError,
+    helpers::{make_t_atom_list_from_atom_list, return_if_not_initialized},
+    patch_file,
+    types::{Atom, Patch},
+};
+
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The source of the ids of all editors, so an id of one editor is unknown to the others.
+static NEXT_OBJ_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Refers to an object in a patch which is edited with a [`CanvasEditor`].
+///
+/// Pd addresses the objects of a patch with their index in the order of creation,
+/// which changes when an object before them is deleted.
+/// An `ObjId` keeps referring to the same object until it is deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjId(usize);
+
+impl ObjId {
+    fn next() -> Self {
+        Self(NEXT_OBJ_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// An object of the patch in the order pd keeps them.
+#[derive(Debug)]
+struct Entry {
+    id: ObjId,
+    /// The top left corner of the object, which is not known for scalars.
+    position: Option<(i32, i32)>,
+}
+
+/// Creates, connects and deletes objects in an open patch.
+///
+/// The editor reads the objects which are already in the patch from its file and keeps track of the ones it adds or deletes.
+/// Objects which are added in another way, e.g. by a second editor or by messages which are sent to the patch,
+/// are not known to it and would mix up the indices which pd uses.
+/// Since the objects an editor adds are not saved to the file, a patch should be edited by a single editor.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::{dynamic::CanvasEditor, open_patch};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let mut patch = open_patch("tests/patches/simple.pd").unwrap();
+/// let mut editor = CanvasEditor::new(&mut patch).unwrap();
+///
+/// let osc = editor.add_object(20, 20, "osc~ 440");
+/// let dac = editor.add_object(20, 60, "dac~");
+/// editor.connect(osc, 0, dac, 0).unwrap();
+/// editor.connect(osc, 0, dac, 1).unwrap();
+///
+/// editor.delete(osc).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct CanvasEditor<'a> {
+    patch: &'a mut Patch,
+    objects: Vec<Entry>,
+}
+
+impl<'a> CanvasEditor<'a> {
+    /// Starts editing an open patch.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`PatchNotOpen`](crate::error::DynamicPatchingError::PatchNotOpen)
+    /// - [`NotReadable`](crate::error::DynamicPatchingError::NotReadable)
+    /// - [`NotInitialized`](crate::error::DynamicPatchingError::NotInitialized)
+    pub fn new(patch: &'a mut Patch) -> Result<Self, DynamicPatchingError> {
+        return_if_not_initialized!(DynamicPatchingError::NotInitialized);
+        if !patch.is_open() {
+            return Err(DynamicPatchingError::PatchNotOpen);
+        }
+        let patch_file = patch.path();
+        let contents = std::fs::read_to_string(&patch_file).map_err(|err| {
+            DynamicPatchingError::NotReadable {
+                path: patch_file.to_string_lossy().to_string(),
+                source: err,
+            }
+        })?;
+        let objects = top_level_positions(&contents)
+            .into_iter()
+            .map(|position| Entry {
+                id: ObjId::next(),
+                position,
+            })
+            .collect();
+        Ok(Self { patch, objects })
+    }
+
+    /// The patch which is edited.
+    #[must_use]
+    pub const fn patch(&self) -> &Patch {
+        self.patch
+    }
+
+    /// All objects of the patch in the order pd keeps them, including the ones which are read from its file.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use libpd_rs::{dynamic::CanvasEditor, open_patch};
+    ///
+    /// libpd_rs::init().unwrap();
+    ///
+    /// let mut patch = open_patch("tests/patches/voice.pd").unwrap();
+    /// let mut editor = CanvasEditor::new(&mut patch).unwrap();
+    ///
+    /// // Feed `[pack f $0]` which is the second object in the file.
+    /// let pack = editor.objects()[1];
+    /// let receiver = editor.add_object(120, 20, "r voice_in");
+    /// editor.connect(receiver, 0, pack, 0).unwrap();
+    /// ```
+    #[must_use]
+    pub fn objects(&self) -> Vec<ObjId> {
+        self.objects.iter().map(|entry| entry.id).collect()
+    }
+
+    /// Creates an object box, `text` is the content of it, e.g. `"osc~ 440"`.
+    ///
+    /// An object which pd can not create is still added as a broken object without inlets and outlets,
+    /// pd prints the reason to its console.
+    pub fn add_object<T: AsRef<str>>(&mut self, x: i32, y: i32, text: T) -> ObjId {
+        self.add(c"obj", x, y, text.as_ref())
+    }
+
+    /// Creates a message box, `text` is the content of it, e.g. `"bang"`.
+    pub fn add_msg<T: AsRef<str>>(&mut self, x: i32, y: i32, text: T) -> ObjId {
+        self.add(c"msg", x, y, text.as_ref())
+    }
+
+    /// Connects an outlet of an object to an inlet of another.
+    ///
+    /// Pd prints an error to its console if the outlet or the inlet does not exist.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`UnknownObject`](crate::error::DynamicPatchingError::UnknownObject)
+    pub fn connect(
+        &self,
+        from: ObjId,
+        outlet: u32,
+        to: ObjId,
+        inlet: u32,
+    ) -> Result<(), DynamicPatchingError> {
+        let arguments = self.connection_arguments(from, outlet, to, inlet)?;
+        self.send_to_canvas(&[(c"connect", &arguments)]);
+        Ok(())
+    }
+
+    /// Removes the connection from an outlet of an object to an inlet of another.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`UnknownObject`](crate::error::DynamicPatchingError::UnknownObject)
+    pub fn disconnect(
+        &self,
+        from: ObjId,
+        outlet: u32,
+        to: ObjId,
+        inlet: u32,
+    ) -> Result<(), DynamicPatchingError> {
+        let arguments = self.connection_arguments(from, outlet, to, inlet)?;
+        self.send_to_canvas(&[(c"disconnect", &arguments)]);
+        Ok(())
+    }
+
+    /// Deletes an object together with its connections.
+    ///
+    /// Pd only deletes selected objects and selecting needs the editor of a visible patch,
+    /// so the patch is made visible, the object is clicked at its top left corner in edit mode and cut
+    /// and the patch is hidden again.
+    /// Without a running gui nothing is shown, with one the window of the patch flashes and it is closed afterwards.
+    /// The object should not be covered by another object at its top left corner, since the one on top would be deleted.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`UnknownObject`](crate::error::DynamicPatchingError::UnknownObject)
+    /// - [`UnknownPosition`](crate::error::DynamicPatchingError::UnknownPosition)
+    pub fn delete(&mut self, obj: ObjId) -> Result<(), DynamicPatchingError> {
+        let index = self.index_of(obj)?;
+        let (x, y) = self.objects[index]
+            .position
+            .ok_or(DynamicPatchingError::UnknownPosition)?;
+        let on = [Atom::Float(1.0)];
+        let off = [Atom::Float(0.0)];
+        // Clicking slightly inside of the box, which begins at the position of the object.
+        let click = [
+            Atom::Float(f64::from(x) + 2.0),
+            Atom::Float(f64::from(y) + 2.0),
+            Atom::Float(1.0),
+            Atom::Float(0.0),
+        ];
+        self.send_to_canvas(&[
+            (c"vis", &on),
+            (c"editmode", &on),
+            (c"mouse", &click),
+            (c"cut", &[]),
+            (c"editmode", &off),
+            (c"vis", &off),
+        ]);
+        self.objects.remove(index);
+        Ok(())
+    }
+
+    fn add(&mut self, kind: &CStr, x: i32, y: i32, text: &str) -> ObjId {
+        let mut arguments = vec![Atom::Float(f64::from(x)), Atom::Float(f64::from(y))];
+        arguments.extend(atoms_from_text(text));
+        self.send_to_canvas(&[(kind, &arguments)]);
+        let id = ObjId::next();
+        self.objects.push(Entry {
+            id,
+            position: Some((x, y)),
+        });
+        id
+    }
+
+    fn index_of(&self, obj: ObjId) -> Result<usize, DynamicPatchingError> {
+        self.objects
+            .iter()
+            .position(|entry| entry.id == obj)
+            .ok_or(DynamicPatchingError::UnknownObject)
+    }
+
+    fn connection_arguments(
+        &self,
+        from: ObjId,
+        outlet: u32,
+        to: ObjId,
+        inlet: u32,
+    ) -> Result<[Atom; 4], DynamicPatchingError> {
+        // This is fine since a patch will not have millions of objects.
+        #[allow(clippy::cast_precision_loss)]
+        Ok([
+            Atom::Float(self.index_of(from)? as f64),
+            Atom::Float(f64::from(outlet)),
+            Atom::Float(self.index_of(to)? as f64),
+            Atom::Float(f64::from(inlet)),
+        ])
+    }
+
+    /// Sends messages to the canvas of the patch in one go.
+    ///
+    /// The canvas is addressed directly instead of through `pd-<file name>`,
+    /// which is shared by the patches which are opened from the same file.
+    fn send_to_canvas(&self, messages: &[(&CStr, &[Atom])]) {
+        let messages = messages
+            .iter()
+            .map(|(selector, list)| {
+                // The symbols are split from text at null bytes by `atoms_from_text`, so they always convert.
+                let atom_list: Vec<libpd_sys::t_atom> =
+                    make_t_atom_list_from_atom_list!(*list).unwrap_or_default();
+                (selector, atom_list)
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            libpd_sys::sys_lock();
+            for (selector, mut atom_list) in messages {
+                #[allow(clippy::cast_possible_wrap)]
+                #[allow(clippy::cast_possible_truncation)]
+                libpd_sys::pd_typedmess(
+                    self.patch.as_mut_ptr().cast(),
+                    libpd_sys::gensym(selector.as_ptr()),
+                    // This is fine since a message will not be millions of elements long.
+                    atom_list.len() as i32,
+                    atom_list.as_mut_ptr(),
+                );
+            }
+            libpd_sys::sys_unlock();
+        }
+    }
+}
+
+/// Splits the text of a box to atoms the way pd would, numbers become floats.
+///
+/// Null bytes separate atoms like whitespace, since they can not be passed to pd.
+fn atoms_from_text(text: &str) -> Vec<Atom> {
+    text.split(|c: char| c.is_whitespace() || c == '\0')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            // Rust also parses words like `inf` which pd keeps as symbols.
+            let looks_like_number =
+                token.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'));
+            match token.parse::<f64>() {
+                Ok(value) if looks_like_number && value.is_finite() => Atom::Float(value),
+                _ => Atom::from(token),
+            }
+        })
+        .collect()
+}
+
+/// Finds the positions of the objects in the top level of a patch file in their order.
+fn top_level_positions(contents: &str) -> Vec<Option<(i32, i32)>> {
+    patch_file::parse(contents)
+        .iter()
+        .filter(|record| record.depth == 1 && record.is_object())
+        .map(patch_file::Record::position)
+        .collect()
+}
@@ -0,0 +1,578 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{
+    error::{
+        EngineError, InitializationError, PatchLifeCycleError, ProcessError, SendError,
+        SubscriptionError,
+    },
+    sink::{ClosureSink, FromReceivedMessage, ReceivedMessage, SinkHandle},
+    symbol::AsPdSymbol,
+    types::{Atom, AudioConfig, Patch, ReceiverHandle},
+};
+
+/// Pd set up for a single audio configuration, with a patch loaded in it.
+///
+/// It initializes pd and its audio, loads a patch, turns audio on, sends messages to the patch and renders audio.
+/// The patch and the subscriptions which are made through it are owned by it and are released when it is dropped.
+///
+/// Pd initializes globally, so there should be a single engine at a time.
+/// Another engine can only be made with the audio configuration which pd is initialized with.
+/// The bare functions of the crate are still there for the things the engine doesn't cover.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{engine::PdEngine, types::AudioConfig};
+///
+/// let mut engine = PdEngine::new(AudioConfig::stereo_output_44k()).unwrap();
+/// engine.load("tests/patches/sine.pd").unwrap();
+/// engine.start().unwrap();
+///
+/// // In the audio callback.
+/// let mut output = vec![0.0_f32; engine.output_len(8)];
+/// engine.render(&[], &mut output).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PdEngine {
+    config: AudioConfig,
+    patch: Option<Patch>,
+    subscriptions: HashMap<String, ReceiverHandle>,
+    running: bool,
+}
+
+impl PdEngine {
+    /// Initializes pd if it is not initialized yet and initializes its audio with the configuration
+    /// if audio is not initialized yet.
+    ///
+    /// Audio is never reconfigured here, since other engines and their [`Renderer`]s render with the configuration.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`Initialization`](crate::error::EngineError::Initialization)
+    /// - [`Audio`](crate::error::EngineError::Audio)
+    /// - [`AudioConfigMismatch`](crate::error::EngineError::AudioConfigMismatch)
+    pub fn new(config: AudioConfig) -> Result<Self, EngineError> {
+        match crate::init() {
+            // Ignore re-initialization errors.
+            Ok(()) | Err(InitializationError::AlreadyInitialized) => {}
+            Err(err) => return Err(EngineError::Initialization(err)),
+        }
+        match crate::audio_config() {
+            None => crate::initialize_audio_with_config(config).map_err(EngineError::Audio)?,
+            Some(configured) if configured != config => {
+                return Err(EngineError::AudioConfigMismatch {
+                    configured,
+                    requested: config,
+                })
+            }
+            Some(_) => {}
+        }
+        Ok(Self {
+            config,
+            patch: None,
+            subscriptions: HashMap::default(),
+            running: false,
+        })
+    }
+
+    /// The audio configuration of the engine.
+    #[must_use]
+    pub const fn config(&self) -> AudioConfig {
+        self.config
+    }
+
+    /// Opens a patch, closing the one which is loaded before.
+    ///
+    /// The path is resolved like the one of [`open_patch`](crate::open_patch).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`FailedToClosePatch`](crate::error::PatchLifeCycleError::FailedToClosePatch)
+    /// - [`FailedToOpenPatch`](crate::error::PatchLifeCycleError::FailedToOpenPatch)
+    /// - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist)
+    /// - [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath)
+    /// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+    /// - [`EvaluationFailed`](crate::error::PatchLifeCycleError::EvaluationFailed)
+    /// - [`UnexpectedExtension`](crate::error::PatchLifeCycleError::UnexpectedExtension)
+    /// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+    pub fn load<T: AsRef<Path>>(&mut self, path: T) -> Result<(), PatchLifeCycleError> {
+        self.unload()?;
+        self.patch = Some(crate::open_patch(path)?);
+        Ok(())
+    }
+
+    /// Closes the loaded patch if there is one.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`FailedToClosePatch`](crate::error::PatchLifeCycleError::FailedToClosePatch)
+    pub fn unload(&mut self) -> Result<(), PatchLifeCycleError> {
+        if let Some(patch) = self.patch.take() {
+            patch.close()?;
+        }
+        Ok(())
+    }
+
+    /// The loaded patch.
+    #[must_use]
+    pub const fn patch(&self) -> Option<&Patch> {
+        self.patch.as_ref()
+    }
+
+    /// Turns audio on in pd, until then [`render`](PdEngine::render) outputs silence.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn start(&mut self) -> Result<(), SendError> {
+        crate::send::send_message_to("pd", "dsp", &[Atom::from(1)])?;
+        self.running = true;
+        Ok(())
+    }
+
+    /// Turns audio off in pd.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn stop(&mut self) -> Result<(), SendError> {
+        crate::send::send_message_to("pd", "dsp", &[Atom::from(0)])?;
+        self.running = false;
+        Ok(())
+    }
+
+    /// Checks if audio is turned on with [`start`](PdEngine::start).
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Sends a bang to a receiver in the patch.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_bang<T: AsRef<str>>(&self, receiver: T) -> Result<(), SendError> {
+        crate::send::send_bang_to(receiver)
+    }
+
+    /// Sends a float to a receiver in the patch.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_float<T: AsRef<str>>(&self, receiver: T, value: f32) -> Result<(), SendError> {
+        crate::send::send_float_to(receiver, value)
+    }
+
+    /// Sends a symbol to a receiver in the patch.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_symbol<T: AsRef<str>, S: AsRef<str>>(
+        &self,
+        receiver: T,
+        value: S,
+    ) -> Result<(), SendError> {
+        crate::send::send_symbol_to(receiver, value)
+    }
+
+    /// Sends a list to a receiver in the patch.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`MessageTooLong`](crate::error::SendError::MessageTooLong)
+    /// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_list<T: AsRef<str>>(&self, receiver: T, list: &[Atom]) -> Result<(), SendError> {
+        crate::send::send_list_to(receiver, list)
+    }
+
+    /// Starts listening to a sender in the patch, listening to it again does nothing.
+    ///
+    /// The messages are delivered to the closures which are registered with the `on_*` functions in the [`receive`](crate::receive) module,
+    /// when they are received with [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`FailedToSubscribeToSender`](crate::error::SubscriptionError::FailedToSubscribeToSender)
+    /// - [`EmptySourceName`](crate::error::SubscriptionError::EmptySourceName)
+    /// - [`NotInitialized`](crate::error::SubscriptionError::NotInitialized)
+    pub fn listen<T: AsRef<str>>(&mut self, sender: T) -> Result<(), SubscriptionError> {
+        if !self.subscriptions.contains_key(sender.as_ref()) {
+            let handle = crate::receive::start_listening_from(sender.as_ref())?;
+            self.subscriptions
+                .insert(sender.as_ref().to_owned(), handle);
+        }
+        Ok(())
+    }
+
+    /// Stops listening to a sender which is listened to with [`listen`](PdEngine::listen).
+    pub fn stop_listening<T: AsRef<str>>(&mut self, sender: T) {
+        if let Some(handle) = self.subscriptions.remove(sender.as_ref()) {
+            crate::receive::stop_listening_from(handle);
+        }
+    }
+
+    /// The length of an interleaved output buffer which is rendered in the given number of ticks.
+    #[must_use]
+    pub fn output_len(&self, ticks: usize) -> usize {
+        ticks * block_size() * channel_count(self.config.output_channels)
+    }
+
+    /// The length of an interleaved input buffer which is rendered in the given number of ticks.
+    #[must_use]
+    pub fn input_len(&self, ticks: usize) -> usize {
+        ticks * block_size() * channel_count(self.config.input_channels)
+    }
+
+    /// Renders interleaved audio through the patch, see [`Renderer::render`].
+    ///
+    /// # Errors
+    ///
+    /// The output buffer is filled with silence when an error occurs.
+    ///
+    /// A list of errors that can occur:
+    /// - [`InputBufferLength`](crate::error::ProcessError::InputBufferLength)
+    /// - [`OutputBufferLength`](crate::error::ProcessError::OutputBufferLength)
+    /// - [`AudioConfigChanged`](crate::error::ProcessError::AudioConfigChanged)
+    /// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+    pub fn render(&self, input: &[f32], output: &mut [f32]) -> Result<(), ProcessError> {
+        self.renderer().render(input, output)
+    }
+
+    /// A handle which renders audio with the configuration of the engine.
+    ///
+    /// It can be moved to the audio callback while the engine stays where messages are sent from.
+    ///
+    /// # Example
+    /// ```rust
+    /// use libpd_rs::{engine::PdEngine, types::AudioConfig};
+    ///
+    /// let mut engine = PdEngine::new(AudioConfig::stereo_output_44k()).unwrap();
+    /// engine.load("tests/patches/sine.pd").unwrap();
+    /// engine.start().unwrap();
+    ///
+    /// let renderer = engine.renderer();
+    /// let audio_thread = std::thread::spawn(move || {
+    ///     let mut output = vec![0.0_f32; 1024];
+    ///     renderer.render(&[], &mut output).unwrap();
+    /// });
+    /// audio_thread.join().unwrap();
+    /// ```
+    #[must_use]
+    pub const fn renderer(&self) -> Renderer {
+        Renderer {
+            config: self.config,
+        }
+    }
+}
+
+impl Drop for PdEngine {
+    /// Turns audio off, stops listening to all senders and closes the loaded patch.
+    ///
+    /// If pd is not alive anymore (e.g. [`release_internal_queues`](crate::release_internal_queues) is called before),
+    /// the handles are forgotten without calling into pd.
+    fn drop(&mut self) {
+        if !crate::is_initialized() {
+            self.subscriptions.clear();
+            self.patch.take();
+            return;
+        }
+        // There is nothing meaningful to do with an error while dropping.
+        if self.running {
+            let _ = self.stop();
+        }
+        for (_, handle) in self.subscriptions.drain() {
+            crate::receive::stop_listening_from(handle);
+        }
+        let _ = self.unload();
+    }
+}
+
+/// Pd with its audio initialized, the shortest way from a patch to sound.
+///
+/// It is a thin layer over a [`PdEngine`] which loads no patch, everything it does could be done with the functions of the crate too:
+/// [`new`](Pd::new) initializes pd and its audio, [`open`](Pd::open) opens patches,
+/// [`audio`](Pd::audio) turns audio on and returns the [`Renderer`] to call in the audio callback,
+/// [`send`](Pd::send) sends values to receivers and [`on`](Pd::on) calls closures with the values which senders send.
+///
+/// When it is dropped the closures of [`on`](Pd::on) are unsubscribed and audio is turned off.
+/// The patches are closed when their handles are dropped, so they are declared after it to be dropped before it.
+/// Pd initializes globally, so there should be a single one at a time, next to no other [`PdEngine`].
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{engine::Pd, types::AudioConfig};
+///
+/// let mut pd = Pd::new(AudioConfig::stereo_output_44k()).unwrap();
+/// let _patch = pd.open("tests/patches/echo.pd").unwrap();
+/// let renderer = pd.audio().unwrap();
+/// pd.on("float_from_pd", |value: f32| println!("pd says {value}")).unwrap();
+/// pd.send("float_from_rust", 440.0).unwrap();
+///
+/// // In the audio callback.
+/// let mut output = vec![0.0_f32; pd.output_len(8)];
+/// renderer.render(&[], &mut output).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Pd {
+    engine: PdEngine,
+    sinks: Vec<SinkHandle>,
+}
+
+impl Pd {
+    /// Initializes pd if it is not initialized yet and initializes its audio with the configuration
+    /// if audio is not initialized yet, like [`PdEngine::new`].
+    ///
+    /// Audio stays off until [`audio`](Pd::audio) is called.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`Initialization`](crate::error::EngineError::Initialization)
+    /// - [`Audio`](crate::error::EngineError::Audio)
+    /// - [`AudioConfigMismatch`](crate::error::EngineError::AudioConfigMismatch)
+    pub fn new(config: AudioConfig) -> Result<Self, EngineError> {
+        Ok(Self {
+            engine: PdEngine::new(config)?,
+            sinks: Vec::new(),
+        })
+    }
+
+    /// The audio configuration of pd.
+    #[must_use]
+    pub const fn config(&self) -> AudioConfig {
+        self.engine.config()
+    }
+
+    /// Opens a patch, see [`open_patch`](crate::open_patch).
+    ///
+    /// The patch is closed when the returned handle is dropped.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`FailedToOpenPatch`](crate::error::PatchLifeCycleError::FailedToOpenPatch)
+    /// - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist)
+    /// - [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath)
+    /// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+    /// - [`EvaluationFailed`](crate::error::PatchLifeCycleError::EvaluationFailed)
+    /// - [`UnexpectedExtension`](crate::error::PatchLifeCycleError::UnexpectedExtension)
+    /// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+    pub fn open<T: AsRef<Path>>(&self, path: T) -> Result<Patch, PatchLifeCycleError> {
+        crate::open_patch(path)
+    }
+
+    /// Turns audio on in pd like [`PdEngine::start`] and returns the [`Renderer`] which runs pd in the audio callback.
+    ///
+    /// The renderer does not deliver the messages for the closures of [`on`](Pd::on), see [`Renderer::render`].
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn audio(&mut self) -> Result<Renderer, SendError> {
+        self.engine.start()?;
+        Ok(self.engine.renderer())
+    }
+
+    /// The length of an interleaved output buffer which is rendered in the given number of ticks.
+    #[must_use]
+    pub fn output_len(&self, ticks: usize) -> usize {
+        self.engine.output_len(ticks)
+    }
+
+    /// The length of an interleaved input buffer which is rendered in the given number of ticks.
+    #[must_use]
+    pub fn input_len(&self, ticks: usize) -> usize {
+        self.engine.input_len(ticks)
+    }
+
+    /// Sends a value to a receiver, a float or a symbol like [`send_double_to`](crate::send::send_double_to)
+    /// and [`send_symbol_to`](crate::send::send_symbol_to).
+    ///
+    /// Use [`send_list_to`](crate::send::send_list_to) and the other functions of the [`send`](crate::send) module for the other messages.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send<T: AsPdSymbol, V: Into<Atom>>(
+        &self,
+        receiver: T,
+        value: V,
+    ) -> Result<(), SendError> {
+        match value.into() {
+            Atom::Float(value) => crate::send::send_double_to(receiver, value),
+            Atom::Symbol(value) => crate::send::send_symbol_to(receiver, value),
+            Atom::Interned(symbol) => crate::send::send_symbol_to(receiver, symbol),
+            Atom::Pointer(_) => Err(SendError::PointerAtom),
+        }
+    }
+
+    /// Calls the closure with the values which are sent from a sender, see [`subscribe_with`](crate::sink::subscribe_with).
+    ///
+    /// The messages which do not convert to the argument of the closure are skipped, see [`FromReceivedMessage`].
+    /// The closure is called when the messages are received, when [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd)
+    /// is called or by the thread of [`spawn_poller`](crate::receive::spawn_poller).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`FailedToSubscribeToSender`](crate::error::SubscriptionError::FailedToSubscribeToSender)
+    /// - [`EmptySourceName`](crate::error::SubscriptionError::EmptySourceName)
+    /// - [`InvalidString`](crate::error::SubscriptionError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SubscriptionError::NotInitialized)
+    pub fn on<T: AsRef<str>, V: FromReceivedMessage, F: FnMut(V) + Send + 'static>(
+        &mut self,
+        sender: T,
+        mut closure: F,
+    ) -> Result<(), SubscriptionError> {
+        let sink = ClosureSink(move |_: &str, message: ReceivedMessage| {
+            if let Some(value) = V::from_received_message(&message) {
+                closure(value);
+            }
+        });
+        self.sinks.push(crate::sink::subscribe_with(sender, sink)?);
+        Ok(())
+    }
+}
+
+impl Drop for Pd {
+    /// Unsubscribes the closures of [`on`](Pd::on), then the engine turns audio off when it is dropped.
+    ///
+    /// If pd is not alive anymore (e.g. [`release_internal_queues`](crate::release_internal_queues) is called before),
+    /// nothing is done.
+    fn drop(&mut self) {
+        if !crate::is_initialized() {
+            return;
+        }
+        for handle in self.sinks.drain(..) {
+            crate::sink::unsubscribe_sink(handle);
+        }
+    }
+}
+
+/// Renders audio through pd with the configuration of a [`PdEngine`] or a [`Pd`].
+///
+/// Retrieved with [`PdEngine::renderer`] or [`Pd::audio`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Renderer {
+    config: AudioConfig,
+}
+
+impl Renderer {
+    /// Renders interleaved audio through the loaded patch.
+    ///
+    /// The output buffer needs to hold whole blocks for every output channel, the number of ticks is found from its length.
+    /// The input buffer needs to hold the same number of ticks for every input channel,
+    /// so it is empty when there are no input channels.
+    /// Without output channels the number of ticks is found from the input buffer.
+    ///
+    /// Nothing is rendered after audio is reinitialized with another configuration, e.g. with
+    /// [`reinitialize_audio`](crate::reinitialize_audio), since pd would fill the buffers for other channel counts.
+    ///
+    /// The messages which pd sends meanwhile are not received here, since their closures should not run on the audio thread.
+    /// Receive them on another thread with [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd)
+    /// in the main loop or with [`spawn_poller`](crate::receive::spawn_poller).
+    ///
+    /// Call this in your **audio callback**.
+    ///
+    /// # Errors
+    ///
+    /// The output buffer is filled with silence when an error occurs.
+    ///
+    /// A list of errors that can occur:
+    /// - [`InputBufferLength`](crate::error::ProcessError::InputBufferLength)
+    /// - [`OutputBufferLength`](crate::error::ProcessError::OutputBufferLength)
+    /// - [`AudioConfigChanged`](crate::error::ProcessError::AudioConfigChanged)
+    /// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+    pub fn render(&self, input: &[f32], output: &mut [f32]) -> Result<(), ProcessError> {
+        #[cfg(not(feature = "skip-init-checks"))]
+        if !crate::libpd_initialized() {
+            output.fill(0.0);
+            return Err(ProcessError::NotInitialized);
+        }
+        // The configuration can not change while processing is locked, until the ticks are processed.
+        let _lock = crate::lock_for_processing();
+        if crate::audio_config() != Some(self.config) {
+            output.fill(0.0);
+            return Err(ProcessError::AudioConfigChanged);
+        }
+        let ticks = match self.ticks(input.len(), output.len()) {
+            Ok(ticks) => ticks,
+            Err(err) => {
+                output.fill(0.0);
+                return Err(err);
+            }
+        };
+        // This is fine since an audio buffer will not be billions of blocks long.
+        #[allow(clippy::cast_possible_wrap)]
+        #[allow(clippy::cast_possible_truncation)]
+        unsafe {
+            libpd_sys::libpd_process_float(ticks as i32, input.as_ptr(), output.as_mut_ptr());
+        }
+        Ok(())
+    }
+
+    /// Finds the number of ticks the buffers hold, checking that they are whole blocks of the same number of ticks.
+    fn ticks(&self, input_length: usize, output_length: usize) -> Result<usize, ProcessError> {
+        let input_tick = block_size() * channel_count(self.config.input_channels);
+        let output_tick = block_size() * channel_count(self.config.output_channels);
+        let ticks = if output_tick == 0 {
+            input_length.div_ceil(input_tick.max(1))
+        } else {
+            output_length.div_ceil(output_tick)
+        };
+        if output_length != ticks * output_tick {
+            return Err(ProcessError::OutputBufferLength {
+                expected: ticks * output_tick,
+                actual: output_length,
+            });
+        }
+        if input_length != ticks * input_tick {
+            return Err(ProcessError::InputBufferLength {
+                expected: ticks * input_tick,
+                actual: input_length,
+            });
+        }
+        Ok(ticks)
+    }
+}
+
+fn block_size() -> usize {
+    usize::try_from(crate::block_size()).unwrap_or(0)
+}
+
+fn channel_count(channels: i32) -> usize {
+    usize::try_from(channels).unwrap_or(0)
+}
@@ -1,5 +1,89 @@
 use thiserror::Error;
 
+use crate::types::AudioConfig;
+
+/// Formats the name which an error is about for its message, e.g. `` to `receiver` ``.
+fn to_name(preposition: &str, name: Option<&str>) -> String {
+    name.map_or_else(String::new, |name| format!(" {preposition} `{name}`"))
+}
+
+/// Any error of this crate.
+///
+/// Every function returns the error of its module, this type collects them
+/// so errors of different modules could be returned with `?` from the same function.
+/// It is transparent, so its message and source are the ones of the error it holds.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::{error::LibpdError, init, initialize_audio, open_patch, send::send_float_to};
+///
+/// fn start() -> Result<(), LibpdError> {
+///     init()?;
+///     initialize_audio(0, 2, 44100)?;
+///     open_patch("tests/patches/echo.pd")?.leak();
+///     send_float_to("float_from_rust", 1.0)?;
+///     Ok(())
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum LibpdError {
+    /// See [`InitializationError`].
+    #[error(transparent)]
+    Initialization(#[from] InitializationError),
+    /// See [`AudioInitializationError`].
+    #[error(transparent)]
+    AudioInitialization(#[from] AudioInitializationError),
+    /// See [`ProcessError`].
+    #[error(transparent)]
+    Process(#[from] ProcessError),
+    /// See [`EngineError`].
+    #[error(transparent)]
+    Engine(#[from] EngineError),
+    /// See [`PatchLifeCycleError`].
+    #[error(transparent)]
+    PatchLifeCycle(#[from] PatchLifeCycleError),
+    /// See [`GuiLifeCycleError`].
+    #[cfg(feature = "gui")]
+    #[error(transparent)]
+    GuiLifeCycle(#[from] GuiLifeCycleError),
+    /// See [`WatchError`].
+    #[cfg(feature = "watch")]
+    #[error(transparent)]
+    Watch(#[from] WatchError),
+    /// See [`WavError`].
+    #[cfg(feature = "wav")]
+    #[error(transparent)]
+    Wav(#[from] WavError),
+    /// See [`DynamicPatchingError`].
+    #[error(transparent)]
+    DynamicPatching(#[from] DynamicPatchingError),
+    /// See [`IoError`].
+    #[error(transparent)]
+    Io(#[from] IoError),
+    /// See [`SendError`].
+    #[error(transparent)]
+    Send(#[from] SendError),
+    /// See [`SubscriptionError`].
+    #[error(transparent)]
+    Subscription(#[from] SubscriptionError),
+    /// See [`SizeError`].
+    #[error(transparent)]
+    Size(#[from] SizeError),
+    /// See [`ArrayError`].
+    #[error(transparent)]
+    Array(#[from] ArrayError),
+    /// See [`SoundfileError`].
+    #[error(transparent)]
+    Soundfile(#[from] SoundfileError),
+    /// See [`AtomParseError`].
+    #[error(transparent)]
+    AtomParse(#[from] AtomParseError),
+    /// See [`AtomConversionError`].
+    #[error(transparent)]
+    AtomConversion(#[from] AtomConversionError),
+}
+
 /// Errors related to initialization.
 #[non_exhaustive]
 #[derive(Error, Debug)]
@@ -7,51 +91,298 @@ pub enum InitializationError {
     /// pd could not be initialized two times.
     #[error("Pure Data is already initialized.")]
     AlreadyInitialized,
-    /// Error in initializing ring buffers.
+    /// The queues which hold messages from pd could not be allocated.
     #[error("Failed to initialize ring buffers which are needed for the message queue.")]
     RingBufferInitializationError,
-    /// A failure happened in pd initialization with an unknown reason.
-    #[error("An unknown error occurred in Pure Data initialization.")]
-    InitializationFailed,
+    /// Libpd returned an error code which this crate doesn't know about.
+    #[error("Pure Data initialization failed with an unexpected return code: `{0}`.")]
+    Unexpected(i32),
 }
 
 /// Errors related to audio initialization.
 #[non_exhaustive]
 #[derive(Error, Debug)]
 pub enum AudioInitializationError {
-    /// A failure happened in pd audio initialization with an unknown reason.
-    #[error("An unknown error occurred in Pure Data audio initialization.")]
-    InitializationFailed,
+    /// The sample rate is not a positive number.
+    #[error("The sample rate `{0}` is invalid, it needs to be a positive number.")]
+    InvalidSampleRate(i32),
+    /// The channel counts are negative or there are no channels at all.
+    #[error("The channel counts (input: `{input}`, output: `{output}`) are invalid, they can not be negative and at least one of them needs to be positive.")]
+    InvalidChannelCount {
+        /// Number of input channels which is requested.
+        input: i32,
+        /// Number of output channels which is requested.
+        output: i32,
+    },
+    /// Pd refused to initialize audio with the contained return code.
+    #[error("Pure Data refused to initialize audio with the return code: `{0}`.")]
+    LibpdRefused(i32),
+    /// Audio is tried to be re-initialized before it is initialized.
+    #[error("Audio needs to be initialized before it can be re-initialized.")]
+    AudioNotInitialized,
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
+}
+
+/// Errors related to processing with the functions which validate the buffers,
+/// e.g. [`try_process_raw`](crate::process::try_process_raw) or [`Renderer::render`](crate::engine::Renderer::render).
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum ProcessError {
+    /// The input buffer does not hold the blocks which are processed for every input channel.
+    #[error("The input buffer has `{actual}` samples but it needs to have `{expected}` for the input channels.")]
+    InputBufferLength {
+        /// `ticks * block_size * input_channels`.
+        expected: usize,
+        /// The length of the buffer which is provided.
+        actual: usize,
+    },
+    /// The output buffer does not hold the blocks which are processed for every output channel.
+    #[error("The output buffer has `{actual}` samples but it needs to have `{expected}` for the output channels.")]
+    OutputBufferLength {
+        /// `ticks * block_size * output_channels`.
+        expected: usize,
+        /// The length of the buffer which is provided.
+        actual: usize,
+    },
+    /// Audio is processed before it is initialized, so the channel counts are not known.
+    #[error("Audio needs to be initialized before processing.")]
+    AudioNotInitialized,
+    /// Audio is reinitialized with other channel counts or another block size since the buffers are made for it,
+    /// e.g. a [`Rechunker`](crate::process::Rechunker) needs to be made again then.
+    #[error(
+        "Audio is reinitialized with another configuration since the buffers are made for it."
+    )]
+    AudioConfigChanged,
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
+}
+
+/// Errors related to creating a [`PdEngine`](crate::engine::PdEngine).
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum EngineError {
+    /// Pd could not be initialized.
+    #[error("Failed to initialize Pure Data.")]
+    Initialization(#[source] InitializationError),
+    /// Audio could not be initialized with the configuration.
+    #[error("Failed to initialize audio.")]
+    Audio(#[source] AudioInitializationError),
+    /// Audio is already initialized with another configuration, which is left as it is
+    /// since other engines and their renderers render with it.
+    #[error("Audio is already initialized with another configuration. Configured: {configured:?}, requested: {requested:?}")]
+    AudioConfigMismatch {
+        /// The configuration which audio is initialized with.
+        configured: AudioConfig,
+        /// The configuration which the engine is made with.
+        requested: AudioConfig,
+    },
 }
 
 /// Errors related to a lifecycle of a pd patch.
 #[non_exhaustive]
 #[derive(Error, Debug)]
 pub enum PatchLifeCycleError {
-    /// Failed to open patch for unknown reason.
-    #[error("Failed to open patch.")]
-    FailedToOpenPatch,
-    /// Failed to close patch, because the handle which was provided is null.
-    #[error("Failed to close patch, because the handle which was provided is null.")]
-    FailedToClosePatch,
+    /// Failed to open the patch at the contained path for unknown reason.
+    #[error("Failed to open patch. Path: {0}")]
+    FailedToOpenPatch(String),
+    /// Failed to close the patch at the contained path, because the handle which was provided is null or already closed.
+    #[error("Failed to close patch, because the handle which was provided is null or already closed. Path: {0}")]
+    FailedToClosePatch(String),
     /// The string which is passed could not be evaluated as a patch.
-    #[error("The string which is passed could not be evaluated as a patch.")]
-    FailedToEvaluateAsPatch { content: String, msg: String },
-    /// The patch which is trying to be communicated with is not open.
-    #[error("The patch which is trying to be communicated with is not open.")]
-    PatchIsNotOpen,
+    #[error("The string which is passed could not be evaluated as a patch: {msg}")]
+    FailedToEvaluateAsPatch {
+        /// The string which is passed.
+        content: String,
+        /// Why it could not be evaluated.
+        msg: String,
+    },
+    /// The patch at the contained path which is trying to be communicated with is not open.
+    ///
+    /// The path is empty when there is no patch at all.
+    #[error("The patch which is trying to be communicated with is not open. Path: {0}")]
+    PatchIsNotOpen(String),
     /// The path to the patch which are being tried to open is invalid.
     #[error("The path you have provided does not exist in the file system. Path: {0}")]
     PathDoesNotExist(String),
+    /// The path does not end with a file name or it is not valid UTF-8, so it can not be passed to pd.
+    #[error("The path can not be passed to Pure Data since it has no file name or it is not valid UTF-8. Path: {0}")]
+    InvalidPath(String),
+    /// The patch file exists but could not be read, e.g. because of its permissions or because it is a directory.
+    #[error("The patch file could not be read. Path: {path}")]
+    NotReadable {
+        /// The path to the patch file.
+        path: String,
+        /// The error of reading the file.
+        #[source]
+        source: std::io::Error,
+    },
+    /// Pd read the patch file but could not open a patch from it, e.g. because it is not a pd patch.
+    #[error("Pure Data could not open a patch from the file. Path: {path}, console output:\n{}", .console_output.join("\n"))]
+    EvaluationFailed {
+        /// The path to the patch file.
+        path: String,
+        /// The lines which pd printed to its console while opening the file.
+        console_output: Vec<String>,
+    },
+    /// Pd could not open a patch from the file and the file does not have the extension of a patch,
+    /// `.pd` or the `.pat` and `.mxt` of Max patches which pd imports.
+    #[error("Pure Data could not open a patch from the file, which does not have the `.pd` extension. Path: {path}, console output:\n{}", .console_output.join("\n"))]
+    UnexpectedExtension {
+        /// The path to the file.
+        path: String,
+        /// The lines which pd printed to its console while opening the file.
+        console_output: Vec<String>,
+    },
+    /// The patch is closed for reloading but could not be opened again.
+    #[error("The patch is closed but could not be opened again. Path: {path}")]
+    FailedToReopenPatch {
+        /// The path to the patch file.
+        path: String,
+        /// The error of opening the patch again.
+        #[source]
+        source: Box<Self>,
+    },
+    /// A string which is passed to pd contains a null byte, pd would only read it up to the null byte.
+    #[error("The {context} contains a null byte so it can not be passed to Pure Data: {input:?}")]
+    InvalidString {
+        /// What the string is passed as, e.g. `receiver name`.
+        context: &'static str,
+        /// The string which contains the null byte.
+        input: String,
+    },
+    /// A creation argument is an [`Atom::Pointer`](crate::types::Atom::Pointer) which is not passed to pd.
+    #[error("A pointer atom can not be a creation argument of a patch.")]
+    PointerArgument,
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
 }
 
 /// Errors related to a lifecycle of a pd gui.
+#[cfg(feature = "gui")]
 #[non_exhaustive]
 #[derive(Error, Debug)]
 pub enum GuiLifeCycleError {
-    /// Failed to open gui, most probably because the path is invalid to the pd binary.
-    #[error("Failed to open gui, please provide a valid path to the pd binary.")]
+    /// The contained path is not the main folder of a pd installation, it has no `tcl/pd-gui.tcl`.
+    #[error(
+        "Failed to find the pd gui in `{0}`, please provide the main folder of a pd installation."
+    )]
+    NotFound(String),
+    /// Failed to launch the gui process or to connect to it.
+    #[error("Failed to open gui, the gui process could not be launched or connected to.")]
     FailedToOpenGui,
+    /// Libpd returned an error code which this crate doesn't know about when starting the gui.
+    #[error("Failed to open gui with an unexpected return code: `{0}`.")]
+    Unexpected(i32),
+    /// No pd installation is found in the contained locations which are probed.
+    #[error("Failed to find a pd installation, the probed locations are: {0:?}")]
+    PdNotFound(Vec<std::path::PathBuf>),
+    /// The gui is not running, it needs to be started with [`start_gui`](crate::gui::start_gui) first.
+    #[error("The gui is not running, start it first.")]
+    NotRunning,
+    /// The window of the contained patch can not be shown or hidden since the patch is closed.
+    #[error("The patch `{0}` is closed, its window can not be shown or hidden.")]
+    PatchClosed(String),
+}
+
+/// Errors related to watching a patch file with [`watch_patch`](crate::watch::watch_patch).
+#[cfg(feature = "watch")]
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum WatchError {
+    /// The patch could not be opened.
+    #[error("Failed to open the patch to watch.")]
+    Patch(#[source] PatchLifeCycleError),
+    /// The directory of the patch could not be watched.
+    #[error("Failed to watch the patch file. Path: {path}")]
+    FailedToWatch {
+        /// The path to the patch file.
+        path: String,
+        /// The error of the file watcher.
+        #[source]
+        source: notify::Error,
+    },
+}
+
+/// Errors related to rendering a patch to a WAV file with [`render_to_wav`](crate::wav::render_to_wav).
+#[cfg(feature = "wav")]
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum WavError {
+    /// The patch could not be opened or closed.
+    #[error("Failed to open or close the patch to render.")]
+    Patch(#[source] PatchLifeCycleError),
+    /// Dsp could not be turned on.
+    #[error("Failed to turn on dsp.")]
+    Send(#[source] SendError),
+    /// The patch could not be rendered.
+    #[error("Failed to render the patch.")]
+    Process(#[source] ProcessError),
+    /// A WAV file could not be read or written.
+    #[error("Failed to read or write the WAV file. Path: {path}")]
+    Wav {
+        /// The path to the WAV file.
+        path: String,
+        /// The error of reading or writing the file.
+        #[source]
+        source: hound::Error,
+    },
+    /// Audio is initialized without output channels so there is nothing to write.
+    #[error(
+        "Audio is initialized without output channels, there is nothing to write to the WAV file."
+    )]
+    NoOutputChannels,
+    /// The channel count of the input file is not the input channel count of audio.
+    #[error("The input file has `{actual}` channels but audio is initialized with `{expected}` input channels.")]
+    InputChannelCount {
+        /// Number of input channels which audio is initialized with.
+        expected: i32,
+        /// Number of channels of the input file.
+        actual: u16,
+    },
+    /// The sample rate of the input file is not the sample rate of audio.
+    #[error(
+        "The input file has the sample rate `{actual}` but audio is initialized with `{expected}`."
+    )]
+    InputSampleRate {
+        /// Sample rate which audio is initialized with.
+        expected: i32,
+        /// Sample rate of the input file.
+        actual: u32,
+    },
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
+}
+
+/// Errors related to editing a patch with a [`CanvasEditor`](crate::dynamic::CanvasEditor).
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum DynamicPatchingError {
+    /// The patch which is trying to be edited is not open.
+    #[error("The patch which is trying to be edited is not open.")]
+    PatchNotOpen,
+    /// The patch file could not be read to find the objects which are already in the patch.
+    #[error("The patch file could not be read. Path: {path}")]
+    NotReadable {
+        /// The path to the patch file.
+        path: String,
+        /// The error of reading the file.
+        #[source]
+        source: std::io::Error,
+    },
+    /// The object is deleted or it does not belong to the edited patch.
+    #[error("The object is not in the patch which is edited.")]
+    UnknownObject,
+    /// The position of the object is not known, e.g. because it is a scalar, so it can not be deleted.
+    #[error("The object can not be deleted since its position in the patch is not known.")]
+    UnknownPosition,
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
 }
 
 /// Errors related to general filesystem access.
@@ -61,6 +392,9 @@ pub enum IoError {
     /// The path to the patch which are being tried to open is invalid.
     #[error("The path you have provided does not exist in the file system. Path: {0}")]
     PathDoesNotExist(String),
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
 }
 
 /// Errors related to sending messages to a pd patch.
@@ -73,6 +407,78 @@ pub enum SendError {
     /// A general error when the values which you are sending to the receiver are out of range.
     #[error("Values which are being sent are out of range.")]
     OutOfRange,
+    /// A message is finished from a receive callback while a message which is started outside of it is still in composition.
+    #[error("A message can not be finished from a receive callback while another message is being composed.")]
+    ReentrantSend,
+    /// The patch which a [`Destination`](crate::types::Destination) belongs to is closed, the receiver is named in it.
+    #[error("The patch of the destination `{0}` is closed.")]
+    PatchClosed(String),
+    /// A string which is passed to pd contains a null byte, pd would only read it up to the null byte.
+    #[error("The {context} contains a null byte so it can not be passed to Pure Data: {input:?}")]
+    InvalidString {
+        /// What the string is passed as, e.g. `receiver name`.
+        context: &'static str,
+        /// The string which contains the null byte.
+        input: String,
+    },
+    /// A list is longer than the atoms libpd takes in a single message,
+    /// see [`MAX_MESSAGE_LENGTH`](crate::send::MAX_MESSAGE_LENGTH) and [`send_large_list_to`](crate::send::send_large_list_to).
+    #[error("A message of `{requested}` atoms is too long, Pure Data takes up to `{max}` atoms in a message.")]
+    MessageTooLong {
+        /// The number of atoms which is tried to be sent.
+        requested: usize,
+        /// The number of atoms which could be sent in a message.
+        max: usize,
+    },
+    /// A list contains an [`Atom::Pointer`](crate::types::Atom::Pointer) which is not sent back to pd.
+    #[error("Pointer atoms can not be sent to Pure Data, the pointer they hold may not be valid anymore.")]
+    PointerAtom,
+    /// Libpd returned an error code which this crate doesn't know about.
+    ///
+    /// The receiver is named when the value is sent to one, MIDI messages are sent without a receiver.
+    #[error("Sending{} failed with an unexpected return code: `{code}`.", to_name("to", receiver.as_deref()))]
+    Unexpected {
+        /// The code which libpd returned.
+        code: i32,
+        /// The name of the receiver which the value is sent to.
+        receiver: Option<String>,
+    },
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
+}
+
+impl SendError {
+    /// Maps a non zero return code of a libpd function which sends values without a receiver, e.g. MIDI messages.
+    ///
+    /// These functions return `-1` when a value is out of its range,
+    /// other codes are [`Unexpected`](SendError::Unexpected).
+    #[must_use]
+    pub const fn from_code(code: i32) -> Self {
+        match code {
+            -1 => Self::OutOfRange,
+            code => Self::Unexpected {
+                code,
+                receiver: None,
+            },
+        }
+    }
+
+    /// Maps a non zero return code of a libpd function which sends to the receiver which is named `receiver`.
+    ///
+    /// These functions return `-1` when there is no receiver with the name in pd,
+    /// other codes are [`Unexpected`](SendError::Unexpected).
+    #[must_use]
+    pub fn from_receiver_code<T: AsRef<str>>(code: i32, receiver: T) -> Self {
+        let receiver = receiver.as_ref().to_owned();
+        match code {
+            -1 => Self::MissingDestination(receiver),
+            code => Self::Unexpected {
+                code,
+                receiver: Some(receiver),
+            },
+        }
+    }
 }
 
 /// Errors related to subscription to senders in a pd patch.
@@ -82,6 +488,20 @@ pub enum SubscriptionError {
     /// A failure of subscription to a sender with an unknown reason.
     #[error("Failed to subscribe to sender: `{0}` in loaded pd patch.")]
     FailedToSubscribeToSender(String),
+    /// The name of the sender to subscribe to is empty.
+    #[error("Can not subscribe to a sender with an empty name.")]
+    EmptySourceName,
+    /// A string which is passed to pd contains a null byte, pd would only read it up to the null byte.
+    #[error("The {context} contains a null byte so it can not be passed to Pure Data: {input:?}")]
+    InvalidString {
+        /// What the string is passed as, e.g. `receiver name`.
+        context: &'static str,
+        /// The string which contains the null byte.
+        input: String,
+    },
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
 }
 
 /// Errors related to sizes of entities.
@@ -89,21 +509,270 @@ pub enum SubscriptionError {
 #[derive(Error, Debug)]
 pub enum SizeError {
     /// The size of the entity is too large.
-    #[error("The maximum size specified is too large.")]
-    TooLarge,
+    #[error("The maximum size `{requested}` specified is too large.")]
+    TooLarge {
+        /// The size which is asked for.
+        requested: i32,
+    },
     /// Could not determine the size of the entity.
+    ///
+    /// The array functions return [`NonExistent`](SizeError::NonExistent) or [`Unexpected`](SizeError::Unexpected) instead.
     #[error("Could not determine the size.")]
     CouldNotDetermine,
+    /// The contained array which the size is asked for doesn't exist.
+    #[error("The array `{0}` which you're trying to access doesn't exist.")]
+    NonExistent(String),
+    /// Pd resized the array to a different size than the requested one, it clips sizes which are not positive or too large.
+    #[error("The array `{array}` is resized to `{actual}` elements instead of the requested `{requested}`, the size is clipped by pd.")]
+    Clipped {
+        /// The name of the array.
+        array: String,
+        /// The size which is asked for.
+        requested: i32,
+        /// The size which the array has after resizing it.
+        actual: usize,
+    },
+    /// Libpd returned an error code which this crate doesn't know about.
+    ///
+    /// The array is named when the size of an array is read or changed, it is not named when a message is started.
+    #[error("Determining the size{} failed with an unexpected return code: `{code}`.", to_name("of the array", array.as_deref()))]
+    Unexpected {
+        /// The code which libpd returned.
+        code: i32,
+        /// The name of the array.
+        array: Option<String>,
+    },
+    /// A message is started from a receive callback while a message which is started outside of it is still in composition.
+    #[error("A message can not be started from a receive callback while another message is being composed.")]
+    ReentrantSend,
+    /// A string which is passed to pd contains a null byte, pd would only read it up to the null byte.
+    #[error("The {context} contains a null byte so it can not be passed to Pure Data: {input:?}")]
+    InvalidString {
+        /// What the string is passed as, e.g. `receiver name`.
+        context: &'static str,
+        /// The string which contains the null byte.
+        input: String,
+    },
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
+}
+
+impl SizeError {
+    /// Maps a negative return code of libpd's array size functions.
+    ///
+    /// These functions return `-1` when the array which is named `array` does not exist,
+    /// other codes are [`Unexpected`](SizeError::Unexpected).
+    #[must_use]
+    pub fn from_code<T: AsRef<str>>(code: i32, array: T) -> Self {
+        let array = array.as_ref().to_owned();
+        match code {
+            -1 => Self::NonExistent(array),
+            code => Self::Unexpected {
+                code,
+                array: Some(array),
+            },
+        }
+    }
+
+    /// Maps a non zero return code of libpd's function which starts a message of `length` elements.
+    ///
+    /// It returns `-1` when the length of the message is too large,
+    /// other codes are [`Unexpected`](SizeError::Unexpected).
+    #[must_use]
+    pub const fn from_message_code(code: i32, length: i32) -> Self {
+        match code {
+            -1 => Self::TooLarge { requested: length },
+            code => Self::Unexpected { code, array: None },
+        }
+    }
 }
 
 /// Errors related to pd arrays.
 #[non_exhaustive]
 #[derive(Error, Debug)]
 pub enum ArrayError {
-    /// The array which is being tried to be accessed doesn't exist.
-    #[error("The array which you're trying to access doesn't exist.")]
-    FailedToFindArray,
-    /// The position in the array which is tried to be written is out of bounds.
-    #[error("The position in array which you're trying to write is out of bounds.")]
-    OutOfBounds,
+    /// The contained array which is being tried to be accessed doesn't exist.
+    #[error("The array `{0}` which you're trying to access doesn't exist.")]
+    FailedToFindArray(String),
+    /// The positions in the array which are tried to be read or written are out of bounds.
+    #[error("Reading or writing `{requested}` elements from `{offset}` is out of the bounds of the array `{array}` with `{array_len}` elements.")]
+    OutOfBounds {
+        /// The name of the array.
+        array: String,
+        /// The position which is read or written from.
+        offset: i32,
+        /// The number of elements which are requested to be read or written.
+        requested: i32,
+        /// The length of the array.
+        array_len: usize,
+    },
+    /// The position in the array which is read or written from is negative.
+    #[error("The offset `{0}` is negative, arrays are read and written from positive offsets.")]
+    NegativeOffset(i32),
+    /// The number of elements which are read or written is negative.
+    #[error("The amount `{0}` of elements to read or write is negative.")]
+    NegativeLength(i32),
+    /// The elements which are read from an array do not fit in the destination slice.
+    #[error(
+        "Reading `{needed}` elements does not fit in the destination with `{available}` elements."
+    )]
+    DestinationTooSmall {
+        /// The number of elements which are read.
+        needed: usize,
+        /// The length of the destination.
+        available: usize,
+    },
+    /// The range of the source slice which is tried to be written to an array does not fit in the slice.
+    #[error(
+        "The range `{start}..{end}` is out of the bounds of the source with `{length}` elements."
+    )]
+    SourceRangeOutOfBounds {
+        /// The start of the range.
+        start: usize,
+        /// The end of the range.
+        end: usize,
+        /// The length of the source.
+        length: usize,
+    },
+    /// The array could not be resized to fit the elements which are written to it, see [`SizeError`].
+    #[error("Resizing the array to fit the written elements failed.")]
+    Resize(#[source] SizeError),
+    /// A string which is passed to pd contains a null byte, pd would only read it up to the null byte.
+    #[error("The {context} contains a null byte so it can not be passed to Pure Data: {input:?}")]
+    InvalidString {
+        /// What the string is passed as, e.g. `receiver name`.
+        context: &'static str,
+        /// The string which contains the null byte.
+        input: String,
+    },
+    /// Libpd returned an error code which this crate doesn't know about.
+    #[error(
+        "Reading or writing the array `{array}` failed with an unexpected return code: `{code}`."
+    )]
+    Unexpected {
+        /// The name of the array.
+        array: String,
+        /// The code which libpd returned.
+        code: i32,
+    },
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
+}
+
+impl ArrayError {
+    /// Maps a non zero return code of libpd's array read and write functions.
+    ///
+    /// These functions return `-1` when the array which is named `array` does not exist
+    /// and `-2` when reading or writing `requested` elements from `offset` is out of the bounds of the array
+    /// with `array_len` elements, other codes are [`Unexpected`](ArrayError::Unexpected).
+    #[must_use]
+    pub fn from_code<T: AsRef<str>>(
+        code: i32,
+        array: T,
+        offset: i32,
+        requested: i32,
+        array_len: usize,
+    ) -> Self {
+        let array = array.as_ref().to_owned();
+        match code {
+            -2 => Self::OutOfBounds {
+                array,
+                offset,
+                requested,
+                array_len,
+            },
+            -1 => Self::FailedToFindArray(array),
+            code => Self::Unexpected { array, code },
+        }
+    }
+}
+
+/// Errors related to loading a sound file to an array with [`load_soundfile_to_array`](crate::array::load_soundfile_to_array).
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum SoundfileError {
+    /// The contained sound file does not exist.
+    #[error("The sound file `{0}` doesn't exist.")]
+    FileNotFound(String),
+    /// The contained array does not exist.
+    #[error("The array `{0}` to load the sound file to doesn't exist.")]
+    MissingArray(String),
+    /// The read message could not be sent, [`MissingDestination`](SendError::MissingDestination)
+    /// means the patch has no `[r $0-soundfiler]` which is connected to a `[soundfiler]`.
+    #[error("Failed to send the read message to the soundfiler of the patch.")]
+    Send(#[source] SendError),
+    /// The `[soundfiler]` could not read the sound file, e.g. because it is not in a format which pd reads.
+    #[error("The soundfiler could not read the sound file. Path: {path}, console output:\n{}", .console_output.join("\n"))]
+    FailedToRead {
+        /// The path to the sound file.
+        path: String,
+        /// The lines which pd printed to its console while reading the file.
+        console_output: Vec<String>,
+    },
+    /// Pd is used before it is initialized with [`init`](crate::init).
+    #[error("Pure Data is not initialized, call `init` first.")]
+    NotInitialized,
+}
+
+/// Errors related to parsing atoms from pd's text syntax with [`parse_atoms`](crate::types::parse_atoms).
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum AtomParseError {
+    /// The text ends with a backslash which escapes nothing.
+    #[error("The text ends with a backslash which doesn't escape any character.")]
+    TrailingBackslash,
+    /// Pd reads an unescaped `;` or `,` as a message separator, which is not an atom.
+    #[error(
+        "Pd reads `{0}` as a message separator which is not an atom, escape it with a backslash."
+    )]
+    Separator(char),
+    /// Pd reads an unescaped `$` followed by a digit as a dollar argument, which is not an atom.
+    #[error("Pd reads `{0}` as a dollar argument which is not an atom, escape the `$` with a backslash.")]
+    DollarArgument(String),
+    /// The text is parsed to a single [`Atom`](crate::types::Atom) but it contains the contained number of atoms.
+    #[error("Expected a single atom but the text contains `{0}` atoms.")]
+    NotOneAtom(usize),
+}
+
+/// Errors related to converting atoms to Rust types, see [`ExtractAtoms`](crate::types::ExtractAtoms).
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum AtomConversionError {
+    /// A float is expected but the atom is the contained symbol.
+    #[error("Expected a float but the atom is the symbol `{0}`.")]
+    ExpectedFloat(String),
+    /// A symbol is expected but the atom is the contained float.
+    #[error("Expected a symbol but the atom is the float `{0}`.")]
+    ExpectedSymbol(f64),
+    /// The atom is an [`Atom::Pointer`](crate::types::Atom::Pointer) which is neither a float nor a symbol.
+    #[error("Expected a float or a symbol but the atom is a pointer.")]
+    Pointer,
+    /// The contained float has a fraction or does not fit in the integer type.
+    #[error(
+        "The float `{0}` can not be converted to the integer type without changing its value."
+    )]
+    NotAnInteger(f64),
+    /// The contained integer is out of the range which pd floats represent exactly,
+    /// see [`MAX_EXACT_INTEGER`](crate::types::MAX_EXACT_INTEGER).
+    #[error("The integer `{0}` can not be represented exactly by a pd float.")]
+    NotExactInteger(String),
+    /// The number of atoms is not the number of values which are extracted.
+    #[error("Expected `{expected}` atoms to extract but there are `{found}`.")]
+    WrongArity {
+        /// The number of values which are extracted.
+        expected: usize,
+        /// The number of atoms.
+        found: usize,
+    },
+    /// The atom at `index` could not be converted.
+    #[error("Failed to convert the atom at index `{index}`.")]
+    Element {
+        /// The position of the atom in the list.
+        index: usize,
+        /// Why the atom could not be converted.
+        #[source]
+        source: Box<Self>,
+    },
 }
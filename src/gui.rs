@@ -1,11 +1,25 @@
-use crate::{error::GuiLifeCycleError, C_STRING_FAILURE};
+use crate::{error::GuiLifeCycleError, helpers::to_c_string};
 
-use std::ffi::CString;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// The script which pd runs to start the gui, relative to the main folder of pd.
+const PD_GUI_SCRIPT: &str = "tcl/pd-gui.tcl";
+
+/// Marks if a gui which is started with [`start_gui`] is connected.
+static GUI_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Counts the calls to [`stop_gui`] so the pollers of a stopped gui know when to stop.
+static GUI_STOPS: AtomicUsize = AtomicUsize::new(0);
 
 /// Opens the current patch within a pd vanilla GUI
 ///
 /// This function requires that there is a valid pd installation in your computer and a path to pd's main folder which contains `bin/`, `tcl/`, etc.
+/// [`find_pd_gui`] looks for it in the conventional locations and [`start_gui_auto`] starts the gui of the one it finds.
+/// It returns after the gui process connects to pd, which [`gui_is_running`] reports from then on.
 ///
 /// Note: Currently fails on windows. See the [issue](https://github.com/libpd/libpd/issues/352).
 ///
@@ -23,26 +37,213 @@ use std::path::Path;
 /// # Errors
 ///
 /// A list of errors that can occur:
+/// - [`NotFound`](crate::error::GuiLifeCycleError::NotFound)
 /// - [`FailedToOpenGui`](crate::error::GuiLifeCycleError::FailedToOpenGui)
+/// - [`Unexpected`](crate::error::GuiLifeCycleError::Unexpected)
 pub fn start_gui<T: AsRef<Path>>(path_to_pd: T) -> Result<(), GuiLifeCycleError> {
-    if path_to_pd.as_ref().exists() {
-        let path_to_pd = path_to_pd.as_ref().to_string_lossy();
-        let path_to_pd = CString::new(path_to_pd.as_ref()).expect(C_STRING_FAILURE);
-        unsafe {
-            match libpd_sys::libpd_start_gui(path_to_pd.as_ptr()) {
-                0 => return Ok(()),
-                _ => return Err(GuiLifeCycleError::FailedToOpenGui),
+    let path_to_pd = path_to_pd.as_ref();
+    if !is_pd_gui(path_to_pd) {
+        return Err(GuiLifeCycleError::NotFound(
+            path_to_pd.to_string_lossy().into_owned(),
+        ));
+    }
+    // A path with a null byte can not be found above.
+    let path_to_pd =
+        to_c_string(&path_to_pd.to_string_lossy()).map_err(GuiLifeCycleError::NotFound)?;
+    unsafe {
+        match libpd_sys::libpd_start_gui(path_to_pd.as_ptr()) {
+            0 => {
+                GUI_RUNNING.store(true, Ordering::SeqCst);
+                Ok(())
             }
+            -1 => Err(GuiLifeCycleError::FailedToOpenGui),
+            code => Err(GuiLifeCycleError::Unexpected(code)),
+        }
+    }
+}
+
+/// Checks if a gui which is started with this crate is connected to pd.
+///
+/// It becomes `true` when [`start_gui`] returns, since pd waits for the gui to connect before that,
+/// and `false` after [`stop_gui`].
+///
+/// Note: When the user quits pd from the gui or the gui process dies, pd ends the whole process
+/// instead of reporting it, so there is no state in between to observe.
+///
+/// # Examples
+/// ```no_run
+/// use libpd_rs::gui::{gui_is_running, start_gui_auto, stop_gui};
+///
+/// libpd_rs::init().unwrap();
+/// assert!(!gui_is_running());
+///
+/// start_gui_auto().unwrap();
+/// assert!(gui_is_running());
+///
+/// stop_gui();
+/// assert!(!gui_is_running());
+/// ```
+#[must_use]
+pub fn gui_is_running() -> bool {
+    GUI_RUNNING.load(Ordering::SeqCst)
+}
+
+/// Looks for the main folder of a pd installation which could be passed to [`start_gui`].
+///
+/// A folder is a pd installation if it contains `tcl/pd-gui.tcl`.
+/// The folders are probed in this order:
+/// - The installation of the `pd` binary which is found in `PATH`, e.g. `/usr/lib/puredata` for `/usr/bin/pd` if it links there.
+/// - On macos the `Pd*.app/Contents/Resources` folders in `/Applications` and `~/Applications`, newer versions first.
+/// - On linux `/usr/lib/puredata`, `/usr/local/lib/pd`, `/usr/lib/pd` and `/usr/local/lib/puredata`.
+/// - On windows `Pd` and the `Pd*` folders in the program files folders, newer versions first.
+///
+/// Returns `None` if none of them is a pd installation.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::gui::{find_pd_gui, start_gui};
+///
+/// libpd_rs::init().unwrap();
+///
+/// if let Some(path_to_pd) = find_pd_gui() {
+///     start_gui(path_to_pd).unwrap();
+/// }
+/// ```
+#[must_use]
+pub fn find_pd_gui() -> Option<PathBuf> {
+    pd_gui_candidates().into_iter().find(|path| is_pd_gui(path))
+}
+
+/// Opens the current patch within the pd vanilla GUI of the installation found by [`find_pd_gui`].
+///
+/// Use [`start_gui`] for installations in other locations.
+/// Returns the path of the installation which is started.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::gui::start_gui_auto;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let path_to_pd = start_gui_auto().unwrap();
+/// println!("Started the gui of {}", path_to_pd.display());
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`PdNotFound`](crate::error::GuiLifeCycleError::PdNotFound)
+/// - [`NotFound`](crate::error::GuiLifeCycleError::NotFound)
+/// - [`FailedToOpenGui`](crate::error::GuiLifeCycleError::FailedToOpenGui)
+/// - [`Unexpected`](crate::error::GuiLifeCycleError::Unexpected)
+pub fn start_gui_auto() -> Result<PathBuf, GuiLifeCycleError> {
+    let candidates = pd_gui_candidates();
+    let path_to_pd = candidates
+        .iter()
+        .find(|path| is_pd_gui(path))
+        .cloned()
+        .ok_or(GuiLifeCycleError::PdNotFound(candidates))?;
+    start_gui(&path_to_pd)?;
+    Ok(path_to_pd)
+}
+
+fn is_pd_gui(path: &Path) -> bool {
+    path.join(PD_GUI_SCRIPT).is_file()
+}
+
+/// Lists the folders which [`find_pd_gui`] probes, in order.
+fn pd_gui_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    // The binary is in the `bin` folder of the installation.
+    if let Some(paths) = std::env::var_os("PATH") {
+        let binary = if cfg!(windows) { "pd.exe" } else { "pd" };
+        candidates.extend(
+            std::env::split_paths(&paths)
+                .map(|directory| directory.join(binary))
+                .filter(|binary| binary.is_file())
+                .filter_map(|binary| std::fs::canonicalize(binary).ok())
+                .filter_map(|binary| Some(binary.parent()?.parent()?.to_path_buf())),
+        );
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let mut applications = vec![PathBuf::from("/Applications")];
+        if let Some(home) = std::env::var_os("HOME") {
+            applications.push(PathBuf::from(home).join("Applications"));
+        }
+        for directory in applications {
+            candidates.extend(
+                versioned_entries(&directory, "Pd", ".app")
+                    .into_iter()
+                    .map(|app| app.join("Contents").join("Resources")),
+            );
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    candidates.extend(
+        [
+            "/usr/lib/puredata",
+            "/usr/local/lib/pd",
+            "/usr/lib/pd",
+            "/usr/local/lib/puredata",
+        ]
+        .into_iter()
+        .map(PathBuf::from),
+    );
+
+    #[cfg(target_os = "windows")]
+    for variable in ["ProgramFiles", "ProgramFiles(x86)"] {
+        if let Some(directory) = std::env::var_os(variable) {
+            let directory = PathBuf::from(directory);
+            candidates.push(directory.join("Pd"));
+            candidates.extend(versioned_entries(&directory, "Pd-", ""));
+        }
+    }
+
+    let mut probed = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if !probed.contains(&candidate) {
+            probed.push(candidate);
         }
     }
-    Err(GuiLifeCycleError::FailedToOpenGui)
+    probed
+}
+
+/// Lists the entries in a directory which have the prefix and suffix, newer versions first.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn versioned_entries(directory: &Path, prefix: &str, suffix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut entries = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(prefix) && name.ends_with(suffix))
+        })
+        .map(|entry| entry.path())
+        .collect::<Vec<PathBuf>>();
+    // Names like `Pd-0.54-1.app` sort by version when their numbers have the same number of digits.
+    entries.sort_unstable_by(|a, b| b.cmp(a));
+    entries
 }
 
 /// Stops the current running pd vanilla GUI if it is running.
+///
+/// Calling it while no gui is running does nothing.
+/// The pollers which are spawned with [`spawn_gui_poller`] stop with it.
 pub fn stop_gui() {
-    unsafe {
-        libpd_sys::libpd_stop_gui();
-    };
+    GUI_STOPS.fetch_add(1, Ordering::SeqCst);
+    if GUI_RUNNING.swap(false, Ordering::SeqCst) {
+        unsafe {
+            libpd_sys::libpd_stop_gui();
+        };
+    }
 }
 
 /// Manually updates and handles any GUI messages
@@ -51,7 +252,8 @@ pub fn stop_gui() {
 ///
 /// Note:
 /// - This also facilitates network message processing, etc so it can be useful to call repeatedly when idle for more throughput.
-/// - Returns a `Some(())` when the polled queue is not empty. In this case it might be desirable to keep polling until it is empty or up to some reasonable limit.
+/// - Returns a `Some(())` when the polled queue is not empty. In this case it might be desirable to keep polling until it is empty or up to some reasonable limit,
+///   which [`poll_gui_for`] does within a time budget.
 ///
 /// # Examples
 /// ```no_run
@@ -74,3 +276,136 @@ pub fn poll_gui() -> Option<()> {
         }
     }
 }
+
+/// Updates and handles the pending GUI messages once, for hosts which poll from their own event loop.
+///
+/// Returns `true` if any GUI work is processed.
+/// See [`poll_gui`] for more details.
+///
+/// # Examples
+/// ```no_run
+/// use libpd_rs::gui::poll_gui_once;
+///
+/// libpd_rs::init();
+///
+/// // Somewhere in the event loop,
+/// while poll_gui_once() {}
+/// ```
+#[must_use]
+pub fn poll_gui_once() -> bool {
+    poll_gui().is_some()
+}
+
+/// Polls the gui until its queue is empty or the budget is spent.
+///
+/// Returns the number of polls which processed gui work.
+/// pd is locked separately for each poll, so audio is not held back for the whole budget.
+///
+/// # Examples
+/// ```no_run
+/// use libpd_rs::gui::poll_gui_for;
+/// use std::time::Duration;
+///
+/// libpd_rs::init();
+///
+/// // Once per frame, spend at most a millisecond on the gui.
+/// let processed = poll_gui_for(Duration::from_millis(1));
+/// ```
+#[must_use]
+pub fn poll_gui_for(budget: Duration) -> usize {
+    let started = Instant::now();
+    let mut processed = 0;
+    while started.elapsed() < budget && poll_gui_once() {
+        processed += 1;
+    }
+    processed
+}
+
+/// Polls the gui on a background thread so it does not freeze while the host is busy elsewhere.
+///
+/// The thread calls [`poll_gui`] every `interval` until the queue is empty.
+/// It skips polling while pd is locked exclusively, e.g. by [`with_pd_lock`](crate::with_pd_lock),
+/// and continues once the lock is free.
+///
+/// The thread stops when the returned [`GuiPoller`] is dropped or [`stop_gui`] is called.
+///
+/// # Examples
+/// ```no_run
+/// use libpd_rs::gui::{spawn_gui_poller, start_gui_auto};
+/// use std::time::Duration;
+///
+/// libpd_rs::init().unwrap();
+/// start_gui_auto().unwrap();
+///
+/// let poller = spawn_gui_poller(Duration::from_millis(10));
+/// // The gui stays responsive until the poller is dropped.
+/// ```
+///
+/// # Panics
+///
+/// Panics if the operating system fails to create the thread.
+#[must_use]
+pub fn spawn_gui_poller(interval: Duration) -> GuiPoller {
+    let stop = Arc::new(AtomicBool::new(false));
+    let stops = GUI_STOPS.load(Ordering::SeqCst);
+    let thread_stop = Arc::clone(&stop);
+    let thread = std::thread::Builder::new()
+        .name("libpd-rs gui poller".to_owned())
+        .spawn(move || {
+            let stopped =
+                || thread_stop.load(Ordering::SeqCst) || GUI_STOPS.load(Ordering::SeqCst) != stops;
+            while !stopped() {
+                while !stopped() {
+                    let Some(_lock) = crate::try_lock_for_processing() else {
+                        break;
+                    };
+                    if poll_gui().is_none() {
+                        break;
+                    }
+                }
+                std::thread::park_timeout(interval);
+            }
+        })
+        .expect("Failed to spawn the gui poller thread.");
+    GuiPoller {
+        stop,
+        thread: Some(thread),
+    }
+}
+
+/// A background thread which polls the gui, spawned with [`spawn_gui_poller`].
+///
+/// Dropping it stops the thread and waits for it to finish.
+#[derive(Debug)]
+pub struct GuiPoller {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl GuiPoller {
+    /// Checks if the thread is still polling.
+    ///
+    /// It is `false` after [`stop_gui`] is called.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_some_and(|thread| !thread.is_finished())
+    }
+
+    /// Stops the thread and waits for it to finish, which is the same as dropping the poller.
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for GuiPoller {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            // A panic in the thread is not propagated to the dropping thread.
+            let _ = thread.join();
+        }
+    }
+}
@@ -1,38 +1,29 @@
 #![allow(clippy::redundant_pub_crate)]
 
-/// Transforms an iterable of type `Atom` to a `Vec<libpd_sys::t_atom>`.
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_int;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::types::{Atom, AtomView, PdPointer};
+
+/// The number of atoms which [`with_t_atoms`] converts on the stack, longer lists are collected to a `Vec`.
+const STACK_ATOMS: usize = 64;
+
+/// The length of symbols which are null terminated on the stack before they are interned,
+/// longer symbols are copied to a `CString`.
+const STACK_SYMBOL_LENGTH: usize = 128;
+
+/// Transforms an iterable of type `Atom` to a `Result<Vec<libpd_sys::t_atom>, AtomListError>`.
+///
+/// The error is the first atom which can not be passed to pd, see [`AtomListError`].
+/// Use [`with_t_atoms`] to pass a list to pd right away without the `Vec`.
 macro_rules! make_t_atom_list_from_atom_list {
     ($list: expr) => {
         $list
             .into_iter()
-            .map(|atom_variant| match atom_variant {
-                Atom::Float(value) => {
-                    let t_atom = libpd_sys::t_atom {
-                        a_type: libpd_sys::t_atomtype_A_FLOAT,
-                        a_w: libpd_sys::word { w_float: *value },
-                    };
-                    let p = &t_atom as *const libpd_sys::t_atom as *mut libpd_sys::t_atom;
-                    // Using a setter us crucial or else float values become 0s when sending a list.
-                    unsafe {
-                        libpd_sys::libpd_set_double(p, *value);
-                    }
-                    t_atom
-                }
-
-                // If there will be a bug related to this later,
-                // Try using libpd_sys::libpd_set_symbol instead of manually setting the value.
-                Atom::Symbol(value) => libpd_sys::t_atom {
-                    a_type: libpd_sys::t_atomtype_A_SYMBOL,
-                    a_w: libpd_sys::word {
-                        w_symbol: unsafe {
-                            let sym = CString::new(value.to_owned()).unwrap();
-                            libpd_sys::gensym(sym.as_ptr())
-                        },
-                    },
-                },
-                // TODO: See if there are more cases to be covered.
-            })
-            .collect::<Vec<libpd_sys::t_atom>>()
+            .map(crate::helpers::to_t_atom)
+            .collect::<Result<Vec<libpd_sys::t_atom>, crate::helpers::AtomListError>>()
     };
 }
 
@@ -43,24 +34,316 @@ macro_rules! make_atom_list_from_t_atom_list {
             .into_iter()
             .map(|atom_type| match atom_type.a_type {
                 libpd_sys::t_atomtype_A_FLOAT => {
-                    let ptr_to_inner =
-                        atom_type as *const libpd_sys::t_atom as *mut libpd_sys::t_atom;
+                    let ptr_to_inner = std::ptr::from_ref(atom_type).cast_mut();
                     let f: f64 = unsafe { libpd_sys::libpd_get_double(ptr_to_inner) };
                     Atom::Float(f)
                 }
                 libpd_sys::t_atomtype_A_SYMBOL => {
-                    let ptr_to_inner =
-                        atom_type as *const libpd_sys::t_atom as *mut libpd_sys::t_atom;
+                    let ptr_to_inner = std::ptr::from_ref(atom_type).cast_mut();
                     let sym: *const std::os::raw::c_char =
                         unsafe { libpd_sys::libpd_get_symbol(ptr_to_inner) };
                     let result = unsafe { CStr::from_ptr(sym) };
-                    Atom::Symbol(result.to_str().unwrap().to_owned())
+                    Atom::Symbol(result.to_string_lossy().into_owned())
+                }
+                libpd_sys::t_atomtype_A_POINTER => {
+                    Atom::Pointer(crate::types::PdPointer::new(unsafe {
+                        atom_type.a_w.w_gpointer as usize
+                    }))
                 }
-                // TODO: See if there are more cases to be covered.
-                _ => unimplemented!(),
+                // Semicolons, commas and dollars are taken as symbols like pd prints them.
+                _ => Atom::Symbol(crate::helpers::special_atom_name(atom_type).into_owned()),
             })
             .collect::<Vec<Atom>>()
     };
 }
 
-pub(crate) use {make_atom_list_from_t_atom_list, make_t_atom_list_from_atom_list};
+/// Returns early with the given error if libpd is not initialized yet.
+///
+/// The check is compiled out with the `skip-init-checks` feature.
+macro_rules! return_if_not_initialized {
+    ($error: expr) => {
+        #[cfg(not(feature = "skip-init-checks"))]
+        {
+            if !crate::libpd_initialized() {
+                return Err($error);
+            }
+        }
+    };
+}
+
+/// The reason an atom in a list can not be passed to pd.
+#[derive(Debug)]
+pub(crate) enum AtomListError {
+    /// The symbol contains a null byte, see [`to_c_string`].
+    InvalidSymbol(String),
+    /// Pointer atoms are not sent back to pd, the pointer could be stale.
+    Pointer,
+}
+
+/// Converts an atom to be passed to pd.
+pub(crate) fn to_t_atom(atom: &Atom) -> Result<libpd_sys::t_atom, AtomListError> {
+    match atom {
+        Atom::Float(value) => {
+            let mut t_atom = libpd_sys::t_atom {
+                a_type: libpd_sys::t_atomtype_A_FLOAT,
+                a_w: libpd_sys::word { w_float: *value },
+            };
+            // Using a setter is crucial or else float values become 0s when sending a list.
+            unsafe {
+                libpd_sys::libpd_set_double(std::ptr::addr_of_mut!(t_atom), *value);
+            }
+            Ok(t_atom)
+        }
+        Atom::Symbol(value) => Ok(libpd_sys::t_atom {
+            a_type: libpd_sys::t_atomtype_A_SYMBOL,
+            a_w: libpd_sys::word {
+                w_symbol: gensym(value)?,
+            },
+        }),
+        Atom::Interned(symbol) => Ok(libpd_sys::t_atom {
+            a_type: libpd_sys::t_atomtype_A_SYMBOL,
+            a_w: libpd_sys::word {
+                w_symbol: symbol.as_ptr(),
+            },
+        }),
+        Atom::Pointer(_) => Err(AtomListError::Pointer),
+    }
+}
+
+/// Converts atoms to be passed to pd and calls `operation` with them.
+///
+/// Lists of up to [`STACK_ATOMS`] atoms are converted on the stack, so the conversion does not allocate
+/// unless they contain symbols longer than [`STACK_SYMBOL_LENGTH`]. Pd could still allocate, e.g. to intern a new symbol.
+pub(crate) fn with_t_atoms<R>(
+    list: &[Atom],
+    operation: impl FnOnce(&mut [libpd_sys::t_atom]) -> R,
+) -> Result<R, AtomListError> {
+    if list.len() > STACK_ATOMS {
+        let mut atoms = make_t_atom_list_from_atom_list!(list)?;
+        return Ok(operation(&mut atoms));
+    }
+    let mut buffer = [libpd_sys::t_atom {
+        a_type: libpd_sys::t_atomtype_A_FLOAT,
+        a_w: libpd_sys::word { w_float: 0.0 },
+    }; STACK_ATOMS];
+    let atoms = buffer.get_mut(..list.len()).unwrap_or_default();
+    for (t_atom, atom) in atoms.iter_mut().zip(list) {
+        *t_atom = to_t_atom(atom)?;
+    }
+    Ok(operation(atoms))
+}
+
+/// Views atoms which are received from pd and calls `operation` with them.
+///
+/// Lists of up to [`STACK_ATOMS`] atoms are viewed on the stack, so viewing them does not allocate
+/// unless they contain dollars or symbols which are not UTF-8, whose names are made for the views.
+pub(crate) fn with_atom_views<R>(
+    atom_list: &[libpd_sys::t_atom],
+    operation: impl FnOnce(&[AtomView]) -> R,
+) -> R {
+    let made_names = atom_list.iter().filter_map(made_name).collect::<Vec<_>>();
+    let mut made_names = made_names.iter();
+    if atom_list.len() > STACK_ATOMS {
+        return operation(
+            &atom_list
+                .iter()
+                .map(|atom| atom_view(atom, &mut made_names))
+                .collect::<Vec<_>>(),
+        );
+    }
+    let mut buffer = [AtomView::Float(0.0); STACK_ATOMS];
+    let views = buffer.get_mut(..atom_list.len()).unwrap_or_default();
+    for (view, atom) in views.iter_mut().zip(atom_list) {
+        *view = atom_view(atom, &mut made_names);
+    }
+    operation(views)
+}
+
+/// Views an atom which is received from pd, the symbol is borrowed from pd.
+///
+/// Atoms which have a [`made_name`] take the next one of `made_names`,
+/// which are the made names of the list in order.
+fn atom_view<'a>(
+    atom: &'a libpd_sys::t_atom,
+    made_names: &mut std::slice::Iter<'a, String>,
+) -> AtomView<'a> {
+    match atom.a_type {
+        libpd_sys::t_atomtype_A_FLOAT => {
+            let ptr_to_inner = std::ptr::from_ref(atom).cast_mut();
+            AtomView::Float(unsafe { libpd_sys::libpd_get_double(ptr_to_inner) })
+        }
+        libpd_sys::t_atomtype_A_POINTER => {
+            AtomView::Pointer(PdPointer::new(unsafe { atom.a_w.w_gpointer as usize }))
+        }
+        libpd_sys::t_atomtype_A_DOLLAR => {
+            AtomView::Symbol(made_names.next().map_or("$", String::as_str))
+        }
+        // Semicolons, commas and dollar symbols are viewed as symbols like pd prints them.
+        _ => match symbol_name(atom).map(CStr::to_str) {
+            Some(Ok(name)) => AtomView::Symbol(name),
+            Some(Err(_)) => AtomView::Symbol(made_names.next().map_or("", String::as_str)),
+            None => AtomView::Symbol(fixed_atom_name(atom)),
+        },
+    }
+}
+
+/// Makes the name of an atom which can not be borrowed from pd.
+///
+/// Dollars are named by their index and symbols which are not UTF-8 are converted lossily,
+/// the other atoms are not named here.
+fn made_name(atom: &libpd_sys::t_atom) -> Option<String> {
+    if atom.a_type == libpd_sys::t_atomtype_A_DOLLAR {
+        return Some(dollar_name(unsafe { atom.a_w.w_index }));
+    }
+    match symbol_name(atom)?.to_string_lossy() {
+        Cow::Owned(name) => Some(name),
+        Cow::Borrowed(_) => None,
+    }
+}
+
+/// The name of a symbol or a dollar symbol, `None` for other atoms.
+fn symbol_name(atom: &libpd_sys::t_atom) -> Option<&'static CStr> {
+    if atom.a_type != libpd_sys::t_atomtype_A_SYMBOL
+        && atom.a_type != libpd_sys::t_atomtype_A_DOLLSYM
+    {
+        return None;
+    }
+    let symbol = unsafe { atom.a_w.w_symbol };
+    if symbol.is_null() {
+        return None;
+    }
+    // Pd never frees the names of symbols.
+    Some(unsafe { CStr::from_ptr((*symbol).s_name) })
+}
+
+/// Names an atom which is not a float, a symbol or a pointer like pd prints it.
+///
+/// Semicolons and commas are `";"` and `","`, dollars are `"$1"` and dollar symbols are their symbol, e.g. `"$1-foo"`.
+/// The other types do not occur in messages, they are named `""`.
+/// Names which are not UTF-8 are converted lossily.
+pub(crate) fn special_atom_name(atom: &libpd_sys::t_atom) -> Cow<'static, str> {
+    made_name(atom).map_or_else(
+        || {
+            Cow::Borrowed(
+                symbol_name(atom)
+                    .and_then(|name| name.to_str().ok())
+                    .unwrap_or_else(|| fixed_atom_name(atom)),
+            )
+        },
+        Cow::Owned,
+    )
+}
+
+/// Names semicolons, commas and other atoms which are not named by [`made_name`] or [`symbol_name`].
+const fn fixed_atom_name(atom: &libpd_sys::t_atom) -> &'static str {
+    match atom.a_type {
+        libpd_sys::t_atomtype_A_SEMI => ";",
+        libpd_sys::t_atomtype_A_COMMA => ",",
+        libpd_sys::t_atomtype_A_DOLLAR => "$",
+        _ => "",
+    }
+}
+
+/// Names the dollar of an index, e.g. `"$1"`.
+fn dollar_name(index: c_int) -> String {
+    if index < 0 {
+        return "$".to_owned();
+    }
+    format!("${index}")
+}
+
+/// Interns a symbol in pd, it is null terminated on the stack if it is short enough.
+fn gensym(symbol: &str) -> Result<*mut libpd_sys::t_symbol, AtomListError> {
+    let bytes = symbol.as_bytes();
+    if bytes.contains(&0) {
+        return Err(AtomListError::InvalidSymbol(symbol.to_owned()));
+    }
+    let mut buffer = [0_u8; STACK_SYMBOL_LENGTH];
+    match buffer.get_mut(..bytes.len()) {
+        // The symbol is shorter than the buffer, so it ends with at least one null byte.
+        Some(start) if bytes.len() < STACK_SYMBOL_LENGTH => {
+            start.copy_from_slice(bytes);
+            Ok(unsafe { libpd_sys::gensym(buffer.as_ptr().cast()) })
+        }
+        _ => {
+            let symbol = to_c_string(symbol).map_err(AtomListError::InvalidSymbol)?;
+            Ok(unsafe { libpd_sys::gensym(symbol.as_ptr()) })
+        }
+    }
+}
+
+/// The object which is left as the last popped object of pd after `$0` is read, see [`dollar_zero`].
+static LAST_POPPED_SENTINEL: AtomicUsize = AtomicUsize::new(0);
+
+/// Ignores the `loadbang` which pd sends to the last popped object after evaluating a file.
+const unsafe extern "C" fn ignore_loadbang(
+    _sentinel: *mut libpd_sys::t_pd,
+    _selector: *mut libpd_sys::t_symbol,
+    _argc: std::os::raw::c_int,
+    _argv: *mut libpd_sys::t_atom,
+) {
+}
+
+/// Reads the `$0` of an open patch.
+///
+/// `libpd_getdollarzero` pushes and pops the patch, which leaves it as the last popped object of pd.
+/// Pd sends a `loadbang` to that object after evaluating a file which creates no canvas,
+/// so after the patch is closed it would be sent to freed memory.
+/// A long lived object which ignores the `loadbang` is pushed and popped after it to take its place.
+///
+/// # Safety
+///
+/// `handle` has to be the handle of an open patch.
+pub(crate) unsafe fn dollar_zero(handle: *mut std::ffi::c_void) -> i32 {
+    let dollar_zero = libpd_sys::libpd_getdollarzero(handle);
+    libpd_sys::sys_lock();
+    let mut sentinel = LAST_POPPED_SENTINEL.load(Ordering::Acquire) as *mut libpd_sys::t_pd;
+    if sentinel.is_null() {
+        // Pd is locked, so the sentinel is made once.
+        let class = libpd_sys::class_new64(
+            libpd_sys::gensym(c"libpd-rs-last-popped".as_ptr()),
+            None,
+            None,
+            std::mem::size_of::<libpd_sys::t_pd>(),
+            // This is fine since the flag is a small constant.
+            #[allow(clippy::cast_possible_wrap)]
+            {
+                libpd_sys::CLASS_PD as i32
+            },
+            libpd_sys::t_atomtype_A_NULL,
+        );
+        libpd_sys::class_addmethod(
+            class,
+            // Pd calls methods which take `A_GIMME` with these arguments.
+            Some(std::mem::transmute::<
+                unsafe extern "C" fn(
+                    *mut libpd_sys::t_pd,
+                    *mut libpd_sys::t_symbol,
+                    std::os::raw::c_int,
+                    *mut libpd_sys::t_atom,
+                ),
+                unsafe extern "C" fn(),
+            >(ignore_loadbang)),
+            libpd_sys::gensym(c"loadbang".as_ptr()),
+            libpd_sys::t_atomtype_A_GIMME,
+            libpd_sys::t_atomtype_A_NULL,
+        );
+        sentinel = libpd_sys::pd_new(class);
+        LAST_POPPED_SENTINEL.store(sentinel as usize, Ordering::Release);
+    }
+    libpd_sys::pd_pushsym(sentinel);
+    libpd_sys::pd_popsym(sentinel);
+    libpd_sys::sys_unlock();
+    dollar_zero
+}
+
+/// Converts a string to be passed to pd, returns the string back if it contains a null byte.
+///
+/// Pd reads strings up to their first null byte, so these strings can not be passed to it.
+pub(crate) fn to_c_string(input: &str) -> Result<CString, String> {
+    CString::new(input).map_err(|_| input.to_owned())
+}
+
+pub(crate) use {
+    make_atom_list_from_t_atom_list, make_t_atom_list_from_atom_list, return_if_not_initialized,
+};
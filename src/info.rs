@@ -0,0 +1,73 @@
+pub use crate::block_size;
+
+/// The number of frames per pd tick in the libpd which libpd-sys builds, the value of [`block_size`].
+///
+/// libpd returns the compile time `DEFDACBLKSIZE` of pd which libpd-sys does not change,
+/// so buffers could be sized at compile time.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::info::{block_size, BLOCK_SIZE};
+///
+/// // A buffer for a stereo tick.
+/// let output = [0.0_f32; BLOCK_SIZE * 2];
+///
+/// assert_eq!(block_size() as usize, BLOCK_SIZE);
+/// assert_eq!(output.len(), 128);
+/// ```
+pub const BLOCK_SIZE: usize = 64;
+
+/// Gets the version of pd which libpd is built with as `(major, minor, bugfix)`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::info::pd_version;
+///
+/// let (major, minor, bugfix) = pd_version();
+/// println!("Linked to Pure Data {major}.{minor}-{bugfix}");
+/// ```
+#[must_use]
+pub fn pd_version() -> (i32, i32, i32) {
+    let (mut major, mut minor, mut bugfix) = (0, 0, 0);
+    unsafe {
+        libpd_sys::sys_getversion(
+            std::ptr::addr_of_mut!(major),
+            std::ptr::addr_of_mut!(minor),
+            std::ptr::addr_of_mut!(bugfix),
+        );
+    }
+    (major, minor, bugfix)
+}
+
+/// Gets the number of bits in a pd float, `32` or `64` if libpd is built with `PD_FLOATSIZE=64`.
+///
+/// The double precision functions like [`send_double_to`](crate::send::send_double_to)
+/// are only full precision with `64`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::info::float_size;
+///
+/// assert!(float_size() == 32 || float_size() == 64);
+/// ```
+#[must_use]
+pub const fn float_size() -> usize {
+    std::mem::size_of::<libpd_sys::t_float>() * 8
+}
+
+/// Gets the number of pd instances.
+///
+/// It is always `1` unless libpd is built with multi instance support.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::info::instance_count;
+///
+/// libpd_rs::init();
+///
+/// assert!(instance_count() >= 1);
+/// ```
+#[must_use]
+pub fn instance_count() -> i32 {
+    unsafe { libpd_sys::libpd_num_instances() }
+}
@@ -1,10 +1,4 @@
-#![warn(
-    clippy::all,
-    clippy::pedantic,
-    clippy::restriction,
-    clippy::nursery,
-    clippy::cargo
-)]
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(
     // Group of too restrictive lints
     clippy::undocumented_unsafe_blocks,
@@ -102,6 +96,29 @@ doc = ::embed_doc_image::embed_image!("phasor_patch", "assets/phasor_patch.png")
 //!
 //! ## Examples and Usage
 //!
+//! The quickest way to get a patch running is [`Pd`](crate::engine::Pd),
+//! which initializes pd and its audio, opens patches, sends to them and calls closures with what they send:
+//!
+//! ```rust
+//! use libpd_rs::{engine::Pd, types::AudioConfig};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let mut pd = Pd::new(AudioConfig::stereo_output_44k())?;
+//!     let _patch = pd.open("tests/patches/echo.pd")?;
+//!     let renderer = pd.audio()?;
+//!     pd.on("float_from_pd", |value: f32| println!("pd says {value}"))?;
+//!     pd.send("float_from_rust", 440.0)?;
+//!
+//!     // Call this in the audio callback, e.g. the one of cpal.
+//!     let mut output = vec![0.0_f32; pd.output_len(8)];
+//!     renderer.render(&[], &mut output)?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`PdEngine`](crate::engine::PdEngine) does the same for a single patch which it owns.
+//! The examples below use the lower level pieces which give more control.
+//!
 //! To start making sound with [libpd-rs](https://github.com/alisomay/libpd-rs), we need to have a pd patch at hand.
 //! Pd patches are `.pd` files which could be read by pd desktop [application](https://puredata.info/downloads).
 //!
@@ -414,6 +431,28 @@ doc = ::embed_doc_image::embed_image!("phasor_patch", "assets/phasor_patch.png")
 //!
 //! Enjoy!
 //!
+//! ## Features
+//!
+//! - `std` (default): Conveniences which need a writable file system,
+//!   [`open_patch_from_str`] and [`PdGlobal::eval_patch`](crate::convenience::PdGlobal::eval_patch)
+//!   write patches to temporary files with the [tempfile](https://crates.io/crates/tempfile) crate.
+//!   Disabling it leaves the wrappers in the [`array`], [`send`], [`receive`] and [`process`] modules
+//!   and opening patches from files.
+//!   The crate still links to the standard library since [libpd-sys](https://crates.io/crates/libpd-sys)
+//!   and [libffi](https://crates.io/crates/libffi) need it.
+//! - `gui`: Adds the [`gui`](crate::gui) module which starts, polls and stops the gui of a pd installation.
+//!   Without it the crate has no code which talks to the gui, calling gui functions is a compile error.
+//!   libpd itself is still compiled with its gui support, which stays idle unless the gui is started.
+//! - `skip-init-checks`: Most of the functions in this crate return a `NotInitialized` error
+//!   when they are called before [`init`], instead of reaching uninitialized state in libpd.
+//!   The check is a single atomic load, enabling this feature compiles it out.
+//! - `watch`: Adds the [`watch`](crate::watch) module which reloads patches when their files change,
+//!   it depends on the [notify](https://crates.io/crates/notify) crate and enables `std`.
+//! - `wav`: Adds the [`wav`](crate::wav) module which renders patches offline to WAV files,
+//!   it depends on the [hound](https://crates.io/crates/hound) crate.
+//! - `rtrb`: Adds [`read_float_array_into_producer`](crate::array::read_float_array_into_producer)
+//!   which reads a pd array into a lock-free ring buffer of the [rtrb](https://crates.io/crates/rtrb) crate.
+//!
 //! ## Things to note
 //!
 //! [libpd](https://github.com/libpd/libpd) is a C library and the implementation allocates libpd **globally**.
@@ -421,6 +460,9 @@ doc = ::embed_doc_image::embed_image!("phasor_patch", "assets/phasor_patch.png")
 //! There is support for [multi instances](https://github.com/libpd/libpd/blob/master/libpd_wrapper/z_libpd.h#L529)
 //! in [libpd](https://github.com/libpd/libpd) but those
 //! will be implemented in [libpd-rs](https://github.com/alisomay/libpd-rs) in the future.
+//! [libpd-sys](https://crates.io/crates/libpd-sys) compiles libpd without `PD_MULTI`,
+//! so instances can not be created, freed or switched between (`libpd_set_instance`) yet
+//! and the single instance lives until the process exits.
 //!
 //! This is not very Rust like and on top of that we do not manage the memory of that instantiation.
 //! Using it from different threads is fine because [libpd](https://github.com/libpd/libpd) has
@@ -428,6 +470,10 @@ doc = ::embed_doc_image::embed_image!("phasor_patch", "assets/phasor_patch.png")
 //!
 //! On the other hand, the programming style and state tracking might be a little different that how we do it in Rust.
 //!
+//! Closures which are registered with the `on_*` functions in the [`receive`](crate::receive) module are called from C.
+//! A panic in one of them is caught before it reaches pd and the closure is disabled afterwards,
+//! see [`take_last_callback_panic`](crate::receive::take_last_callback_panic).
+//!
 //! There are many functions and less data structures in this crate.
 //! Because all, of those functions act as methods for a globally
 //! initialized singleton libpd instance.
@@ -521,6 +567,12 @@ doc = ::embed_doc_image::embed_image!("phasor_patch", "assets/phasor_patch.png")
 /// }
 /// ```
 pub mod array;
+/// Typed context for callbacks
+///
+/// Callbacks which are registered to pd often need the state of the application.
+/// Instead of capturing globals, a context of any type could be attached with [`set_context`](crate::context::set_context)
+/// and borrowed in the callbacks with [`context_mut`](crate::context::context_mut).
+pub mod context;
 /// Convenience functions and types which encapsulate common actions when communicating with pd
 ///
 /// `libpd-rs` is a safe wrapper around [`libpd`](https://github.com/libpd/libpd) which provides a convenient interface to pd for Rust ecosystem.
@@ -576,6 +628,62 @@ pub mod array;
 /// }
 /// ```
 pub mod convenience;
+/// Dynamic patching
+///
+/// Pd patches can be edited while they are running by sending messages to their canvas,
+/// which address the objects of a patch by their order of creation.
+///
+/// This module provides [`CanvasEditor`](crate::dynamic::CanvasEditor) which creates, connects and deletes objects
+/// and keeps the order of them, so objects are referred to with an [`ObjId`](crate::dynamic::ObjId) which does not change.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::{dynamic::CanvasEditor, open_patch, send::send_float_to};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let mut patch = open_patch("tests/patches/simple.pd").unwrap();
+/// let mut editor = CanvasEditor::new(&mut patch).unwrap();
+///
+/// let receiver = editor.add_object(20, 20, "r frequency");
+/// let osc = editor.add_object(20, 50, "osc~");
+/// let dac = editor.add_object(20, 80, "dac~");
+/// editor.connect(receiver, 0, osc, 0).unwrap();
+/// editor.connect(osc, 0, dac, 0).unwrap();
+///
+/// send_float_to("frequency", 440.0).unwrap();
+/// ```
+pub mod dynamic;
+/// A single entry point to pd
+///
+/// Making sound with pd takes a few steps, pd and its audio are initialized, a patch is opened,
+/// audio is turned on and the scheduler of pd is run in the audio callback.
+///
+/// [`Pd`](crate::engine::Pd) does all of these and calls closures with the values which senders in patches send,
+/// so it is the easiest way to get started. [`PdEngine`](crate::engine::PdEngine) does them for a single patch which it owns.
+/// The rest of the crate is there when more control is needed.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{engine::PdEngine, types::AudioConfig};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mut engine = PdEngine::new(AudioConfig::stereo_output_44k())?;
+///     engine.load("tests/patches/sine.pd")?;
+///     engine.start()?;
+///
+///     // The renderer could be moved to the audio callback.
+///     let renderer = engine.renderer();
+///     let mut output = vec![0.0_f32; engine.output_len(8)];
+///     renderer.render(&[], &mut output)?;
+///
+///     // Messages are sent from where the engine is.
+///     engine.send_float("frequency", 220.0).ok();
+///
+///     Ok(())
+/// }
+/// ```
+pub mod engine;
 /// All errors
 ///
 /// This module contains all the errors which can be returned by the library.
@@ -586,14 +694,42 @@ pub mod error;
 ///
 /// If the pd desktop application is installed in your computer.
 /// You may use these functions to launch or quit it.
+///
+/// This module is only available with the `gui` feature.
+#[cfg(feature = "gui")]
 pub mod gui;
 
+/// Diagnostics
+///
+/// This module provides [`pd_version`](crate::info::pd_version), [`float_size`](crate::info::float_size),
+/// [`instance_count`](crate::info::instance_count) and [`BLOCK_SIZE`](crate::info::BLOCK_SIZE)
+/// which tell how the linked libpd is built,
+/// e.g. to include them in bug reports or to handle differences between pd versions.
+pub mod info;
+/// Parameters mirrored between Rust and a patch
+///
+/// [`bind_param`](crate::params::bind_param) binds a name to a [`Param`](crate::params::Param) whose value is sent
+/// as a float to the receivers of the name in the patch, e.g. `[r cutoff]`.
+/// Values which are set rapidly are sent once with [`flush_params`](crate::params::flush_params),
+/// and a parameter could listen to the sends of the patch to the same name to follow the changes made there.
+///
+/// Parameters keep their values in atomics, so they could be set and read from any thread.
+/// Their values could be captured in a [`Preset`](crate::params::Preset) with [`snapshot`](crate::params::snapshot)
+/// and sent back at once with [`apply`](crate::params::apply),
+/// or crossfaded to over a duration with [`start_morph`](crate::params::start_morph).
+pub mod params;
+
 /// Audio processing
 ///
 /// Process functions which you call in your audio callback are collected here.
 ///
 /// These functions also run the scheduler of pd. The chosen function needs to be called in a loop to keep pd "running".
 ///
+/// Every processed tick advances the logical time of pd by one block, independent of the wall clock.
+/// Time based objects like `[metro]`, `[delay]` or `[vline~]` follow the processed audio,
+/// so calling the functions faster than realtime renders a patch offline without any extra scheduling,
+/// see [`render_offline`](crate::process::render_offline).
+///
 /// # Examples
 ///
 /// ```rust
@@ -869,6 +1005,24 @@ pub mod receive;
 /// }
 /// ```
 pub mod send;
+/// Route received messages to sinks
+///
+/// The closures of the [`receive`](crate::receive) module receive the messages of every source.
+/// A [`MessageSink`](crate::sink::MessageSink) receives the messages of the sources which it is subscribed to
+/// with [`subscribe_with`](crate::sink::subscribe_with), so where the messages go is chosen per source.
+///
+/// Sinks which call a closure, send to a channel or collect the messages in a `Vec` are provided,
+/// others could be made by implementing the trait.
+pub mod sink;
+/// Intern symbols in pd
+///
+/// Every name which is passed to pd, like the receiver of a message, is interned by pd to a symbol before it is used.
+/// [`intern`](crate::symbol::intern) interns a name once to an [`InternedSymbol`](crate::symbol::InternedSymbol)
+/// which the send functions pass to pd as it is, so sending to the same receiver often does not convert or look up its name again.
+///
+/// Names which are passed as strings are interned through a small cache of the most recently used ones,
+/// see [`AsPdSymbol`](crate::symbol::AsPdSymbol).
+pub mod symbol;
 /// Types for working with pd
 ///
 /// Pd wraps primitive types such as a float or a string in a type called atom.
@@ -878,41 +1032,172 @@ pub mod send;
 ///
 /// It also exposes some others to hold file or receiver handles returned from libpd functions.
 pub mod types;
+/// Allocate voices to notes
+///
+/// Synth patches often route their notes through `[poly]` to play them with a number of voices.
+/// [`VoiceAllocator`](crate::voice::VoiceAllocator) allocates the voices on the Rust side instead
+/// and sends the notes to a receiver in the `voice note velocity` format which `[poly]` outputs, stealing voices when they run out.
+pub mod voice;
+/// Reload patches when their files change
+///
+/// This module is only available with the `watch` feature.
+///
+/// It provides [`watch_patch`](crate::watch::watch_patch) which reloads a patch whenever the file of it is saved,
+/// which is useful while developing a patch alongside the program which hosts it.
+#[cfg(feature = "watch")]
+pub mod watch;
+/// Render patches to WAV files
+///
+/// This module is only available with the `wav` feature.
+///
+/// It provides [`render_to_wav`](crate::wav::render_to_wav) which renders a patch offline for a duration
+/// and writes its output to a WAV file with the [hound](https://crates.io/crates/hound) crate,
+/// optionally feeding the input of the patch from another WAV file.
+#[cfg(feature = "wav")]
+pub mod wav;
 
 pub(crate) mod helpers;
+pub(crate) mod patch_file;
+pub(crate) mod queue;
+pub(crate) mod registry;
 use error::{AudioInitializationError, PatchLifeCycleError};
+use helpers::{make_t_atom_list_from_atom_list, return_if_not_initialized, to_c_string};
 
 use crate::{
     error::{InitializationError, IoError},
-    types::PatchFileHandle,
+    types::{Atom, AudioConfig, InitOptions, Patch, PatchInfo},
 };
 
-use std::ffi::CString;
+use std::cell::Cell;
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Tracks if pd is initialized and its internal queues are alive.
+///
+/// It is set by [`init`] and cleared by [`release_internal_queues`].
+/// Anything which cleans up pd resources on drop checks this before calling into pd,
+/// so dropping a handle after the queues are released does not touch freed state.
+static PD_INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Tracks if libpd is initialized once.
+///
+/// Libpd can not be de-initialized so this is never cleared.
+/// Functions which need libpd's global state check it to return an error instead of crashing.
+static LIBPD_INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Checks if libpd is initialized once, even if the internal queues are released afterwards.
+#[cfg(not(feature = "skip-init-checks"))]
+pub(crate) fn libpd_initialized() -> bool {
+    LIBPD_INITIALIZED.load(Ordering::SeqCst)
+}
+
+/// Serializes calls to [`init`] so concurrent callers can not initialize pd twice.
+static INITIALIZATION_LOCK: Mutex<()> = Mutex::new(());
+
+/// The audio configuration which pd is initialized with.
+static AUDIO_CONFIG: Mutex<Option<AudioConfig>> = Mutex::new(None);
+
+/// Serializes opening patches, since pd gives the creation arguments to the next patch which is opened.
+static OPENING_LOCK: Mutex<()> = Mutex::new(());
+
+/// Processing functions hold a read lock of it while they run.
+///
+/// Changing the audio configuration and [`with_pd_lock`] hold a write lock of it,
+/// so nothing else can happen in the middle of processing.
+static PROCESSING_LOCK: RwLock<()> = RwLock::new(());
+
+thread_local! {
+    /// Marks if the current thread holds the write lock of [`PROCESSING_LOCK`].
+    static HOLDS_PD_LOCK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Locks for the duration of a processing call.
+///
+/// Returns `None` if the current thread already holds the lock exclusively.
+pub(crate) fn lock_for_processing() -> Option<RwLockReadGuard<'static, ()>> {
+    if HOLDS_PD_LOCK.with(Cell::get) {
+        return None;
+    }
+    // Poisoning is irrelevant here since the lock guards no data.
+    Some(
+        PROCESSING_LOCK
+            .read()
+            .unwrap_or_else(PoisonError::into_inner),
+    )
+}
 
-// TODO: Currently panicing is enough since this is a rare case, but may be improved later with a dedicated error.
-pub(crate) const C_STRING_FAILURE: &str =
-    "Provided an invalid CString, check if your string contains null bytes in the middle.";
-pub(crate) const C_STR_FAILURE: &str = "Converting a CStr to an &str is failed.";
+/// Locks like [`lock_for_processing`] without waiting.
+///
+/// Returns `None` if the lock is held exclusively by any thread at the moment.
+#[cfg(feature = "gui")]
+pub(crate) fn try_lock_for_processing() -> Option<RwLockReadGuard<'static, ()>> {
+    use std::sync::TryLockError;
+
+    if HOLDS_PD_LOCK.with(Cell::get) {
+        return None;
+    }
+    match PROCESSING_LOCK.try_read() {
+        Ok(guard) => Some(guard),
+        // Poisoning is irrelevant here since the lock guards no data.
+        Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+/// Locks exclusively, waiting for running processing calls to finish.
+///
+/// Returns `None` if the current thread already holds the lock exclusively.
+fn lock_exclusively() -> Option<RwLockWriteGuard<'static, ()>> {
+    if HOLDS_PD_LOCK.with(Cell::get) {
+        return None;
+    }
+    Some(
+        PROCESSING_LOCK
+            .write()
+            .unwrap_or_else(PoisonError::into_inner),
+    )
+}
+
+/// Gets the audio configuration for reading or writing.
+fn audio_config_slot() -> MutexGuard<'static, Option<AudioConfig>> {
+    // Poisoning is irrelevant here since the configuration is always written as a whole.
+    AUDIO_CONFIG.lock().unwrap_or_else(PoisonError::into_inner)
+}
 
 /// Initializes libpd.
 ///
 /// This function should be called **before** any other in this crate.
-/// It initializes libpd **globally** and also initializes ring buffers for internal message passing.
+/// It initializes libpd **globally** and also allocates the queues which hold messages from pd until they are received,
+/// with the default capacities of [`InitOptions`]. Use [`init_with_options`] to choose the capacities.
 ///
-/// After setting internal hooks, it initializes `libpd` by calling the underlying
+/// After setting internal hooks which write to the queues, it initializes `libpd` by calling the underlying
 /// C function which is [`libpd_init`](https://github.com/libpd/libpd/blob/master/libpd_wrapper/z_libpd.c#L68).
-/// See [`libpd_queued_init`](https://github.com/libpd/libpd/blob/master/libpd_wrapper/util/z_queued.c#L308) to
-/// explore what it is doing.
+///
+/// What pd prints while it initializes reaches the closure of [`on_print`](crate::receive::on_print)
+/// with the first call to [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd),
+/// if it is registered before calling this function.
+///
+/// Initialization is guarded, calling this function again while pd is initialized
+/// returns [`AlreadyInitialized`](crate::error::InitializationError::AlreadyInitialized) without touching libpd.
+/// This makes it safe for many libraries in the same process to call it.
+/// Use [`is_initialized`] to check the state without causing an error.
+///
+/// If [`release_internal_queues`] is called before, this function creates the internal queues again.
 ///
 /// **Note**: *Support for multi instances of pd is not implemented yet.*
+/// *Pd is always initialized in queued mode where messages from pd are collected and then delivered
+/// by calling [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd).*
 ///
 /// # Example
 /// ```rust
-/// use libpd_rs::init;
+/// use libpd_rs::{init, is_initialized, error::InitializationError};
 ///
+/// assert!(!is_initialized());
 /// assert_eq!(init().is_ok(), true);
-/// assert_eq!(init().is_err(), true);
+/// assert!(is_initialized());
+/// assert!(matches!(init(), Err(InitializationError::AlreadyInitialized)));
 /// ```
 ///
 /// # Errors
@@ -922,32 +1207,163 @@ pub(crate) const C_STR_FAILURE: &str = "Converting a CStr to an &str is failed."
 /// A list of errors that can occur:
 /// - [`AlreadyInitialized`](crate::error::InitializationError::AlreadyInitialized)
 /// - [`RingBufferInitializationError`](crate::error::InitializationError::RingBufferInitializationError)
-/// - [`InitializationFailed`](crate::error::InitializationError::InitializationFailed)
+/// - [`Unexpected`](crate::error::InitializationError::Unexpected)
 pub fn init() -> Result<(), InitializationError> {
-    unsafe {
-        match libpd_sys::libpd_queued_init() {
-            0 => Ok(()),
-            -1 => Err(InitializationError::AlreadyInitialized),
-            -2 => Err(InitializationError::RingBufferInitializationError),
-            _ => Err(InitializationError::InitializationFailed),
+    init_with_options(InitOptions::default())
+}
+
+/// Initializes libpd like [`init`] with queues of the given capacities.
+///
+/// Messages which do not fit into their queue are dropped and counted,
+/// see [`queued_overflow_count`](crate::receive::queued_overflow_count).
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{init_with_options, types::InitOptions};
+///
+/// let options = InitOptions::default().with_message_queue_bytes(1 << 20);
+/// init_with_options(options).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`AlreadyInitialized`](crate::error::InitializationError::AlreadyInitialized)
+/// - [`RingBufferInitializationError`](crate::error::InitializationError::RingBufferInitializationError)
+/// - [`Unexpected`](crate::error::InitializationError::Unexpected)
+pub fn init_with_options(options: InitOptions) -> Result<(), InitializationError> {
+    // Poisoning is irrelevant here since the lock guards no data.
+    let _guard = INITIALIZATION_LOCK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if is_initialized() {
+        return Err(InitializationError::AlreadyInitialized);
+    }
+    queue::allocate(options.message_queue_bytes, options.midi_queue_bytes)
+        .map_err(|_| InitializationError::RingBufferInitializationError)?;
+    match queue::init() {
+        // `-1` means that pd was initialized before and the queues were released in between.
+        // In that case the queues are created again and pd is ready to use.
+        0 | -1 => {
+            LIBPD_INITIALIZED.store(true, Ordering::SeqCst);
+            PD_INITIALIZED.store(true, Ordering::SeqCst);
+            Ok(())
         }
+        code => Err(InitializationError::Unexpected(code)),
     }
 }
 
-/// Frees the internal queued ring buffers.
+/// Checks if pd is initialized and ready to use.
+///
+/// It returns `true` after a successful call to [`init`]
+/// and `false` after [`release_internal_queues`] is called.
+///
+/// This is useful for libraries which want to configure hooks or open patches
+/// without knowing if pd is initialized by someone else in the same process.
+#[must_use]
+pub fn is_initialized() -> bool {
+    PD_INITIALIZED.load(Ordering::SeqCst)
+}
+
+/// Frees the internal queues which hold messages from pd.
 ///
 /// Currently I don't see a necessity to call this function in any case.
 /// If you find a valid use case, please open an [issue](https://github.com/alisomay/libpd-rs/issues).
+///
+/// After calling this function, cleanup which happens on drop (e.g. dropping a [`PdGlobal`](crate::convenience::PdGlobal))
+/// will not call into pd anymore until [`init`] is called again.
 pub fn release_internal_queues() {
-    unsafe {
-        libpd_sys::libpd_queued_release();
-    };
+    PD_INITIALIZED.store(false, Ordering::SeqCst);
+    queue::release();
+}
+
+/// Returns pd to a near fresh state without releasing it.
+///
+/// This function,
+/// - closes every patch which is opened by [`open_patch`] and not closed yet,
+/// - unbinds every receiver which is created by [`start_listening_from`](crate::receive::start_listening_from) and not unbound yet,
+/// - unsets every hook registered with the functions in the [`receive`](crate::receive) module and drops their closures,
+/// - clears the search paths like [`clear_search_paths`].
+///
+/// What is **not** reset:
+/// - the state of DSP, the [`audio_config`] and the audio settings in pd,
+/// - the verbosity of printing, see [`verbose_print_state`],
+/// - patches opened or receivers bound by calling `libpd_sys` directly,
+/// - pd's global state such as its symbol table, loaded abstractions and externals.
+///
+/// Handles which are held by the user become stale.
+/// Closing or unbinding them later is ignored by this crate.
+///
+/// Do not call this function while another thread is processing audio or receiving messages from pd.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{init, open_patch, close_patch, reset};
+///
+/// init().unwrap();
+/// let patch_handle = open_patch("tests/patches/sine.pd").unwrap();
+///
+/// reset();
+/// // The patch is closed already.
+/// assert!(close_patch(patch_handle).is_err());
+/// ```
+pub fn reset() {
+    if is_initialized() {
+        // Every patch is closed, the number of them does not matter here.
+        let _ = close_all_patches();
+        receive::stop_listening_from_all();
+        clear_search_paths();
+    }
+    // Hooks can be registered before initialization, always clear them.
+    receive::clear_all_hooks();
+}
+
+/// Brings the crate back to a clean state so a following [`init`] starts from scratch.
+///
+/// This function turns DSP off, calls [`reset`], forgets the [`audio_config`]
+/// and releases the internal queues like [`release_internal_queues`].
+///
+/// Libpd itself can not be de-initialized once it is initialized.
+/// What is **not** reclaimed:
+/// - pd's global state such as its symbol table, loaded abstractions and externals,
+/// - the audio settings in pd, call [`initialize_audio`] after [`init`] to set them again.
+///
+/// Handles which are held by the user become stale.
+/// Closing or unbinding them later is ignored by this crate.
+///
+/// Do not call this function while another thread is processing audio or receiving messages from pd.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{init, is_initialized, terminate};
+///
+/// init().unwrap();
+/// // Use pd..
+/// terminate();
+/// assert!(!is_initialized());
+///
+/// // Start again from a clean slate.
+/// init().unwrap();
+/// ```
+pub fn terminate() {
+    if is_initialized() {
+        // Dsp can be off already, there is nothing to do if it fails.
+        let _ = convenience::dsp_off();
+    }
+    reset();
+    *audio_config_slot() = None;
+    release_internal_queues();
 }
 
 /// Clears all the paths where libpd searches for patches and assets.
 ///
-/// This function is also called by [`init`].
+/// The paths which are tracked by [`search_paths`] are also cleared.
 pub fn clear_search_paths() {
+    registry::registry().search_paths.clear();
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !libpd_initialized() {
+        return;
+    }
     unsafe {
         libpd_sys::libpd_clear_search_path();
     }
@@ -955,36 +1371,230 @@ pub fn clear_search_paths() {
 
 /// Adds a path to the list of paths where pd searches in.
 ///
-/// Relative paths are relative to the current working directory.
+/// Relative paths are resolved against the current working directory at the time of the call
+/// and the path is stored in its absolute form, `.` components and trailing separators are removed.
+/// Adding a path which is already added does nothing.
 ///
 /// Unlike the desktop pd application, **no** search paths are set by default.
 ///
+/// # Example
+/// ```rust
+/// use libpd_rs::{add_to_search_paths, search_paths};
+///
+/// libpd_rs::init().unwrap();
+///
+/// add_to_search_paths("tests/patches").unwrap();
+/// add_to_search_paths("./tests/patches/").unwrap();
+/// assert_eq!(search_paths().len(), 1);
+/// assert!(search_paths()[0].is_absolute());
+/// ```
+///
 /// # Errors
 ///
 /// A list of errors that can occur:
 /// - [`PathDoesNotExist`](crate::error::IoError::PathDoesNotExist)
+/// - [`NotInitialized`](crate::error::IoError::NotInitialized)
 pub fn add_to_search_paths<T: AsRef<Path>>(path: T) -> Result<(), IoError> {
+    return_if_not_initialized!(IoError::NotInitialized);
+    let path_does_not_exist =
+        || IoError::PathDoesNotExist(path.as_ref().to_string_lossy().to_string());
     if !path.as_ref().exists() {
-        return Err(IoError::PathDoesNotExist(
-            path.as_ref().to_string_lossy().to_string(),
-        ));
+        return Err(path_does_not_exist());
+    }
+    let path: PathBuf = std::env::current_dir()
+        .map_err(|_| path_does_not_exist())?
+        .join(path.as_ref())
+        .components()
+        .collect();
+
+    let mut registry = registry::registry();
+    if registry.search_paths.contains(&path) {
+        return Ok(());
     }
     unsafe {
-        let c_path = CString::new(&*path.as_ref().to_string_lossy()).expect(C_STRING_FAILURE);
+        // A path with a null byte does not exist, which is checked above.
+        let c_path = to_c_string(&path.to_string_lossy()).map_err(IoError::PathDoesNotExist)?;
         libpd_sys::libpd_add_to_search_path(c_path.as_ptr());
-        Ok(())
+    }
+    registry.search_paths.push(path);
+    drop(registry);
+    Ok(())
+}
+
+/// Returns the paths which are added to the search paths of pd with [`add_to_search_paths`], in the order they are added.
+///
+/// Paths which are added by other means, e.g. by calling the sys crate directly, are not listed.
+#[must_use]
+pub fn search_paths() -> Vec<PathBuf> {
+    registry::registry().search_paths.clone()
+}
+
+/// Adds the search paths which the desktop pd application takes from the environment.
+///
+/// Reads the `PD_PATH` environment variable, which is a list of directories separated
+/// by the separator of the platform (`:` on unix and `;` on windows), and adds each existing directory to the search paths.
+/// Entries which do not exist are skipped with a warning which is printed to the pd console,
+/// it could be seen through [`on_print`](crate::receive::on_print).
+///
+/// If `include_user_directories` is `true`, the conventional directories for externals and abstractions
+/// of the platform are also added if they exist:
+/// - all platforms: `~/Documents/Pd/externals`
+/// - linux: `~/.local/lib/pd/extra` and `~/pd-externals`
+/// - macOS: `~/Library/Pd`
+/// - windows: `%AppData%/Pd`
+///
+/// Returns the paths which are added, in their absolute form like [`search_paths`] lists them.
+/// Paths which are already in the search paths are not returned again.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::add_search_paths_from_env;
+///
+/// libpd_rs::init().unwrap();
+///
+/// for path in add_search_paths_from_env(true) {
+///     println!("Searching in {}", path.display());
+/// }
+/// ```
+// The returned paths are informational, it is fine to call it only to add the paths.
+#[allow(clippy::must_use_candidate)]
+pub fn add_search_paths_from_env(include_user_directories: bool) -> Vec<PathBuf> {
+    let mut added = Vec::new();
+    let mut add = |path: &Path, warn_if_missing: bool| {
+        if !path.is_dir() {
+            if warn_if_missing {
+                post(&format!(
+                    "warning: libpd-rs: {} does not exist, it is not added to the search paths",
+                    path.display()
+                ));
+            }
+            return;
+        }
+        let before = search_paths();
+        if add_to_search_paths(path).is_ok() {
+            added.extend(
+                search_paths()
+                    .into_iter()
+                    .filter(|path| !before.contains(path)),
+            );
+        }
+    };
+
+    if let Some(pd_path) = std::env::var_os("PD_PATH") {
+        for path in std::env::split_paths(&pd_path) {
+            if !path.as_os_str().is_empty() {
+                add(&path, true);
+            }
+        }
+    }
+    if include_user_directories {
+        for path in user_search_directories() {
+            add(&path, false);
+        }
+    }
+    added
+}
+
+/// The conventional directories of the platform where pd looks for externals and abstractions of the user.
+fn user_search_directories() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }) else {
+        return Vec::new();
+    };
+    let home = PathBuf::from(home);
+    #[allow(unused_mut)]
+    let mut directories = vec![home.join("Documents").join("Pd").join("externals")];
+    #[cfg(target_os = "linux")]
+    directories.extend([
+        home.join(".local").join("lib").join("pd").join("extra"),
+        home.join("pd-externals"),
+    ]);
+    #[cfg(target_os = "macos")]
+    directories.push(home.join("Library").join("Pd"));
+    #[cfg(target_os = "windows")]
+    if let Some(app_data) = std::env::var_os("APPDATA") {
+        directories.push(PathBuf::from(app_data).join("Pd"));
+    }
+    directories
+}
+
+/// Prints a line to the pd console.
+fn post(line: &str) {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !libpd_initialized() {
+        return;
+    }
+    let format = c"%s";
+    // Null bytes are removed so the rest of the line is not cut off, which also makes the conversion infallible.
+    let Ok(line) = to_c_string(&line.replace('\0', "")) else {
+        return;
+    };
+    unsafe {
+        libpd_sys::post(format.as_ptr(), line.as_ptr());
+    }
+}
+
+/// Removes the `\\?\` prefix which [`std::fs::canonicalize`] adds to paths on Windows, since pd can not open them.
+// It can only be const on the other platforms.
+#[allow(clippy::missing_const_for_fn)]
+pub(crate) fn without_verbatim_prefix(path: PathBuf) -> PathBuf {
+    #[cfg(windows)]
+    if let Some(stripped) = path.to_str().and_then(|path| path.strip_prefix(r"\\?\")) {
+        // Network paths need the prefix.
+        if !stripped.starts_with(r"UNC\") {
+            return PathBuf::from(stripped);
+        }
+    }
+    path
+}
+
+/// Describes why pd created no patch from a file which it could read.
+///
+/// Pd opens files with any extension, a file which isn't named as a patch is likely not one.
+fn evaluation_error(path: &Path) -> PatchLifeCycleError {
+    let console_output = receive::receive_console_output();
+    let is_patch_file = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|extension| matches!(extension, "pd" | "pat" | "mxt"));
+    let path = path.to_string_lossy().to_string();
+    if is_patch_file {
+        PatchLifeCycleError::EvaluationFailed {
+            path,
+            console_output,
+        }
+    } else {
+        PatchLifeCycleError::UnexpectedExtension {
+            path,
+            console_output,
+        }
     }
 }
 
 /// Opens a pd patch.
 ///
-/// The argument should be an absolute path to the patch file.
-/// It would be useful to keep the return value of this function.
-/// It can be used later to close it.
+/// The argument is the whole path to the patch file,
+/// it is split to the directory and the file name which pd takes separately.
+/// It would be useful to keep the returned [`Patch`].
+/// It can be used later to close it and it carries the location and the `$0` of the patch.
 /// Absolute and relative paths are supported.
 /// Relative paths and single file names are tried in executable directory and manifest directory.
 ///
-/// Tha function **first** checks the executable directory and **then** the manifest directory.
+/// The function **first** checks the executable directory and **then** the manifest directory.
+/// A single file name is treated as `./my_patch.pd`, so it is looked for in those directories too.
+///
+/// The directory which is found is canonicalized, passed to pd and could be retrieved with [`Patch::directory`].
+/// Relative paths inside the patch, e.g. files which are read by `[soundfiler]` or `[text define]`,
+/// are resolved against this directory first and then against the search paths.
+/// They are **not** resolved against the current working directory of the process.
+///
+/// Paths which don't end with a file name, like `/` or `..`, and paths which are not valid UTF-8 can not be passed to pd
+/// and return [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath).
+/// The file is checked to exist and to be readable before it is passed to pd.
+/// If pd can not open a patch from it, the returned error contains what pd printed to its console.
+/// To get that output the messages which are waiting in the pd message queue are received,
+/// so the registered closures could be called before this function returns.
+/// Objects which pd could not create, like a missing abstraction, don't fail opening the patch
+/// but pd prints them to its console which could be seen with [`on_print`](crate::receive::on_print).
 ///
 /// # Examples
 /// ```no_run
@@ -1004,83 +1614,242 @@ pub fn add_to_search_paths<T: AsRef<Path>>(path: T) -> Result<(), IoError> {
 /// A list of errors that can occur:
 /// - [`FailedToOpenPatch`](crate::error::PatchLifeCycleError::FailedToOpenPatch)
 /// - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist)
-pub fn open_patch<T: AsRef<Path>>(
+/// - [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath)
+/// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+/// - [`EvaluationFailed`](crate::error::PatchLifeCycleError::EvaluationFailed)
+/// - [`UnexpectedExtension`](crate::error::PatchLifeCycleError::UnexpectedExtension)
+/// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+pub fn open_patch<T: AsRef<Path>>(path_to_patch: T) -> Result<Patch, PatchLifeCycleError> {
+    open_patch_with_args(path_to_patch, &[])
+}
+
+/// Opens a pd patch with creation arguments, like an abstraction which is created with `[my_patch 440 sine]`.
+///
+/// The patch file is found the same way as in [`open_patch`].
+/// The arguments are given to the root canvas of the patch,
+/// so `$1`, `$2` and so on in its object boxes are replaced by them the same way they would be in an abstraction.
+/// `$0` is still the unique number of the patch and `$1` in a message box still refers to the incoming message.
+/// A `$n` which has no argument becomes `0` and pd prints an error about it to its console.
+///
+/// The arguments are kept in the returned [`Patch`] and they are passed again when it is [reloaded](Patch::reload).
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::{open_patch_with_args, types::Atom};
+///
+/// libpd_rs::init().unwrap();
+///
+/// // `[osc~ $1]` in the patch becomes `[osc~ 440]`.
+/// let patch = open_patch_with_args("tests/patches/sine.pd", &[Atom::from(440), Atom::from("sine")]).unwrap();
+/// assert_eq!(patch.arguments(), &[Atom::from(440), Atom::from("sine")]);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`FailedToOpenPatch`](crate::error::PatchLifeCycleError::FailedToOpenPatch)
+/// - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist)
+/// - [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath)
+/// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+/// - [`EvaluationFailed`](crate::error::PatchLifeCycleError::EvaluationFailed)
+/// - [`UnexpectedExtension`](crate::error::PatchLifeCycleError::UnexpectedExtension)
+/// - [`InvalidString`](crate::error::PatchLifeCycleError::InvalidString)
+/// - [`PointerArgument`](crate::error::PatchLifeCycleError::PointerArgument)
+/// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+pub fn open_patch_with_args<T: AsRef<Path>>(
     path_to_patch: T,
-) -> Result<PatchFileHandle, PatchLifeCycleError> {
+    args: &[Atom],
+) -> Result<Patch, PatchLifeCycleError> {
+    return_if_not_initialized!(PatchLifeCycleError::NotInitialized);
+    let path_to_patch = path_to_patch.as_ref();
+    let invalid_path =
+        || PatchLifeCycleError::InvalidPath(path_to_patch.to_string_lossy().to_string());
+    // Pd takes the directory and the file name as separate strings.
+    if path_to_patch.to_str().is_none() {
+        return Err(invalid_path());
+    }
     let file_name = path_to_patch
-        .as_ref()
         .file_name()
-        .ok_or(PatchLifeCycleError::FailedToOpenPatch)?;
-    let file_name = file_name.to_string_lossy();
-    let file_name = file_name.as_ref();
-    let parent_path = path_to_patch
-        .as_ref()
-        .parent()
-        .unwrap_or_else(|| std::path::Path::new("/"));
-    let parent_path_string: String = parent_path.to_string_lossy().into();
-
-    // Assume absolute path.
-    let mut directory: String = parent_path_string.clone();
-
-    if !parent_path.is_absolute() {
-        // "../some.pd" --> prepend working directory
-        if parent_path.is_relative() {
-            let mut app_dir = std::env::current_exe()
-                .map_err(|_| -> PatchLifeCycleError { PatchLifeCycleError::FailedToOpenPatch })?;
-            app_dir.pop();
-            app_dir.push(parent_path);
-            let parent_path_str = app_dir.to_string_lossy();
-
-            if app_dir.exists() {
-                directory = parent_path_str.into();
-            } else {
-                let manifest_dir =
-                    PathBuf::from(&std::env!("CARGO_MANIFEST_DIR")).join(parent_path);
-                // Try manifest dir.
-                let manifest_dir_str = manifest_dir.to_string_lossy();
-                directory = manifest_dir_str.into();
-            }
-        }
-        // "some.pd" --> prepend working directory
-        if parent_path_string.is_empty() {
-            let mut app_dir = std::env::current_exe()
-                .map_err(|_| -> PatchLifeCycleError { PatchLifeCycleError::FailedToOpenPatch })?;
-            app_dir.pop();
-            app_dir.push(file_name);
-            let parent_path_str = app_dir.to_string_lossy();
-
-            if app_dir.exists() {
-                directory = parent_path_str.into();
-            } else {
-                // Try manifest dir.
-                directory = std::env!("CARGO_MANIFEST_DIR").into();
-            }
+        .and_then(OsStr::to_str)
+        .ok_or_else(invalid_path)?;
+    // "some.pd" --> the parent is empty.
+    let parent_path = match path_to_patch.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let directory = if parent_path.is_absolute() {
+        parent_path.to_path_buf()
+    } else {
+        // "../some.pd" or "some.pd" --> try the executable directory and then the manifest directory.
+        let mut app_dir = std::env::current_exe().map_err(|_| {
+            PatchLifeCycleError::FailedToOpenPatch(path_to_patch.to_string_lossy().to_string())
+        })?;
+        app_dir.pop();
+        let app_dir = app_dir.join(parent_path);
+        if app_dir.join(file_name).exists() {
+            app_dir
+        } else {
+            PathBuf::from(&std::env!("CARGO_MANIFEST_DIR")).join(parent_path)
         }
-    }
+    };
 
     // Invalid path.
-    let calculated_patch_path = PathBuf::from(&directory).join(file_name);
+    let calculated_patch_path = directory.join(file_name);
     if !calculated_patch_path.exists() {
         return Err(PatchLifeCycleError::PathDoesNotExist(
             calculated_patch_path.to_string_lossy().to_string(),
         ));
     }
+    // Pd doesn't tell why it couldn't read a file.
+    let not_readable = |source| PatchLifeCycleError::NotReadable {
+        path: calculated_patch_path.to_string_lossy().to_string(),
+        source,
+    };
+    let readable = std::fs::File::open(&calculated_patch_path).and_then(|file| {
+        if file.metadata()?.is_dir() {
+            Err(std::io::Error::other("The path is a directory."))
+        } else {
+            Ok(())
+        }
+    });
+    readable.map_err(not_readable)?;
+    let directory = std::fs::canonicalize(&directory).map_err(not_readable)?;
+    let directory = without_verbatim_prefix(directory)
+        .to_str()
+        .ok_or_else(invalid_path)?
+        .to_owned();
+
     // All good.
+    let _opening = OPENING_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let arguments: Vec<libpd_sys::t_atom> =
+        make_t_atom_list_from_atom_list!(args).map_err(|error| match error {
+            helpers::AtomListError::InvalidSymbol(input) => PatchLifeCycleError::InvalidString {
+                context: "argument",
+                input,
+            },
+            helpers::AtomListError::Pointer => PatchLifeCycleError::PointerArgument,
+        })?;
     unsafe {
-        let name = CString::new(file_name).expect(C_STRING_FAILURE);
-        let directory = CString::new(directory).expect(C_STRING_FAILURE);
-        let file_handle =
-            libpd_sys::libpd_openfile(name.as_ptr(), directory.as_ptr()).cast::<std::ffi::c_void>();
+        let name = to_c_string(file_name).map_err(|_| invalid_path())?;
+        let c_directory = to_c_string(&directory).map_err(|_| invalid_path())?;
+        if !arguments.is_empty() {
+            libpd_sys::sys_lock();
+            // This is fine since there will not be millions of arguments.
+            #[allow(clippy::cast_possible_wrap)]
+            #[allow(clippy::cast_possible_truncation)]
+            libpd_sys::canvas_setargs(arguments.len() as i32, arguments.as_ptr());
+            libpd_sys::sys_unlock();
+        }
+        let file_handle = libpd_sys::libpd_openfile(name.as_ptr(), c_directory.as_ptr())
+            .cast::<std::ffi::c_void>();
         if file_handle.is_null() {
-            return Err(PatchLifeCycleError::FailedToOpenPatch);
+            if !arguments.is_empty() {
+                // Pd keeps the arguments which no canvas took for the next patch.
+                libpd_sys::sys_lock();
+                libpd_sys::canvas_setargs(0, std::ptr::null());
+                libpd_sys::sys_unlock();
+            }
+            return Err(evaluation_error(&calculated_patch_path));
         }
-        Ok(file_handle.into())
+        let dollar_zero = helpers::dollar_zero(file_handle);
+        let mut patch = Patch::new(
+            file_handle,
+            PathBuf::from(directory),
+            file_name.to_owned(),
+            dollar_zero,
+        );
+        patch.set_arguments(args.to_vec());
+        patch.register();
+        Ok(patch)
+    }
+}
+
+/// Opens a pd patch from its contents, e.g. one which is embedded with [`include_str!`].
+///
+/// Pd can only open patches from files.
+/// The contents are written to a file called `name` in a new temporary directory which is unique to the patch
+/// and the file is opened with [`open_patch`].
+/// The `.pd` extension is appended to `name` if it doesn't have it.
+/// The temporary directory is removed when the returned [`Patch`] is closed or dropped,
+/// which works the same on every platform which has a writable temporary directory.
+/// This function is only available with the `std` feature which is enabled by default.
+///
+/// Since the patch is opened from the temporary directory, relative paths inside the patch
+/// and abstractions next to the original patch file can not be found there.
+/// Pass the directory of the original patch as `search_path` to add it to the search paths of pd.
+/// Search paths are global and stay until they are cleared with [`clear_search_paths`].
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::open_patch_from_str;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let contents = include_str!("../tests/patches/sine.pd");
+/// let patch = open_patch_from_str("sine", contents, None).unwrap();
+/// assert_eq!(patch.file_name(), "sine.pd");
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`FailedToEvaluateAsPatch`](crate::error::PatchLifeCycleError::FailedToEvaluateAsPatch)
+/// - [`FailedToOpenPatch`](crate::error::PatchLifeCycleError::FailedToOpenPatch)
+/// - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist)
+/// - [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath)
+/// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+/// - [`EvaluationFailed`](crate::error::PatchLifeCycleError::EvaluationFailed)
+/// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+#[cfg(feature = "std")]
+pub fn open_patch_from_str<N: AsRef<str>, C: AsRef<str>>(
+    name: N,
+    contents: C,
+    search_path: Option<&Path>,
+) -> Result<Patch, PatchLifeCycleError> {
+    return_if_not_initialized!(PatchLifeCycleError::NotInitialized);
+    let evaluation_error = |msg: String| PatchLifeCycleError::FailedToEvaluateAsPatch {
+        content: contents.as_ref().to_owned(),
+        msg,
+    };
+
+    let name = name.as_ref();
+    if Path::new(name).file_name() != Some(OsStr::new(name)) {
+        return Err(evaluation_error(format!(
+            "The name of the patch needs to be a file name, got \"{name}\"."
+        )));
     }
+    let file_name = if Path::new(name).extension() == Some(OsStr::new("pd")) {
+        name.to_owned()
+    } else {
+        format!("{name}.pd")
+    };
+
+    if let Some(search_path) = search_path {
+        add_to_search_paths(search_path).map_err(|_| {
+            PatchLifeCycleError::PathDoesNotExist(search_path.to_string_lossy().to_string())
+        })?;
+    }
+
+    let directory = tempfile::Builder::new()
+        .prefix("libpd-rs-")
+        .tempdir()
+        .map_err(|err| evaluation_error(err.to_string()))?;
+    let patch_path = directory.path().join(file_name);
+    std::fs::write(&patch_path, contents.as_ref())
+        .map_err(|err| evaluation_error(err.to_string()))?;
+
+    let mut patch = open_patch(&patch_path)?;
+    patch.keep_temporary_directory(directory);
+    Ok(patch)
 }
 
 /// Closes a pd patch which has opened before.
 ///
 /// Handle needs to point to a valid opened patch file.
+/// Patches which are already closed (e.g. by [`terminate`]) are not closed again and return an error.
+///
+/// Patches are also closed when they are dropped, this function is for observing the errors of closing.
 ///
 /// # Examples
 /// ```no_run
@@ -1096,11 +1865,17 @@ pub fn open_patch<T: AsRef<Path>>(
 ///
 /// A list of errors that can occur:
 /// - [`FailedToClosePatch`](crate::error::PatchLifeCycleError::FailedToClosePatch)
-pub fn close_patch(handle: PatchFileHandle) -> Result<(), PatchLifeCycleError> {
+/// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+pub fn close_patch(handle: Patch) -> Result<(), PatchLifeCycleError> {
+    return_if_not_initialized!(PatchLifeCycleError::NotInitialized);
+    let registration = handle.registration();
+    let path = handle.path();
     unsafe {
         let ptr: *mut std::ffi::c_void = handle.into();
-        if ptr.is_null() {
-            Err(PatchLifeCycleError::FailedToClosePatch)
+        if ptr.is_null() || !registry::registry().untrack_patch(ptr as usize, registration) {
+            Err(PatchLifeCycleError::FailedToClosePatch(
+                path.to_string_lossy().to_string(),
+            ))
         } else {
             libpd_sys::libpd_closefile(ptr);
             Ok(())
@@ -1108,6 +1883,65 @@ pub fn close_patch(handle: PatchFileHandle) -> Result<(), PatchLifeCycleError> {
     }
 }
 
+/// Lists the patches which are opened with this crate and not closed yet, in the order they are opened.
+///
+/// Patches are removed from the list when they are closed, dropped or reloaded, a reloaded patch is listed again with its new `$0`.
+/// Patches which are kept open with [`Patch::leak`] or [`Patch::into_raw`] are listed until they are closed.
+/// Patches which are opened by calling `libpd_sys` directly are not listed.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{init, open_patch, open_patches};
+///
+/// init().unwrap();
+/// let patch = open_patch("tests/patches/sine.pd").unwrap();
+///
+/// let open = open_patches();
+/// assert!(open.iter().any(|info| info.dollar_zero == patch.dollar_zero()));
+/// ```
+#[must_use]
+pub fn open_patches() -> Vec<PatchInfo> {
+    registry::registry()
+        .patches
+        .iter()
+        .map(|patch| patch.info.clone())
+        .collect()
+}
+
+/// Closes every patch which is listed by [`open_patches`] and returns how many are closed.
+///
+/// This is useful to clean up after an error skipped closing some patches, e.g. before calling [`terminate`].
+///
+/// The [`Patch`] handles of the closed patches become stale,
+/// closing or dropping them later does not call into pd and closing them returns an error.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{close_all_patches, init, open_patch, open_patches};
+///
+/// init().unwrap();
+/// let patch = open_patch("tests/patches/sine.pd").unwrap();
+/// open_patch("tests/patches/sine.pd").unwrap().leak();
+///
+/// assert!(close_all_patches() >= 2);
+/// assert!(open_patches().is_empty());
+/// assert!(patch.close().is_err());
+/// ```
+#[must_use]
+pub fn close_all_patches() -> usize {
+    if !is_initialized() {
+        return 0;
+    }
+    let patches = std::mem::take(&mut registry::registry().patches);
+    for patch in &patches {
+        patch.mark_closed();
+        unsafe {
+            libpd_sys::libpd_closefile(patch.info.handle());
+        }
+    }
+    patches.len()
+}
+
 /// Gets the `$0` of the running patch.
 ///
 /// `$0` id in pd could be thought as a auto generated unique identifier number for the patch.
@@ -1116,10 +1950,14 @@ pub fn close_patch(handle: PatchFileHandle) -> Result<(), PatchLifeCycleError> {
 ///
 /// A list of errors that can occur:
 /// - [`PatchIsNotOpen`](crate::error::PatchLifeCycleError::PatchIsNotOpen)
-pub fn get_dollar_zero(handle: &PatchFileHandle) -> Result<i32, PatchLifeCycleError> {
+/// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+pub fn get_dollar_zero(handle: &Patch) -> Result<i32, PatchLifeCycleError> {
+    return_if_not_initialized!(PatchLifeCycleError::NotInitialized);
     unsafe {
-        match libpd_sys::libpd_getdollarzero(handle.as_mut_ptr()) {
-            0 => Err(PatchLifeCycleError::PatchIsNotOpen),
+        match helpers::dollar_zero(handle.as_mut_ptr()) {
+            0 => Err(PatchLifeCycleError::PatchIsNotOpen(
+                handle.path().to_string_lossy().to_string(),
+            )),
             other => Ok(other),
         }
     }
@@ -1132,6 +1970,9 @@ pub fn get_dollar_zero(handle: &PatchFileHandle) -> Result<i32, PatchLifeCycleEr
 /// For every pd tick, pd will process frames by the amount of block size.
 /// e.g. this would make 128 samples if we have a stereo output and the default block size.
 ///
+/// The block size is fixed when libpd is compiled, it is the same before and after [`init`]
+/// and it is available as a constant in [`BLOCK_SIZE`](crate::info::BLOCK_SIZE).
+///
 /// It will first process the input buffers and then will continue with the output buffers.
 /// Check the [`PROCESS`](https://github.com/libpd/libpd/blob/master/libpd_wrapper/z_libpd.c#L177) macro in `libpd` [source](https://github.com/libpd/libpd/blob/master/libpd_wrapper) for more information.
 ///
@@ -1160,21 +2001,207 @@ pub fn block_size() -> i32 {
 /// This doesn't mean that the audio is actually playing.
 /// To start audio processing please call [`dsp_on`](crate::convenience::dsp_on) function after the initialization.
 ///
+/// The configuration is recorded on success and could be retrieved with [`audio_config`].
+/// [`initialize_audio_with_config`] takes the same values as an [`AudioConfig`].
+///
+/// The sample rate needs to be positive.
+/// Channel counts can not be negative and at least one of them needs to be positive.
+///
 /// # Errors
 ///
 /// A list of errors that can occur:
-/// - [`InitializationFailed`](crate::error::AudioInitializationError::InitializationFailed)
+/// - [`InvalidSampleRate`](crate::error::AudioInitializationError::InvalidSampleRate)
+/// - [`InvalidChannelCount`](crate::error::AudioInitializationError::InvalidChannelCount)
+/// - [`LibpdRefused`](crate::error::AudioInitializationError::LibpdRefused)
+/// - [`NotInitialized`](crate::error::AudioInitializationError::NotInitialized)
 pub fn initialize_audio(
     input_channels: i32,
     output_channels: i32,
     sample_rate: i32,
 ) -> Result<(), AudioInitializationError> {
+    return_if_not_initialized!(AudioInitializationError::NotInitialized);
+    if sample_rate <= 0 {
+        return Err(AudioInitializationError::InvalidSampleRate(sample_rate));
+    }
+    if input_channels < 0 || output_channels < 0 || (input_channels == 0 && output_channels == 0) {
+        return Err(AudioInitializationError::InvalidChannelCount {
+            input: input_channels,
+            output: output_channels,
+        });
+    }
+    let _lock = lock_exclusively();
     unsafe {
         match libpd_sys::libpd_init_audio(input_channels, output_channels, sample_rate) {
-            0 => Ok(()),
-            _ => Err(AudioInitializationError::InitializationFailed),
+            0 => {
+                *audio_config_slot() = Some(AudioConfig {
+                    input_channels,
+                    output_channels,
+                    sample_rate,
+                });
+                Ok(())
+            }
+            code => Err(AudioInitializationError::LibpdRefused(code)),
+        }
+    }
+}
+
+/// Initializes audio rendering with an [`AudioConfig`].
+///
+/// This is the same as calling [`initialize_audio`] with the fields of the configuration,
+/// use [`initialize_audio`] directly for unusual configurations which have no preset.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{init, initialize_audio_with_config, audio_config, types::AudioConfig};
+///
+/// init().unwrap();
+/// initialize_audio_with_config(AudioConfig::stereo_48k()).unwrap();
+/// assert_eq!(audio_config(), Some(AudioConfig::stereo_48k()));
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`InvalidSampleRate`](crate::error::AudioInitializationError::InvalidSampleRate)
+/// - [`InvalidChannelCount`](crate::error::AudioInitializationError::InvalidChannelCount)
+/// - [`LibpdRefused`](crate::error::AudioInitializationError::LibpdRefused)
+/// - [`NotInitialized`](crate::error::AudioInitializationError::NotInitialized)
+pub fn initialize_audio_with_config(config: AudioConfig) -> Result<(), AudioInitializationError> {
+    initialize_audio(
+        config.input_channels,
+        config.output_channels,
+        config.sample_rate,
+    )
+}
+
+/// Re-initializes audio rendering with a new configuration at runtime.
+///
+/// Use this when the sample rate or the channel count of the audio device changes
+/// after [`initialize_audio`] is called.
+///
+/// This function waits for any running processing function in the [`process`](crate::process) module
+/// to finish and blocks new ones until the new configuration is applied.
+/// After it returns, the audio callback should size its buffers and calculate ticks with the new configuration.
+///
+/// It is safe to call this function while DSP is off.
+/// Turn DSP off with [`dsp_off`](crate::convenience::dsp_off) before calling it
+/// and turn it on again with [`dsp_on`](crate::convenience::dsp_on) afterwards.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{audio_config, init, initialize_audio, reinitialize_audio};
+///
+/// init().unwrap();
+/// initialize_audio(2, 2, 44100).unwrap();
+///
+/// // The audio device changed its sample rate.
+/// reinitialize_audio(2, 2, 48000).unwrap();
+/// assert_eq!(audio_config().unwrap().sample_rate, 48000);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`AudioNotInitialized`](crate::error::AudioInitializationError::AudioNotInitialized)
+/// - [`InvalidSampleRate`](crate::error::AudioInitializationError::InvalidSampleRate)
+/// - [`InvalidChannelCount`](crate::error::AudioInitializationError::InvalidChannelCount)
+/// - [`LibpdRefused`](crate::error::AudioInitializationError::LibpdRefused)
+/// - [`NotInitialized`](crate::error::AudioInitializationError::NotInitialized)
+pub fn reinitialize_audio(
+    input_channels: i32,
+    output_channels: i32,
+    sample_rate: i32,
+) -> Result<(), AudioInitializationError> {
+    if audio_config().is_none() {
+        return Err(AudioInitializationError::AudioNotInitialized);
+    }
+    initialize_audio(input_channels, output_channels, sample_rate)
+}
+
+/// Gets the audio configuration which pd is initialized with.
+///
+/// Returns `None` if audio is not initialized yet.
+#[must_use]
+pub fn audio_config() -> Option<AudioConfig> {
+    *audio_config_slot()
+}
+
+/// Gets a snapshot of the state of pd which is tracked by this crate.
+///
+/// It tells everything about pd in one call, so it could be logged or included in bug reports.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{init, initialize_audio, open_patch, status};
+///
+/// init().unwrap();
+/// initialize_audio(0, 2, 44100).unwrap();
+/// let patch = open_patch("tests/patches/sine.pd").unwrap();
+///
+/// let status = status();
+/// assert!(status.initialized);
+/// assert!(status.open_patches.iter().any(|info| info.dollar_zero == patch.dollar_zero()));
+/// println!("{status}");
+/// ```
+#[must_use]
+pub fn status() -> types::PdStatus {
+    let initialized = is_initialized();
+    types::PdStatus {
+        version: info::pd_version(),
+        initialized,
+        // Pd is not asked before it is initialized.
+        dsp_on: initialized && unsafe { libpd_sys::pd_getdspstate() } != 0,
+        audio_config: audio_config(),
+        block_size: block_size(),
+        open_patches: open_patches(),
+        subscriptions: receive::active_subscriptions(),
+    }
+}
+
+/// Runs a closure while no audio is processed.
+///
+/// Multi step operations like resizing an array and then filling it
+/// or sending a batch of messages could be made atomic with respect to the audio thread with this function.
+///
+/// It waits for any running processing function in the [`process`](crate::process) module to finish
+/// and blocks new ones until the closure returns.
+/// Functions of this crate could be called freely in the closure, libpd still locks itself for each call.
+/// Calling this function again in the closure runs the inner closure directly.
+///
+/// The lock is released even if the closure panics.
+///
+/// **Note**: *The guarantee only holds if audio is processed through the functions in the [`process`](crate::process) module.*
+/// *The audio thread should not call this function, since it would wait for itself to finish processing.*
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{init, initialize_audio, with_pd_lock, send::send_float_to};
+///
+/// init().unwrap();
+/// initialize_audio(0, 2, 44100).unwrap();
+///
+/// // Both messages arrive in the same tick.
+/// with_pd_lock(|| {
+///     let _ = send_float_to("frequency", 440.0);
+///     let _ = send_float_to("amplitude", 0.5);
+/// });
+/// ```
+pub fn with_pd_lock<R, F: FnOnce() -> R>(operation: F) -> R {
+    /// Resets the re-entrancy mark when dropped, also on unwind.
+    struct Mark;
+    impl Drop for Mark {
+        fn drop(&mut self) {
+            HOLDS_PD_LOCK.with(|holds| holds.set(false));
         }
     }
+
+    let Some(_lock) = lock_exclusively() else {
+        // Already held by this thread.
+        return operation();
+    };
+    HOLDS_PD_LOCK.with(|holds| holds.set(true));
+    let _mark = Mark;
+    operation()
 }
 
 /// Sets the flag for the functionality of verbose printing to the pd console
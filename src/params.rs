@@ -0,0 +1,675 @@
+use crate::{
+    error::{SendError, SubscriptionError},
+    send::send_float_to,
+    sink::{subscribe_with, unsubscribe_sink, MessageSink, SinkHandle},
+    symbol::{intern, InternedSymbol},
+};
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::Duration;
+
+/// The parameters which are bound, [`flush_params`] sends the ones which are set since it is called last.
+static PARAMS: Mutex<Vec<Weak<ParamState>>> = Mutex::new(Vec::new());
+
+/// A named parameter which is mirrored between Rust and a patch, made with [`bind_param`].
+///
+/// [`set`](Param::set) stores the value and marks it to be sent, the value is sent to the receivers of the name
+/// in the patch by the next [`flush`](Param::flush) or [`flush_params`].
+/// Setting it many times in between sends only the last value, so a slider could be dragged without flooding pd.
+///
+/// The value is kept in atomics, so the parameter is `Send` and `Sync` and could be shared with an `Arc`
+/// to set and get it from any thread, e.g. a gui thread.
+///
+/// Dropping it stops sending the value and unsubscribes it from the patch if it [listens](Param::listen_to_patch) to it.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::params::{bind_param, flush_params};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let cutoff = bind_param("cutoff", 800.0).unwrap();
+/// cutoff.set(1200.0);
+/// cutoff.set(1600.0);
+/// assert_eq!(cutoff.get(), 1600.0);
+///
+/// // Only the last value is sent, e.g. once per audio block.
+/// let _ = flush_params();
+/// ```
+#[derive(Debug)]
+pub struct Param {
+    state: Arc<ParamState>,
+    subscription: Option<SinkHandle>,
+}
+
+/// The part of a parameter which is shared with the registry and the sink which listens to the patch.
+#[derive(Debug)]
+struct ParamState {
+    name: InternedSymbol,
+    /// The bits of the `f32` value.
+    value: AtomicU32,
+    /// Whether the value is set since it is sent last.
+    dirty: AtomicBool,
+    /// Whether the parameter is subscribed to the sends of the patch.
+    listening: AtomicBool,
+    /// The number of values which are sent to pd and not received back yet while listening.
+    pending_echoes: AtomicUsize,
+}
+
+/// Binds a named parameter with an initial value, which is sent to the patch with the next flush.
+///
+/// The value is sent as a float to the receivers of `name` in the patch, e.g. `[r cutoff]`.
+/// See [`Param`] for how values are sent and [`Param::listen_to_patch`] to receive the changes which the patch makes.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::params::bind_param;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let cutoff = bind_param("cutoff", 800.0).unwrap();
+/// assert_eq!(cutoff.name(), "cutoff");
+/// assert_eq!(cutoff.get(), 800.0);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn bind_param<T: AsRef<str>>(name: T, initial: f32) -> Result<Param, SendError> {
+    let state = Arc::new(ParamState {
+        name: intern(name)?,
+        value: AtomicU32::new(initial.to_bits()),
+        dirty: AtomicBool::new(true),
+        listening: AtomicBool::new(false),
+        pending_echoes: AtomicUsize::new(0),
+    });
+    {
+        let mut params = params();
+        params.retain(|param| param.strong_count() > 0);
+        params.push(Arc::downgrade(&state));
+    }
+    Ok(Param {
+        state,
+        subscription: None,
+    })
+}
+
+/// Sends the values of the bound parameters which are set since they are sent last.
+///
+/// Returns the number of values which are sent. A value which can not be sent, e.g. since nothing receives from its name yet,
+/// stays marked and is sent by a later call.
+///
+/// Call it where the patch should see the values, e.g. before processing each audio block.
+/// The poller of [`spawn_poller`](crate::receive::spawn_poller) calls it every time it polls.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::params::{bind_param, flush_params};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let gain = bind_param("gain", 0.5).unwrap();
+/// let _ = flush_params();
+/// ```
+pub fn flush_params() -> usize {
+    // The parameters are collected first, so they are sent without holding the lock.
+    let states = params()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect::<Vec<_>>();
+    states
+        .iter()
+        .filter(|state| state.flush().is_ok_and(|sent| sent))
+        .count()
+}
+
+impl Param {
+    /// The name of the receivers which the value is sent to.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.state.name.as_str()
+    }
+
+    /// The value which is set last, from Rust or from the patch while [listening](Param::listen_to_patch) to it.
+    #[must_use]
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.state.value.load(Ordering::Acquire))
+    }
+
+    /// Sets the value, it is sent to the patch by the next flush.
+    pub fn set(&self, value: f32) {
+        self.state.value.store(value.to_bits(), Ordering::Release);
+        self.state.dirty.store(true, Ordering::Release);
+    }
+
+    /// Checks if the value is set since it is sent last.
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.state.dirty.load(Ordering::Acquire)
+    }
+
+    /// Sends the value to the patch right away if it is set since it is sent last.
+    ///
+    /// Returns whether the value is sent.
+    ///
+    /// # Errors
+    ///
+    /// The value stays marked to be sent if it is not sent.
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn flush(&self) -> Result<bool, SendError> {
+        self.state.flush()
+    }
+
+    /// Subscribes the parameter to the sends of the patch to its name, e.g. `[s cutoff]`, so changes made in the patch update the value.
+    ///
+    /// The values which pd sends to the name are received with [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd).
+    /// They are not marked to be sent back, and the values which are sent from Rust and received back are skipped,
+    /// so the value does not bounce between Rust and the patch or fall back to an older value which is received late.
+    /// The values of the patch are dropped while a value which is set from Rust is not flushed yet, it is sent by the next flush.
+    ///
+    /// Listening more than once does nothing.
+    ///
+    /// # Example
+    /// ```rust
+    /// use libpd_rs::params::bind_param;
+    ///
+    /// libpd_rs::init().unwrap();
+    ///
+    /// let mut resonance = bind_param("resonance", 0.5).unwrap();
+    /// resonance.listen_to_patch().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`FailedToSubscribeToSender`](crate::error::SubscriptionError::FailedToSubscribeToSender)
+    /// - [`EmptySourceName`](crate::error::SubscriptionError::EmptySourceName)
+    /// - [`NotInitialized`](crate::error::SubscriptionError::NotInitialized)
+    pub fn listen_to_patch(&mut self) -> Result<(), SubscriptionError> {
+        if self.subscription.is_some() {
+            return Ok(());
+        }
+        let sink = ParamSink(Arc::downgrade(&self.state));
+        self.subscription = Some(subscribe_with(self.name(), sink)?);
+        self.state.listening.store(true, Ordering::Release);
+        Ok(())
+    }
+}
+
+impl Drop for Param {
+    fn drop(&mut self) {
+        if let Some(subscription) = self.subscription.take() {
+            unsubscribe_sink(subscription);
+        }
+    }
+}
+
+impl ParamState {
+    /// Sends the value if it is dirty, marks it dirty again if sending fails.
+    fn flush(&self) -> Result<bool, SendError> {
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(false);
+        }
+        let listening = self.listening.load(Ordering::Acquire);
+        // Pd sends the value back to the subscription of the parameter too.
+        if listening {
+            self.pending_echoes.fetch_add(1, Ordering::AcqRel);
+        }
+        let value = f32::from_bits(self.value.load(Ordering::Acquire));
+        match send_float_to(&self.name, value) {
+            Ok(()) => Ok(true),
+            Err(error) => {
+                if listening {
+                    self.pending_echoes.fetch_sub(1, Ordering::AcqRel);
+                }
+                self.dirty.store(true, Ordering::Release);
+                Err(error)
+            }
+        }
+    }
+}
+
+/// The format version which [`snapshot`] writes to a [`Preset`].
+pub const PRESET_FORMAT_VERSION: u32 = 1;
+
+/// The values of named parameters which are captured with [`snapshot`] and sent back with [`apply`].
+///
+/// With the `serde` feature it is serialized with its format version and the values by the names of their parameters,
+/// e.g. in JSON `{"version":1,"values":{"cutoff":800.0,"resonance":0.5}}`.
+/// Deserializing fails for a version which is newer than [`PRESET_FORMAT_VERSION`] or `0`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::params::Preset;
+///
+/// let mut preset = Preset::new();
+/// preset.insert("cutoff", 800.0);
+/// assert_eq!(preset.get("cutoff"), Some(800.0));
+/// assert_eq!(preset.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PresetFormat"))]
+pub struct Preset {
+    version: u32,
+    values: BTreeMap<String, f32>,
+}
+
+/// A preset as it is written, before its version is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PresetFormat {
+    version: u32,
+    values: BTreeMap<String, f32>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PresetFormat> for Preset {
+    type Error = String;
+
+    /// Every version up to the current one is read as it is, there is only the first one yet.
+    fn try_from(format: PresetFormat) -> Result<Self, Self::Error> {
+        if (1..=PRESET_FORMAT_VERSION).contains(&format.version) {
+            Ok(Self {
+                version: format.version,
+                values: format.values,
+            })
+        } else {
+            Err(format!(
+                "unsupported preset format version {}, versions up to {PRESET_FORMAT_VERSION} are supported",
+                format.version
+            ))
+        }
+    }
+}
+
+impl Default for Preset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Preset {
+    /// Creates an empty preset of the current [format version](PRESET_FORMAT_VERSION).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            version: PRESET_FORMAT_VERSION,
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// The format version which the preset is written with.
+    #[must_use]
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The value of a parameter in the preset.
+    #[must_use]
+    pub fn get<T: AsRef<str>>(&self, name: T) -> Option<f32> {
+        self.values.get(name.as_ref()).copied()
+    }
+
+    /// Sets the value of a parameter in the preset, returns the value which it replaces.
+    pub fn insert<T: AsRef<str>>(&mut self, name: T, value: f32) -> Option<f32> {
+        self.values.insert(name.as_ref().to_owned(), value)
+    }
+
+    /// Removes a parameter from the preset, returns its value.
+    pub fn remove<T: AsRef<str>>(&mut self, name: T) -> Option<f32> {
+        self.values.remove(name.as_ref())
+    }
+
+    /// The names and values of the parameters in the preset, ordered by their names.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+
+    /// The number of parameters in the preset.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if the preset has no parameters.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Captures the current values of every bound parameter in a preset.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::params::{bind_param, snapshot};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let cutoff = bind_param("cutoff", 800.0).unwrap();
+/// let preset = snapshot();
+/// assert_eq!(preset.get("cutoff"), Some(800.0));
+/// ```
+#[must_use]
+pub fn snapshot() -> Preset {
+    let mut preset = Preset::new();
+    for state in params().iter().filter_map(Weak::upgrade) {
+        preset.insert(
+            state.name.as_str(),
+            f32::from_bits(state.value.load(Ordering::Acquire)),
+        );
+    }
+    preset
+}
+
+/// Sets the bound parameters to the values of a preset and sends them to the patch at once.
+///
+/// The values are sent in [`with_pd_lock`](crate::with_pd_lock), so no audio is processed until all of them are sent
+/// and the patch never sees a half applied preset. Like [`with_pd_lock`](crate::with_pd_lock) it should not be called from the audio thread.
+///
+/// Returns the names of the parameters in the preset which are not bound anymore, e.g. since the patch changed,
+/// the other values are applied anyway. Bound parameters which are not in the preset keep their values.
+/// A value which can not be sent stays marked and is sent by a later flush, see [`flush_params`].
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::params::{apply, bind_param, Preset};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let cutoff = bind_param("cutoff", 800.0).unwrap();
+/// let mut preset = Preset::new();
+/// preset.insert("cutoff", 1200.0);
+/// preset.insert("removed_from_the_patch", 0.5);
+///
+/// let unbound = apply(&preset);
+/// assert_eq!(cutoff.get(), 1200.0);
+/// assert_eq!(unbound, vec!["removed_from_the_patch".to_owned()]);
+/// ```
+#[must_use]
+pub fn apply(preset: &Preset) -> Vec<String> {
+    let states = params()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect::<Vec<_>>();
+    let mut to_send = Vec::new();
+    for state in &states {
+        if let Some(value) = preset.get(state.name.as_str()) {
+            state.value.store(value.to_bits(), Ordering::Release);
+            state.dirty.store(true, Ordering::Release);
+            to_send.push(state);
+        }
+    }
+    crate::with_pd_lock(|| {
+        for state in to_send {
+            let _ = state.flush();
+        }
+    });
+    preset
+        .iter()
+        .filter(|(name, _)| !states.iter().any(|state| state.name == *name))
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+/// The smallest change of a value which [`morph_tick`] sends to the patch.
+pub const MORPH_EPSILON: f32 = 1e-6;
+
+/// Interpolates linearly between the values of two presets, `t` is clipped to `0.0..=1.0`.
+///
+/// A parameter which is in only one of the presets is not interpolated, it takes the value in `from` while `t < 0.5`
+/// and the value in `to` once `t >= 0.5`, so it is left out of the result if it is not in the preset whose value it takes.
+/// The result has the format version of `to`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::params::{morph, Preset};
+///
+/// let mut from = Preset::new();
+/// from.insert("cutoff", 400.0);
+/// from.insert("drive", 0.2);
+/// let mut to = Preset::new();
+/// to.insert("cutoff", 800.0);
+///
+/// let halfway = morph(&from, &to, 0.25);
+/// assert_eq!(halfway.get("cutoff"), Some(500.0));
+/// assert_eq!(halfway.get("drive"), Some(0.2));
+///
+/// let halfway = morph(&from, &to, 0.5);
+/// assert_eq!(halfway.get("cutoff"), Some(600.0));
+/// assert_eq!(halfway.get("drive"), None);
+/// ```
+#[must_use]
+pub fn morph(from: &Preset, to: &Preset, t: f32) -> Preset {
+    let t = t.clamp(0.0, 1.0);
+    let mut result = Preset {
+        version: to.version,
+        values: BTreeMap::new(),
+    };
+    for (name, to_value) in to.iter() {
+        match from.get(name) {
+            Some(from_value) => {
+                // Weighting both ends keeps them exact, `to` is reached when `t` is `1.0`.
+                result.insert(name, from_value.mul_add(1.0 - t, to_value * t));
+            }
+            None if t >= 0.5 => {
+                result.insert(name, to_value);
+            }
+            None => {}
+        }
+    }
+    if t < 0.5 {
+        for (name, from_value) in from.iter() {
+            if to.get(name).is_none() {
+                result.insert(name, from_value);
+            }
+        }
+    }
+    result
+}
+
+/// The morph which [`morph_tick`] advances, started with [`start_morph`].
+static MORPH: Mutex<Option<ActiveMorph>> = Mutex::new(None);
+
+/// A morph from the values which the bound parameters have when it starts to a preset.
+struct ActiveMorph {
+    from: Preset,
+    to: Preset,
+    duration: Duration,
+    elapsed: Duration,
+    progress: Arc<MorphProgress>,
+}
+
+/// The progress of a morph which is shared with its handles.
+#[derive(Debug)]
+struct MorphProgress {
+    /// The bits of the `f32` position of the morph from `0.0` to `1.0`.
+    t: AtomicU32,
+    /// Whether the morph reached its preset.
+    finished: AtomicBool,
+    /// Whether the morph is still advanced by [`morph_tick`].
+    active: AtomicBool,
+}
+
+/// A handle to observe a morph which is started with [`start_morph`].
+///
+/// Dropping it does not stop the morph, see [`cancel`](MorphHandle::cancel).
+#[derive(Debug, Clone)]
+pub struct MorphHandle {
+    progress: Arc<MorphProgress>,
+}
+
+impl MorphHandle {
+    /// How far the morph is advanced, from `0.0` when it starts to `1.0` when it reaches its preset.
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        f32::from_bits(self.progress.t.load(Ordering::Acquire))
+    }
+
+    /// Checks if the morph reached its preset.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.progress.finished.load(Ordering::Acquire)
+    }
+
+    /// Checks if the morph is still advanced, it is not once it finishes, is cancelled or is replaced by another morph.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.progress.active.load(Ordering::Acquire)
+    }
+
+    /// Stops the morph where it is, the parameters keep the values which are sent last.
+    ///
+    /// Cancelling a morph which is not active does nothing.
+    pub fn cancel(&self) {
+        let mut morph = active_morph();
+        if morph
+            .as_ref()
+            .is_some_and(|active| Arc::ptr_eq(&active.progress, &self.progress))
+        {
+            self.progress.active.store(false, Ordering::Release);
+            *morph = None;
+        }
+    }
+}
+
+/// Starts to morph the bound parameters from their current values to a preset over a duration.
+///
+/// The morph is advanced by [`morph_tick`], which the caller calls with the time which passes, e.g. once per gui frame.
+/// Starting a morph replaces the one which is running, it stops where it is.
+/// The values are interpolated like [`morph`] does and parameters which are not bound are skipped.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::params::{bind_param, morph_tick, start_morph, Preset};
+/// use std::time::Duration;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let cutoff = bind_param("cutoff", 400.0).unwrap();
+/// let mut bright = Preset::new();
+/// bright.insert("cutoff", 800.0);
+///
+/// let handle = start_morph(&bright, Duration::from_secs(2));
+/// let _ = morph_tick(Duration::from_secs(1));
+/// assert_eq!(cutoff.get(), 600.0);
+/// let _ = morph_tick(Duration::from_secs(1));
+/// assert!(handle.is_finished());
+/// assert_eq!(cutoff.get(), 800.0);
+/// ```
+#[must_use = "the handle is the only way to follow or cancel the morph, dropping it does not stop the morph"]
+pub fn start_morph(to: &Preset, duration: Duration) -> MorphHandle {
+    let progress = Arc::new(MorphProgress {
+        t: AtomicU32::new(0.0_f32.to_bits()),
+        finished: AtomicBool::new(false),
+        active: AtomicBool::new(true),
+    });
+    let from = snapshot();
+    let replaced = active_morph().replace(ActiveMorph {
+        from,
+        to: to.clone(),
+        duration,
+        elapsed: Duration::ZERO,
+        progress: Arc::clone(&progress),
+    });
+    if let Some(replaced) = replaced {
+        replaced.progress.active.store(false, Ordering::Release);
+    }
+    MorphHandle { progress }
+}
+
+/// Advances the running morph by `dt` and sends the values of the parameters which change.
+///
+/// Only the values which change by more than [`MORPH_EPSILON`] are sent, so ticking often does not flood the patch.
+/// Returns the number of values which are sent, `0` if no morph is running.
+/// A value which can not be sent stays marked and is sent by a later flush, see [`flush_params`].
+///
+/// Like [`apply`] it locks the morph and the bound parameters and it allocates the interpolated values,
+/// so call it from a control thread, e.g. a gui frame or a timer, rather than the audio thread.
+pub fn morph_tick(dt: Duration) -> usize {
+    let target = {
+        let mut running = active_morph();
+        let Some(active) = running.as_mut() else {
+            return 0;
+        };
+        active.elapsed = active.elapsed.saturating_add(dt);
+        let t = if active.duration.is_zero() {
+            1.0
+        } else {
+            (active.elapsed.as_secs_f32() / active.duration.as_secs_f32()).min(1.0)
+        };
+        let target = morph(&active.from, &active.to, t);
+        active.progress.t.store(t.to_bits(), Ordering::Release);
+        if t >= 1.0 {
+            active.progress.finished.store(true, Ordering::Release);
+            active.progress.active.store(false, Ordering::Release);
+            *running = None;
+        }
+        target
+    };
+    let states = params()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect::<Vec<_>>();
+    states
+        .iter()
+        .filter(|state| {
+            let Some(value) = target.get(state.name.as_str()) else {
+                return false;
+            };
+            let current = f32::from_bits(state.value.load(Ordering::Acquire));
+            if (value - current).abs() <= MORPH_EPSILON {
+                return false;
+            }
+            state.value.store(value.to_bits(), Ordering::Release);
+            state.dirty.store(true, Ordering::Release);
+            state.flush().is_ok_and(|sent| sent)
+        })
+        .count()
+}
+
+/// Updates a parameter with the values which the patch sends to its name.
+struct ParamSink(Weak<ParamState>);
+
+impl MessageSink for ParamSink {
+    fn on_float(&mut self, _source: &str, value: f64) {
+        let Some(state) = self.0.upgrade() else {
+            return;
+        };
+        let is_echo = state
+            .pending_echoes
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+                pending.checked_sub(1)
+            })
+            .is_ok();
+        // A value which is set from Rust and not flushed yet is newer than the values the patch sent before it is sent.
+        if !is_echo && !state.dirty.load(Ordering::Acquire) {
+            // This is fine since the values which are sent from Rust are `f32`s, pd floats are kept at the same precision.
+            #[allow(clippy::cast_possible_truncation)]
+            state
+                .value
+                .store((value as f32).to_bits(), Ordering::Release);
+        }
+    }
+}
+
+/// Locks the bound parameters, the list holds no invariants which a panic could break.
+fn params() -> MutexGuard<'static, Vec<Weak<ParamState>>> {
+    PARAMS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Locks the running morph, it holds no invariants which a panic could break.
+fn active_morph() -> MutexGuard<'static, Option<ActiveMorph>> {
+    MORPH.lock().unwrap_or_else(PoisonError::into_inner)
+}
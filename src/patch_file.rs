@@ -0,0 +1,261 @@
+#![allow(clippy::redundant_pub_crate)]
+
+use crate::types::{ArrayInfo, Atom};
+
+/// The default size of `[table]` and `[array define]`.
+const DEFAULT_ARRAY_SIZE: usize = 100;
+
+/// Gui objects with the indices of their send and receive names in their arguments.
+const GUI_NAME_INDICES: &[(&str, Option<usize>, usize)] = &[
+    ("bng", Some(4), 5),
+    ("tgl", Some(2), 3),
+    ("nbx", Some(6), 7),
+    ("hsl", Some(6), 7),
+    ("vsl", Some(6), 7),
+    ("hradio", Some(4), 5),
+    ("vradio", Some(4), 5),
+    ("hdl", Some(4), 5),
+    ("vdl", Some(4), 5),
+    ("cnv", Some(3), 4),
+    ("vu", None, 2),
+];
+
+/// A record of a patch file, e.g. `#X obj 20 20 osc~ 440`.
+pub(crate) struct Record<'c> {
+    /// The depth of the canvas which contains the record, the canvas of the patch itself is at depth `1`.
+    pub(crate) depth: usize,
+    pub(crate) tokens: Vec<&'c str>,
+}
+
+impl Record<'_> {
+    /// The kind of a `#X` record, e.g. `obj` or `msg`.
+    pub(crate) fn kind(&self) -> Option<&str> {
+        match self.tokens.as_slice() {
+            ["#X", kind, ..] => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// Checks if the record adds an object to the canvas which contains it.
+    pub(crate) fn is_object(&self) -> bool {
+        matches!(
+            self.kind(),
+            Some(
+                "obj"
+                    | "msg"
+                    | "floatatom"
+                    | "symbolatom"
+                    | "listbox"
+                    | "text"
+                    | "scalar"
+                    | "restore"
+            )
+        )
+    }
+
+    /// The top left corner of an object, which is not known for scalars.
+    pub(crate) fn position(&self) -> Option<(i32, i32)> {
+        let x = self.tokens.get(2)?.parse().ok()?;
+        let y = self.tokens.get(3)?.parse().ok()?;
+        Some((x, y))
+    }
+}
+
+/// Splits a patch file to its records and finds the canvas each of them is in.
+///
+/// Everything after an unescaped comma, e.g. the width of an object box in `#X obj 20 20 print, f 20`, is dropped.
+pub(crate) fn parse(contents: &str) -> Vec<Record<'_>> {
+    let mut depth: usize = 0;
+    split_records(contents)
+        .into_iter()
+        .map(|record| {
+            let mut tokens = vec![];
+            for token in record.split_whitespace() {
+                if token == "," {
+                    break;
+                }
+                if let Some(before_comma) = token.strip_suffix(',') {
+                    if !before_comma.ends_with('\\') {
+                        tokens.push(before_comma);
+                        break;
+                    }
+                }
+                tokens.push(token);
+            }
+            match tokens.as_slice() {
+                ["#N", "canvas", ..] => depth += 1,
+                // A subpatch is closed and becomes an object of the canvas which contains it.
+                ["#X", "restore", ..] => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            Record { depth, tokens }
+        })
+        .collect()
+}
+
+/// Splits a patch file at the semicolons which end its records, escaped semicolons are a part of a record.
+fn split_records(contents: &str) -> Vec<&str> {
+    let mut records = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, character) in contents.char_indices() {
+        match character {
+            ';' if !escaped => {
+                // The indices are at characters which are a single byte.
+                records.extend(contents.get(start..index));
+                start = index + 1;
+            }
+            '\\' => {
+                escaped = !escaped;
+                continue;
+            }
+            _ => {}
+        }
+        escaped = false;
+    }
+    records
+}
+
+/// The names a patch file receives from and sends to and the arrays it defines.
+#[derive(Debug, Default)]
+pub(crate) struct Endpoints {
+    pub(crate) receivers: Vec<String>,
+    pub(crate) senders: Vec<String>,
+    pub(crate) arrays: Vec<ArrayInfo>,
+}
+
+impl Endpoints {
+    /// Finds the endpoints in the contents of a patch file, resolving the dollar signs in the names.
+    pub(crate) fn find(contents: &str, dollar_zero: i32, arguments: &[Atom]) -> Self {
+        let mut endpoints = Self::default();
+        let resolve = |token: &str| resolve_dollars(token, dollar_zero, arguments);
+        for record in parse(contents) {
+            let tokens = record.tokens.as_slice();
+            match record.kind() {
+                Some("obj") => {
+                    let (Some(class), Some(args)) = (tokens.get(4), tokens.get(5..)) else {
+                        continue;
+                    };
+                    endpoints.add_object(class, args, resolve);
+                }
+                // The receive and send names come after the width, range, label position and label.
+                Some("floatatom" | "symbolatom" | "listbox") => {
+                    if let Some(receiver) = tokens.get(9) {
+                        push_name(&mut endpoints.receivers, resolve(receiver));
+                    }
+                    if let Some(sender) = tokens.get(10) {
+                        push_name(&mut endpoints.senders, resolve(sender));
+                    }
+                }
+                // Messages which start with a semicolon are sent to the receiver which follows it.
+                Some("msg") => {
+                    for pair in tokens.windows(2) {
+                        if let ["\\;", sender] = pair {
+                            push_name(&mut endpoints.senders, resolve(sender));
+                        }
+                    }
+                }
+                // An array in a graph, `#X array name size float flags`.
+                Some("array") => {
+                    if let Some(name) = tokens.get(2) {
+                        let size = tokens.get(3).and_then(|size| size.parse().ok());
+                        endpoints.add_array(resolve(name), size);
+                    }
+                }
+                _ => {}
+            }
+        }
+        endpoints
+    }
+
+    fn add_object(&mut self, class: &str, args: &[&str], resolve: impl Fn(&str) -> String) {
+        match (class, args) {
+            ("r" | "receive" | "r~" | "receive~", [name, ..]) => {
+                push_name(&mut self.receivers, resolve(name));
+            }
+            ("s" | "send" | "s~" | "send~", [name, ..]) => {
+                push_name(&mut self.senders, resolve(name));
+            }
+            ("table", [name, rest @ ..]) => {
+                let size = rest.first().and_then(|size| size.parse().ok());
+                self.add_array(resolve(name), size);
+            }
+            ("array", ["define", rest @ ..]) => {
+                let mut rest = rest.iter();
+                while let Some(token) = rest.next() {
+                    match *token {
+                        "-k" => {}
+                        "-yrange" | "-pix" => {
+                            rest.nth(1);
+                        }
+                        name => {
+                            let size = rest.next().and_then(|size| size.parse().ok());
+                            self.add_array(resolve(name), size);
+                            break;
+                        }
+                    }
+                }
+            }
+            _ => {
+                if let Some((_, send, receive)) = GUI_NAME_INDICES
+                    .iter()
+                    .find(|(gui_class, _, _)| *gui_class == class)
+                {
+                    if let Some(sender) = send.and_then(|index| args.get(index)) {
+                        push_name(&mut self.senders, resolve(sender));
+                    }
+                    if let Some(receiver) = args.get(*receive) {
+                        push_name(&mut self.receivers, resolve(receiver));
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_array(&mut self, name: String, size: Option<usize>) {
+        if self.arrays.iter().all(|array| array.name != name) {
+            self.arrays.push(ArrayInfo {
+                name,
+                size: size.unwrap_or(DEFAULT_ARRAY_SIZE),
+            });
+        }
+    }
+}
+
+/// Adds a name once, skipping the placeholders which pd writes for names which are not set.
+fn push_name(names: &mut Vec<String>, name: String) {
+    if !matches!(name.as_str(), "" | "-" | "empty") && !names.contains(&name) {
+        names.push(name);
+    }
+}
+
+/// Replaces `$0` with the `$0` of the patch and `$1`, `$2` and so on with its creation arguments.
+///
+/// Dollar signs without an argument are kept as they are.
+fn resolve_dollars(token: &str, dollar_zero: i32, arguments: &[Atom]) -> String {
+    let token = token.replace("\\$", "$");
+    let mut resolved = String::with_capacity(token.len());
+    let mut characters = token.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character != '$' {
+            resolved.push(character);
+            continue;
+        }
+        let mut digits = String::new();
+        while let Some(digit) = characters.next_if(char::is_ascii_digit) {
+            digits.push(digit);
+        }
+        let value = match digits.parse::<usize>() {
+            Ok(0) => Some(dollar_zero.to_string()),
+            Ok(index) => arguments.get(index - 1).map(ToString::to_string),
+            Err(_) => None,
+        };
+        if let Some(value) = value {
+            resolved.push_str(&value);
+        } else {
+            resolved.push('$');
+            resolved.push_str(&digits);
+        }
+    }
+    resolved
+}
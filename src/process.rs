@@ -1,7 +1,17 @@
+use std::time::Duration;
+
+use crate::error::ProcessError;
+
+/// The number of ticks which [`render_offline`] processes at a time.
+const OFFLINE_TICKS_PER_CHUNK: usize = 16;
+
 /// Processes the audio buffer of `f32` in place through the loaded pd patch.
 ///
 /// The processing order is like the following, `input_buffer -> libpd -> output_buffer`.
 ///
+/// Every tick advances the logical time of pd by one block, so it can also be called in a loop to render offline,
+/// see [`render_offline`].
+///
 /// Call this in your **audio callback**.
 ///
 /// # Examples
@@ -29,8 +39,17 @@
 ///
 /// This function may panic for multiple reasons,
 /// first of all there is a mutex lock used internally and also it processes buffers in place so there are possibilities of segfaults.
+/// While this function runs, [`reinitialize_audio`](crate::reinitialize_audio) waits for it to finish.
+///
+/// If pd is not initialized yet, the output buffer is filled with silence.
 /// Use with care.
 pub fn process_float(ticks: i32, input_buffer: &[f32], output_buffer: &mut [f32]) {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return;
+    }
+    let _lock = crate::lock_for_processing();
     unsafe {
         libpd_sys::libpd_process_float(ticks, input_buffer.as_ptr(), output_buffer.as_mut_ptr());
     }
@@ -72,9 +91,17 @@ pub fn process_float(ticks: i32, input_buffer: &[f32], output_buffer: &mut [f32]
 ///
 /// This function may panic for multiple reasons,
 /// first of all there is a mutex lock used internally and also it processes buffers in place so there are possibilities of segfaults.
+/// While this function runs, [`reinitialize_audio`](crate::reinitialize_audio) waits for it to finish.
+///
+/// If pd is not initialized yet, the output buffer is filled with silence.
 /// Use with care.
-
 pub fn process_short(ticks: i32, input_buffer: &[i16], output_buffer: &mut [i16]) {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return;
+    }
+    let _lock = crate::lock_for_processing();
     unsafe {
         libpd_sys::libpd_process_short(ticks, input_buffer.as_ptr(), output_buffer.as_mut_ptr());
     }
@@ -111,8 +138,17 @@ pub fn process_short(ticks: i32, input_buffer: &[i16], output_buffer: &mut [i16]
 ///
 /// This function may panic for multiple reasons,
 /// first of all there is a mutex lock used internally and also it processes buffers in place so there are possibilities of segfaults.
+/// While this function runs, [`reinitialize_audio`](crate::reinitialize_audio) waits for it to finish.
+///
+/// If pd is not initialized yet, the output buffer is filled with silence.
 /// Use with care.
 pub fn process_double(ticks: i32, input_buffer: &[f64], output_buffer: &mut [f64]) {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return;
+    }
+    let _lock = crate::lock_for_processing();
     unsafe {
         libpd_sys::libpd_process_double(ticks, input_buffer.as_ptr(), output_buffer.as_mut_ptr());
     }
@@ -124,6 +160,9 @@ pub fn process_double(ticks: i32, input_buffer: &[f64], output_buffer: &mut [f64
 ///
 /// Copies buffer contents to/from libpd without striping.
 ///
+/// The buffers are not checked, pd reads and writes one block for every channel.
+/// Use [`try_process_raw`] to validate their lengths.
+///
 /// Call this in your **audio callback**.
 ///
 /// # Examples
@@ -145,8 +184,17 @@ pub fn process_double(ticks: i32, input_buffer: &[f64], output_buffer: &mut [f64
 ///
 /// This function may panic for multiple reasons,
 /// first of all there is a mutex lock used internally and also it processes buffers in place so there are possibilities of segfaults.
+/// While this function runs, [`reinitialize_audio`](crate::reinitialize_audio) waits for it to finish.
+///
+/// If pd is not initialized yet, the output buffer is filled with silence.
 /// Use with care.
 pub fn process_raw(input_buffer: &[f32], output_buffer: &mut [f32]) {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return;
+    }
+    let _lock = crate::lock_for_processing();
     unsafe {
         libpd_sys::libpd_process_raw(input_buffer.as_ptr(), output_buffer.as_mut_ptr());
     }
@@ -158,6 +206,9 @@ pub fn process_raw(input_buffer: &[f32], output_buffer: &mut [f32]) {
 ///
 /// Copies buffer contents to/from libpd without striping.
 ///
+/// The buffers are not checked, pd reads and writes one block for every channel.
+/// Use [`try_process_raw_short`] to validate their lengths.
+///
 /// Float samples are converted to short by multiplying by `32767` and casting,
 /// so any values received from pd patches beyond `-1` to `1` will result in garbage.
 ///
@@ -184,9 +235,17 @@ pub fn process_raw(input_buffer: &[f32], output_buffer: &mut [f32]) {
 ///
 /// This function may panic for multiple reasons,
 /// first of all there is a mutex lock used internally and also it processes buffers in place so there are possibilities of segfaults.
+/// While this function runs, [`reinitialize_audio`](crate::reinitialize_audio) waits for it to finish.
+///
+/// If pd is not initialized yet, the output buffer is filled with silence.
 /// Use with care.
-
 pub fn process_raw_short(input_buffer: &[i16], output_buffer: &mut [i16]) {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return;
+    }
+    let _lock = crate::lock_for_processing();
     unsafe {
         libpd_sys::libpd_process_raw_short(input_buffer.as_ptr(), output_buffer.as_mut_ptr());
     }
@@ -198,6 +257,9 @@ pub fn process_raw_short(input_buffer: &[i16], output_buffer: &mut [i16]) {
 ///
 /// Copies buffer contents to/from libpd without striping.
 ///
+/// The buffers are not checked, pd reads and writes one block for every channel.
+/// Use [`try_process_raw_double`] to validate their lengths.
+///
 /// Call this in your **audio callback**.
 ///
 /// # Examples
@@ -219,9 +281,500 @@ pub fn process_raw_short(input_buffer: &[i16], output_buffer: &mut [i16]) {
 ///
 /// This function may panic for multiple reasons,
 /// first of all there is a mutex lock used internally and also it processes buffers in place so there are possibilities of segfaults.
+/// While this function runs, [`reinitialize_audio`](crate::reinitialize_audio) waits for it to finish.
+///
+/// If pd is not initialized yet, the output buffer is filled with silence.
 /// Use with care.
 pub fn process_raw_double(input_buffer: &[f64], output_buffer: &mut [f64]) {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return;
+    }
+    let _lock = crate::lock_for_processing();
     unsafe {
         libpd_sys::libpd_process_raw_double(input_buffer.as_ptr(), output_buffer.as_mut_ptr());
     }
 }
+
+/// Processes exactly one tick of **non-interleaved** `f32` audio through the loaded pd patch, validating the buffers.
+///
+/// The processing order is like the following, `input_buffer -> libpd -> output_buffer`.
+///
+/// Unlike [`process_float`] which takes interleaved frames for any number of ticks,
+/// the buffers are planar and hold a single block:
+/// the `block_size` samples of the first channel, then the ones of the second channel and so on.
+/// The input buffer needs to be `block_size * input_channels` long and the output buffer `block_size * output_channels`,
+/// with the channel counts which audio is initialized with. An empty input buffer fits when there are no input channels.
+///
+/// Call this in your **audio callback**.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{block_size, init, initialize_audio, process::try_process_raw};
+///
+/// init().unwrap();
+/// initialize_audio(1, 2, 44100).unwrap();
+///
+/// let block_size = block_size() as usize;
+/// let input_buffer = vec![0.0_f32; block_size];
+/// let mut output_buffer = vec![0.0_f32; block_size * 2];
+///
+/// try_process_raw(&input_buffer, &mut output_buffer).unwrap();
+/// let (left, right) = output_buffer.split_at(block_size);
+/// # assert_eq!(left.len(), right.len());
+///
+/// // A buffer for more than one tick is refused.
+/// assert!(try_process_raw(&input_buffer, &mut vec![0.0_f32; block_size * 4]).is_err());
+/// ```
+///
+/// # Errors
+///
+/// The output buffer is filled with silence when an error occurs.
+///
+/// A list of errors that can occur:
+/// - [`InputBufferLength`](crate::error::ProcessError::InputBufferLength)
+/// - [`OutputBufferLength`](crate::error::ProcessError::OutputBufferLength)
+/// - [`AudioNotInitialized`](crate::error::ProcessError::AudioNotInitialized)
+/// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+pub fn try_process_raw(
+    input_buffer: &[f32],
+    output_buffer: &mut [f32],
+) -> Result<(), ProcessError> {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return Err(ProcessError::NotInitialized);
+    }
+    let _lock = crate::lock_for_processing();
+    if let Err(err) = validate_raw_buffers(input_buffer.len(), output_buffer.len()) {
+        output_buffer.fill(Default::default());
+        return Err(err);
+    }
+    unsafe {
+        libpd_sys::libpd_process_raw(input_buffer.as_ptr(), output_buffer.as_mut_ptr());
+    }
+    Ok(())
+}
+
+/// Processes exactly one tick of **non-interleaved** `i16` audio through the loaded pd patch, validating the buffers.
+///
+/// The buffers are laid out like the ones of [`try_process_raw`].
+///
+/// Float samples are converted to short by multiplying by `32767` and casting,
+/// so any values received from pd patches beyond `-1` to `1` will result in garbage.
+///
+/// Call this in your **audio callback**.
+///
+/// # Errors
+///
+/// The output buffer is filled with silence when an error occurs.
+///
+/// A list of errors that can occur:
+/// - [`InputBufferLength`](crate::error::ProcessError::InputBufferLength)
+/// - [`OutputBufferLength`](crate::error::ProcessError::OutputBufferLength)
+/// - [`AudioNotInitialized`](crate::error::ProcessError::AudioNotInitialized)
+/// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+pub fn try_process_raw_short(
+    input_buffer: &[i16],
+    output_buffer: &mut [i16],
+) -> Result<(), ProcessError> {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return Err(ProcessError::NotInitialized);
+    }
+    let _lock = crate::lock_for_processing();
+    if let Err(err) = validate_raw_buffers(input_buffer.len(), output_buffer.len()) {
+        output_buffer.fill(Default::default());
+        return Err(err);
+    }
+    unsafe {
+        libpd_sys::libpd_process_raw_short(input_buffer.as_ptr(), output_buffer.as_mut_ptr());
+    }
+    Ok(())
+}
+
+/// Processes exactly one tick of **non-interleaved** `f64` audio through the loaded pd patch, validating the buffers.
+///
+/// The buffers are laid out like the ones of [`try_process_raw`].
+///
+/// Call this in your **audio callback**.
+///
+/// # Errors
+///
+/// The output buffer is filled with silence when an error occurs.
+///
+/// A list of errors that can occur:
+/// - [`InputBufferLength`](crate::error::ProcessError::InputBufferLength)
+/// - [`OutputBufferLength`](crate::error::ProcessError::OutputBufferLength)
+/// - [`AudioNotInitialized`](crate::error::ProcessError::AudioNotInitialized)
+/// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+pub fn try_process_raw_double(
+    input_buffer: &[f64],
+    output_buffer: &mut [f64],
+) -> Result<(), ProcessError> {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        output_buffer.fill(Default::default());
+        return Err(ProcessError::NotInitialized);
+    }
+    let _lock = crate::lock_for_processing();
+    if let Err(err) = validate_raw_buffers(input_buffer.len(), output_buffer.len()) {
+        output_buffer.fill(Default::default());
+        return Err(err);
+    }
+    unsafe {
+        libpd_sys::libpd_process_raw_double(input_buffer.as_ptr(), output_buffer.as_mut_ptr());
+    }
+    Ok(())
+}
+
+/// Renders the loaded patches faster than realtime for a duration and passes the interleaved output to `sink`.
+///
+/// The duration is rounded up to whole blocks with the sample rate which audio is initialized with.
+/// The blocks are processed in chunks like with [`process_float`] and the input is silent,
+/// use [`render_offline_with_input`] to provide it.
+/// Pd's logical time advances with every processed block, so time based objects in the patch
+/// behave like they would in realtime.
+/// `sink` is called with one chunk at a time,
+/// the chunks are a multiple of `block_size * output_channels` samples long and the last one can be shorter.
+///
+/// After every chunk the messages which pd sent meanwhile are received from the message queue,
+/// see [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd),
+/// so the registered closures run between the chunks.
+/// Dsp needs to be turned on for pd to produce sound.
+///
+/// The chunks are made for the channel counts and the block size which audio is initialized with when it is called.
+/// If audio is reinitialized with another configuration meanwhile, e.g. by `sink` or a registered closure,
+/// rendering stops with an error.
+///
+/// Returns the number of frames which are rendered.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use libpd_rs::{convenience::dsp_on, init, initialize_audio, open_patch, process::render_offline};
+///
+/// init().unwrap();
+/// initialize_audio(0, 2, 44100).unwrap();
+/// dsp_on().unwrap();
+/// let patch = open_patch("tests/patches/sine.pd").unwrap();
+///
+/// // Bounce 2 seconds of the patch.
+/// let mut bounced = Vec::new();
+/// let frames = render_offline(Duration::from_secs(2), |chunk| bounced.extend_from_slice(chunk)).unwrap();
+/// assert_eq!(bounced.len(), frames * 2);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`AudioNotInitialized`](crate::error::ProcessError::AudioNotInitialized)
+/// - [`AudioConfigChanged`](crate::error::ProcessError::AudioConfigChanged)
+/// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+pub fn render_offline<F: FnMut(&[f32])>(
+    duration: Duration,
+    sink: F,
+) -> Result<usize, ProcessError> {
+    render_offline_with_input(duration, |_| {}, sink)
+}
+
+/// Renders the loaded patches faster than realtime like [`render_offline`], with an input.
+///
+/// Before every chunk `input` is called with a silent interleaved input buffer to fill,
+/// which is as long as the output chunk for the input channels.
+/// The message queue is received after every chunk and audio may not be reinitialized meanwhile,
+/// like with [`render_offline`].
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use libpd_rs::{init, initialize_audio, process::render_offline_with_input};
+///
+/// init().unwrap();
+/// initialize_audio(1, 1, 44100).unwrap();
+///
+/// let mut phase = 0.0_f32;
+/// render_offline_with_input(
+///     Duration::from_millis(100),
+///     |input| {
+///         for sample in input {
+///             *sample = phase.sin();
+///             phase += 0.1;
+///         }
+///     },
+///     |_output| {},
+/// )
+/// .unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`AudioNotInitialized`](crate::error::ProcessError::AudioNotInitialized)
+/// - [`AudioConfigChanged`](crate::error::ProcessError::AudioConfigChanged)
+/// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+pub fn render_offline_with_input<I: FnMut(&mut [f32]), F: FnMut(&[f32])>(
+    duration: Duration,
+    mut input: I,
+    mut sink: F,
+) -> Result<usize, ProcessError> {
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        return Err(ProcessError::NotInitialized);
+    }
+    let config = crate::audio_config().ok_or(ProcessError::AudioNotInitialized)?;
+    let block_size = usize::try_from(crate::block_size()).unwrap_or(0);
+    let input_channels = usize::try_from(config.input_channels).unwrap_or(0);
+    let output_channels = usize::try_from(config.output_channels).unwrap_or(0);
+
+    // This is fine since the sample rate is positive and durations are not long enough to lose precision.
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    let frames = (duration.as_secs_f64() * f64::from(config.sample_rate)).ceil() as usize;
+    let total_ticks = frames.div_ceil(block_size.max(1));
+
+    let mut input_buffer = vec![0.0; OFFLINE_TICKS_PER_CHUNK * block_size * input_channels];
+    let mut output_buffer = vec![0.0; OFFLINE_TICKS_PER_CHUNK * block_size * output_channels];
+    let mut rendered_ticks = 0;
+    while rendered_ticks < total_ticks {
+        let ticks = OFFLINE_TICKS_PER_CHUNK.min(total_ticks - rendered_ticks);
+        let input_chunk = &mut input_buffer[..ticks * block_size * input_channels];
+        input_chunk.fill(0.0);
+        input(input_chunk);
+        let output_chunk = &mut output_buffer[..ticks * block_size * output_channels];
+        {
+            // The configuration can not change while processing is locked, until the chunk is processed.
+            let _lock = crate::lock_for_processing();
+            match crate::audio_config() {
+                None => return Err(ProcessError::AudioNotInitialized),
+                Some(current)
+                    if current != config
+                        || usize::try_from(crate::block_size()).unwrap_or(0) != block_size =>
+                {
+                    return Err(ProcessError::AudioConfigChanged);
+                }
+                Some(_) => {}
+            }
+            // This is fine since a chunk is a few ticks.
+            #[allow(clippy::cast_possible_wrap)]
+            #[allow(clippy::cast_possible_truncation)]
+            unsafe {
+                libpd_sys::libpd_process_float(
+                    ticks as i32,
+                    input_chunk.as_ptr(),
+                    output_chunk.as_mut_ptr(),
+                );
+            }
+        }
+        sink(output_chunk);
+        crate::receive::receive_messages_from_pd();
+        rendered_ticks += ticks;
+    }
+    Ok(total_ticks * block_size)
+}
+
+/// Renders interleaved buffers of any number of frames through the loaded patches, e.g. when the host's buffer size
+/// is not a multiple of the block size of pd.
+///
+/// Pd processes whole blocks, so the frames of a buffer which do not fill a block are kept until the next call
+/// completes it. The output is delayed by one block for this, the first block of output is silent.
+/// See [`latency`](Rechunker::latency).
+///
+/// The channel counts and the block size are taken when it is made, so it needs to be made again
+/// after audio is reinitialized, [`render`](Rechunker::render) refuses to process until then.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{convenience::dsp_on, init, initialize_audio, open_patch, process::Rechunker};
+///
+/// init().unwrap();
+/// initialize_audio(1, 2, 44100).unwrap();
+/// dsp_on().unwrap();
+/// let patch = open_patch("tests/patches/sine.pd").unwrap();
+///
+/// let mut rechunker = Rechunker::new().unwrap();
+/// // In the audio callback, with 100 frames which is not a multiple of 64.
+/// let input = vec![0.0_f32; 100];
+/// let mut output = vec![0.0_f32; 200];
+/// rechunker.render(&input, &mut output).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rechunker {
+    block_size: usize,
+    input_channels: usize,
+    output_channels: usize,
+    /// The frames of input which wait for the block to be filled.
+    input_block: Vec<f32>,
+    /// The output of the last processed block which is being handed out.
+    output_block: Vec<f32>,
+    /// The frame in the blocks where the next input is written to and the next output is read from.
+    position: usize,
+}
+
+impl Rechunker {
+    /// Makes a rechunker for the channels which audio is initialized with.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`AudioNotInitialized`](crate::error::ProcessError::AudioNotInitialized)
+    /// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+    pub fn new() -> Result<Self, ProcessError> {
+        #[cfg(not(feature = "skip-init-checks"))]
+        if !crate::libpd_initialized() {
+            return Err(ProcessError::NotInitialized);
+        }
+        let config = crate::audio_config().ok_or(ProcessError::AudioNotInitialized)?;
+        let block_size = usize::try_from(crate::block_size()).unwrap_or(0);
+        let input_channels = usize::try_from(config.input_channels).unwrap_or(0);
+        let output_channels = usize::try_from(config.output_channels).unwrap_or(0);
+        Ok(Self {
+            block_size,
+            input_channels,
+            output_channels,
+            input_block: vec![0.0; block_size * input_channels],
+            output_block: vec![0.0; block_size * output_channels],
+            position: 0,
+        })
+    }
+
+    /// The number of frames which the output is delayed by, which is the block size.
+    #[must_use]
+    pub const fn latency(&self) -> usize {
+        self.block_size
+    }
+
+    /// Drops the frames which are kept between calls, the next output starts with a silent block again.
+    pub fn reset(&mut self) {
+        self.input_block.fill(0.0);
+        self.output_block.fill(0.0);
+        self.position = 0;
+    }
+
+    /// Renders interleaved audio of any number of frames.
+    ///
+    /// The output buffer needs to hold whole frames for the output channels
+    /// and the input buffer needs to hold the same number of frames for the input channels,
+    /// so it is empty when there are no input channels.
+    /// Without output channels the number of frames is found from the input buffer.
+    ///
+    /// A block is processed like [`process_float`] whenever the input fills it.
+    /// Messages from pd are not received here, receive them from a control thread,
+    /// e.g. with [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd) or [`spawn_poller`](crate::receive::spawn_poller).
+    ///
+    /// Call this in your **audio callback**, it does not allocate.
+    ///
+    /// # Errors
+    ///
+    /// The output buffer is filled with silence and the kept frames stay as they are when an error occurs.
+    ///
+    /// A list of errors that can occur:
+    /// - [`InputBufferLength`](crate::error::ProcessError::InputBufferLength)
+    /// - [`OutputBufferLength`](crate::error::ProcessError::OutputBufferLength)
+    /// - [`AudioConfigChanged`](crate::error::ProcessError::AudioConfigChanged)
+    /// - [`NotInitialized`](crate::error::ProcessError::NotInitialized)
+    pub fn render(&mut self, input: &[f32], output: &mut [f32]) -> Result<(), ProcessError> {
+        #[cfg(not(feature = "skip-init-checks"))]
+        if !crate::libpd_initialized() {
+            output.fill(0.0);
+            return Err(ProcessError::NotInitialized);
+        }
+        // The configuration can not change while processing is locked, until the blocks are processed.
+        let _lock = crate::lock_for_processing();
+        if !self.fits_audio_config() {
+            output.fill(0.0);
+            return Err(ProcessError::AudioConfigChanged);
+        }
+        let frames = match self.frames(input.len(), output.len()) {
+            Ok(frames) => frames,
+            Err(err) => {
+                output.fill(0.0);
+                return Err(err);
+            }
+        };
+        let (input_channels, output_channels) = (self.input_channels, self.output_channels);
+        let mut done = 0;
+        while done < frames {
+            let length = (frames - done).min(self.block_size - self.position);
+            let block = self.position..self.position + length;
+            let buffer = done..done + length;
+            self.input_block[block.start * input_channels..block.end * input_channels]
+                .copy_from_slice(
+                    &input[buffer.start * input_channels..buffer.end * input_channels],
+                );
+            output[buffer.start * output_channels..buffer.end * output_channels].copy_from_slice(
+                &self.output_block[block.start * output_channels..block.end * output_channels],
+            );
+            self.position += length;
+            done += length;
+            if self.position == self.block_size {
+                unsafe {
+                    libpd_sys::libpd_process_float(
+                        1,
+                        self.input_block.as_ptr(),
+                        self.output_block.as_mut_ptr(),
+                    );
+                }
+                self.position = 0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that audio is still initialized with the channel counts and the block size which the blocks are made for.
+    fn fits_audio_config(&self) -> bool {
+        crate::audio_config().is_some_and(|config| {
+            usize::try_from(config.input_channels).unwrap_or(0) == self.input_channels
+                && usize::try_from(config.output_channels).unwrap_or(0) == self.output_channels
+                && usize::try_from(crate::block_size()).unwrap_or(0) == self.block_size
+        })
+    }
+
+    /// Finds the number of frames the buffers hold, checking that they are whole frames of the same number of frames.
+    fn frames(&self, input_length: usize, output_length: usize) -> Result<usize, ProcessError> {
+        let frames = if self.output_channels == 0 {
+            input_length.div_ceil(self.input_channels.max(1))
+        } else {
+            output_length.div_ceil(self.output_channels)
+        };
+        if output_length != frames * self.output_channels {
+            return Err(ProcessError::OutputBufferLength {
+                expected: frames * self.output_channels,
+                actual: output_length,
+            });
+        }
+        if input_length != frames * self.input_channels {
+            return Err(ProcessError::InputBufferLength {
+                expected: frames * self.input_channels,
+                actual: input_length,
+            });
+        }
+        Ok(frames)
+    }
+}
+
+/// Checks that the buffers hold one block for every channel which audio is initialized with.
+///
+/// Called while processing is locked, so the audio configuration can not change before the buffers are processed.
+fn validate_raw_buffers(input_length: usize, output_length: usize) -> Result<(), ProcessError> {
+    let config = crate::audio_config().ok_or(ProcessError::AudioNotInitialized)?;
+    let block_size = usize::try_from(crate::block_size()).unwrap_or(0);
+    let expected_input = block_size * usize::try_from(config.input_channels).unwrap_or(0);
+    let expected_output = block_size * usize::try_from(config.output_channels).unwrap_or(0);
+    if input_length != expected_input {
+        return Err(ProcessError::InputBufferLength {
+            expected: expected_input,
+            actual: input_length,
+        });
+    }
+    if output_length != expected_output {
+        return Err(ProcessError::OutputBufferLength {
+            expected: expected_output,
+            actual: output_length,
+        });
+    }
+    Ok(())
+}
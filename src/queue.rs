@@ -0,0 +1,576 @@
+#![allow(clippy::redundant_pub_crate)]
+
+use std::cell::RefCell;
+use std::collections::TryReserveError;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{helpers::make_atom_list_from_t_atom_list, types::Atom};
+
+use libpd_sys::{
+    t_atom, t_libpd_aftertouchhook, t_libpd_banghook, t_libpd_controlchangehook,
+    t_libpd_doublehook, t_libpd_floathook, t_libpd_listhook, t_libpd_messagehook,
+    t_libpd_midibytehook, t_libpd_noteonhook, t_libpd_pitchbendhook, t_libpd_polyaftertouchhook,
+    t_libpd_printhook, t_libpd_programchangehook, t_libpd_symbolhook,
+};
+
+/// A message which pd sent.
+///
+/// Sources, selectors and symbols are the names of symbols which pd interned, so they stay valid.
+#[derive(Clone, Copy)]
+enum Entry {
+    /// The text is the next `length` bytes of the text of the queue, including the null byte.
+    Print {
+        length: usize,
+    },
+    Bang {
+        source: *const c_char,
+    },
+    Float {
+        source: *const c_char,
+        value: f64,
+    },
+    Symbol {
+        source: *const c_char,
+        symbol: *const c_char,
+    },
+    /// The atoms are the next `length` atoms of the queue.
+    List {
+        source: *const c_char,
+        length: c_int,
+    },
+    /// The atoms are the next `length` atoms of the queue.
+    Message {
+        source: *const c_char,
+        selector: *const c_char,
+        length: c_int,
+    },
+}
+
+// SAFETY: The sources, selectors and symbols are the names of symbols which pd interned and never frees,
+// they are only read.
+unsafe impl Send for Entry {}
+
+/// A MIDI message which pd sent.
+#[derive(Clone, Copy)]
+enum MidiEntry {
+    NoteOn(c_int, c_int, c_int),
+    ControlChange(c_int, c_int, c_int),
+    ProgramChange(c_int, c_int),
+    PitchBend(c_int, c_int),
+    AfterTouch(c_int, c_int),
+    PolyAfterTouch(c_int, c_int, c_int),
+    Byte(c_int, c_int),
+}
+
+/// The messages which pd sent and which are not received yet, pd drops the messages which do not fit its capacity.
+///
+/// The capacity is counted in bytes of the stored entries, atoms and text.
+/// It is allocated up front twice, so neither pd writing to the queue nor taking the messages out allocates.
+struct Queue<T> {
+    entries: Vec<T>,
+    atoms: Vec<t_atom>,
+    text: Vec<u8>,
+    capacity: usize,
+    /// The empty buffers which are swapped in when the messages are taken out.
+    spare: Option<Taken<T>>,
+}
+
+/// The entries, atoms and text which are taken out of a queue to be delivered.
+struct Taken<T> {
+    entries: Vec<T>,
+    atoms: Vec<t_atom>,
+    text: Vec<u8>,
+    /// The capacity of the queue they are taken from.
+    capacity: usize,
+}
+
+impl<T> Taken<T> {
+    fn allocate(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut entries = Vec::new();
+        entries.try_reserve_exact(capacity.div_ceil(size_of::<T>()))?;
+        let mut atoms = Vec::new();
+        atoms.try_reserve_exact(capacity.div_ceil(size_of::<t_atom>()))?;
+        let mut text = Vec::new();
+        text.try_reserve_exact(capacity)?;
+        Ok(Self {
+            entries,
+            atoms,
+            text,
+            capacity,
+        })
+    }
+}
+
+// SAFETY: The atoms are plain values whose symbols are interned by pd, which never frees them,
+// so they point to the same symbols from any thread. Pointer atoms are only handed back to pd.
+// The queue, its spare buffers included, is only reached through the mutex which it is stored in.
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl<T: Send> Send for Queue<T> {}
+
+impl<T> Queue<T> {
+    const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            atoms: Vec::new(),
+            text: Vec::new(),
+            capacity: 0,
+            spare: None,
+        }
+    }
+
+    /// Checks if an entry with `atoms` atoms and `text` bytes of text fits in the queue, counts a dropped message if not.
+    fn fits(&self, atoms: usize, text: usize) -> bool {
+        let used = (self.entries.len() + 1) * size_of::<T>()
+            + (self.atoms.len() + atoms) * size_of::<t_atom>()
+            + self.text.len()
+            + text;
+        let fits = used <= self.capacity;
+        // A queue without a capacity is released, nothing is counted then.
+        if !fits && self.capacity > 0 {
+            OVERFLOWS.fetch_add(1, Ordering::Relaxed);
+        }
+        fits
+    }
+
+    /// Sets the capacity and allocates for it, dropping the messages which are not received yet.
+    fn allocate(&mut self, capacity: usize) -> Result<(), TryReserveError> {
+        let Taken {
+            entries,
+            atoms,
+            text,
+            capacity,
+        } = Taken::allocate(capacity)?;
+        *self = Self {
+            entries,
+            atoms,
+            text,
+            capacity,
+            spare: Some(Taken::allocate(capacity)?),
+        };
+        Ok(())
+    }
+
+    /// Frees the queue, messages which pd sends afterwards are dropped.
+    fn free(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Swaps the messages out for the spare buffers, so pd could write the next ones while they are delivered.
+    ///
+    /// Returns `None` while the spare buffers are out, e.g. when a hook receives again, the messages wait then.
+    fn take(&mut self) -> Option<Taken<T>> {
+        let spare = self.spare.take()?;
+        Some(Taken {
+            entries: std::mem::replace(&mut self.entries, spare.entries),
+            atoms: std::mem::replace(&mut self.atoms, spare.atoms),
+            text: std::mem::replace(&mut self.text, spare.text),
+            capacity: self.capacity,
+        })
+    }
+
+    /// Empties the buffers of delivered messages and keeps them as the spare buffers, which keeps their allocation.
+    ///
+    /// Returns them back if the queue is allocated again or freed meanwhile,
+    /// so they are dropped after the lock of the queue is released.
+    fn give_back(&mut self, mut taken: Taken<T>) -> Option<Taken<T>> {
+        if self.spare.is_some() || taken.capacity != self.capacity {
+            return Some(taken);
+        }
+        taken.entries.clear();
+        taken.atoms.clear();
+        taken.text.clear();
+        self.spare = Some(taken);
+        None
+    }
+}
+
+static MESSAGES: Mutex<Queue<Entry>> = Mutex::new(Queue::new());
+static MIDI_MESSAGES: Mutex<Queue<MidiEntry>> = Mutex::new(Queue::new());
+/// The number of messages which are dropped since they do not fit in their queue.
+static OVERFLOWS: AtomicUsize = AtomicUsize::new(0);
+
+fn messages() -> MutexGuard<'static, Queue<Entry>> {
+    // Poisoning is irrelevant here since a queue is consistent after every push.
+    MESSAGES.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn midi_messages() -> MutexGuard<'static, Queue<MidiEntry>> {
+    MIDI_MESSAGES.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The hooks which the messages are delivered to when they are received, like the queued hooks of libpd.
+#[derive(Clone, Copy)]
+pub(crate) struct QueuedHooks {
+    pub(crate) print: t_libpd_printhook,
+    pub(crate) bang: t_libpd_banghook,
+    pub(crate) float: t_libpd_floathook,
+    pub(crate) double: t_libpd_doublehook,
+    pub(crate) symbol: t_libpd_symbolhook,
+    pub(crate) list: t_libpd_listhook,
+    pub(crate) message: t_libpd_messagehook,
+    pub(crate) note_on: t_libpd_noteonhook,
+    pub(crate) control_change: t_libpd_controlchangehook,
+    pub(crate) program_change: t_libpd_programchangehook,
+    pub(crate) pitch_bend: t_libpd_pitchbendhook,
+    pub(crate) after_touch: t_libpd_aftertouchhook,
+    pub(crate) poly_after_touch: t_libpd_polyaftertouchhook,
+    pub(crate) midi_byte: t_libpd_midibytehook,
+}
+
+impl QueuedHooks {
+    /// No hooks are set, the messages are dropped when they are received.
+    pub(crate) const NONE: Self = Self {
+        print: None,
+        bang: None,
+        float: None,
+        double: None,
+        symbol: None,
+        list: None,
+        message: None,
+        note_on: None,
+        control_change: None,
+        program_change: None,
+        pitch_bend: None,
+        after_touch: None,
+        poly_after_touch: None,
+        midi_byte: None,
+    };
+}
+
+static QUEUED_HOOKS: Mutex<QueuedHooks> = Mutex::new(QueuedHooks::NONE);
+
+/// Locks the hooks which received messages are delivered to.
+pub(crate) fn queued_hooks() -> MutexGuard<'static, QueuedHooks> {
+    // Poisoning is irrelevant here since the hooks are plain values.
+    QUEUED_HOOKS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Allocates the queues with capacities in bytes.
+pub(crate) fn allocate(
+    message_capacity: usize,
+    midi_capacity: usize,
+) -> Result<(), TryReserveError> {
+    messages().allocate(message_capacity)?;
+    midi_messages().allocate(midi_capacity)
+}
+
+/// Sets the hooks of pd to write to the queues and initializes pd, returns what [`libpd_init`](libpd_sys::libpd_init) returns.
+pub(crate) fn init() -> c_int {
+    unsafe {
+        libpd_sys::libpd_set_printhook(Some(queue_print));
+        libpd_sys::libpd_set_banghook(Some(queue_bang));
+        libpd_sys::libpd_set_doublehook(Some(queue_float));
+        libpd_sys::libpd_set_symbolhook(Some(queue_symbol));
+        libpd_sys::libpd_set_listhook(Some(queue_list));
+        libpd_sys::libpd_set_messagehook(Some(queue_message));
+        libpd_sys::libpd_set_noteonhook(Some(queue_note_on));
+        libpd_sys::libpd_set_controlchangehook(Some(queue_control_change));
+        libpd_sys::libpd_set_programchangehook(Some(queue_program_change));
+        libpd_sys::libpd_set_pitchbendhook(Some(queue_pitch_bend));
+        libpd_sys::libpd_set_aftertouchhook(Some(queue_after_touch));
+        libpd_sys::libpd_set_polyaftertouchhook(Some(queue_poly_after_touch));
+        libpd_sys::libpd_set_midibytehook(Some(queue_midi_byte));
+        libpd_sys::libpd_init()
+    }
+}
+
+/// Frees the queues, messages which pd sends afterwards are dropped until they are allocated again.
+pub(crate) fn release() {
+    messages().free();
+    midi_messages().free();
+}
+
+/// The number of messages which are dropped since they do not fit in their queue.
+pub(crate) fn overflow_count() -> usize {
+    OVERFLOWS.load(Ordering::Relaxed)
+}
+
+thread_local! {
+    /// The text which pd prints on this thread while it is captured with [`capture_print`].
+    static CAPTURED_PRINT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Calls `operation` and returns its result with the text which pd printed on this thread meanwhile.
+///
+/// Pd prints on the thread which sends it a message while it handles the message.
+/// The text is still queued for the print hook, nothing is received from the queue.
+pub(crate) fn capture_print<R>(operation: impl FnOnce() -> R) -> (R, String) {
+    let outer = CAPTURED_PRINT.with(|captured| captured.replace(Some(String::new())));
+    let result = operation();
+    let printed = CAPTURED_PRINT.with(|captured| captured.replace(outer));
+    (result, printed.unwrap_or_default())
+}
+
+unsafe extern "C" fn queue_print(text: *const c_char) {
+    let text = CStr::from_ptr(text);
+    CAPTURED_PRINT.with(|captured| {
+        if let Some(captured) = captured.borrow_mut().as_mut() {
+            captured.push_str(&text.to_string_lossy());
+        }
+    });
+    let text = text.to_bytes_with_nul();
+    let mut queue = messages();
+    if queue.fits(0, text.len()) {
+        queue.entries.push(Entry::Print { length: text.len() });
+        queue.text.extend_from_slice(text);
+    }
+}
+
+/// Pushes a message which has no atoms.
+fn push(entry: Entry) {
+    let mut queue = messages();
+    if queue.fits(0, 0) {
+        queue.entries.push(entry);
+    }
+}
+
+/// Pushes a message and its atoms.
+unsafe fn push_with_atoms(entry: Entry, length: c_int, atoms: *mut t_atom) {
+    let atoms = match usize::try_from(length) {
+        Ok(length) if length > 0 && !atoms.is_null() => std::slice::from_raw_parts(atoms, length),
+        _ => &[],
+    };
+    let mut queue = messages();
+    if queue.fits(atoms.len(), 0) {
+        queue.entries.push(entry);
+        queue.atoms.extend_from_slice(atoms);
+    }
+}
+
+unsafe extern "C" fn queue_bang(source: *const c_char) {
+    push(Entry::Bang { source });
+}
+
+unsafe extern "C" fn queue_float(source: *const c_char, value: f64) {
+    push(Entry::Float { source, value });
+}
+
+unsafe extern "C" fn queue_symbol(source: *const c_char, symbol: *const c_char) {
+    push(Entry::Symbol { source, symbol });
+}
+
+unsafe extern "C" fn queue_list(source: *const c_char, length: c_int, atoms: *mut t_atom) {
+    push_with_atoms(Entry::List { source, length }, length, atoms);
+}
+
+unsafe extern "C" fn queue_message(
+    source: *const c_char,
+    selector: *const c_char,
+    length: c_int,
+    atoms: *mut t_atom,
+) {
+    push_with_atoms(
+        Entry::Message {
+            source,
+            selector,
+            length,
+        },
+        length,
+        atoms,
+    );
+}
+
+fn push_midi(entry: MidiEntry) {
+    let mut queue = midi_messages();
+    if queue.fits(0, 0) {
+        queue.entries.push(entry);
+    }
+}
+
+unsafe extern "C" fn queue_note_on(channel: c_int, pitch: c_int, velocity: c_int) {
+    push_midi(MidiEntry::NoteOn(channel, pitch, velocity));
+}
+
+unsafe extern "C" fn queue_control_change(channel: c_int, controller: c_int, value: c_int) {
+    push_midi(MidiEntry::ControlChange(channel, controller, value));
+}
+
+unsafe extern "C" fn queue_program_change(channel: c_int, value: c_int) {
+    push_midi(MidiEntry::ProgramChange(channel, value));
+}
+
+unsafe extern "C" fn queue_pitch_bend(channel: c_int, value: c_int) {
+    push_midi(MidiEntry::PitchBend(channel, value));
+}
+
+unsafe extern "C" fn queue_after_touch(channel: c_int, value: c_int) {
+    push_midi(MidiEntry::AfterTouch(channel, value));
+}
+
+unsafe extern "C" fn queue_poly_after_touch(channel: c_int, pitch: c_int, value: c_int) {
+    push_midi(MidiEntry::PolyAfterTouch(channel, pitch, value));
+}
+
+unsafe extern "C" fn queue_midi_byte(port: c_int, byte: c_int) {
+    push_midi(MidiEntry::Byte(port, byte));
+}
+
+/// Delivers the messages which are in the queue to the queued hooks and the subscribed sinks.
+///
+/// Messages which pd sends meanwhile, e.g. since a hook sends to pd, wait for the next call.
+pub(crate) fn receive_messages() {
+    let Some(mut taken) = messages().take() else {
+        return;
+    };
+    let (atoms, text) = (&mut taken.atoms, &taken.text);
+    let mut atoms_position = 0;
+    let mut text_position = 0;
+    for entry in taken.entries.iter().copied() {
+        // A hook could replace the hooks, so they are looked up for every message.
+        let hooks = *queued_hooks();
+        // Hooks get a pointer to the atoms of their message.
+        let mut take_atoms = |length: c_int| {
+            let atoms = atoms.as_mut_ptr().wrapping_add(atoms_position);
+            atoms_position += usize::try_from(length).unwrap_or(0);
+            atoms
+        };
+        unsafe {
+            match entry {
+                Entry::Print { length } => {
+                    let line = text.get(text_position..text_position + length);
+                    text_position += length;
+                    if let (Some(hook), Some(line)) = (hooks.print, line) {
+                        hook(line.as_ptr().cast());
+                    }
+                }
+                Entry::Bang { source } => {
+                    if let Some(hook) = hooks.bang {
+                        hook(source);
+                    }
+                    route(source, || (), |sink, source, ()| sink.on_bang(source));
+                }
+                Entry::Float { source, value } => {
+                    match (hooks.double, hooks.float) {
+                        (Some(hook), _) => hook(source, value),
+                        // This is fine since the float hook takes `f32` values like pd built with 32 bit floats.
+                        #[allow(clippy::cast_possible_truncation)]
+                        (None, Some(hook)) => hook(source, value as f32),
+                        (None, None) => {}
+                    }
+                    route(
+                        source,
+                        || value,
+                        |sink, source, value| sink.on_float(source, *value),
+                    );
+                }
+                Entry::Symbol { source, symbol } => {
+                    if let Some(hook) = hooks.symbol {
+                        hook(source, symbol);
+                    }
+                    route(
+                        source,
+                        || CStr::from_ptr(symbol).to_string_lossy(),
+                        |sink, source, symbol| sink.on_symbol(source, symbol),
+                    );
+                }
+                Entry::List { source, length } => {
+                    let atoms = take_atoms(length);
+                    if let Some(hook) = hooks.list {
+                        hook(source, length, atoms);
+                    }
+                    route(
+                        source,
+                        || atom_list(atoms, length),
+                        |sink, source, list| sink.on_list(source, list),
+                    );
+                }
+                Entry::Message {
+                    source,
+                    selector,
+                    length,
+                } => {
+                    let atoms = take_atoms(length);
+                    if let Some(hook) = hooks.message {
+                        hook(source, selector, length, atoms);
+                    }
+                    route(
+                        source,
+                        || {
+                            let selector = CStr::from_ptr(selector).to_string_lossy();
+                            (selector, atom_list(atoms, length))
+                        },
+                        |sink, source, (selector, arguments)| {
+                            sink.on_message(source, selector, arguments);
+                        },
+                    );
+                }
+            }
+        }
+    }
+    let rejected = messages().give_back(taken);
+    drop(rejected);
+}
+
+/// Routes a message to the sinks which are subscribed to its source, see [`crate::sink::route`].
+unsafe fn route<M>(
+    source: *const c_char,
+    message: impl FnOnce() -> M,
+    deliver: impl FnMut(&mut dyn crate::sink::MessageSink, &str, &M),
+) {
+    if !source.is_null() {
+        crate::sink::route(CStr::from_ptr(source), message, deliver);
+    }
+}
+
+/// Converts the atoms of a message to the atoms which are passed to sinks.
+unsafe fn atom_list(atoms: *mut t_atom, length: c_int) -> Vec<Atom> {
+    let atoms = match usize::try_from(length) {
+        Ok(length) if length > 0 && !atoms.is_null() => std::slice::from_raw_parts(atoms, length),
+        _ => &[],
+    };
+    make_atom_list_from_t_atom_list!(atoms)
+}
+
+/// Delivers the MIDI messages which are in the queue to the queued hooks.
+pub(crate) fn receive_midi_messages() {
+    let Some(taken) = midi_messages().take() else {
+        return;
+    };
+    for entry in taken.entries.iter().copied() {
+        let hooks = *queued_hooks();
+        unsafe {
+            match entry {
+                MidiEntry::NoteOn(channel, pitch, velocity) => {
+                    if let Some(hook) = hooks.note_on {
+                        hook(channel, pitch, velocity);
+                    }
+                }
+                MidiEntry::ControlChange(channel, controller, value) => {
+                    if let Some(hook) = hooks.control_change {
+                        hook(channel, controller, value);
+                    }
+                }
+                MidiEntry::ProgramChange(channel, value) => {
+                    if let Some(hook) = hooks.program_change {
+                        hook(channel, value);
+                    }
+                }
+                MidiEntry::PitchBend(channel, value) => {
+                    if let Some(hook) = hooks.pitch_bend {
+                        hook(channel, value);
+                    }
+                }
+                MidiEntry::AfterTouch(channel, value) => {
+                    if let Some(hook) = hooks.after_touch {
+                        hook(channel, value);
+                    }
+                }
+                MidiEntry::PolyAfterTouch(channel, pitch, value) => {
+                    if let Some(hook) = hooks.poly_after_touch {
+                        hook(channel, pitch, value);
+                    }
+                }
+                MidiEntry::Byte(port, byte) => {
+                    if let Some(hook) = hooks.midi_byte {
+                        hook(port, byte);
+                    }
+                }
+            }
+        }
+    }
+    let rejected = midi_messages().give_back(taken);
+    drop(rejected);
+}
@@ -1,8 +1,13 @@
 use crate::{
     error::SubscriptionError,
-    helpers::make_atom_list_from_t_atom_list,
-    types::{Atom, ReceiverHandle},
-    C_STRING_FAILURE, C_STR_FAILURE,
+    helpers::{
+        make_atom_list_from_t_atom_list, return_if_not_initialized, to_c_string, with_atom_views,
+    },
+    registry::{
+        register_hook, register_hook_calling, registry, take_user_closure, Dispatch, Hook,
+        UserClosure,
+    },
+    types::{Atom, AtomView, CallbackPanic, PdConsoleError, PrintKind, ReceiverHandle},
 };
 
 use libffi::high::{
@@ -14,7 +19,29 @@ use libpd_sys::{
     t_libpd_noteonhook, t_libpd_pitchbendhook, t_libpd_polyaftertouchhook, t_libpd_printhook,
     t_libpd_programchangehook, t_libpd_symbolhook,
 };
-use std::ffi::{CStr, CString};
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A closure which is registered with [`on_bang`], it is returned when it is replaced.
+pub type BangClosure = Box<dyn FnMut(&str) + Send + Sync>;
+/// A closure which is registered with [`on_float`], it is returned when it is replaced.
+pub type FloatClosure = Box<dyn FnMut(&str, f32) + Send + Sync>;
+/// A closure which is registered with [`on_double`], it is returned when it is replaced.
+pub type DoubleClosure = Box<dyn FnMut(&str, f64) + Send + Sync>;
+/// A closure which is registered with [`on_symbol`], it is returned when it is replaced.
+pub type SymbolClosure = Box<dyn FnMut(&str, &str) + Send + Sync>;
+/// A closure which is registered with [`on_list`], it is returned when it is replaced.
+pub type ListClosure = Box<dyn FnMut(&str, &[Atom]) + Send + Sync>;
+/// A closure which is registered with [`on_list_borrowed`], it is returned when it is replaced.
+pub type BorrowedListClosure = Box<dyn FnMut(&str, &[AtomView]) + Send + Sync>;
+/// A closure which is registered with [`on_message`], it is returned when it is replaced.
+pub type MessageClosure = Box<dyn FnMut(&str, &str, &[Atom]) + Send + Sync>;
+/// A closure which is registered with [`on_message_borrowed`], it is returned when it is replaced.
+pub type BorrowedMessageClosure = Box<dyn FnMut(&str, &str, &[AtomView]) + Send + Sync>;
 
 type PrintHookCodePtr = *const FnPtr1<'static, *const i8, ()>;
 type BangHookCodePtr = *const FnPtr1<'static, *const i8, ()>;
@@ -33,6 +60,43 @@ type MidiAfterTouchCodePtr = *const FnPtr2<'static, i32, i32, ()>;
 type MidiPolyAfterTouchCodePtr = *const FnPtr3<'static, i32, i32, i32, ()>;
 type MidiByteCodePtr = *const FnPtr2<'static, i32, i32, ()>;
 
+/// The default capacity of the internal queue which holds messages from pd until they are received, in bytes.
+///
+/// The capacity could be chosen with [`init_with_options`](crate::init_with_options).
+/// When the queue is full, new messages from pd are **dropped** and counted by [`queued_overflow_count`],
+/// so call [`receive_messages_from_pd`] often enough to keep up with bursts of messages.
+///
+/// Every queued message takes a small header plus its contents,
+/// e.g. a list takes space for each of its atoms.
+pub const MESSAGE_QUEUE_SIZE_IN_BYTES: usize = 16384;
+
+/// The default capacity of the internal queue which holds MIDI messages from pd until they are received, in bytes.
+///
+/// Like [`MESSAGE_QUEUE_SIZE_IN_BYTES`], new messages are **dropped** and counted when it is full.
+/// Call [`receive_midi_messages_from_pd`] often enough to keep up with bursts of MIDI messages.
+pub const MIDI_QUEUE_SIZE_IN_BYTES: usize = 16384;
+
+/// Returns the number of messages and MIDI messages which are dropped since they did not fit into their queue.
+///
+/// The count starts with the process and is not reset by [`init`](crate::init),
+/// so compare it between two calls to detect new drops.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::{queued_overflow_count, receive_messages_from_pd};
+///
+/// libpd_rs::init().unwrap();
+/// let dropped = queued_overflow_count();
+/// receive_messages_from_pd();
+/// if queued_overflow_count() > dropped {
+///     eprintln!("Messages from pd were dropped, receive them more often.");
+/// }
+/// ```
+#[must_use]
+pub fn queued_overflow_count() -> usize {
+    crate::queue::overflow_count()
+}
+
 /// Subscribes to messages sent to a receiver in the loaded pd patch
 ///
 /// `start_listening_from("foo")` would add a **virtual** `|r foo|` which would
@@ -64,8 +128,19 @@ type MidiByteCodePtr = *const FnPtr2<'static, i32, i32, ()>;
 ///
 /// A list of errors that can occur:
 /// - [`FailedToSubscribeToSender`](crate::error::SubscriptionError::FailedToSubscribeToSender)
+/// - [`EmptySourceName`](crate::error::SubscriptionError::EmptySourceName)
+/// - [`InvalidString`](crate::error::SubscriptionError::InvalidString)
+/// - [`NotInitialized`](crate::error::SubscriptionError::NotInitialized)
 pub fn start_listening_from<T: AsRef<str>>(sender: T) -> Result<ReceiverHandle, SubscriptionError> {
-    let send = CString::new(sender.as_ref()).expect(C_STRING_FAILURE);
+    return_if_not_initialized!(SubscriptionError::NotInitialized);
+    // Pd would happily bind to an empty symbol which can not be sent to from a patch.
+    if sender.as_ref().is_empty() {
+        return Err(SubscriptionError::EmptySourceName);
+    }
+    let send = to_c_string(sender.as_ref()).map_err(|input| SubscriptionError::InvalidString {
+        context: "sender name",
+        input,
+    })?;
 
     unsafe {
         let handle = libpd_sys::libpd_bind(send.as_ptr());
@@ -74,11 +149,47 @@ pub fn start_listening_from<T: AsRef<str>>(sender: T) -> Result<ReceiverHandle,
                 sender.as_ref().to_owned(),
             ))
         } else {
+            registry()
+                .receivers
+                .push((handle as usize, sender.as_ref().to_owned()));
             Ok(ReceiverHandle::from(handle))
         }
     }
 }
 
+/// Subscribes to messages sent to a receiver like [`start_listening_from`] and tells if it was newly bound
+///
+/// The returned boolean is `true` if nothing was bound to the receiver before,
+/// neither a `|r foo|` in a patch nor an earlier subscription.
+/// Pd allows binding to the same receiver many times and every binding receives every message,
+/// so this helps to avoid wiring the same receiver twice, e.g. when a plugin is reloaded.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::start_listening_from_checked;
+///
+/// libpd_rs::init();
+///
+/// let (handle, newly_bound) = start_listening_from_checked("foo").unwrap();
+/// assert!(newly_bound);
+/// let (other_handle, newly_bound) = start_listening_from_checked("foo").unwrap();
+/// assert!(!newly_bound);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`FailedToSubscribeToSender`](crate::error::SubscriptionError::FailedToSubscribeToSender)
+/// - [`EmptySourceName`](crate::error::SubscriptionError::EmptySourceName)
+/// - [`InvalidString`](crate::error::SubscriptionError::InvalidString)
+/// - [`NotInitialized`](crate::error::SubscriptionError::NotInitialized)
+pub fn start_listening_from_checked<T: AsRef<str>>(
+    sender: T,
+) -> Result<(ReceiverHandle, bool), SubscriptionError> {
+    let newly_bound = !source_to_listen_from_exists(sender.as_ref());
+    start_listening_from(sender).map(|handle| (handle, newly_bound))
+}
+
 /// Unsubscribes from messages sent to the receiver in the loaded pd patch
 ///
 ///`stop_listening_from("foo")` would **remove** the virtual `|r foo|`.
@@ -99,9 +210,74 @@ pub fn stop_listening_from(source: ReceiverHandle) {
     if handle.is_null() {
         return;
     }
+    // Receivers which are already unbound by this crate are ignored.
+    if !registry().untrack_receiver(handle as usize) {
+        return;
+    }
     unsafe {
         libpd_sys::libpd_unbind(handle);
     }
+    crate::sink::unsubscribe(handle as usize);
+}
+
+/// Lists the names of the receivers which are bound with [`start_listening_from`] and not unbound yet.
+///
+/// The names are sorted and a name is listed as many times as it is bound.
+/// Receivers which are bound by calling `libpd_sys` directly are not listed.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::{active_subscriptions, start_listening_from, stop_listening_from};
+///
+/// libpd_rs::init();
+///
+/// let foo = start_listening_from("foo").unwrap();
+/// let bar = start_listening_from("bar").unwrap();
+/// assert_eq!(active_subscriptions(), vec!["bar", "foo"]);
+///
+/// stop_listening_from(foo);
+/// assert_eq!(active_subscriptions(), vec!["bar"]);
+/// # stop_listening_from(bar);
+/// ```
+#[must_use]
+pub fn active_subscriptions() -> Vec<String> {
+    let mut names = registry()
+        .receivers
+        .iter()
+        .map(|(_, name)| name.clone())
+        .collect::<Vec<String>>();
+    names.sort_unstable();
+    names
+}
+
+/// Unbinds every receiver which is created by [`start_listening_from`] and not unbound yet.
+pub(crate) fn stop_listening_from_all() {
+    let receivers = std::mem::take(&mut registry().receivers);
+    for (handle, _) in receivers {
+        unsafe {
+            libpd_sys::libpd_unbind(handle as *mut std::ffi::c_void);
+        }
+    }
+    crate::sink::unsubscribe_all();
+}
+
+/// Unsets every hook and drops the closures which are registered to them.
+pub(crate) fn clear_all_hooks() {
+    unsafe {
+        libpd_sys::libpd_set_concatenated_printhook(None);
+    }
+    *crate::queue::queued_hooks() = crate::queue::QueuedHooks::NONE;
+    *print_hook() = None;
+    // Drop the closures outside of the lock.
+    let pd_error_callback = pd_error_callback().take();
+    drop(pd_error_callback);
+    let mut registry = registry();
+    let hooks = std::mem::take(&mut registry.hooks)
+        .into_iter()
+        .filter_map(|(_, registration)| registry.retire(registration))
+        .collect::<Vec<_>>();
+    drop(registry);
+    drop(hooks);
 }
 
 /// Checks if a source to listen from exists.
@@ -119,14 +295,196 @@ pub fn stop_listening_from(source: ReceiverHandle) {
 /// }
 /// ```
 pub fn source_to_listen_from_exists<T: AsRef<str>>(sender: T) -> bool {
-    let send = CString::new(sender.as_ref()).expect(C_STRING_FAILURE);
+    #[cfg(not(feature = "skip-init-checks"))]
+    if !crate::libpd_initialized() {
+        return false;
+    }
+    // A name with a null byte can not be bound to.
+    let Ok(send) = to_c_string(sender.as_ref()) else {
+        return false;
+    };
     unsafe { matches!(libpd_sys::libpd_exists(send.as_ptr()), 1) }
 }
 
+/// Returns the last panic which is caught in a closure registered with one of the `on_*` functions and clears it.
+///
+/// Panics in these closures are caught before they unwind into pd, which would be undefined behavior.
+/// A closure which panicked is **disabled** and will not be called again until it is registered again.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::{on_float, take_last_callback_panic};
+///
+/// libpd_rs::init();
+///
+/// on_float(|_: &str, _: f32| {
+///   panic!("Oops");
+/// });
+///
+/// // After receiving messages from pd.
+/// if let Some(callback_panic) = take_last_callback_panic() {
+///   eprintln!("{} panicked: {}", callback_panic.callback, callback_panic.message);
+/// }
+/// ```
+#[must_use]
+pub fn take_last_callback_panic() -> Option<CallbackPanic> {
+    last_callback_panic().take()
+}
+
+static LAST_CALLBACK_PANIC: Mutex<Option<CallbackPanic>> = Mutex::new(None);
+
+fn last_callback_panic() -> MutexGuard<'static, Option<CallbackPanic>> {
+    LAST_CALLBACK_PANIC
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+thread_local! {
+    /// Number of messages which are delivered to the registered closures on this thread.
+    static DELIVERED_MESSAGES: Cell<usize> = const { Cell::new(0) };
+    /// Number of registered closures which are running on this thread, they could be nested.
+    static CALLBACK_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns how many registered closures are running on this thread.
+pub(crate) fn callback_depth() -> usize {
+    CALLBACK_DEPTH.with(Cell::get)
+}
+
+/// Calls `receive` until it does not deliver any messages and returns the number of delivered messages.
+fn drain(receive: fn()) -> usize {
+    let delivered = || DELIVERED_MESSAGES.with(Cell::get);
+    let mut total: usize = 0;
+    loop {
+        let before = delivered();
+        receive();
+        let count = delivered().wrapping_sub(before);
+        if count == 0 {
+            return total;
+        }
+        total = total.saturating_add(count);
+    }
+}
+
+/// The pd and MIDI message queues support a single reader.
+static RECEIVE_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// If this thread is receiving from the pd message queue, a closure could receive again.
+    static IS_RECEIVING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Receives from the pd or MIDI message queue on one thread at a time.
+fn receive_exclusively<F: FnOnce()>(receive: F) {
+    if IS_RECEIVING.with(Cell::get) {
+        receive();
+        return;
+    }
+    let _lock = RECEIVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let _dispatch = Dispatch::start();
+    IS_RECEIVING.with(|receiving| receiving.set(true));
+    receive();
+    IS_RECEIVING.with(|receiving| receiving.set(false));
+}
+
+/// The pd console output which is received while it is captured.
+static CAPTURED_CONSOLE_OUTPUT: Mutex<String> = Mutex::new(String::new());
+static FORWARD_CAPTURED_CONSOLE_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+fn captured_console_output() -> MutexGuard<'static, String> {
+    CAPTURED_CONSOLE_OUTPUT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+unsafe extern "C" fn capture_print(out: *const c_char) {
+    captured_console_output().push_str(&CStr::from_ptr(out).to_string_lossy());
+    if FORWARD_CAPTURED_CONSOLE_OUTPUT.load(Ordering::SeqCst) {
+        libpd_sys::libpd_print_concatenator(out);
+    }
+}
+
+/// Receives the messages which are waiting in the pd message queue and returns the lines which pd printed to its console.
+///
+/// The lines still reach the closure which is registered with [`on_print`].
+/// Nothing is received if this thread is already receiving, e.g. in a registered closure.
+pub(crate) fn receive_console_output() -> Vec<String> {
+    if IS_RECEIVING.with(Cell::get) {
+        return Vec::new();
+    }
+    let has_print_hook = || {
+        pd_error_callback().is_some()
+            || registry()
+                .hooks
+                .iter()
+                .any(|(hook, _)| *hook == Hook::Print)
+    };
+    receive_exclusively(|| {
+        FORWARD_CAPTURED_CONSOLE_OUTPUT.store(has_print_hook(), Ordering::SeqCst);
+        crate::queue::queued_hooks().print = Some(capture_print);
+        crate::queue::receive_messages();
+        let print_hook: unsafe extern "C" fn(*const c_char) = libpd_sys::libpd_print_concatenator;
+        crate::queue::queued_hooks().print = has_print_hook().then_some(print_hook);
+    });
+    let output = std::mem::take(&mut *captured_console_output());
+    output.lines().map(str::to_owned).collect()
+}
+
+/// Keeps panics of a registered closure from unwinding into pd.
+struct PanicGuard {
+    callback: &'static str,
+    disabled: bool,
+}
+
+impl PanicGuard {
+    const fn new(callback: &'static str) -> Self {
+        Self {
+            callback,
+            disabled: false,
+        }
+    }
+
+    /// Runs the body unless it panicked before.
+    fn run<F: FnOnce()>(&mut self, body: F) {
+        if self.disabled {
+            DELIVERED_MESSAGES.with(|delivered| delivered.set(delivered.get().wrapping_add(1)));
+            return;
+        }
+        self.disabled = run_callback(self.callback, body);
+    }
+}
+
+/// Runs a callback which a message is delivered to and returns if it panicked.
+///
+/// The message is counted as delivered, the panic is kept from unwinding into pd
+/// and stored for [`take_last_callback_panic`].
+pub(crate) fn run_callback<F: FnOnce()>(callback: &'static str, body: F) -> bool {
+    DELIVERED_MESSAGES.with(|delivered| delivered.set(delivered.get().wrapping_add(1)));
+    CALLBACK_DEPTH.with(|depth| depth.set(depth.get().wrapping_add(1)));
+    let result = panic::catch_unwind(AssertUnwindSafe(body));
+    CALLBACK_DEPTH.with(|depth| depth.set(depth.get().wrapping_sub(1)));
+    let Err(payload) = result else {
+        return false;
+    };
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_owned());
+    *last_callback_panic() = Some(CallbackPanic { callback, message });
+    true
+}
+
 /// Sets a closure to be called when a message is written to the pd console.
 ///
 /// There is also no prior call to `start_listening_from` to listen from pd console.
 ///
+/// It could be called before [`init`](crate::init) to see what pd prints while it initializes,
+/// like the version banner and the errors of externals which fail to load.
+/// pd queues these lines and they reach the closure with the first call to [`receive_messages_from_pd`].
+/// Lines which are received before a closure is registered are not printed anywhere,
+/// so registering it before [`init`](crate::init) is the way to not miss any.
+///
 /// Note: Do not register this listener while pd DSP is running.
 ///
 /// # Example
@@ -139,28 +497,159 @@ pub fn source_to_listen_from_exists<T: AsRef<str>>(sender: T) -> bool {
 ///
 /// libpd_rs::init();
 /// ```
-pub fn on_print<F: FnMut(&str) + Send + Sync + 'static>(mut user_provided_closure: F) {
-    let closure: &'static mut _ = Box::leak(Box::new(move |out: *const std::os::raw::c_char| {
-        let out = unsafe { CStr::from_ptr(out).to_str().expect(C_STR_FAILURE) };
-        user_provided_closure(out);
+pub fn on_print<F: FnMut(&str) + Send + Sync + 'static>(user_provided_closure: F) {
+    set_print_closure("on_print", user_provided_closure);
+}
+
+/// Sets a closure to be called with the [`PrintKind`] of every line which is written to the pd console.
+///
+/// Libpd has no separate hook for errors, the kind is told from the prefix pd writes errors with, see [`PrintKind::of`].
+/// The line is passed untouched with its prefix, this lets an app route errors to stderr and other lines elsewhere.
+///
+/// It replaces the closure which is registered with [`on_print`], they share the same hook.
+/// Errors still go to the closure of [`on_pd_error`] instead if one is registered.
+///
+/// Note: Do not register this listener while pd DSP is running.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::on_print_with_kind;
+///
+/// on_print_with_kind(|kind, line| {
+///     if kind.is_error() {
+///         eprintln!("{line}");
+///     } else {
+///         println!("{line}");
+///     }
+/// });
+///
+/// libpd_rs::init();
+/// ```
+pub fn on_print_with_kind<F: FnMut(PrintKind, &str) + Send + Sync + 'static>(
+    mut user_provided_closure: F,
+) {
+    set_print_closure("on_print_with_kind", move |line: &str| {
+        user_provided_closure(PrintKind::of(line), line);
+    });
+}
+
+/// Registers the closure which lines of the pd console are routed to, `callback` names the function which registers it.
+fn set_print_closure<F: FnMut(&str) + Send + Sync + 'static>(
+    callback: &'static str,
+    mut user_provided_closure: F,
+) {
+    let mut guard = PanicGuard::new(callback);
+    let closure = Box::into_raw(Box::new(move |out: *const std::os::raw::c_char| {
+        guard.run(|| {
+            let out = unsafe { CStr::from_ptr(out).to_string_lossy() };
+            user_provided_closure(&out);
+        });
     }));
-    let callback = ClosureMut1::new(closure);
+    let callback = ClosureMut1::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as PrintHookCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_printhook>() };
-    std::mem::forget(callback);
+
+    // Lines are routed to the closure, unless they are errors which go to the closure of `on_pd_error`.
+    *print_hook() = ptr;
+    route_console_lines();
 
     unsafe {
-        libpd_sys::libpd_set_queued_printhook(Some(libpd_sys::libpd_print_concatenator));
-    };
+        register_hook(Hook::Print, closure, callback);
+    }
+}
+
+type PdErrorCallback = Arc<Mutex<dyn FnMut(PdConsoleError) + Send>>;
 
-    // Always concatenate
+/// The closure which is registered with [`on_print`], lines of the pd console are routed to it.
+static PRINT_HOOK: Mutex<t_libpd_printhook> = Mutex::new(None);
+/// The closure which is registered with [`on_pd_error`].
+static PD_ERROR_CALLBACK: Mutex<Option<PdErrorCallback>> = Mutex::new(None);
+/// The last line of the pd console which is not an error, it describes the object of the next error.
+static PREVIOUS_CONSOLE_LINE: Mutex<String> = Mutex::new(String::new());
+
+fn print_hook() -> MutexGuard<'static, t_libpd_printhook> {
+    PRINT_HOOK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn pd_error_callback() -> MutexGuard<'static, Option<PdErrorCallback>> {
+    PD_ERROR_CALLBACK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Concatenates what pd prints to lines and routes them with [`route_console_line`].
+fn route_console_lines() {
+    crate::queue::queued_hooks().print = Some(libpd_sys::libpd_print_concatenator);
     unsafe {
-        libpd_sys::libpd_set_concatenated_printhook(ptr);
-    };
+        libpd_sys::libpd_set_concatenated_printhook(Some(route_console_line));
+    }
+}
+
+/// Routes an error line of the pd console to the closure of [`on_pd_error`] and any other line to the closure of [`on_print`].
+unsafe extern "C" fn route_console_line(line: *const c_char) {
+    let callback = pd_error_callback().clone();
+    if let Some(callback) = callback {
+        let text = CStr::from_ptr(line).to_string_lossy();
+        let mut previous_line = PREVIOUS_CONSOLE_LINE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(error) = PdConsoleError::from_line(&text, &previous_line) {
+            previous_line.clear();
+            drop(previous_line);
+            // The closure is busy if it receives messages itself, the error is printed then.
+            if let Ok(mut callback) = callback.try_lock() {
+                callback(error);
+                return;
+            }
+        } else {
+            *previous_line = text.into_owned();
+        }
+    }
+    let print_hook = *print_hook();
+    if let Some(print_hook) = print_hook {
+        print_hook(line);
+    }
+}
+
+/// Sets a closure to be called when pd writes an error to its console.
+///
+/// Pd reports errors of a patch, like objects which couldn't be created or messages which an object doesn't understand,
+/// as console lines which start with `error:`.
+/// These lines and the verbose notes which pd writes along with them are given to this closure as a [`PdConsoleError`]
+/// instead of the closure which is registered with [`on_print`], other lines still reach that closure untouched.
+///
+/// Like [`on_print`], it could be called before [`init`](crate::init)
+/// and the errors reach the closure when messages are received with [`receive_messages_from_pd`].
+///
+/// Note: Do not register this listener while pd DSP is running.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::{on_pd_error, on_print};
+///
+/// on_print(|line: &str| println!("{line}"));
+/// on_pd_error(|error| match error.object {
+///     Some(object) => eprintln!("{object}: {}", error.message),
+///     None => eprintln!("{}", error.message),
+/// });
+///
+/// libpd_rs::init();
+/// ```
+pub fn on_pd_error<F: FnMut(PdConsoleError) + Send + Sync + 'static>(mut user_provided_closure: F) {
+    let mut guard = PanicGuard::new("on_pd_error");
+    let callback: PdErrorCallback = Arc::new(Mutex::new(move |error: PdConsoleError| {
+        guard.run(|| user_provided_closure(error));
+    }));
+    // Drop the previous closure outside of the lock.
+    let previous = pd_error_callback().replace(callback);
+    route_console_lines();
+    drop(previous);
 }
 
 /// Sets a closure to be called when a bang is received from a subscribed receiver
 ///
+/// Returns the closure which it replaces, like [`on_float`].
+///
 /// Note: Do not register this listener while pd DSP is running.
 ///
 /// # Example
@@ -180,20 +669,28 @@ pub fn on_print<F: FnMut(&str) + Send + Sync + 'static>(mut user_provided_closur
 /// let foo_receiver_handle = start_listening_from("foo").unwrap();
 /// let bar_receiver_handle = start_listening_from("bar").unwrap();
 /// ```
-pub fn on_bang<F: FnMut(&str) + Send + Sync + 'static>(mut user_provided_closure: F) {
-    let closure: &'static mut _ =
-        Box::leak(Box::new(move |source: *const std::os::raw::c_char| {
-            let source = unsafe { CStr::from_ptr(source).to_str().expect(C_STR_FAILURE) };
-            user_provided_closure(source);
-        }));
-    let callback = ClosureMut1::new(closure);
+pub fn on_bang<F: FnMut(&str) + Send + Sync + 'static>(
+    user_provided_closure: F,
+) -> Option<BangClosure> {
+    let user_closure = UserClosure::new(Box::new(user_provided_closure) as BangClosure);
+    let user_provided_closure = user_closure.pointer();
+    let mut guard = PanicGuard::new("on_bang");
+    let closure = Box::into_raw(Box::new(move |source: *const std::os::raw::c_char| {
+        guard.run(|| {
+            let source = unsafe { CStr::from_ptr(source).to_string_lossy() };
+            unsafe {
+                (*user_provided_closure)(&source);
+            }
+        });
+    }));
+    let callback = ClosureMut1::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as BangHookCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_banghook>() };
-    std::mem::forget(callback);
 
-    unsafe {
-        libpd_sys::libpd_set_queued_banghook(ptr);
-    };
+    crate::queue::queued_hooks().bang = ptr;
+
+    let previous = unsafe { register_hook_calling(Hook::Bang, closure, callback, user_closure) };
+    take_user_closure::<BangClosure>(previous?).ok()
 }
 
 /// Sets a closure to be called when an `f32` is received from a subscribed receiver
@@ -202,6 +699,14 @@ pub fn on_bang<F: FnMut(&str) + Send + Sync + 'static>(mut user_provided_closure
 ///
 /// If you set both, the one you have set the latest will **overwrite the previously set one**.
 ///
+/// Pd has a single float hook, so the closure replaces the one which is registered before,
+/// like [`std::mem::replace`] the replaced closure is returned, if there is one.
+/// It could be registered again to restore it or be called from the new closure to chain them.
+/// A closure which is registered with [`on_double`] is returned with its values converted from `f32`.
+///
+/// Nothing is returned if it is called while messages are received, e.g. from a registered closure,
+/// since the replaced closure could be running then. It is dropped when receiving finishes instead.
+///
 /// Note: Do not register this listener while pd DSP is running.
 ///
 /// # Example
@@ -220,22 +725,50 @@ pub fn on_bang<F: FnMut(&str) + Send + Sync + 'static>(mut user_provided_closure
 ///
 /// let foo_receiver_handle = start_listening_from("foo").unwrap();
 /// let bar_receiver_handle = start_listening_from("bar").unwrap();
+///
+/// // Print every float for a while and then restore the previous closure.
+/// let previous = on_float(|source: &str, value: f32| println!("{source}: {value}"));
+/// if let Some(previous) = previous {
+///     on_float(previous);
+/// }
 /// ```
-pub fn on_float<F: FnMut(&str, f32) + Send + Sync + 'static>(mut user_provided_closure: F) {
-    let closure: &'static mut _ = Box::leak(Box::new(
+pub fn on_float<F: FnMut(&str, f32) + Send + Sync + 'static>(
+    user_provided_closure: F,
+) -> Option<FloatClosure> {
+    let user_closure = UserClosure::new(Box::new(user_provided_closure) as FloatClosure);
+    let user_provided_closure = user_closure.pointer();
+    let mut guard = PanicGuard::new("on_float");
+    let closure = Box::into_raw(Box::new(
         move |source: *const std::os::raw::c_char, float: f32| {
-            let source = unsafe { CStr::from_ptr(source).to_str().expect(C_STR_FAILURE) };
-            user_provided_closure(source, float);
+            guard.run(|| {
+                let source = unsafe { CStr::from_ptr(source).to_string_lossy() };
+                unsafe {
+                    (*user_provided_closure)(&source, float);
+                }
+            });
         },
     ));
-    let callback = ClosureMut2::new(closure);
+    let callback = ClosureMut2::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as FloatHookCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_floathook>() };
-    std::mem::forget(callback);
 
-    unsafe {
-        libpd_sys::libpd_set_queued_floathook(ptr);
-    };
+    {
+        let mut hooks = crate::queue::queued_hooks();
+        hooks.float = ptr;
+        hooks.double = None;
+    }
+
+    let previous = unsafe { register_hook_calling(Hook::Float, closure, callback, user_closure) };
+    match take_user_closure::<FloatClosure>(previous?) {
+        Ok(previous) => Some(previous),
+        Err(previous) => {
+            take_user_closure::<DoubleClosure>(previous)
+                .ok()
+                .map(|mut previous| -> FloatClosure {
+                    Box::new(move |source: &str, value: f32| previous(source, f64::from(value)))
+                })
+        }
+    }
 }
 
 /// Sets a closure to be called when an `f64` is received from a subscribed receiver
@@ -244,6 +777,9 @@ pub fn on_float<F: FnMut(&str, f32) + Send + Sync + 'static>(mut user_provided_c
 ///
 /// If you set both, the one you have set the latest will **overwrite the previously set one**.
 ///
+/// Returns the closure which it replaces, like [`on_float`].
+/// A closure which is registered with [`on_float`] is returned with its values cast to `f32`.
+///
 /// Note: Do not register this listener while pd DSP is running.
 ///
 /// # Example
@@ -263,25 +799,51 @@ pub fn on_float<F: FnMut(&str, f32) + Send + Sync + 'static>(mut user_provided_c
 /// let foo_receiver_handle = start_listening_from("foo").unwrap();
 /// let bar_receiver_handle = start_listening_from("bar").unwrap();
 /// ```
-pub fn on_double<F: FnMut(&str, f64) + Send + Sync + 'static>(mut user_provided_closure: F) {
-    let closure: &'static mut _ = Box::leak(Box::new(
+pub fn on_double<F: FnMut(&str, f64) + Send + Sync + 'static>(
+    user_provided_closure: F,
+) -> Option<DoubleClosure> {
+    let user_closure = UserClosure::new(Box::new(user_provided_closure) as DoubleClosure);
+    let user_provided_closure = user_closure.pointer();
+    let mut guard = PanicGuard::new("on_double");
+    let closure = Box::into_raw(Box::new(
         move |source: *const std::os::raw::c_char, double: f64| {
-            let source = unsafe { CStr::from_ptr(source).to_str().expect(C_STR_FAILURE) };
-            user_provided_closure(source, double);
+            guard.run(|| {
+                let source = unsafe { CStr::from_ptr(source).to_string_lossy() };
+                unsafe {
+                    (*user_provided_closure)(&source, double);
+                }
+            });
         },
     ));
-    let callback = ClosureMut2::new(closure);
+    let callback = ClosureMut2::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as DoubleHookCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_doublehook>() };
-    std::mem::forget(callback);
 
-    unsafe {
-        libpd_sys::libpd_set_queued_doublehook(ptr);
-    };
+    {
+        let mut hooks = crate::queue::queued_hooks();
+        hooks.float = None;
+        hooks.double = ptr;
+    }
+
+    let previous = unsafe { register_hook_calling(Hook::Float, closure, callback, user_closure) };
+    match take_user_closure::<DoubleClosure>(previous?) {
+        Ok(previous) => Some(previous),
+        Err(previous) => {
+            take_user_closure::<FloatClosure>(previous)
+                .ok()
+                .map(|mut previous| -> DoubleClosure {
+                    // This is fine since the previous closure received pd floats as `f32` before.
+                    #[allow(clippy::cast_possible_truncation)]
+                    Box::new(move |source: &str, value: f64| previous(source, value as f32))
+                })
+        }
+    }
 }
 
 /// Sets a closure to be called when a symbol is received from a subscribed receiver
 ///
+/// Returns the closure which it replaces, like [`on_float`].
+///
 /// Note: Do not register this listener while pd DSP is running.
 ///
 /// # Example
@@ -301,26 +863,38 @@ pub fn on_double<F: FnMut(&str, f64) + Send + Sync + 'static>(mut user_provided_
 /// let foo_receiver_handle = start_listening_from("foo").unwrap();
 /// let bar_receiver_handle = start_listening_from("bar").unwrap();
 /// ```
-pub fn on_symbol<F: FnMut(&str, &str) + Send + Sync + 'static>(mut user_provided_closure: F) {
-    let closure: &'static mut _ = Box::leak(Box::new(
+pub fn on_symbol<F: FnMut(&str, &str) + Send + Sync + 'static>(
+    user_provided_closure: F,
+) -> Option<SymbolClosure> {
+    let user_closure = UserClosure::new(Box::new(user_provided_closure) as SymbolClosure);
+    let user_provided_closure = user_closure.pointer();
+    let mut guard = PanicGuard::new("on_symbol");
+    let closure = Box::into_raw(Box::new(
         move |source: *const std::os::raw::c_char, symbol: *const std::os::raw::c_char| {
-            let source = unsafe { CStr::from_ptr(source).to_str().expect(C_STR_FAILURE) };
-            let symbol = unsafe { CStr::from_ptr(symbol).to_str().expect(C_STR_FAILURE) };
-            user_provided_closure(source, symbol);
+            guard.run(|| {
+                let source = unsafe { CStr::from_ptr(source).to_string_lossy() };
+                let symbol = unsafe { CStr::from_ptr(symbol).to_string_lossy() };
+                unsafe {
+                    (*user_provided_closure)(&source, &symbol);
+                }
+            });
         },
     ));
-    let callback = ClosureMut2::new(closure);
+    let callback = ClosureMut2::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as SymbolHookCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_symbolhook>() };
 
-    std::mem::forget(callback);
-    unsafe {
-        libpd_sys::libpd_set_queued_symbolhook(ptr);
-    };
+    crate::queue::queued_hooks().symbol = ptr;
+
+    let previous = unsafe { register_hook_calling(Hook::Symbol, closure, callback, user_closure) };
+    take_user_closure::<SymbolClosure>(previous?).ok()
 }
 
 /// Sets a closure to be called when a list is received from a subscribed receiver
 ///
+/// Returns the closure which it replaces, like [`on_float`].
+/// A closure which is registered with [`on_list_borrowed`] is returned with the atoms viewed for it.
+///
 /// Note: Do not register this listener while pd DSP is running.
 ///
 /// # Example
@@ -363,27 +937,116 @@ pub fn on_symbol<F: FnMut(&str, &str) + Send + Sync + 'static>(mut user_provided
 /// let foo_receiver_handle = start_listening_from("foo").unwrap();
 /// let bar_receiver_handle = start_listening_from("bar").unwrap();
 /// ```
-pub fn on_list<F: FnMut(&str, &[Atom]) + Send + Sync + 'static>(mut user_provided_closure: F) {
-    let closure: &'static mut _ = Box::leak(Box::new(
+pub fn on_list<F: FnMut(&str, &[Atom]) + Send + Sync + 'static>(
+    user_provided_closure: F,
+) -> Option<ListClosure> {
+    let user_closure = UserClosure::new(Box::new(user_provided_closure) as ListClosure);
+    let user_provided_closure = user_closure.pointer();
+    let mut guard = PanicGuard::new("on_list");
+    let closure = Box::into_raw(Box::new(
         move |source: *const std::os::raw::c_char,
               list_length: i32,
               atom_list: *mut libpd_sys::t_atom| {
-            let source = unsafe { CStr::from_ptr(source).to_str().expect(C_STR_FAILURE) };
-            // It is practically impossible that this list will have a negative size or a size of millions so this is safe.
-            #[allow(clippy::cast_sign_loss)]
-            let atom_list = unsafe { std::slice::from_raw_parts(atom_list, list_length as usize) };
-            let atoms = make_atom_list_from_t_atom_list!(atom_list);
-            user_provided_closure(source, &atoms);
+            guard.run(|| {
+                let source = unsafe { CStr::from_ptr(source).to_string_lossy() };
+                // It is practically impossible that this list will have a negative size or a size of millions so this is safe.
+                #[allow(clippy::cast_sign_loss)]
+                let atom_list =
+                    unsafe { std::slice::from_raw_parts(atom_list, list_length as usize) };
+                let atoms = make_atom_list_from_t_atom_list!(atom_list);
+                unsafe {
+                    (*user_provided_closure)(&source, &atoms);
+                }
+            });
         },
     ));
-    let callback = ClosureMut3::new(closure);
+    let callback = ClosureMut3::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as ListHookCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_listhook>() };
-    std::mem::forget(callback);
 
-    unsafe {
-        libpd_sys::libpd_set_queued_listhook(ptr);
-    };
+    crate::queue::queued_hooks().list = ptr;
+
+    let previous = unsafe { register_hook_calling(Hook::List, closure, callback, user_closure) };
+    match take_user_closure::<ListClosure>(previous?) {
+        Ok(previous) => Some(previous),
+        Err(previous) => take_user_closure::<BorrowedListClosure>(previous).ok().map(
+            |mut previous| -> ListClosure {
+                Box::new(move |source: &str, list: &[Atom]| {
+                    previous(source, &list.iter().map(AtomView::from).collect::<Vec<_>>());
+                })
+            },
+        ),
+    }
+}
+
+/// Sets a closure to be called with a borrowed view of a list which is received from a subscribed receiver
+///
+/// Unlike [`on_list`] the atoms are not copied to a `Vec<Atom>` with a `String` for every symbol,
+/// the closure receives [`AtomView`]s which borrow the symbols from pd and are only valid while it runs.
+/// Lists of up to 64 atoms are viewed without allocating, convert the atoms with [`AtomView::to_atom`] to keep them.
+///
+/// It replaces the closure which is registered with [`on_list`], they share the same hook.
+/// The replaced closure is returned like in [`on_float`], one of [`on_list`] with the views converted to atoms for it.
+///
+/// Note: Do not register this listener while pd DSP is running.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::{on_list_borrowed, start_listening_from};
+/// use libpd_rs::types::AtomView;
+///
+/// on_list_borrowed(|source: &str, list: &[AtomView]| {
+///     if let [AtomView::Symbol("freq"), AtomView::Float(frequency)] = list {
+///         println!("{source} sets the frequency to {frequency}");
+///     }
+/// });
+///
+/// libpd_rs::init();
+///
+/// let foo_receiver_handle = start_listening_from("foo").unwrap();
+/// ```
+pub fn on_list_borrowed<F: FnMut(&str, &[AtomView]) + Send + Sync + 'static>(
+    user_provided_closure: F,
+) -> Option<BorrowedListClosure> {
+    let user_closure = UserClosure::new(Box::new(user_provided_closure) as BorrowedListClosure);
+    let user_provided_closure = user_closure.pointer();
+    let mut guard = PanicGuard::new("on_list_borrowed");
+    let closure = Box::into_raw(Box::new(
+        move |source: *const std::os::raw::c_char,
+              list_length: i32,
+              atom_list: *mut libpd_sys::t_atom| {
+            guard.run(|| {
+                let source = unsafe { CStr::from_ptr(source).to_string_lossy() };
+                // It is practically impossible that this list will have a negative size or a size of millions so this is safe.
+                #[allow(clippy::cast_sign_loss)]
+                let atom_list =
+                    unsafe { std::slice::from_raw_parts(atom_list, list_length as usize) };
+                with_atom_views(atom_list, |atoms| unsafe {
+                    (*user_provided_closure)(&source, atoms);
+                });
+            });
+        },
+    ));
+    let callback = ClosureMut3::new(unsafe { &mut *closure });
+    let code = callback.code_ptr() as ListHookCodePtr;
+    let ptr = unsafe { *code.cast::<t_libpd_listhook>() };
+
+    crate::queue::queued_hooks().list = ptr;
+
+    let previous = unsafe { register_hook_calling(Hook::List, closure, callback, user_closure) };
+    match take_user_closure::<BorrowedListClosure>(previous?) {
+        Ok(previous) => Some(previous),
+        Err(previous) => take_user_closure::<ListClosure>(previous).ok().map(
+            |mut previous| -> BorrowedListClosure {
+                Box::new(move |source: &str, list: &[AtomView]| {
+                    previous(
+                        source,
+                        &list.iter().map(AtomView::to_atom).collect::<Vec<_>>(),
+                    );
+                })
+            },
+        ),
+    }
 }
 
 /// Sets a closure to be called when a typed message is received from a subscribed receiver
@@ -400,6 +1063,9 @@ pub fn on_list<F: FnMut(&str, &[Atom]) + Send + Sync + 'static>(mut user_provide
 /// values: [Atom::from(1.0), Atom::from("merhaba")]
 /// ```
 ///
+/// Returns the closure which it replaces, like [`on_float`].
+/// A closure which is registered with [`on_message_borrowed`] is returned with the arguments viewed for it.
+///
 /// Note: Do not register this listener while pd DSP is running.
 ///
 /// # Example
@@ -430,36 +1096,127 @@ pub fn on_list<F: FnMut(&str, &[Atom]) + Send + Sync + 'static>(mut user_provide
 /// let foo_receiver_handle = start_listening_from("foo").unwrap();
 /// ```
 pub fn on_message<F: FnMut(&str, &str, &[Atom]) + Send + Sync + 'static>(
-    mut user_provided_closure: F,
-) {
-    let closure: &'static mut _ = Box::leak(Box::new(
+    user_provided_closure: F,
+) -> Option<MessageClosure> {
+    let user_closure = UserClosure::new(Box::new(user_provided_closure) as MessageClosure);
+    let user_provided_closure = user_closure.pointer();
+    let mut guard = PanicGuard::new("on_message");
+    let closure = Box::into_raw(Box::new(
         move |source: *const std::os::raw::c_char,
               message: *const std::os::raw::c_char,
               list_length: i32,
               atom_list: *mut libpd_sys::t_atom| {
-            let source = unsafe { CStr::from_ptr(source).to_str().expect(C_STR_FAILURE) };
-            let message = unsafe { CStr::from_ptr(message).to_str().expect(C_STR_FAILURE) };
-            // It is practically impossible that this list will have a negative size or a size of millions so this is safe.
-            #[allow(clippy::cast_sign_loss)]
-            let atom_list = unsafe { std::slice::from_raw_parts(atom_list, list_length as usize) };
-            let atoms = make_atom_list_from_t_atom_list!(atom_list);
-            user_provided_closure(source, message, &atoms);
+            guard.run(|| {
+                let source = unsafe { CStr::from_ptr(source).to_string_lossy() };
+                let message = unsafe { CStr::from_ptr(message).to_string_lossy() };
+                // It is practically impossible that this list will have a negative size or a size of millions so this is safe.
+                #[allow(clippy::cast_sign_loss)]
+                let atom_list =
+                    unsafe { std::slice::from_raw_parts(atom_list, list_length as usize) };
+                let atoms = make_atom_list_from_t_atom_list!(atom_list);
+                unsafe {
+                    (*user_provided_closure)(&source, &message, &atoms);
+                }
+            });
         },
     ));
-    let callback = ClosureMut4::new(closure);
+    let callback = ClosureMut4::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as MessageHookCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_messagehook>() };
-    std::mem::forget(callback);
 
-    unsafe {
-        libpd_sys::libpd_set_queued_messagehook(ptr);
-    };
+    crate::queue::queued_hooks().message = ptr;
+
+    let previous = unsafe { register_hook_calling(Hook::Message, closure, callback, user_closure) };
+    match take_user_closure::<MessageClosure>(previous?) {
+        Ok(previous) => Some(previous),
+        Err(previous) => take_user_closure::<BorrowedMessageClosure>(previous)
+            .ok()
+            .map(|mut previous| -> MessageClosure {
+                Box::new(move |source: &str, message: &str, arguments: &[Atom]| {
+                    let arguments = arguments.iter().map(AtomView::from).collect::<Vec<_>>();
+                    previous(source, message, &arguments);
+                })
+            }),
+    }
+}
+
+/// Sets a closure to be called with a borrowed view of a typed message which is received from a subscribed receiver
+///
+/// Like [`on_list_borrowed`] for [`on_message`], the arguments are [`AtomView`]s which are only valid while the closure runs.
+///
+/// It replaces the closure which is registered with [`on_message`], they share the same hook.
+/// The replaced closure is returned like in [`on_float`], one of [`on_message`] with the views converted to atoms for it.
+///
+/// Note: Do not register this listener while pd DSP is running.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::{on_message_borrowed, start_listening_from};
+/// use libpd_rs::types::AtomView;
+///
+/// on_message_borrowed(|source: &str, message: &str, values: &[AtomView]| {
+///     if let ("set", [AtomView::Float(value)]) = (message, values) {
+///         println!("{source} is set to {value}");
+///     }
+/// });
+///
+/// libpd_rs::init();
+///
+/// let foo_receiver_handle = start_listening_from("foo").unwrap();
+/// ```
+pub fn on_message_borrowed<F: FnMut(&str, &str, &[AtomView]) + Send + Sync + 'static>(
+    user_provided_closure: F,
+) -> Option<BorrowedMessageClosure> {
+    let user_closure = UserClosure::new(Box::new(user_provided_closure) as BorrowedMessageClosure);
+    let user_provided_closure = user_closure.pointer();
+    let mut guard = PanicGuard::new("on_message_borrowed");
+    let closure = Box::into_raw(Box::new(
+        move |source: *const std::os::raw::c_char,
+              message: *const std::os::raw::c_char,
+              list_length: i32,
+              atom_list: *mut libpd_sys::t_atom| {
+            guard.run(|| {
+                let source = unsafe { CStr::from_ptr(source).to_string_lossy() };
+                let message = unsafe { CStr::from_ptr(message).to_string_lossy() };
+                // It is practically impossible that this list will have a negative size or a size of millions so this is safe.
+                #[allow(clippy::cast_sign_loss)]
+                let atom_list =
+                    unsafe { std::slice::from_raw_parts(atom_list, list_length as usize) };
+                with_atom_views(atom_list, |atoms| unsafe {
+                    (*user_provided_closure)(&source, &message, atoms);
+                });
+            });
+        },
+    ));
+    let callback = ClosureMut4::new(unsafe { &mut *closure });
+    let code = callback.code_ptr() as MessageHookCodePtr;
+    let ptr = unsafe { *code.cast::<t_libpd_messagehook>() };
+
+    crate::queue::queued_hooks().message = ptr;
+
+    let previous = unsafe { register_hook_calling(Hook::Message, closure, callback, user_closure) };
+    match take_user_closure::<BorrowedMessageClosure>(previous?) {
+        Ok(previous) => Some(previous),
+        Err(previous) => take_user_closure::<MessageClosure>(previous).ok().map(
+            |mut previous| -> BorrowedMessageClosure {
+                Box::new(move |source: &str, message: &str, arguments: &[AtomView]| {
+                    let arguments = arguments.iter().map(AtomView::to_atom).collect::<Vec<_>>();
+                    previous(source, message, &arguments);
+                })
+            },
+        ),
+    }
 }
 
 /// Receives messages from pd message queue.
 ///
 /// This should be called repeatedly in the **application's main loop** or the **audio callback** to fetch messages from pd.
 ///
+/// Messages which do not fit into the queue are dropped, see [`MESSAGE_QUEUE_SIZE_IN_BYTES`].
+///
+/// The queue supports a single reader, calls from different threads wait for each other.
+/// Called from a registered closure it delivers nothing, the messages which pd sends meanwhile wait for the next call.
+///
 /// # Example
 /// ```no_run
 /// use libpd_rs::receive::{start_listening_from, on_symbol, receive_messages_from_pd};
@@ -480,9 +1237,41 @@ pub fn on_message<F: FnMut(&str, &str, &[Atom]) + Send + Sync + 'static>(
 /// }
 /// ```
 pub fn receive_messages_from_pd() {
-    unsafe {
-        libpd_sys::libpd_queued_receive_pd_messages();
-    };
+    receive_exclusively(crate::queue::receive_messages);
+}
+
+/// Receives messages from pd message queue until it is empty and returns the number of received messages.
+///
+/// A single call of [`receive_messages_from_pd`] only delivers the messages which are in the queue when it is called,
+/// messages which pd sends in the meantime wait for the next call.
+/// This function keeps receiving until a call delivers nothing,
+/// so a burst of messages does not pile up when messages are only received once per frame.
+///
+/// Only messages which are delivered to a closure registered with one of the `on_*` functions are counted,
+/// once more for every [sink](crate::sink::subscribe_with) they are routed to,
+/// messages without a closure or a sink are still removed from the queue.
+/// Printed messages are counted per line.
+///
+/// Call this from the **application's main loop** or another control thread, **not** from the audio callback,
+/// since the time it takes grows with the number of messages which are waiting.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::receive::{drain_all_messages, on_float, start_listening_from};
+///
+/// on_float(|source: &str, value: f32| {
+///   println!("{source}: {value}");
+/// });
+///
+/// let receiver_handle = start_listening_from("foo").unwrap();
+///
+/// loop {
+///     let received = drain_all_messages();
+///     println!("Received {received} messages in this frame.");
+/// }
+/// ```
+pub fn drain_all_messages() -> usize {
+    drain(receive_messages_from_pd)
 }
 
 /// Sets a closure to be called when a MIDI note on event is received.
@@ -510,18 +1299,21 @@ pub fn receive_messages_from_pd() {
 pub fn on_midi_note_on<F: FnMut(i32, i32, i32) + Send + Sync + 'static>(
     mut user_provided_closure: F,
 ) {
-    let closure: &'static mut _ =
-        Box::leak(Box::new(move |channel: i32, pitch: i32, velocity: i32| {
+    let mut guard = PanicGuard::new("on_midi_note_on");
+    let closure = Box::into_raw(Box::new(move |channel: i32, pitch: i32, velocity: i32| {
+        guard.run(|| {
             user_provided_closure(channel, pitch, velocity);
-        }));
-    let callback = ClosureMut3::new(closure);
+        });
+    }));
+    let callback = ClosureMut3::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as MidiNoteOnCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_noteonhook>() };
-    std::mem::forget(callback);
+
+    crate::queue::queued_hooks().note_on = ptr;
 
     unsafe {
-        libpd_sys::libpd_set_queued_noteonhook(ptr);
-    };
+        register_hook(Hook::MidiNoteOn, closure, callback);
+    }
 }
 
 /// Sets a closure to be called when a MIDI control change event is received.
@@ -547,19 +1339,23 @@ pub fn on_midi_note_on<F: FnMut(i32, i32, i32) + Send + Sync + 'static>(
 pub fn on_midi_control_change<F: FnMut(i32, i32, i32) + Send + Sync + 'static>(
     mut user_provided_closure: F,
 ) {
-    let closure: &'static mut _ = Box::leak(Box::new(
+    let mut guard = PanicGuard::new("on_midi_control_change");
+    let closure = Box::into_raw(Box::new(
         move |channel: i32, controller: i32, value: i32| {
-            user_provided_closure(channel, controller, value);
+            guard.run(|| {
+                user_provided_closure(channel, controller, value);
+            });
         },
     ));
-    let callback = ClosureMut3::new(closure);
+    let callback = ClosureMut3::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as MidiControlChangeCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_controlchangehook>() };
-    std::mem::forget(callback);
+
+    crate::queue::queued_hooks().control_change = ptr;
 
     unsafe {
-        libpd_sys::libpd_set_queued_controlchangehook(ptr);
-    };
+        register_hook(Hook::MidiControlChange, closure, callback);
+    }
 }
 
 /// Sets a closure to be called when a MIDI program change event is received.
@@ -585,17 +1381,21 @@ pub fn on_midi_control_change<F: FnMut(i32, i32, i32) + Send + Sync + 'static>(
 pub fn on_midi_program_change<F: FnMut(i32, i32) + Send + Sync + 'static>(
     mut user_provided_closure: F,
 ) {
-    let closure: &'static mut _ = Box::leak(Box::new(move |channel: i32, value: i32| {
-        user_provided_closure(channel, value);
+    let mut guard = PanicGuard::new("on_midi_program_change");
+    let closure = Box::into_raw(Box::new(move |channel: i32, value: i32| {
+        guard.run(|| {
+            user_provided_closure(channel, value);
+        });
     }));
-    let callback = ClosureMut2::new(closure);
+    let callback = ClosureMut2::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as MidiProgramChangeCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_programchangehook>() };
-    std::mem::forget(callback);
+
+    crate::queue::queued_hooks().program_change = ptr;
 
     unsafe {
-        libpd_sys::libpd_set_queued_programchangehook(ptr);
-    };
+        register_hook(Hook::MidiProgramChange, closure, callback);
+    }
 }
 
 /// Sets a closure to be called when a MIDI pitch bend event is received.
@@ -623,17 +1423,21 @@ pub fn on_midi_program_change<F: FnMut(i32, i32) + Send + Sync + 'static>(
 pub fn on_midi_pitch_bend<F: FnMut(i32, i32) + Send + Sync + 'static>(
     mut user_provided_closure: F,
 ) {
-    let closure: &'static mut _ = Box::leak(Box::new(move |channel: i32, value: i32| {
-        user_provided_closure(channel, value);
+    let mut guard = PanicGuard::new("on_midi_pitch_bend");
+    let closure = Box::into_raw(Box::new(move |channel: i32, value: i32| {
+        guard.run(|| {
+            user_provided_closure(channel, value);
+        });
     }));
-    let callback = ClosureMut2::new(closure);
+    let callback = ClosureMut2::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as MidiPitchBendCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_pitchbendhook>() };
-    std::mem::forget(callback);
+
+    crate::queue::queued_hooks().pitch_bend = ptr;
 
     unsafe {
-        libpd_sys::libpd_set_queued_pitchbendhook(ptr);
-    };
+        register_hook(Hook::MidiPitchBend, closure, callback);
+    }
 }
 
 /// Sets a closure to be called when a MIDI after touch event is received.
@@ -659,17 +1463,21 @@ pub fn on_midi_pitch_bend<F: FnMut(i32, i32) + Send + Sync + 'static>(
 pub fn on_midi_after_touch<F: FnMut(i32, i32) + Send + Sync + 'static>(
     mut user_provided_closure: F,
 ) {
-    let closure: &'static mut _ = Box::leak(Box::new(move |channel: i32, value: i32| {
-        user_provided_closure(channel, value);
+    let mut guard = PanicGuard::new("on_midi_after_touch");
+    let closure = Box::into_raw(Box::new(move |channel: i32, value: i32| {
+        guard.run(|| {
+            user_provided_closure(channel, value);
+        });
     }));
-    let callback = ClosureMut2::new(closure);
+    let callback = ClosureMut2::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as MidiAfterTouchCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_aftertouchhook>() };
-    std::mem::forget(callback);
+
+    crate::queue::queued_hooks().after_touch = ptr;
 
     unsafe {
-        libpd_sys::libpd_set_queued_aftertouchhook(ptr);
-    };
+        register_hook(Hook::MidiAfterTouch, closure, callback);
+    }
 }
 
 /// Sets a closure to be called when a MIDI poly after touch event is received.
@@ -695,61 +1503,130 @@ pub fn on_midi_after_touch<F: FnMut(i32, i32) + Send + Sync + 'static>(
 pub fn on_midi_poly_after_touch<F: FnMut(i32, i32, i32) + Send + Sync + 'static>(
     mut user_provided_closure: F,
 ) {
-    let closure: &'static mut _ =
-        Box::leak(Box::new(move |channel: i32, pitch: i32, value: i32| {
+    let mut guard = PanicGuard::new("on_midi_poly_after_touch");
+    let closure = Box::into_raw(Box::new(move |channel: i32, pitch: i32, value: i32| {
+        guard.run(|| {
             user_provided_closure(channel, pitch, value);
-        }));
-    let callback = ClosureMut3::new(closure);
+        });
+    }));
+    let callback = ClosureMut3::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as MidiPolyAfterTouchCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_polyaftertouchhook>() };
-    std::mem::forget(callback);
+
+    crate::queue::queued_hooks().poly_after_touch = ptr;
 
     unsafe {
-        libpd_sys::libpd_set_queued_polyaftertouchhook(ptr);
-    };
+        register_hook(Hook::MidiPolyAfterTouch, closure, callback);
+    }
 }
 
 /// Sets a closure to be called when a single raw MIDI byte is received.
 ///
 /// You do not need to register this listener explicitly.
 ///
-/// Port is zero-indexed and byte is `0-255`
+/// Port is zero-indexed and byte is `0-255`.
+/// Bytes sent to `[midiout]` or `[sysexout]` objects in the patch arrive here in the order they are sent.
 ///
 /// Note: Out of range values which are sent from the patch are clamped.
 ///
+/// This shares the raw MIDI hook with [`on_sysex`], setting one replaces the other.
+///
 /// # Example
 /// ```rust
 /// use libpd_rs::receive::{on_midi_byte};
 ///
 /// libpd_rs::init();
 ///
-/// on_midi_byte(|port: i32, byte: i32| {
-///   println!("Raw MIDI Byte: port {port}, byte {byte}");
+/// on_midi_byte(|port: i32, byte: u8| {
+///   println!("Raw MIDI Byte: port {port}, byte {byte:#04X}");
 /// });
 /// ```
-pub fn on_midi_byte<F: FnMut(i32, i32) + Send + Sync + 'static>(mut user_provided_closure: F) {
-    let closure: &'static mut _ = Box::leak(Box::new(move |port: i32, byte: i32| {
+pub fn on_midi_byte<F: FnMut(i32, u8) + Send + Sync + 'static>(mut user_provided_closure: F) {
+    set_midi_byte_hook("on_midi_byte", move |port: i32, byte: u8| {
         user_provided_closure(port, byte);
+    });
+}
+
+/// Sets a closure to be called when a complete system exclusive message is received.
+///
+/// You do not need to register this listener explicitly.
+///
+/// Raw MIDI bytes are collected per port from a `0xF0` start byte until a `0xF7` end byte,
+/// the closure then receives the port and the whole message including the start and end bytes.
+/// Realtime bytes which interleave the message are skipped and any other status byte discards the incomplete message.
+/// Bytes which are not a part of a system exclusive message are ignored.
+///
+/// This shares the raw MIDI hook with [`on_midi_byte`], setting one replaces the other.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::{on_sysex};
+///
+/// libpd_rs::init();
+///
+/// on_sysex(|port: i32, message: &[u8]| {
+///   println!("Sysex: port {port}, message {message:02X?}");
+/// });
+/// ```
+pub fn on_sysex<F: FnMut(i32, &[u8]) + Send + Sync + 'static>(mut user_provided_closure: F) {
+    let mut messages_in_progress: Vec<(i32, Vec<u8>)> = Vec::new();
+    set_midi_byte_hook("on_sysex", move |port: i32, byte: u8| {
+        let in_progress = messages_in_progress
+            .iter()
+            .position(|(stored_port, _)| *stored_port == port);
+        match (byte, in_progress) {
+            (0xF0, Some(index)) => messages_in_progress[index].1 = vec![byte],
+            (0xF0, None) => messages_in_progress.push((port, vec![byte])),
+            (0xF7, Some(index)) => {
+                let (_, mut message) = messages_in_progress.swap_remove(index);
+                message.push(byte);
+                user_provided_closure(port, &message);
+            }
+            // Realtime messages are allowed in between.
+            (0xF8..=0xFF, _) | (_, None) => {}
+            (0x80..=0xFF, Some(index)) => {
+                messages_in_progress.swap_remove(index);
+            }
+            (_, Some(index)) => messages_in_progress[index].1.push(byte),
+        }
+    });
+}
+
+fn set_midi_byte_hook<F: FnMut(i32, u8) + Send + Sync + 'static>(
+    callback_name: &'static str,
+    mut closure: F,
+) {
+    let mut guard = PanicGuard::new(callback_name);
+    let closure = Box::into_raw(Box::new(move |port: i32, byte: i32| {
+        guard.run(|| {
+            // libpd clamps the byte to `0-255` before calling the hook.
+            closure(port, u8::try_from(byte).unwrap_or(u8::MAX));
+        });
     }));
-    let callback = ClosureMut2::new(closure);
+    let callback = ClosureMut2::new(unsafe { &mut *closure });
     let code = callback.code_ptr() as MidiByteCodePtr;
     let ptr = unsafe { *code.cast::<t_libpd_midibytehook>() };
-    std::mem::forget(callback);
+
+    crate::queue::queued_hooks().midi_byte = ptr;
 
     unsafe {
-        libpd_sys::libpd_set_queued_midibytehook(ptr);
-    };
+        register_hook(Hook::MidiByte, closure, callback);
+    }
 }
 
 /// Receives messages from pd midi message queue.
 ///
 /// This should be called repeatedly in the **application's main loop** or the **audio callback** to fetch MIDI messages from pd.
 ///
+/// Messages which do not fit into the queue are dropped, see [`MIDI_QUEUE_SIZE_IN_BYTES`].
+///
+/// Like [`receive_messages_from_pd`], calls from different threads wait for each other.
+///
 /// # Example
 /// ```no_run
 /// use libpd_rs::receive::{on_midi_byte, receive_midi_messages_from_pd};
 ///
-/// on_midi_byte(|port: i32, byte: i32| {
+/// on_midi_byte(|port: i32, byte: u8| {
 ///     println!("{port}, {byte}");
 /// });
 ///
@@ -758,7 +1635,135 @@ pub fn on_midi_byte<F: FnMut(i32, i32) + Send + Sync + 'static>(mut user_provide
 /// }
 /// ```
 pub fn receive_midi_messages_from_pd() {
-    unsafe {
-        libpd_sys::libpd_queued_receive_midi_messages();
-    };
+    receive_exclusively(crate::queue::receive_midi_messages);
+}
+
+/// Receives messages from pd midi message queue until it is empty and returns the number of received messages.
+///
+/// This is the MIDI counterpart of [`drain_all_messages`] and the same notes apply.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::receive::{drain_all_midi_messages, on_midi_byte};
+///
+/// on_midi_byte(|port: i32, byte: u8| {
+///     println!("{port}, {byte}");
+/// });
+///
+/// loop {
+///     drain_all_midi_messages();
+/// }
+/// ```
+pub fn drain_all_midi_messages() -> usize {
+    drain(receive_midi_messages_from_pd)
+}
+
+/// Receives messages from pd on a background thread, for hosts which do not have a natural place to poll from.
+///
+/// Every `interval` the thread receives the pd and MIDI messages until their queues are empty,
+/// like [`drain_all_messages`] and [`drain_all_midi_messages`], and polls the gui like [`poll_gui`](crate::gui::poll_gui)
+/// with the `gui` feature. The parameters which are set since the last poll are sent first with [`flush_params`](crate::params::flush_params).
+/// Polling is skipped while pd is not initialized.
+///
+/// The thread runs with the pd instance which is active on the thread that spawns it.
+/// It stops when the returned [`PollerHandle`] is dropped.
+///
+/// # Thread safety
+///
+/// - The closures which are registered with the `on_*` functions are called on the poller thread,
+///   which is why they need to be `Send`. The state they share with other threads still needs to be synchronized, e.g. with a channel.
+/// - The pd and MIDI message queues support a single reader. Other threads which call [`receive_messages_from_pd`]
+///   or [`receive_midi_messages_from_pd`] wait for the poller, the messages are delivered by whichever thread receives first.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::receive::{on_float, spawn_poller, start_listening_from};
+/// use std::time::Duration;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// on_float(move |source: &str, value: f32| {
+///     let _ = sender.send((source.to_owned(), value));
+/// });
+/// let _level = start_listening_from("level").unwrap();
+///
+/// let poller = spawn_poller(Duration::from_millis(10));
+/// // The values arrive in the channel without polling in this thread.
+/// drop(poller);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the operating system fails to create the thread.
+#[must_use]
+pub fn spawn_poller(interval: std::time::Duration) -> PollerHandle {
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = Arc::clone(&stop);
+    // Pd instances are not `Send`, the address is passed to the thread instead.
+    let instance = unsafe { libpd_sys::libpd_this_instance() } as usize;
+    let thread = std::thread::Builder::new()
+        .name("libpd-rs poller".to_owned())
+        .spawn(move || {
+            unsafe {
+                libpd_sys::libpd_set_instance(instance as *mut libpd_sys::t_pdinstance);
+            }
+            while !thread_stop.load(Ordering::SeqCst) {
+                if crate::is_initialized() {
+                    crate::params::flush_params();
+                    drain_all_messages();
+                    drain_all_midi_messages();
+                    #[cfg(feature = "gui")]
+                    while !thread_stop.load(Ordering::SeqCst) {
+                        let Some(_lock) = crate::try_lock_for_processing() else {
+                            break;
+                        };
+                        if crate::gui::poll_gui().is_none() {
+                            break;
+                        }
+                    }
+                }
+                std::thread::park_timeout(interval);
+            }
+        })
+        .expect("Failed to spawn the poller thread.");
+    PollerHandle {
+        stop,
+        thread: Some(thread),
+    }
+}
+
+/// A background thread which receives messages from pd, spawned with [`spawn_poller`].
+///
+/// Dropping it stops the thread and waits for it to finish.
+#[derive(Debug)]
+pub struct PollerHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl PollerHandle {
+    /// Checks if the thread is still polling.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_some_and(|thread| !thread.is_finished())
+    }
+
+    /// Stops the thread and waits for it to finish, which is the same as dropping the handle.
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for PollerHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            // A panic in the thread is not propagated to the dropping thread.
+            let _ = thread.join();
+        }
+    }
 }
@@ -0,0 +1,306 @@
+#![allow(clippy::redundant_pub_crate)]
+
+use std::any::Any;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::types::PatchInfo;
+
+/// The hooks which this crate registers closures for.
+///
+/// Float and double hooks share the same slot since pd clears one when the other is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Hook {
+    Print,
+    Bang,
+    Float,
+    Symbol,
+    List,
+    Message,
+    MidiNoteOn,
+    MidiControlChange,
+    MidiProgramChange,
+    MidiPitchBend,
+    MidiAfterTouch,
+    MidiPolyAfterTouch,
+    MidiByte,
+}
+
+/// The boxed closure and the libffi closure which points to it.
+struct HookParts<C, K> {
+    closure: *mut C,
+    callback: K,
+}
+
+// SAFETY: `C` and `K` are not bounded since the closures of the hooks capture the raw pointers of
+// `UserClosure`s, which are `Send` themselves. The contract of `HookRegistration::new` asks the closure
+// to capture only values which could be sent, and the libffi closure only points to that closure.
+// The parts are never used through this struct, they are only dropped by `release`.
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl<C, K> Send for HookParts<C, K> {}
+
+impl<C, K> HookParts<C, K> {
+    fn release(self) {
+        // The code pointer needs to go first since it refers to the closure.
+        drop(self.callback);
+        drop(unsafe { Box::from_raw(self.closure) });
+    }
+}
+
+/// A closure of the user which the closure of a hook calls through a pointer,
+/// so it could be handed back when the hook is replaced.
+pub(crate) struct UserClosure<T> {
+    pointer: *mut T,
+}
+
+// The closure is only called by the hook, which is registered from a single place at a time.
+unsafe impl<T: Send> Send for UserClosure<T> {}
+
+impl<T> UserClosure<T> {
+    pub(crate) fn new(closure: T) -> Self {
+        Self {
+            pointer: Box::into_raw(Box::new(closure)),
+        }
+    }
+
+    /// The pointer to call the closure with, which is valid until the closure is dropped or taken back.
+    pub(crate) const fn pointer(&self) -> *mut T {
+        self.pointer
+    }
+
+    fn into_inner(mut self) -> T {
+        let closure = unsafe { Box::from_raw(self.pointer) };
+        self.pointer = std::ptr::null_mut();
+        *closure
+    }
+}
+
+impl<T> Drop for UserClosure<T> {
+    fn drop(&mut self) {
+        if !self.pointer.is_null() {
+            drop(unsafe { Box::from_raw(self.pointer) });
+        }
+    }
+}
+
+/// Owns the closure behind a hook which is registered to pd.
+///
+/// Dropping it frees the closure, so it should only be dropped after pd stops pointing to it.
+pub(crate) struct HookRegistration {
+    release: Option<Box<dyn FnOnce() + Send>>,
+    user_closure: Option<Box<dyn Any + Send>>,
+}
+
+impl HookRegistration {
+    /// Takes the ownership of a closure and the libffi closure made from it.
+    ///
+    /// # Safety
+    ///
+    /// `closure` needs to be created with [`Box::into_raw`] and `callback` should be the only one using it.
+    /// The closure could be dropped from any thread, so it should only capture values which could be sent,
+    /// raw pointers included only if they point to a closure owned by a [`UserClosure`] of a `Send` closure.
+    pub(crate) unsafe fn new<C: 'static, K: 'static>(closure: *mut C, callback: K) -> Self {
+        let parts = HookParts { closure, callback };
+        Self {
+            release: Some(Box::new(move || parts.release())),
+            user_closure: None,
+        }
+    }
+
+    /// Frees the closure which pd points to and hands back the closure of the user which it calls.
+    fn into_user_closure(mut self) -> Option<Box<dyn Any + Send>> {
+        let user_closure = self.user_closure.take();
+        drop(self);
+        user_closure
+    }
+}
+
+impl Drop for HookRegistration {
+    fn drop(&mut self) {
+        if let Some(release) = self.release.take() {
+            release();
+        }
+    }
+}
+
+/// A patch which is opened with this crate and the flag which tells its destinations if it is still open.
+pub(crate) struct TrackedPatch {
+    pub(crate) info: PatchInfo,
+    pub(crate) open: Arc<AtomicBool>,
+}
+
+impl TrackedPatch {
+    /// Marks the patch as closed, before pd closes it.
+    pub(crate) fn mark_closed(&self) {
+        self.open.store(false, Ordering::Release);
+    }
+}
+
+/// Book keeping of everything this crate creates in pd.
+///
+/// Receiver handles are stored as their addresses together with their names, patches together with their path and `$0`.
+pub(crate) struct Registry {
+    pub(crate) patches: Vec<TrackedPatch>,
+    pub(crate) search_paths: Vec<PathBuf>,
+    pub(crate) receivers: Vec<(usize, String)>,
+    pub(crate) hooks: Vec<(Hook, HookRegistration)>,
+    /// The registrations which are replaced while closures of hooks could be running, they are dropped when the last [`Dispatch`] ends.
+    retired: Vec<HookRegistration>,
+    /// The number of [`Dispatch`]es which are running.
+    dispatching: usize,
+}
+
+impl Registry {
+    /// Stores a hook registration and returns the one it replaces if it could be dropped now, see [`retire`](Registry::retire).
+    pub(crate) fn replace_hook(
+        &mut self,
+        hook: Hook,
+        registration: HookRegistration,
+    ) -> Option<HookRegistration> {
+        let previous = self.take_hook(hook);
+        self.hooks.push((hook, registration));
+        self.retire(previous?)
+    }
+
+    /// Keeps a registration which pd does not point to anymore until no closure of a hook could be running,
+    /// returns it back if none is running now.
+    pub(crate) fn retire(&mut self, registration: HookRegistration) -> Option<HookRegistration> {
+        if self.dispatching == 0 {
+            return Some(registration);
+        }
+        self.retired.push(registration);
+        None
+    }
+
+    /// Removes a hook registration if it exists.
+    pub(crate) fn take_hook(&mut self, hook: Hook) -> Option<HookRegistration> {
+        let index = self.hooks.iter().position(|(stored, _)| *stored == hook)?;
+        Some(self.hooks.swap_remove(index).1)
+    }
+
+    /// Removes a patch by its address and returns if it was there.
+    ///
+    /// A registration which is not `0` needs to match too, so a stale handle does not remove a patch which is opened later at the same address.
+    pub(crate) fn untrack_patch(&mut self, address: usize, registration: usize) -> bool {
+        self.patches
+            .iter()
+            .position(|patch| {
+                patch.info.handle() as usize == address
+                    && (registration == 0 || patch.info.registration() == registration)
+            })
+            .map(|index| self.patches.remove(index).mark_closed())
+            .is_some()
+    }
+
+    /// Removes a receiver by its address and returns if it was there.
+    pub(crate) fn untrack_receiver(&mut self, address: usize) -> bool {
+        self.receivers
+            .iter()
+            .position(|(stored, _)| *stored == address)
+            .map(|index| self.receivers.swap_remove(index))
+            .is_some()
+    }
+}
+
+/// The source of the registrations of patches, `0` is left for the patches which are not known to be registered.
+static NEXT_REGISTRATION: AtomicUsize = AtomicUsize::new(1);
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    patches: Vec::new(),
+    search_paths: Vec::new(),
+    receivers: Vec::new(),
+    hooks: Vec::new(),
+    retired: Vec::new(),
+    dispatching: 0,
+});
+
+/// A number which is not given to any other patch.
+pub(crate) fn next_registration() -> usize {
+    NEXT_REGISTRATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Locks the registry.
+pub(crate) fn registry() -> MutexGuard<'static, Registry> {
+    // Poisoning is irrelevant here since every operation leaves the registry consistent.
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Marks that pd could call the closures of hooks until it is dropped, e.g. while messages are received.
+///
+/// The registrations which are replaced meanwhile stay alive until the last one is dropped,
+/// so a closure is not freed while it runs, even if it replaces itself.
+pub(crate) struct Dispatch {
+    _private: (),
+}
+
+impl Dispatch {
+    pub(crate) fn start() -> Self {
+        registry().dispatching += 1;
+        Self { _private: () }
+    }
+}
+
+impl Drop for Dispatch {
+    fn drop(&mut self) {
+        let retired = {
+            let mut registry = registry();
+            registry.dispatching -= 1;
+            if registry.dispatching == 0 {
+                std::mem::take(&mut registry.retired)
+            } else {
+                Vec::new()
+            }
+        };
+        // Drop the closures outside of the lock.
+        drop(retired);
+    }
+}
+
+/// Keeps the closure of a hook alive, dropping the one it replaces when no closure of a hook is running.
+///
+/// # Safety
+///
+/// See [`HookRegistration::new`], pd should already point to the new closure.
+pub(crate) unsafe fn register_hook<C: 'static, K: 'static>(
+    hook: Hook,
+    closure: *mut C,
+    callback: K,
+) {
+    let registration = HookRegistration::new(closure, callback);
+    // Drop the previous closure outside of the lock.
+    let previous = registry().replace_hook(hook, registration);
+    drop(previous);
+}
+
+/// Keeps the closure of a hook and the closure of the user which it calls alive,
+/// and hands back the closure of the user which the previous closure of the hook calls.
+///
+/// The previous closure of the user is handed back as a [`UserClosure`] of its own type, see [`take_user_closure`].
+/// Nothing is handed back while a [`Dispatch`] runs since the closure could be running,
+/// it is dropped when the dispatch ends then.
+///
+/// # Safety
+///
+/// See [`register_hook`], `closure` should call `user_closure` only through its [`pointer`](UserClosure::pointer).
+pub(crate) unsafe fn register_hook_calling<C: 'static, K: 'static, T: Send + 'static>(
+    hook: Hook,
+    closure: *mut C,
+    callback: K,
+    user_closure: UserClosure<T>,
+) -> Option<Box<dyn Any + Send>> {
+    let mut registration = HookRegistration::new(closure, callback);
+    registration.user_closure = Some(Box::new(user_closure));
+    // Drop the previous closure outside of the lock.
+    let previous = registry().replace_hook(hook, registration);
+    previous.and_then(HookRegistration::into_user_closure)
+}
+
+/// Takes the closure of the user back from [`register_hook_calling`] if it is of the type `T`.
+pub(crate) fn take_user_closure<T: 'static>(
+    user_closure: Box<dyn Any + Send>,
+) -> Result<T, Box<dyn Any + Send>> {
+    user_closure
+        .downcast::<UserClosure<T>>()
+        .map(|user_closure| user_closure.into_inner())
+}
@@ -1,11 +1,36 @@
 use crate::{
     error::{SendError, SizeError},
-    helpers::make_t_atom_list_from_atom_list,
-    types::Atom,
-    C_STRING_FAILURE,
+    helpers::{return_if_not_initialized, to_c_string, with_t_atoms, AtomListError},
+    receive::callback_depth,
+    symbol::{AsPdSymbol, InternedSymbol},
+    types::{Atom, IntoAtoms, NotesOff},
 };
 
-use std::ffi::CString;
+use std::cell::Cell;
+use std::ffi::{CStr, CString};
+use std::num::NonZeroUsize;
+
+thread_local! {
+    /// The callback depth which the message in composition is started at.
+    static STARTED_MESSAGE_DEPTH: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// The number of atoms which could be sent in a single list or typed message.
+///
+/// Libpd takes the length of a message as an `int`, longer lists are split with [`send_large_list_to`].
+#[allow(clippy::cast_sign_loss)]
+// This is fine since the maximum of an `i32` is positive.
+pub const MAX_MESSAGE_LENGTH: usize = i32::MAX as usize;
+
+/// The MIDI controller of the all notes off message.
+pub const ALL_NOTES_OFF_CONTROLLER: i32 = 123;
+
+/// Checks if a message which is started outside of the running receive callback is still in composition.
+fn message_is_started_outside_of_callback() -> bool {
+    STARTED_MESSAGE_DEPTH
+        .with(Cell::get)
+        .is_some_and(|depth| depth < callback_depth())
+}
 
 /// Sends a `bang` to the pd receiver object specified in `receiver` the argument
 ///
@@ -29,14 +54,15 @@ use std::ffi::CString;
 ///
 /// A list of errors that can occur:
 /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
-pub fn send_bang_to<T: AsRef<str>>(receiver: T) -> Result<(), SendError> {
-    let recv = CString::new(receiver.as_ref()).expect(C_STRING_FAILURE);
-    unsafe {
-        match libpd_sys::libpd_bang(recv.as_ptr()) {
-            0 => Ok(()),
-            _ => Err(SendError::MissingDestination(receiver.as_ref().to_owned())),
-        }
-    }
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_bang_to<T: AsPdSymbol>(receiver: T) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let recv = pd_symbol(receiver, "receiver name")?;
+    send_to_receiver(&recv, |destination| unsafe {
+        libpd_sys::pd_bang(destination);
+    })
 }
 
 /// Sends an `f32` value to the pd receiver object specified in the `receiver` argument
@@ -61,14 +87,48 @@ pub fn send_bang_to<T: AsRef<str>>(receiver: T) -> Result<(), SendError> {
 ///
 /// A list of errors that can occur:
 /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
-pub fn send_float_to<T: AsRef<str>>(receiver: T, value: f32) -> Result<(), SendError> {
-    let recv = CString::new(receiver.as_ref()).expect(C_STRING_FAILURE);
-    unsafe {
-        match libpd_sys::libpd_float(recv.as_ptr(), value) {
-            0 => Ok(()),
-            _ => Err(SendError::MissingDestination(receiver.as_ref().to_owned())),
-        }
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_float_to<T: AsPdSymbol>(receiver: T, value: f32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let recv = pd_symbol(receiver, "receiver name")?;
+    send_to_receiver(&recv, |destination| unsafe {
+        libpd_sys::pd_float(destination, value.into());
+    })
+}
+
+/// Sends each of the `f32` values as a separate float message to the pd receiver object specified in the `receiver` argument.
+///
+/// `send_floats_to("foo", &[1.0, 2.0])` sends `1.0` and then `2.0` to `|s foo|`, like calling [`send_float_to`] in a loop.
+/// A `|r foo|` object outputs every value one after another, unlike [`send_list_to`] which sends one message `1.0 2.0`.
+///
+/// Sending stops at the first value which fails, the values before it are sent.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::send::send_floats_to;
+///
+/// // Feed samples one at a time to an object which expects floats.
+/// send_floats_to("foo", &[0.0, 0.5, 1.0]).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_floats_to<T: AsPdSymbol>(receiver: T, values: &[f32]) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let recv = pd_symbol(receiver, "receiver name")?;
+    for value in values {
+        send_to_receiver(&recv, |destination| unsafe {
+            libpd_sys::pd_float(destination, (*value).into());
+        })?;
     }
+    Ok(())
 }
 
 /// Sends an `f64` value to the pd receiver object specified in the `receiver` argument
@@ -93,14 +153,15 @@ pub fn send_float_to<T: AsRef<str>>(receiver: T, value: f32) -> Result<(), SendE
 ///
 /// A list of errors that can occur:
 /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
-pub fn send_double_to<T: AsRef<str>>(receiver: T, value: f64) -> Result<(), SendError> {
-    let recv = CString::new(receiver.as_ref()).expect(C_STRING_FAILURE);
-    unsafe {
-        match libpd_sys::libpd_double(recv.as_ptr(), value) {
-            0 => Ok(()),
-            _ => Err(SendError::MissingDestination(receiver.as_ref().to_owned())),
-        }
-    }
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_double_to<T: AsPdSymbol>(receiver: T, value: f64) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let recv = pd_symbol(receiver, "receiver name")?;
+    send_to_receiver(&recv, |destination| unsafe {
+        libpd_sys::pd_float(destination, value);
+    })
 }
 
 /// Sends a symbol to the pd receiver object specified in the `receiver` argument
@@ -125,25 +186,145 @@ pub fn send_double_to<T: AsRef<str>>(receiver: T, value: f64) -> Result<(), Send
 ///
 /// A list of errors that can occur:
 /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
-pub fn send_symbol_to<T: AsRef<str>, S: AsRef<str>>(
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_symbol_to<T: AsPdSymbol, S: AsPdSymbol>(
     receiver: T,
     value: S,
 ) -> Result<(), SendError> {
-    let recv = CString::new(receiver.as_ref()).expect(C_STRING_FAILURE);
-    let sym = CString::new(value.as_ref()).expect(C_STRING_FAILURE);
-    unsafe {
-        match libpd_sys::libpd_symbol(recv.as_ptr(), sym.as_ptr()) {
-            0 => Ok(()),
-            _ => Err(SendError::MissingDestination(receiver.as_ref().to_owned())),
-        }
+    return_if_not_initialized!(SendError::NotInitialized);
+    let recv = pd_symbol(receiver, "receiver name")?;
+    let sym = pd_symbol(value, "symbol")?;
+    send_to_receiver(&recv, |destination| unsafe {
+        libpd_sys::pd_symbol(destination, sym.as_ptr());
+    })
+}
+
+/// Sends a `bang` like [`send_bang_to`] to a receiver whose name is already a C string.
+///
+/// The name is passed to pd as it is, nothing is allocated unless sending fails.
+/// This is the path for sending to the same receiver many times, e.g. from an audio or control loop.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::send::send_bang_to_c_str;
+///
+/// // A literal needs no allocation at all.
+/// send_bang_to_c_str(c"foo").unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_bang_to_c_str(receiver: &CStr) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let code = unsafe { libpd_sys::libpd_bang(receiver.as_ptr()) };
+    c_str_result(code, receiver)
+}
+
+/// Sends an `f32` value like [`send_float_to`] to a receiver whose name is already a C string.
+///
+/// The name is passed to pd as it is, nothing is allocated unless sending fails.
+///
+/// # Example
+/// ```no_run
+/// use std::ffi::{CStr, CString};
+/// use libpd_rs::send::send_float_to_c_str;
+///
+/// // Make the name once and send to it as often as needed.
+/// let receiver = CString::new("freq").unwrap();
+/// for step in 0..1000 {
+///     send_float_to_c_str(&receiver, 440.0 + step as f32).unwrap();
+/// }
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_float_to_c_str(receiver: &CStr, value: f32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let code = unsafe { libpd_sys::libpd_float(receiver.as_ptr(), value) };
+    c_str_result(code, receiver)
+}
+
+/// Sends an `f64` value like [`send_double_to`] to a receiver whose name is already a C string.
+///
+/// The name is passed to pd as it is, nothing is allocated unless sending fails.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::send::send_double_to_c_str;
+///
+/// send_double_to_c_str(c"freq", 440.0).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_double_to_c_str(receiver: &CStr, value: f64) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let code = unsafe { libpd_sys::libpd_double(receiver.as_ptr(), value) };
+    c_str_result(code, receiver)
+}
+
+/// Sends a symbol like [`send_symbol_to`] to a receiver whose name and symbol are already C strings.
+///
+/// Both are passed to pd as they are, nothing is allocated unless sending fails.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::send::send_symbol_to_c_str;
+///
+/// send_symbol_to_c_str(c"waveform", c"saw").unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_symbol_to_c_str(receiver: &CStr, value: &CStr) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let code = unsafe { libpd_sys::libpd_symbol(receiver.as_ptr(), value.as_ptr()) };
+    c_str_result(code, receiver)
+}
+
+/// Maps the return code of sending to a receiver whose name is a C string.
+fn c_str_result(code: i32, receiver: &CStr) -> Result<(), SendError> {
+    match code {
+        0 => Ok(()),
+        code => Err(SendError::from_receiver_code(
+            code,
+            receiver.to_string_lossy(),
+        )),
     }
 }
 
 /// Start composition of a new list or typed message of up to max **element** length
 ///
 /// Messages can be of a smaller length as max length is only an upper bound.
-/// No cleanup is required for unfinished messages.
-/// Returns error if the length is too large.
+/// Returns error if the length is too large, [`send_large_list_to`] sends long lists in chunks instead.
+///
+/// Pd composes a single message at a time.
+/// Starting a message from a closure registered in the [`receive`](crate::receive) module
+/// while a message which is started outside of it is not finished yet returns an error
+/// instead of overwriting the message in composition.
+/// Use [`send_list_to`] or [`send_message_to`] in closures since they don't use the message in composition.
+///
+/// A message which is not going to be finished, e.g. since adding to it failed, is abandoned with [`cancel_message`].
+/// Otherwise it counts as in composition and the closures can not compose messages
+/// until a message is started or finished outside of them again.
 ///
 /// # Example
 /// ```rust
@@ -162,11 +343,21 @@ pub fn send_symbol_to<T: AsRef<str>, S: AsRef<str>>(
 ///
 /// A list of errors that can occur:
 /// - [`TooLarge`](crate::error::SizeError::TooLarge)
+/// - [`Unexpected`](crate::error::SizeError::Unexpected)
+/// - [`ReentrantSend`](crate::error::SizeError::ReentrantSend)
+/// - [`NotInitialized`](crate::error::SizeError::NotInitialized)
 pub fn start_message(length: i32) -> Result<(), SizeError> {
+    return_if_not_initialized!(SizeError::NotInitialized);
+    if message_is_started_outside_of_callback() {
+        return Err(SizeError::ReentrantSend);
+    }
     unsafe {
         match libpd_sys::libpd_start_message(length) {
-            0 => Ok(()),
-            _ => Err(SizeError::TooLarge),
+            0 => {
+                STARTED_MESSAGE_DEPTH.with(|depth| depth.set(Some(callback_depth())));
+                Ok(())
+            }
+            code => Err(SizeError::from_message_code(code, length)),
         }
     }
 }
@@ -232,7 +423,7 @@ pub fn add_double_to_started_message(value: f64) {
 /// // Arbitrary length
 /// let message_length = 4;
 /// if start_message(message_length).is_ok() {
-///   add_symbol_to_started_message("foo");
+///   add_symbol_to_started_message("foo").unwrap();
 /// }
 /// ```
 ///
@@ -240,11 +431,17 @@ pub fn add_double_to_started_message(value: f64) {
 /// To be honest I'd expect this to panic if you overflow a message buffer.
 ///
 /// Although I didn't check that, please create an [issue](https://github.com/alisomay/libpd-rs/issues).
-pub fn add_symbol_to_started_message<T: AsRef<str>>(value: T) {
-    let sym = CString::new(value.as_ref()).expect(C_STRING_FAILURE);
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+pub fn add_symbol_to_started_message<T: AsRef<str>>(value: T) -> Result<(), SendError> {
+    let sym = c_string(value.as_ref(), "symbol")?;
     unsafe {
         libpd_sys::libpd_add_symbol(sym.as_ptr());
     }
+    Ok(())
 }
 
 /// Finishes the current message and send as a list to a receiver in the loaded pd patch
@@ -263,7 +460,7 @@ pub fn add_symbol_to_started_message<T: AsRef<str>>(value: T) {
 /// let message_length = 2;
 /// if start_message(message_length).is_ok() {
 ///   add_float_to_started_message(42.0);
-///   add_symbol_to_started_message("bar");
+///   add_symbol_to_started_message("bar").unwrap();
 ///   finish_message_as_list_and_send_to("foo").unwrap_or_else(|err| {
 ///      println!("{}", err);
 ///   });
@@ -274,12 +471,21 @@ pub fn add_symbol_to_started_message<T: AsRef<str>>(value: T) {
 ///
 /// A list of errors that can occur:
 /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`ReentrantSend`](crate::error::SendError::ReentrantSend)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn finish_message_as_list_and_send_to<T: AsRef<str>>(receiver: T) -> Result<(), SendError> {
-    let recv = CString::new(receiver.as_ref()).expect(C_STRING_FAILURE);
+    return_if_not_initialized!(SendError::NotInitialized);
+    if message_is_started_outside_of_callback() {
+        return Err(SendError::ReentrantSend);
+    }
+    STARTED_MESSAGE_DEPTH.with(|depth| depth.set(None));
+    let recv = c_string(receiver.as_ref(), "receiver name")?;
     unsafe {
         match libpd_sys::libpd_finish_list(recv.as_ptr()) {
             0 => Ok(()),
-            _ => Err(SendError::MissingDestination(receiver.as_ref().to_owned())),
+            code => Err(SendError::from_receiver_code(code, receiver)),
         }
     }
 }
@@ -312,26 +518,59 @@ pub fn finish_message_as_list_and_send_to<T: AsRef<str>>(receiver: T) -> Result<
 ///
 /// A list of errors that can occur:
 /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`ReentrantSend`](crate::error::SendError::ReentrantSend)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn finish_message_as_typed_message_and_send_to<T: AsRef<str>, S: AsRef<str>>(
     receiver: T,
     message_header: S,
 ) -> Result<(), SendError> {
-    let recv = CString::new(receiver.as_ref()).expect(C_STRING_FAILURE);
-    let msg = CString::new(message_header.as_ref()).expect(C_STRING_FAILURE);
+    return_if_not_initialized!(SendError::NotInitialized);
+    if message_is_started_outside_of_callback() {
+        return Err(SendError::ReentrantSend);
+    }
+    STARTED_MESSAGE_DEPTH.with(|depth| depth.set(None));
+    let recv = c_string(receiver.as_ref(), "receiver name")?;
+    let msg = c_string(message_header.as_ref(), "message header")?;
     unsafe {
         match libpd_sys::libpd_finish_message(recv.as_ptr(), msg.as_ptr()) {
             0 => Ok(()),
-            _ => Err(SendError::MissingDestination(receiver.as_ref().to_owned())),
+            code => Err(SendError::from_receiver_code(code, receiver)),
         }
     }
 }
 
+/// Abandons the message in composition without sending it
+///
+/// The closures registered in the [`receive`](crate::receive) module could compose messages again afterwards,
+/// see [`start_message`]. Cancelling when no message is in composition does nothing.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::send::{start_message, add_symbol_to_started_message, cancel_message};
+///
+/// libpd_rs::init();
+///
+/// if start_message(1).is_ok() {
+///   if add_symbol_to_started_message("foo\0bar").is_err() {
+///     cancel_message();
+///   }
+/// }
+/// ```
+pub fn cancel_message() {
+    // Pd overwrites the message in composition when the next one is started, so it is only forgotten here.
+    STARTED_MESSAGE_DEPTH.with(|depth| depth.set(None));
+}
+
 /// Sends a list to a receiver in the loaded pd patch
 ///
 /// The following example will send a list `42.0 bar` to `|s foo|` on the next tick.
 ///
 /// The list can be received from a `|r foo|` object in the loaded pd patch.
 ///
+/// The list could be anything which implements [`IntoAtoms`], like a slice of atoms or a tuple of values.
+///
 /// # Example
 /// ```rust
 /// use libpd_rs::send::{send_list_to};
@@ -346,31 +585,125 @@ pub fn finish_message_as_typed_message_and_send_to<T: AsRef<str>, S: AsRef<str>>
 /// });
 /// // or don't care..
 /// let _ = send_list_to("foo", &list);
+/// // Small fixed shapes could be sent as tuples.
+/// let _ = send_list_to("env", (0.0, 50.0, 1.0, 200.0));
 /// ```
 ///
 /// # Errors
 ///
 /// A list of errors that can occur:
 /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
-pub fn send_list_to<T: AsRef<str>>(receiver: T, list: &[Atom]) -> Result<(), SendError> {
-    let recv = CString::new(receiver.as_ref()).expect(C_STRING_FAILURE);
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`MessageTooLong`](crate::error::SendError::MessageTooLong)
+/// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_list_to<T: AsPdSymbol, L: IntoAtoms>(receiver: T, list: L) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let recv = pd_symbol(receiver, "receiver name")?;
+    let selector = pd_symbol("list", "message selector")?;
 
-    let mut atom_list: Vec<libpd_sys::t_atom> = make_t_atom_list_from_atom_list!(list);
-    let atom_list_slice = atom_list.as_mut_slice();
+    // Short lists are converted to pd atoms on the stack, only that conversion is free of allocations.
+    list.with_atoms(|list| {
+        let length = message_length(list)?;
+        with_t_atoms(list, |atom_list| {
+            send_to_receiver(&recv, |destination| unsafe {
+                libpd_sys::pd_list(
+                    destination,
+                    selector.as_ptr(),
+                    length,
+                    atom_list.as_mut_ptr(),
+                );
+            })
+        })
+        .map_err(invalid_atom)?
+    })
+}
 
-    unsafe {
-        #[allow(clippy::cast_possible_wrap)]
-        #[allow(clippy::cast_possible_truncation)]
-        match libpd_sys::libpd_list(
-            recv.as_ptr(),
-            // This is fine since a list will not be millions of elements long and not negative for sure.
-            list.len() as i32,
-            atom_list_slice.as_mut_ptr(),
-        ) {
-            0 => Ok(()),
-            _ => Err(SendError::MissingDestination(receiver.as_ref().to_owned())),
-        }
+/// Sends a list of atoms to a receiver in the loaded pd patch
+///
+/// Same as [`send_list_to`] but the list could be anything which is viewed as a slice of atoms,
+/// like an array, a `Vec<Atom>` or a slice.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::send::send_to;
+/// use libpd_rs::types::Atom;
+///
+/// libpd_rs::init();
+///
+/// let _ = send_to("synth", [440.0.into(), "on".into()]);
+/// let _ = send_to("synth", vec![Atom::from(220), Atom::from("off")]);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`MessageTooLong`](crate::error::SendError::MessageTooLong)
+/// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+#[allow(clippy::needless_pass_by_value)]
+// This is fine since taking the list by value is what lets arrays and vectors be passed without a borrow.
+pub fn send_to<T: AsPdSymbol, L: AsRef<[Atom]>>(receiver: T, list: L) -> Result<(), SendError> {
+    send_list_to(receiver, list.as_ref())
+}
+
+/// Sends a list which could be too long for a single message to a receiver in the loaded pd patch in chunks
+///
+/// The list is sent as lists of up to `chunk_length` atoms each led by the index of their first atom in `list`,
+/// With a `chunk_length` of `2`, sending `1 2 3` to `foo` sends `0 1 2` and then `2 3` to `|s foo|`.
+/// The receiving patch could reassemble them, e.g. by splitting the index off to the onset inlet of an `|array set foo|`.
+/// Nothing is sent for an empty list.
+///
+/// Sending stops at the first chunk which fails, the chunks before it are sent.
+/// Indexes above [`MAX_EXACT_INTEGER`](crate::types::MAX_EXACT_INTEGER) may not be exact,
+/// it depends on the [`float_size`](crate::info::float_size) which libpd is built with.
+///
+/// # Example
+/// ```rust
+/// use std::num::NonZeroUsize;
+/// use libpd_rs::send::send_large_list_to;
+/// use libpd_rs::types::Atom;
+///
+/// libpd_rs::init();
+///
+/// let waveform = (0..4096)
+///     .map(|index| Atom::from((f64::from(index) / 4096.0 * std::f64::consts::TAU).sin()))
+///     .collect::<Vec<_>>();
+/// let _ = send_large_list_to("waveform", &waveform, NonZeroUsize::new(512).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`MessageTooLong`](crate::error::SendError::MessageTooLong)
+/// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_large_list_to<T: AsPdSymbol>(
+    receiver: T,
+    list: &[Atom],
+    chunk_length: NonZeroUsize,
+) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let chunk_length = chunk_length.get();
+    // The receiver is interned once for all of the chunks.
+    let receiver = pd_symbol(receiver, "receiver name")?;
+    let mut message = Vec::with_capacity(chunk_length.min(list.len()) + 1);
+    for (index, chunk) in list.chunks(chunk_length).enumerate() {
+        message.clear();
+        // This is fine since pd could not tell indexes apart which are too large to be exact anyway.
+        #[allow(clippy::cast_precision_loss)]
+        message.push(Atom::Float((index * chunk_length) as f64));
+        message.extend_from_slice(chunk);
+        send_list_to(&receiver, &message)?;
     }
+    Ok(())
 }
 
 /// Sends a typed message to a receiver in the loaded pd patch
@@ -379,6 +712,8 @@ pub fn send_list_to<T: AsRef<str>>(receiver: T, list: &[Atom]) -> Result<(), Sen
 ///
 /// The equivalent of this example message would have looked like `[; pd dsp 1]` in pd gui.
 ///
+/// The arguments could be anything which implements [`IntoAtoms`], like `(1,)` or `[1]`.
+///
 /// # Example
 /// ```rust
 /// use libpd_rs::send::{send_message_to};
@@ -399,31 +734,30 @@ pub fn send_list_to<T: AsRef<str>>(receiver: T, list: &[Atom]) -> Result<(), Sen
 ///
 /// A list of errors that can occur:
 /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
-pub fn send_message_to<T: AsRef<str>>(
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`MessageTooLong`](crate::error::SendError::MessageTooLong)
+/// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_message_to<T: AsPdSymbol, S: AsPdSymbol, L: IntoAtoms>(
     receiver: T,
-    message: T,
-    list: &[Atom],
+    message: S,
+    list: L,
 ) -> Result<(), SendError> {
-    let recv = CString::new(receiver.as_ref()).expect(C_STRING_FAILURE);
-    let msg = CString::new(message.as_ref()).expect(C_STRING_FAILURE);
+    return_if_not_initialized!(SendError::NotInitialized);
+    let recv = pd_symbol(receiver, "receiver name")?;
+    let msg = pd_symbol(message, "message selector")?;
 
-    let mut atom_list: Vec<libpd_sys::t_atom> = make_t_atom_list_from_atom_list!(list);
-    let atom_list_slice = atom_list.as_mut_slice();
-
-    unsafe {
-        #[allow(clippy::cast_possible_wrap)]
-        #[allow(clippy::cast_possible_truncation)]
-        match libpd_sys::libpd_message(
-            recv.as_ptr(),
-            msg.as_ptr(),
-            // This is fine since a list will not be millions of elements long and not negative for sure.
-            list.len() as i32,
-            atom_list_slice.as_mut_ptr(),
-        ) {
-            0 => Ok(()),
-            _ => Err(SendError::MissingDestination(receiver.as_ref().to_owned())),
-        }
-    }
+    // Short lists are converted to pd atoms on the stack, only that conversion is free of allocations.
+    list.with_atoms(|list| {
+        let length = message_length(list)?;
+        with_t_atoms(list, |atom_list| {
+            send_to_receiver(&recv, |destination| unsafe {
+                libpd_sys::pd_typedmess(destination, msg.as_ptr(), length, atom_list.as_mut_ptr());
+            })
+        })
+        .map_err(invalid_atom)?
+    })
 }
 
 /// Sends a MIDI note on message to `|notein|` objects in pd.
@@ -452,12 +786,15 @@ pub fn send_message_to<T: AsRef<str>>(
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn send_note_on(channel: i32, pitch: i32, velocity: i32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
         match libpd_sys::libpd_noteon(channel, pitch, velocity) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
@@ -486,12 +823,15 @@ pub fn send_note_on(channel: i32, pitch: i32, velocity: i32) -> Result<(), SendE
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn send_control_change(channel: i32, controller: i32, value: i32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
         match libpd_sys::libpd_controlchange(channel, controller, value) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
@@ -520,23 +860,28 @@ pub fn send_control_change(channel: i32, controller: i32, value: i32) -> Result<
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn send_program_change(channel: i32, value: i32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
         match libpd_sys::libpd_programchange(channel, value) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
 
 /// Sends a MIDI pitch bend message to `|bendin|` objects in pd.
 ///
-/// Channel is zero-indexed, value is `-8192 to 8192`.
+/// Channel is zero-indexed, value is the raw 14 bit value in `-8192..=8191` where `0` is the center.
 ///
 /// Channels encode MIDI ports via: `libpd_channel = pd_channel + 16 * pd_port`
 ///
-/// Note: `|bendin|` outputs 0-16383 while `|bendout|` accepts `-8192 to 8192`
+/// Note: `|bendin|` outputs 0-16383 while `|bendout|` accepts `-8192 to 8191`
+///
+/// See [`send_pitch_bend_normalized`] to send a value in `-1.0..=1.0`.
 ///
 /// # Example
 /// ```rust
@@ -556,16 +901,62 @@ pub fn send_program_change(channel: i32, value: i32) -> Result<(), SendError> {
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn send_pitch_bend(channel: i32, value: i32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
         match libpd_sys::libpd_pitchbend(channel, value) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
 
+/// Sends a MIDI pitch bend message to `|bendin|` objects in pd with a normalized value.
+///
+/// Channel is zero-indexed, value is in `-1.0..=1.0` where `0.0` is the center.
+///
+/// The value is clamped to `-1.0..=1.0` and then scaled to the 14 bit range of [`send_pitch_bend`].
+/// Since the range is not symmetric, negative values are multiplied by `8192` and positive values by `8191`.
+/// The result is rounded to the nearest integer, halfway cases are rounded away from `0.0`.
+/// So `-1.0` sends `-8192`, `0.0` sends `0` and `1.0` sends `8191`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::send::{send_pitch_bend_normalized};
+///
+/// libpd_rs::init();
+///
+/// // Bend all the way up.
+/// send_pitch_bend_normalized(0, 1.0).unwrap();
+/// // Values out of range are clamped.
+/// send_pitch_bend_normalized(0, -2.0).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+///   - If the channel is negative or the value is `NaN`.
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_pitch_bend_normalized(channel: i32, value: f32) -> Result<(), SendError> {
+    if value.is_nan() {
+        return Err(SendError::OutOfRange);
+    }
+    let value = value.clamp(-1.0, 1.0);
+    let scaled = if value < 0.0 {
+        value * 8192.0
+    } else {
+        value * 8191.0
+    };
+    // The value is clamped to the 14 bit range above so it fits.
+    #[allow(clippy::cast_possible_truncation)]
+    send_pitch_bend(channel, scaled.round() as i32)
+}
+
 /// Sends a MIDI after touch message to `|touchin|` objects in pd.
 ///
 /// Channel is zero-indexed, value is `0-127`.
@@ -590,12 +981,15 @@ pub fn send_pitch_bend(channel: i32, value: i32) -> Result<(), SendError> {
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn send_after_touch(channel: i32, value: i32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
         match libpd_sys::libpd_aftertouch(channel, value) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
@@ -624,19 +1018,104 @@ pub fn send_after_touch(channel: i32, value: i32) -> Result<(), SendError> {
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn send_poly_after_touch(channel: i32, pitch: i32, value: i32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
         match libpd_sys::libpd_polyaftertouch(channel, pitch, value) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
 
+/// Clears the stuck notes of a MIDI channel, the panic button of a synth.
+///
+/// Channel is zero-indexed and encodes the MIDI port like in [`send_note_on`].
+///
+/// This sends both the all notes off controller message and a note off for every pitch, see [`NotesOff::Both`].
+/// Pd patches usually listen to notes only with `|notein|`, so the note offs clear their notes
+/// while the controller message clears the notes of patches or devices which handle it.
+/// Use [`all_notes_off_with`] to send only one of them.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::send::all_notes_off;
+///
+/// libpd_rs::init();
+///
+/// all_notes_off(0).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn all_notes_off(channel: i32) -> Result<(), SendError> {
+    all_notes_off_with(channel, NotesOff::Both)
+}
+
+/// Clears the stuck notes of a MIDI channel with the given [`NotesOff`] messages.
+///
+/// Channel is zero-indexed and encodes the MIDI port like in [`send_note_on`].
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{send::all_notes_off_with, types::NotesOff};
+///
+/// libpd_rs::init();
+///
+/// // The patch handles the controller with a `|ctlin 123|` object.
+/// all_notes_off_with(0, NotesOff::Controller).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn all_notes_off_with(channel: i32, notes_off: NotesOff) -> Result<(), SendError> {
+    if matches!(notes_off, NotesOff::Controller | NotesOff::Both) {
+        send_control_change(channel, ALL_NOTES_OFF_CONTROLLER, 0)?;
+    }
+    if matches!(notes_off, NotesOff::NoteOffs | NotesOff::Both) {
+        for pitch in 0..=127 {
+            send_note_on(channel, pitch, 0)?;
+        }
+    }
+    Ok(())
+}
+
+/// Clears the stuck notes of the 16 MIDI channels of the first port with [`all_notes_off`].
+///
+/// Call [`all_notes_off`] with the channels of the other ports, e.g. `16-31` for the second port, to clear them too.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::send::all_notes_off_all_channels;
+///
+/// libpd_rs::init();
+///
+/// all_notes_off_all_channels().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn all_notes_off_all_channels() -> Result<(), SendError> {
+    (0..16).try_for_each(all_notes_off)
+}
+
 /// Sends a raw MIDI byte to `|midiin|` objects in pd.
 ///
-/// Port is zero-indexed and byte is `0-255`
+/// Port is zero-indexed and `0-4095`, byte is `0-255`
 ///
 /// # Example
 /// ```rust
@@ -656,48 +1135,86 @@ pub fn send_poly_after_touch(channel: i32, pitch: i32, value: i32) -> Result<(),
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
-pub fn send_midi_byte(port: i32, byte: i32) -> Result<(), SendError> {
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_midi_byte(port: i32, byte: u8) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
-        match libpd_sys::libpd_midibyte(port, byte) {
+        match libpd_sys::libpd_midibyte(port, i32::from(byte)) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
 
-/// Sends a raw MIDI byte to `|sysexin|` objects in pd.
+/// Sends a single byte of a sysex message to `|sysexin|` objects in pd.
 ///
-/// Port is zero-indexed and byte is `0-255`
+/// Port is zero-indexed and `0-4095`, byte is `0-255`
+///
+/// See [`send_sysex`] to send a whole sysex message.
 ///
 /// # Example
 /// ```rust
-/// use libpd_rs::send::{send_sysex};
+/// use libpd_rs::send::{send_sysex_byte};
 ///
 /// libpd_rs::init();
 ///
 /// // Handle the error if the receiver object is not found
-/// send_sysex(0, 0x7F).unwrap_or_else(|err| {
+/// send_sysex_byte(0, 0x7F).unwrap_or_else(|err| {
 ///   dbg!("{err}");
 /// });
 /// // or don't care..
-/// let _ = send_sysex(0, 0x7F);
+/// let _ = send_sysex_byte(0, 0x7F);
 /// ```
 ///
 /// # Errors
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
-pub fn send_sysex(port: i32, byte: i32) -> Result<(), SendError> {
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_sysex_byte(port: i32, byte: u8) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
-        match libpd_sys::libpd_sysex(port, byte) {
+        match libpd_sys::libpd_sysex(port, i32::from(byte)) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
 
+/// Sends a whole sysex message to `|sysexin|` objects in pd byte by byte.
+///
+/// Port is zero-indexed and `0-4095`.
+/// The message is sent as it is, so it should start with `0xF0` and end with `0xF7`.
+///
+/// Sending stops at the first byte which fails to be sent.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::send::{send_sysex};
+///
+/// libpd_rs::init();
+///
+/// // A universal non-realtime message.
+/// send_sysex(0, &[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7]).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn send_sysex(port: i32, message: &[u8]) -> Result<(), SendError> {
+    for byte in message {
+        send_sysex_byte(port, *byte)?;
+    }
+    Ok(())
+}
+
 /// Sends a raw MIDI byte to `|midirealtimein|` objects in pd.
 ///
 /// Port is zero-indexed and byte is `0-255`
@@ -720,12 +1237,64 @@ pub fn send_sysex(port: i32, byte: i32) -> Result<(), SendError> {
 ///
 /// A list of errors that can occur:
 /// - [`OutOfRange`](crate::error::SendError::OutOfRange)
+/// - [`Unexpected`](crate::error::SendError::Unexpected)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
 pub fn send_sys_realtime(port: i32, byte: i32) -> Result<(), SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
     unsafe {
         // Returns 0 on success or -1 if an argument is out of range
         match libpd_sys::libpd_sysrealtime(port, byte) {
             0 => Ok(()),
-            _ => Err(SendError::OutOfRange),
+            code => Err(SendError::from_code(code)),
         }
     }
 }
+
+/// Converts a string to be passed to pd as the contained `context`, e.g. `receiver name`.
+pub(crate) fn c_string(input: &str, context: &'static str) -> Result<CString, SendError> {
+    to_c_string(input).map_err(|input| SendError::InvalidString { context, input })
+}
+
+/// Interns a name to be passed to pd as the contained `context`, e.g. `receiver name`.
+fn pd_symbol<T: AsPdSymbol>(name: T, context: &'static str) -> Result<InternedSymbol, SendError> {
+    name.into_pd_symbol().map_err(|error| match error {
+        SendError::InvalidString { input, .. } => SendError::InvalidString { context, input },
+        error => error,
+    })
+}
+
+/// Calls `send` with the object which is bound to `receiver` in pd, like libpd does after interning the name of a receiver.
+///
+/// Pd is locked while sending, the error is [`MissingDestination`](crate::error::SendError::MissingDestination)
+/// if nothing is bound to the receiver.
+fn send_to_receiver<R>(
+    receiver: &InternedSymbol,
+    send: impl FnOnce(*mut libpd_sys::t_pd) -> R,
+) -> Result<R, SendError> {
+    unsafe {
+        libpd_sys::sys_lock();
+        let destination = (*receiver.as_ptr()).s_thing;
+        let result = (!destination.is_null()).then(|| send(destination));
+        libpd_sys::sys_unlock();
+        result.ok_or_else(|| SendError::MissingDestination(receiver.as_str().to_owned()))
+    }
+}
+
+/// Returns the length of a list to pass to pd or an error if it does not fit in a single message.
+pub(crate) fn message_length(list: &[Atom]) -> Result<i32, SendError> {
+    i32::try_from(list.len()).map_err(|_| SendError::MessageTooLong {
+        requested: list.len(),
+        max: MAX_MESSAGE_LENGTH,
+    })
+}
+
+/// Makes the error of an atom in a list which can not be passed to pd.
+pub(crate) fn invalid_atom(error: AtomListError) -> SendError {
+    match error {
+        AtomListError::InvalidSymbol(input) => SendError::InvalidString {
+            context: "symbol",
+            input,
+        },
+        AtomListError::Pointer => SendError::PointerAtom,
+    }
+}
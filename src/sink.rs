@@ -0,0 +1,478 @@
+use crate::{
+    error::SubscriptionError,
+    receive::{run_callback, start_listening_from, stop_listening_from},
+    types::{Atom, ReceiverHandle},
+};
+
+use std::ffi::CStr;
+use std::sync::mpsc::{Sender, SyncSender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A destination for the messages which are received from a source in pd, see [`subscribe_with`].
+///
+/// Every method does nothing by default, so a sink only implements the kinds of messages it cares about.
+/// [`on_message`](MessageSink::on_message) is called for typed messages, e.g. `set 1 2`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::sink::{subscribe_with, MessageSink};
+///
+/// struct Frequency(f64);
+///
+/// impl MessageSink for Frequency {
+///     fn on_float(&mut self, _source: &str, value: f64) {
+///         self.0 = value;
+///     }
+/// }
+///
+/// libpd_rs::init().unwrap();
+///
+/// subscribe_with("frequency", Frequency(440.0)).unwrap();
+/// ```
+pub trait MessageSink: Send {
+    /// A `bang` is received from `source`.
+    fn on_bang(&mut self, _source: &str) {}
+    /// A float is received from `source`.
+    fn on_float(&mut self, _source: &str, _value: f64) {}
+    /// A symbol is received from `source`.
+    fn on_symbol(&mut self, _source: &str, _value: &str) {}
+    /// A list is received from `source`.
+    fn on_list(&mut self, _source: &str, _list: &[Atom]) {}
+    /// A typed message which starts with `selector` is received from `source`.
+    fn on_message(&mut self, _source: &str, _selector: &str, _arguments: &[Atom]) {}
+}
+
+/// A message which is received from pd, the owned form of the calls to a [`MessageSink`].
+///
+/// With the `serde` feature it is serialized as an enum which is tagged with the kind of the message,
+/// e.g. in JSON `"bang"`, `{"float":440.0}` or `{"message":{"selector":"set","arguments":[1.0]}}`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ReceivedMessage {
+    /// A `bang`.
+    Bang,
+    /// A float.
+    Float(f64),
+    /// A symbol.
+    Symbol(String),
+    /// A list.
+    List(Vec<Atom>),
+    /// A typed message, e.g. `set 1 2` has the selector `set` and the arguments `1 2`.
+    Message {
+        /// The first element of the message.
+        selector: String,
+        /// The rest of the message.
+        arguments: Vec<Atom>,
+    },
+}
+
+/// A value which could be taken from a [`ReceivedMessage`], like the argument of the closure of [`Pd::on`](crate::engine::Pd::on).
+///
+/// Numbers and strings are taken from floats, symbols and lists of a single atom which convert to them,
+/// `()` from bangs, a `Vec<Atom>` from every message but a bang as the atoms of it and a `ReceivedMessage` is the message itself.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{sink::{FromReceivedMessage, ReceivedMessage}, types::Atom};
+///
+/// let message = ReceivedMessage::List(vec![Atom::from(440)]);
+/// assert_eq!(f32::from_received_message(&message), Some(440.0));
+/// assert_eq!(String::from_received_message(&message), None);
+/// ```
+pub trait FromReceivedMessage: Sized {
+    /// Takes the value from the message, `None` if the message does not convert to it.
+    fn from_received_message(message: &ReceivedMessage) -> Option<Self>;
+}
+
+macro_rules! from_received_message_for_single_atom {
+    ($($type:ty),+) => {
+        $(impl FromReceivedMessage for $type {
+            fn from_received_message(message: &ReceivedMessage) -> Option<Self> {
+                match message {
+                    ReceivedMessage::Float(value) => Self::try_from(&Atom::Float(*value)).ok(),
+                    ReceivedMessage::Symbol(symbol) => {
+                        Self::try_from(&Atom::Symbol(symbol.clone())).ok()
+                    }
+                    ReceivedMessage::List(list) => match list.as_slice() {
+                        [atom] => Self::try_from(atom).ok(),
+                        _ => None,
+                    },
+                    ReceivedMessage::Bang | ReceivedMessage::Message { .. } => None,
+                }
+            }
+        })+
+    };
+}
+
+from_received_message_for_single_atom!(
+    f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, String
+);
+
+impl FromReceivedMessage for () {
+    fn from_received_message(message: &ReceivedMessage) -> Option<Self> {
+        matches!(message, ReceivedMessage::Bang).then_some(())
+    }
+}
+
+impl FromReceivedMessage for Vec<Atom> {
+    fn from_received_message(message: &ReceivedMessage) -> Option<Self> {
+        match message {
+            ReceivedMessage::Bang => None,
+            ReceivedMessage::Float(value) => Some(vec![Atom::Float(*value)]),
+            ReceivedMessage::Symbol(symbol) => Some(vec![Atom::Symbol(symbol.clone())]),
+            ReceivedMessage::List(list) => Some(list.clone()),
+            ReceivedMessage::Message {
+                selector,
+                arguments,
+            } => Some(
+                std::iter::once(Atom::Symbol(selector.clone()))
+                    .chain(arguments.iter().cloned())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl FromReceivedMessage for ReceivedMessage {
+    fn from_received_message(message: &ReceivedMessage) -> Option<Self> {
+        Some(message.clone())
+    }
+}
+
+/// Implements the methods of [`MessageSink`] by making a [`ReceivedMessage`] of every call and delivering it.
+macro_rules! deliver_received_messages {
+    (|$sink:ident, $source:ident, $message:ident| $deliver:expr) => {
+        fn on_bang(&mut self, source: &str) {
+            let ($sink, $source, $message) = (self, source, ReceivedMessage::Bang);
+            $deliver
+        }
+        fn on_float(&mut self, source: &str, value: f64) {
+            let ($sink, $source, $message) = (self, source, ReceivedMessage::Float(value));
+            $deliver
+        }
+        fn on_symbol(&mut self, source: &str, value: &str) {
+            let message = ReceivedMessage::Symbol(value.to_owned());
+            let ($sink, $source, $message) = (self, source, message);
+            $deliver
+        }
+        fn on_list(&mut self, source: &str, list: &[Atom]) {
+            let message = ReceivedMessage::List(list.to_vec());
+            let ($sink, $source, $message) = (self, source, message);
+            $deliver
+        }
+        fn on_message(&mut self, source: &str, selector: &str, arguments: &[Atom]) {
+            let message = ReceivedMessage::Message {
+                selector: selector.to_owned(),
+                arguments: arguments.to_vec(),
+            };
+            let ($sink, $source, $message) = (self, source, message);
+            $deliver
+        }
+    };
+}
+
+/// Calls a closure with the source and the [`ReceivedMessage`] for every message.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::sink::{subscribe_with, ClosureSink};
+///
+/// libpd_rs::init().unwrap();
+///
+/// subscribe_with("notes", ClosureSink(|source: &str, message| {
+///     println!("{source}: {message:?}");
+/// }))
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClosureSink<F: FnMut(&str, ReceivedMessage) + Send>(pub F);
+
+impl<F: FnMut(&str, ReceivedMessage) + Send> MessageSink for ClosureSink<F> {
+    deliver_received_messages!(|sink, source, message| (sink.0)(source, message));
+}
+
+/// Sends every message with its source to the receiving end of a channel.
+///
+/// Messages are dropped once the receiving end is dropped.
+///
+/// # Example
+/// ```rust
+/// use std::sync::mpsc::channel;
+/// use libpd_rs::sink::subscribe_with;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let (sender, receiver) = channel();
+/// subscribe_with("notes", sender).unwrap();
+///
+/// // After receiving messages from pd.
+/// for (source, message) in receiver.try_iter() {
+///     println!("{source}: {message:?}");
+/// }
+/// ```
+impl MessageSink for Sender<(String, ReceivedMessage)> {
+    deliver_received_messages!(|sink, source, message| {
+        // The receiving end is gone, nobody is interested in the message.
+        let _ = sink.send((source.to_owned(), message));
+    });
+}
+
+/// Sends every message with its source to the receiving end of a bounded channel.
+///
+/// Messages are dropped when the channel is full, so pd is never blocked by a slow receiver,
+/// or once the receiving end is dropped.
+impl MessageSink for SyncSender<(String, ReceivedMessage)> {
+    deliver_received_messages!(|sink, source, message| {
+        let _ = sink.try_send((source.to_owned(), message));
+    });
+}
+
+/// Collects the messages with their sources, e.g. to check what a patch sends in tests.
+///
+/// The clones of it share the collected messages, so a clone could be kept to read what the subscribed one collects.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::sink::{subscribe_with, VecSink};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let collected = VecSink::new();
+/// subscribe_with("notes", collected.clone()).unwrap();
+///
+/// // After receiving messages from pd.
+/// for (source, message) in collected.take() {
+///     println!("{source}: {message:?}");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VecSink(Arc<Mutex<Vec<(String, ReceivedMessage)>>>);
+
+impl VecSink {
+    /// Makes a sink which has not collected anything yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes the messages which are collected so far with their sources, in the order they are received.
+    #[must_use]
+    pub fn take(&self) -> Vec<(String, ReceivedMessage)> {
+        std::mem::take(&mut *self.messages())
+    }
+
+    fn messages(&self) -> MutexGuard<'_, Vec<(String, ReceivedMessage)>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl MessageSink for VecSink {
+    deliver_received_messages!(|sink, source, message| sink
+        .messages()
+        .push((source.to_owned(), message)));
+}
+
+/// The handle which is returned from subscribing a sink with [`subscribe_with`].
+///
+/// It is passed to [`unsubscribe_sink`] to stop routing messages to the sink and drop it.
+#[derive(Debug)]
+pub struct SinkHandle(usize);
+
+/// A source which sinks are subscribed to, pd is bound to it once whatever the number of its sinks is.
+struct Source {
+    name: String,
+    receiver: usize,
+    sinks: Vec<(usize, Arc<Mutex<dyn MessageSink>>)>,
+}
+
+struct Subscriptions {
+    sources: Vec<Source>,
+    next_sink: usize,
+}
+
+static SUBSCRIPTIONS: Mutex<Subscriptions> = Mutex::new(Subscriptions {
+    sources: Vec::new(),
+    next_sink: 0,
+});
+
+impl Subscriptions {
+    /// Adds a sink to a source which is bound already and returns the handle of the sink.
+    fn add(&mut self, source: &str, sink: Arc<Mutex<dyn MessageSink>>) -> usize {
+        let handle = self.next_sink;
+        self.next_sink = handle.wrapping_add(1);
+        if let Some(subscribed) = self
+            .sources
+            .iter_mut()
+            .find(|subscribed| subscribed.name == source)
+        {
+            subscribed.sinks.push((handle, sink));
+        }
+        handle
+    }
+}
+
+fn subscriptions() -> MutexGuard<'static, Subscriptions> {
+    SUBSCRIPTIONS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Subscribes to messages sent to a receiver in the loaded pd patch and routes them to a [`MessageSink`].
+///
+/// A source could be subscribed with many sinks, each of them receives every message of it once.
+/// The returned handle is passed to [`unsubscribe_sink`] to drop the sink,
+/// the source is unsubscribed from when its last sink is dropped.
+///
+/// The messages reach the sinks when they are received with [`receive_messages_from_pd`](crate::receive::receive_messages_from_pd),
+/// right after the closures of the [`receive`](crate::receive) module which are registered for that kind of message,
+/// so sinks and closures receive the messages side by side.
+/// A sink which panics is not called again, the panic could be taken with
+/// [`take_last_callback_panic`](crate::receive::take_last_callback_panic).
+///
+/// Note: Do not subscribe with a sink while pd DSP is running.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{
+///     receive::receive_messages_from_pd,
+///     send::send_float_to,
+///     sink::{subscribe_with, unsubscribe_sink, ReceivedMessage, VecSink},
+/// };
+///
+/// libpd_rs::init().unwrap();
+/// let patch = libpd_rs::open_patch("tests/patches/echo.pd").unwrap();
+///
+/// let collected = VecSink::new();
+/// let handle = subscribe_with("float_from_pd", collected.clone()).unwrap();
+///
+/// send_float_to("float_from_rust", 42.0).unwrap();
+/// receive_messages_from_pd();
+/// assert_eq!(
+///     collected.take(),
+///     vec![("float_from_pd".to_owned(), ReceivedMessage::Float(42.0))]
+/// );
+///
+/// unsubscribe_sink(handle);
+/// # patch.close().unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`FailedToSubscribeToSender`](crate::error::SubscriptionError::FailedToSubscribeToSender)
+/// - [`EmptySourceName`](crate::error::SubscriptionError::EmptySourceName)
+/// - [`InvalidString`](crate::error::SubscriptionError::InvalidString)
+/// - [`NotInitialized`](crate::error::SubscriptionError::NotInitialized)
+pub fn subscribe_with<T: AsRef<str>, S: MessageSink + 'static>(
+    source: T,
+    sink: S,
+) -> Result<SinkHandle, SubscriptionError> {
+    let source = source.as_ref();
+    let handle = {
+        // The source is looked up and bound under one lock, so it is bound once when sinks subscribe at the same time.
+        let mut subscriptions = subscriptions();
+        let is_bound = subscriptions
+            .sources
+            .iter()
+            .any(|subscribed| subscribed.name == source);
+        if !is_bound {
+            let receiver = start_listening_from(source)?;
+            subscriptions.sources.push(Source {
+                name: source.to_owned(),
+                receiver: receiver.address(),
+                sinks: Vec::new(),
+            });
+        }
+        subscriptions.add(source, Arc::new(Mutex::new(sink)))
+    };
+    Ok(SinkHandle(handle))
+}
+
+/// Stops routing messages to a sink which is subscribed with [`subscribe_with`] and drops it.
+///
+/// The source is unsubscribed from when this is its last sink.
+/// Handles of sinks which are already dropped, e.g. since pd is [released](crate::release), are ignored.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::sink::{subscribe_with, unsubscribe_sink, VecSink};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let handle = subscribe_with("foo", VecSink::new()).unwrap();
+/// unsubscribe_sink(handle);
+/// ```
+// This is fine since the handle is taken to not be used again.
+#[allow(clippy::needless_pass_by_value)]
+pub fn unsubscribe_sink(handle: SinkHandle) {
+    let SinkHandle(handle) = handle;
+    let mut removed = None;
+    let mut unbound = None;
+    subscriptions().sources.retain_mut(|source| {
+        let Some(position) = source.sinks.iter().position(|(sink, _)| *sink == handle) else {
+            return true;
+        };
+        removed = Some(source.sinks.remove(position));
+        if source.sinks.is_empty() {
+            unbound = Some(source.receiver);
+        }
+        !source.sinks.is_empty()
+    });
+    // Drop the sink and unbind outside of the lock.
+    drop(removed);
+    if let Some(receiver) = unbound {
+        stop_listening_from(ReceiverHandle::from(receiver as *mut std::ffi::c_void));
+    }
+}
+
+/// Routes a message which is received from `source` to every sink which is subscribed to it.
+///
+/// The message is made by `message` once, only if `source` has sinks, and passed to `deliver` for every sink.
+/// It is called by the message queue after the closures of the [`receive`](crate::receive) module.
+pub(crate) fn route<M>(
+    source: &CStr,
+    message: impl FnOnce() -> M,
+    mut deliver: impl FnMut(&mut dyn MessageSink, &str, &M),
+) {
+    // Sources are subscribed to with Rust strings, so a source which is not UTF-8 has no sinks.
+    let Ok(source) = source.to_str() else {
+        return;
+    };
+    let sinks = subscriptions()
+        .sources
+        .iter()
+        .filter(|subscribed| subscribed.name == source)
+        .flat_map(|subscribed| subscribed.sinks.iter().map(|(_, sink)| Arc::clone(sink)))
+        .collect::<Vec<_>>();
+    if sinks.is_empty() {
+        return;
+    }
+    let message = message();
+    // The lock is released, so a sink could subscribe or unsubscribe.
+    for sink in sinks {
+        // A sink which is already delivering a message, e.g. since it receives messages itself, skips this one.
+        // A sink which panicked is poisoned and skips every message.
+        if let Ok(mut sink) = sink.try_lock() {
+            run_callback("subscribe_with", || deliver(&mut *sink, source, &message));
+        }
+    }
+}
+
+/// Drops the sinks of a source which is unbound with [`stop_listening_from`].
+pub(crate) fn unsubscribe(receiver: usize) {
+    let mut removed = Vec::new();
+    subscriptions().sources.retain_mut(|source| {
+        if source.receiver != receiver {
+            return true;
+        }
+        removed.append(&mut source.sinks);
+        false
+    });
+    // Drop the sinks outside of the lock.
+    drop(removed);
+}
+
+/// Drops every subscribed sink.
+pub(crate) fn unsubscribe_all() {
+    let removed = std::mem::take(&mut subscriptions().sources);
+    drop(removed);
+}
@@ -0,0 +1,233 @@
+use crate::{error::SendError, helpers::return_if_not_initialized, send::c_string, types::Atom};
+
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// The number of names which are kept interned in the symbol cache, see [`AsPdSymbol`].
+///
+/// The least recently used name is evicted when a new name is interned in a full cache.
+pub const SYMBOL_CACHE_CAPACITY: usize = 256;
+
+/// The names which are interned implicitly when they are passed as a `&str`.
+static SYMBOL_CACHE: Mutex<Option<SymbolCache>> = Mutex::new(None);
+
+/// A symbol which is interned in pd once and passed to it without looking up its name again.
+///
+/// Pd keeps every symbol it interns until it exits, so an interned symbol stays valid for the life of the program.
+/// Cloning it is cheap, the name is shared.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{send::send_float_to, symbol::intern};
+///
+/// libpd_rs::init().unwrap();
+///
+/// let frequency = intern("frequency").unwrap();
+/// for step in 0..8 {
+///     let _ = send_float_to(&frequency, 220.0 * (step + 1) as f32);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct InternedSymbol {
+    /// The name which is passed to pd.
+    name: Arc<CStr>,
+    /// The address of the `t_symbol` which pd interned for the name.
+    address: usize,
+}
+
+impl InternedSymbol {
+    /// The name of the symbol.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // This is fine since the name is made from a `&str`.
+        unsafe { std::str::from_utf8_unchecked(self.name.to_bytes()) }
+    }
+
+    /// The name of the symbol as it is passed to pd.
+    #[must_use]
+    pub fn as_c_str(&self) -> &CStr {
+        &self.name
+    }
+
+    /// The symbol which pd interned for the name.
+    pub(crate) const fn as_ptr(&self) -> *mut libpd_sys::t_symbol {
+        self.address as *mut libpd_sys::t_symbol
+    }
+}
+
+impl fmt::Debug for InternedSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("InternedSymbol")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+impl fmt::Display for InternedSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Pd interns a name once, so symbols are equal if their addresses are.
+impl PartialEq for InternedSymbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address
+    }
+}
+
+impl Eq for InternedSymbol {}
+
+impl Hash for InternedSymbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address.hash(state);
+    }
+}
+
+impl PartialEq<str> for InternedSymbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for InternedSymbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl From<InternedSymbol> for Atom {
+    fn from(symbol: InternedSymbol) -> Self {
+        Self::Interned(symbol)
+    }
+}
+
+impl From<&InternedSymbol> for Atom {
+    fn from(symbol: &InternedSymbol) -> Self {
+        Self::Interned(symbol.clone())
+    }
+}
+
+/// Interns a name in pd to pass it as a symbol without looking it up again.
+///
+/// The symbol could be passed anywhere a name is taken as [`AsPdSymbol`], e.g. as the receiver of [`send_float_to`](crate::send::send_float_to).
+/// Names which are passed as a `&str` are interned implicitly and kept in a small cache,
+/// interning the names which are sent to often saves the lookup in the cache too.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::symbol::intern;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let gate = intern("gate").unwrap();
+/// assert_eq!(gate.as_str(), "gate");
+/// assert_eq!(gate, intern("gate").unwrap());
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`InvalidString`](crate::error::SendError::InvalidString)
+/// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+pub fn intern<T: AsRef<str>>(name: T) -> Result<InternedSymbol, SendError> {
+    return_if_not_initialized!(SendError::NotInitialized);
+    let name: Arc<CStr> = c_string(name.as_ref(), "symbol")?.into();
+    // Pd is locked since the symbol table could be changed by another thread, e.g. while a patch is opened.
+    let address = unsafe {
+        libpd_sys::sys_lock();
+        let symbol = libpd_sys::gensym(name.as_ptr());
+        libpd_sys::sys_unlock();
+        symbol
+    } as usize;
+    Ok(InternedSymbol { name, address })
+}
+
+/// A name which could be passed to pd as an interned symbol.
+///
+/// An [`InternedSymbol`] is passed as it is,
+/// strings are interned and kept in a cache of the [`SYMBOL_CACHE_CAPACITY`] most recently used names.
+pub trait AsPdSymbol {
+    /// Interns the name in pd or returns the symbol if it is interned already.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    fn into_pd_symbol(self) -> Result<InternedSymbol, SendError>;
+}
+
+impl AsPdSymbol for InternedSymbol {
+    fn into_pd_symbol(self) -> Result<InternedSymbol, SendError> {
+        Ok(self)
+    }
+}
+
+impl AsPdSymbol for &InternedSymbol {
+    fn into_pd_symbol(self) -> Result<InternedSymbol, SendError> {
+        Ok(self.clone())
+    }
+}
+
+impl<T: AsRef<str>> AsPdSymbol for T {
+    fn into_pd_symbol(self) -> Result<InternedSymbol, SendError> {
+        cached_symbol(self.as_ref())
+    }
+}
+
+/// The number of names which are in the symbol cache now, up to [`SYMBOL_CACHE_CAPACITY`].
+#[must_use]
+pub fn cached_symbol_count() -> usize {
+    symbol_cache()
+        .as_ref()
+        .map_or(0, |cache| cache.entries.len())
+}
+
+/// Empties the symbol cache, the symbols which are interned already stay valid.
+pub fn clear_symbol_cache() {
+    *symbol_cache() = None;
+}
+
+/// The names which are interned implicitly and when they are used last.
+#[derive(Default)]
+struct SymbolCache {
+    /// The symbols by their names with the tick which they are used at last.
+    entries: HashMap<Box<str>, (InternedSymbol, u64)>,
+    /// Counts the uses of the cache to order them.
+    tick: u64,
+}
+
+/// Locks the symbol cache, it holds no invariants which a panic could break.
+fn symbol_cache() -> MutexGuard<'static, Option<SymbolCache>> {
+    SYMBOL_CACHE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the cached symbol of a name or interns the name and caches it.
+fn cached_symbol(name: &str) -> Result<InternedSymbol, SendError> {
+    let mut guard = symbol_cache();
+    let cache = guard.get_or_insert_with(SymbolCache::default);
+    cache.tick += 1;
+    let tick = cache.tick;
+    if let Some((symbol, last_used)) = cache.entries.get_mut(name) {
+        *last_used = tick;
+        return Ok(symbol.clone());
+    }
+    let symbol = intern(name)?;
+    if cache.entries.len() >= SYMBOL_CACHE_CAPACITY {
+        let least_recently_used = cache
+            .entries
+            .iter()
+            .min_by_key(|(_, (_, last_used))| *last_used)
+            .map(|(name, _)| name.clone());
+        if let Some(name) = least_recently_used {
+            cache.entries.remove(&name);
+        }
+    }
+    cache.entries.insert(name.into(), (symbol.clone(), tick));
+    drop(guard);
+    Ok(symbol)
+}
@@ -1,115 +1,2202 @@
+use crate::{
+    error::{AtomConversionError, AtomParseError, PatchLifeCycleError, SendError},
+    helpers::{return_if_not_initialized, with_t_atoms},
+    patch_file::Endpoints,
+    send::{invalid_atom, message_length},
+    symbol::InternedSymbol,
+};
+
+#[cfg(feature = "gui")]
+use crate::error::GuiLifeCycleError;
+
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use tempfile::TempDir;
+
 /// A type to represent a pd Atom type in Rust side.
 ///
 /// Pd has floating point numbers and symbols as primitive types.
 /// This enum maps those to their Rust counterparts.
+///
+/// Atoms could be used as keys of a [`HashMap`](std::collections::HashMap) or be deduplicated.
+/// Floats are compared like [`f64`] values, so `0.0` and `-0.0` are equal and `NaN` is not equal to anything,
+/// not even to itself. A `NaN` key can be inserted to a map but it is never found again.
+/// Symbols are equal when their strings are. Use [`TotalAtom`] or [`Atom::total_cmp`]
+/// for keys which are compared with a total order instead, e.g. in a [`BTreeMap`](std::collections::BTreeMap).
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::types::Atom;
+/// use std::collections::HashMap;
+///
+/// let mut routes: HashMap<Atom, &str> = HashMap::new();
+/// routes.insert(Atom::from("play"), "transport");
+/// routes.insert(Atom::from(0.0), "reset");
+///
+/// assert_eq!(routes.get(&Atom::from("play")), Some(&"transport"));
+/// assert_eq!(routes.get(&Atom::from(-0.0)), Some(&"reset"));
+/// assert_eq!(routes.get(&Atom::from(f64::NAN)), None);
+/// ```
+///
+/// With the `serde` feature atoms are serialized as plain numbers and strings in human readable formats,
+/// e.g. `[440.0, "sine"]` in JSON, and as an enum with `Float` and `Symbol` variants in binary formats.
+/// JSON has no `NaN` or infinities so those floats are not read back and pointer atoms are not serialized.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Clone)]
 pub enum Atom {
     /// A floating point number from pd.
     Float(f64),
     /// A symbol from pd. Symbols are interned in pd, but it can be treated as Strings in Rust.
     Symbol(String),
+    /// A symbol which is interned in pd already with [`intern`](crate::symbol::intern),
+    /// so it is passed to pd without looking up its name.
+    ///
+    /// It is made by converting an [`InternedSymbol`] and behaves like a [`Symbol`](Atom::Symbol) with the same name otherwise,
+    /// e.g. they are equal and [`as_symbol`](Atom::as_symbol) returns the name.
+    Interned(InternedSymbol),
+    /// A pointer from pd, e.g. to a scalar which is output by a `[pointer]` object.
+    ///
+    /// It keeps lists which contain pointers at their length, the pointer itself can not be used.
+    /// Pointers are only valid while pd dispatches the message they are received in,
+    /// so lists which contain them can not be sent back to pd.
+    Pointer(PdPointer),
+}
+
+/// An opaque pointer which is received in an [`Atom::Pointer`].
+///
+/// It holds the address of the pd `t_gpointer` but it is never dereferenced,
+/// the address could only be compared to tell pointers apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PdPointer(usize);
+
+impl PdPointer {
+    pub(crate) const fn new(address: usize) -> Self {
+        Self(address)
+    }
+
+    /// Returns the address of the pointer.
+    #[must_use]
+    pub const fn address(self) -> usize {
+        self.0
+    }
+}
+
+/// A borrowed view of an atom which is received from pd, see [`on_list_borrowed`](crate::receive::on_list_borrowed).
+///
+/// Symbols are borrowed from pd instead of being copied to a `String`,
+/// so the view is only valid while the closure which receives it runs.
+/// Convert it with [`to_atom`](AtomView::to_atom) to keep it.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AtomView<'a> {
+    /// A floating point number from pd.
+    Float(f64),
+    /// A symbol from pd.
+    Symbol(&'a str),
+    /// A pointer from pd, see [`Atom::Pointer`].
+    Pointer(PdPointer),
+}
+
+impl AtomView<'_> {
+    /// Returns the value if the atom is a float.
+    #[must_use]
+    pub const fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if the atom is a symbol.
+    #[must_use]
+    pub const fn as_symbol(&self) -> Option<&str> {
+        match self {
+            Self::Symbol(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Converts the view to an owned atom, copying its symbol.
+    ///
+    /// # Example
+    /// ```rust
+    /// use libpd_rs::types::{Atom, AtomView};
+    ///
+    /// let views = [AtomView::Symbol("freq"), AtomView::Float(440.0)];
+    /// let atoms = views.iter().map(AtomView::to_atom).collect::<Vec<_>>();
+    /// assert_eq!(atoms, vec![Atom::from("freq"), Atom::from(440.0)]);
+    /// ```
+    #[must_use]
+    pub fn to_atom(&self) -> Atom {
+        match self {
+            Self::Float(value) => Atom::Float(*value),
+            Self::Symbol(value) => Atom::Symbol((*value).to_owned()),
+            Self::Pointer(pointer) => Atom::Pointer(*pointer),
+        }
+    }
+}
+
+impl From<AtomView<'_>> for Atom {
+    fn from(view: AtomView<'_>) -> Self {
+        view.to_atom()
+    }
+}
+
+impl From<&AtomView<'_>> for Atom {
+    fn from(view: &AtomView<'_>) -> Self {
+        view.to_atom()
+    }
+}
+
+impl<'a> From<&'a Atom> for AtomView<'a> {
+    fn from(atom: &'a Atom) -> Self {
+        match atom {
+            Atom::Float(value) => Self::Float(*value),
+            Atom::Symbol(value) => Self::Symbol(value),
+            Atom::Interned(symbol) => Self::Symbol(symbol.as_str()),
+            Atom::Pointer(pointer) => Self::Pointer(*pointer),
+        }
+    }
+}
+
+impl PartialEq<Atom> for AtomView<'_> {
+    fn eq(&self, other: &Atom) -> bool {
+        match (self, other) {
+            (Self::Float(value), Atom::Float(other)) => value == other,
+            (Self::Symbol(value), other) => other.as_symbol() == Some(*value),
+            (Self::Pointer(pointer), Atom::Pointer(other)) => pointer == other,
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq for Atom {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Float(float), Self::Float(other)) => float == other,
+            (Self::Pointer(pointer), Self::Pointer(other)) => pointer == other,
+            // Interned symbols are equal to symbols with the same name.
+            _ => {
+                matches!((self.as_symbol(), other.as_symbol()), (Some(symbol), Some(other)) if symbol == other)
+            }
+        }
+    }
+}
+
+impl PartialOrd for Atom {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        match (self, other) {
+            (Self::Float(float), Self::Float(other)) => float.partial_cmp(other),
+            (Self::Pointer(pointer), Self::Pointer(other)) => pointer.partial_cmp(other),
+            _ => match (self.as_symbol(), other.as_symbol()) {
+                (Some(symbol), Some(other)) => symbol.partial_cmp(other),
+                _ => self.variant_rank().partial_cmp(&other.variant_rank()),
+            },
+        }
+    }
+}
+
+impl Atom {
+    /// Returns the value if the atom is a float.
+    ///
+    /// # Example
+    /// ```rust
+    /// use libpd_rs::types::Atom;
+    ///
+    /// // A list which is received from pd, e.g. in `on_list`.
+    /// let list = vec![Atom::from("freq"), Atom::from(440.0)];
+    ///
+    /// assert_eq!(list.get(1).and_then(Atom::as_float), Some(440.0));
+    /// assert_eq!(list.get(0).and_then(Atom::as_float), None);
+    /// ```
+    #[must_use]
+    pub const fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            Self::Symbol(_) | Self::Interned(_) | Self::Pointer(_) => None,
+        }
+    }
+
+    /// Returns the value as an `f32` if the atom is a float.
+    ///
+    /// Pd stores floats as `f64` in this crate, the value is cast which could lose precision.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn as_f32(&self) -> Option<f32> {
+        self.as_float().map(|value| value as f32)
+    }
+
+    /// Returns the value as an `i64` if the atom is a float which is a whole number in the range of `i64`.
+    ///
+    /// Pd has floats only, so integers like note numbers and indices arrive as floats.
+    ///
+    /// # Example
+    /// ```rust
+    /// use libpd_rs::types::Atom;
+    ///
+    /// assert_eq!(Atom::from(60).as_exact_i64(), Some(60));
+    /// assert_eq!(Atom::from(-0.0).as_exact_i64(), Some(0));
+    /// assert_eq!(Atom::from(60.5).as_exact_i64(), None);
+    /// assert_eq!(Atom::from("60").as_exact_i64(), None);
+    /// ```
+    #[must_use]
+    pub fn as_exact_i64(&self) -> Option<i64> {
+        i64::try_from(self).ok()
+    }
+
+    /// Returns the value if the atom is a symbol.
+    ///
+    /// # Example
+    /// ```rust
+    /// use libpd_rs::types::Atom;
+    ///
+    /// // A list which is received from pd, e.g. in `on_list`.
+    /// let list = vec![Atom::from("freq"), Atom::from(440.0)];
+    ///
+    /// if let Some("freq") = list.first().and_then(Atom::as_symbol) {
+    ///     let frequency = list.get(1).and_then(Atom::as_float).unwrap_or_default();
+    ///     println!("Frequency is {frequency}");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn as_symbol(&self) -> Option<&str> {
+        match self {
+            Self::Float(_) | Self::Pointer(_) => None,
+            Self::Symbol(value) => Some(value),
+            Self::Interned(symbol) => Some(symbol.as_str()),
+        }
+    }
+
+    /// Returns the value if the atom is a symbol.
+    ///
+    /// Same as [`as_symbol`](Atom::as_symbol), named after [`String::as_str`].
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_symbol()
+    }
+
+    /// Checks if the atom is a float.
+    #[must_use]
+    pub const fn is_float(&self) -> bool {
+        matches!(self, Self::Float(_))
+    }
+
+    /// Checks if the atom is a symbol.
+    #[must_use]
+    pub const fn is_symbol(&self) -> bool {
+        matches!(self, Self::Symbol(_) | Self::Interned(_))
+    }
+
+    /// Checks if the atom is a pointer.
+    #[must_use]
+    pub const fn is_pointer(&self) -> bool {
+        matches!(self, Self::Pointer(_))
+    }
+
+    /// Returns the value of a float atom.
+    ///
+    /// Useful in tests, prefer [`as_float`](Atom::as_float) otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the atom is not a float.
+    #[must_use]
+    #[track_caller]
+    pub const fn unwrap_float(&self) -> f64 {
+        self.as_float().expect("Expected a float atom")
+    }
+
+    /// Returns the value of a symbol atom.
+    ///
+    /// Useful in tests, prefer [`as_symbol`](Atom::as_symbol) otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the atom is not a symbol.
+    #[must_use]
+    #[track_caller]
+    pub fn unwrap_symbol(&self) -> &str {
+        self.as_symbol().expect("Expected a symbol atom")
+    }
+
+    /// Compares atoms with a total order, floats are ordered with [`f64::total_cmp`].
+    ///
+    /// Floats come before symbols and symbols before pointers.
+    /// Symbols are compared as strings and pointers by their addresses.
+    /// Unlike `==`, `-0.0` is less than `0.0` and `NaN` is equal to a `NaN` with the same bits,
+    /// positive `NaN`s are greater than any other float and negative ones less.
+    ///
+    /// # Example
+    /// ```rust
+    /// use libpd_rs::types::Atom;
+    /// use std::cmp::Ordering;
+    ///
+    /// let mut list = vec![Atom::from("b"), Atom::from(f64::NAN), Atom::from(1), Atom::from("a")];
+    /// list.sort_by(Atom::total_cmp);
+    /// assert_eq!(list[0], Atom::from(1));
+    /// assert!(list[1].as_float().unwrap().is_nan());
+    /// assert_eq!(&list[2..], &[Atom::from("a"), Atom::from("b")]);
+    ///
+    /// assert_eq!(Atom::from(-0.0).total_cmp(&Atom::from(0.0)), Ordering::Less);
+    /// ```
+    #[must_use]
+    pub fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+        match (self, other) {
+            (Self::Float(float), Self::Float(other)) => float.total_cmp(other),
+            (Self::Pointer(pointer), Self::Pointer(other)) => pointer.cmp(other),
+            _ => match (self.as_symbol(), other.as_symbol()) {
+                (Some(symbol), Some(other)) => symbol.cmp(other),
+                _ => self.variant_rank().cmp(&other.variant_rank()),
+            },
+        }
+    }
+
+    /// The position of the variant in the order of [`total_cmp`](Atom::total_cmp).
+    const fn variant_rank(&self) -> u8 {
+        match self {
+            Self::Float(_) => 0,
+            Self::Symbol(_) | Self::Interned(_) => 1,
+            Self::Pointer(_) => 2,
+        }
+    }
+}
+
+/// Makes a `Vec<Atom>` from values which convert to an [`Atom`].
+///
+/// Like [`vec!`], it takes a list of values or a value and a count to repeat it.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{atoms, types::Atom};
+///
+/// let frequency = 220.0;
+/// assert_eq!(
+///     atoms![frequency * 2.0, "sine", 0.5],
+///     vec![Atom::from(440.0), Atom::from("sine"), Atom::from(0.5)]
+/// );
+/// assert_eq!(atoms![0.0; 16].len(), 16);
+/// assert!(atoms![].is_empty());
+/// ```
+///
+/// Values which do not convert to an atom do not compile.
+/// ```compile_fail
+/// use libpd_rs::atoms;
+///
+/// let list = atoms![440.0, vec![1, 2]];
+/// ```
+#[macro_export]
+macro_rules! atoms {
+    () => {
+        ::std::vec::Vec::<$crate::types::Atom>::new()
+    };
+    ($atom:expr; $count:expr) => {
+        ::std::vec![$crate::types::Atom::from($atom); $count]
+    };
+    ($($atom:expr),+ $(,)?) => {
+        ::std::vec![$($crate::types::Atom::from($atom)),+]
+    };
+}
+
+macro_rules! atom_from_number_type {
+    ($type:ty) => {
+        impl From<$type> for Atom {
+            fn from(value: $type) -> Self {
+                Self::Float(value.into())
+            }
+        }
+    };
+}
+
+macro_rules! atom_from_reference_number_type {
+    ($type:ty) => {
+        impl From<$type> for Atom {
+            fn from(value: $type) -> Self {
+                Self::Float((*value).into())
+            }
+        }
+    };
+}
+
+// Pd floats have 53 bits of mantissa, so larger integers are rounded to the nearest float.
+// Use `TryIntoAtom` to get an error instead.
+macro_rules! atom_from_lossy_number_type {
+    ($type:ty) => {
+        impl From<$type> for Atom {
+            #[allow(clippy::cast_precision_loss)]
+            fn from(value: $type) -> Self {
+                Self::Float(value as f64)
+            }
+        }
+        impl From<&$type> for Atom {
+            #[allow(clippy::cast_precision_loss)]
+            fn from(value: &$type) -> Self {
+                Self::Float(*value as f64)
+            }
+        }
+    };
+}
+
+atom_from_number_type!(i8);
+atom_from_number_type!(i16);
+atom_from_number_type!(i32);
+atom_from_number_type!(u8);
+atom_from_number_type!(u16);
+atom_from_number_type!(u32);
+atom_from_number_type!(f32);
+atom_from_number_type!(f64);
+atom_from_reference_number_type!(&i8);
+atom_from_reference_number_type!(&i16);
+atom_from_reference_number_type!(&i32);
+atom_from_reference_number_type!(&u8);
+atom_from_reference_number_type!(&u16);
+atom_from_reference_number_type!(&u32);
+atom_from_reference_number_type!(&f32);
+atom_from_reference_number_type!(&f64);
+atom_from_lossy_number_type!(i64);
+atom_from_lossy_number_type!(i128);
+atom_from_lossy_number_type!(isize);
+atom_from_lossy_number_type!(u64);
+atom_from_lossy_number_type!(u128);
+atom_from_lossy_number_type!(usize);
+
+// Pd has no booleans, toggles send `1` and `0` instead.
+impl From<bool> for Atom {
+    fn from(value: bool) -> Self {
+        Self::Float(if value { 1.0 } else { 0.0 })
+    }
+}
+impl From<&bool> for Atom {
+    fn from(value: &bool) -> Self {
+        Self::from(*value)
+    }
+}
+
+impl From<String> for Atom {
+    fn from(s: String) -> Self {
+        Self::Symbol(s)
+    }
+}
+impl From<&String> for Atom {
+    fn from(s: &String) -> Self {
+        Self::Symbol(s.clone())
+    }
+}
+impl From<&str> for Atom {
+    fn from(s: &str) -> Self {
+        Self::Symbol(s.to_owned())
+    }
+}
+impl From<Cow<'_, str>> for Atom {
+    fn from(s: Cow<'_, str>) -> Self {
+        Self::Symbol(s.into_owned())
+    }
+}
+impl From<char> for Atom {
+    fn from(c: char) -> Self {
+        Self::Symbol(c.to_string())
+    }
+}
+impl From<&char> for Atom {
+    fn from(c: &char) -> Self {
+        Self::Symbol(c.to_string())
+    }
+}
+
+impl TryFrom<&Atom> for f64 {
+    type Error = AtomConversionError;
+
+    fn try_from(atom: &Atom) -> Result<Self, Self::Error> {
+        match atom {
+            Atom::Float(value) => Ok(*value),
+            Atom::Symbol(symbol) => Err(AtomConversionError::ExpectedFloat(symbol.clone())),
+            Atom::Interned(symbol) => Err(AtomConversionError::ExpectedFloat(
+                symbol.as_str().to_owned(),
+            )),
+            Atom::Pointer(_) => Err(AtomConversionError::Pointer),
+        }
+    }
+}
+
+impl TryFrom<&Atom> for f32 {
+    type Error = AtomConversionError;
+
+    /// Pd stores floats as `f64` in this crate, the value is cast which could lose precision.
+    #[allow(clippy::cast_possible_truncation)]
+    fn try_from(atom: &Atom) -> Result<Self, Self::Error> {
+        f64::try_from(atom).map(|value| value as Self)
+    }
+}
+
+macro_rules! integer_try_from_atom {
+    ($type:ty) => {
+        impl TryFrom<&Atom> for $type {
+            type Error = AtomConversionError;
+
+            #[allow(clippy::cast_possible_truncation)]
+            #[allow(clippy::cast_precision_loss)]
+            #[allow(clippy::cast_lossless)]
+            #[allow(clippy::cast_sign_loss)]
+            fn try_from(atom: &Atom) -> Result<Self, Self::Error> {
+                let value = f64::try_from(atom)?;
+                // This is fine since the value is checked to be a whole number in the range of the type.
+                // The maximum is rounded up to a power of two when it is cast, so it is excluded.
+                if value.fract() == 0.0
+                    && value >= Self::MIN as f64
+                    && value < Self::MAX as f64 + 1.0
+                {
+                    Ok(value as Self)
+                } else {
+                    Err(AtomConversionError::NotAnInteger(value))
+                }
+            }
+        }
+    };
+}
+
+integer_try_from_atom!(i8);
+integer_try_from_atom!(i16);
+integer_try_from_atom!(i32);
+integer_try_from_atom!(i64);
+integer_try_from_atom!(isize);
+integer_try_from_atom!(u8);
+integer_try_from_atom!(u16);
+integer_try_from_atom!(u32);
+integer_try_from_atom!(u64);
+integer_try_from_atom!(usize);
+
+/// The largest integer up to which pd floats represent every integer exactly.
+///
+/// It is `2^24` if libpd is built with 32 bit floats and `2^53` if it is built with `PD_FLOATSIZE=64`,
+/// see [`float_size`](crate::info::float_size).
+/// Integers are stored as `f64` in [`Atom`] but pd rounds them to its own floats when they are sent.
+/// With 32 bit floats `16777216` (`2^24`) is sent exactly while `16777217` arrives at pd as `16777216`.
+pub const MAX_EXACT_INTEGER: i64 = if crate::info::float_size() == 64 {
+    1 << 53
+} else {
+    1 << 24
+};
+
+/// Integers which are converted to an [`Atom`] only if pd floats represent them exactly.
+///
+/// The `From` conversions of integers to atoms round the integers which pd floats can not represent,
+/// this conversion returns an error for them instead.
+/// Integers in `-MAX_EXACT_INTEGER..=MAX_EXACT_INTEGER` are converted, see [`MAX_EXACT_INTEGER`].
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::types::{Atom, TryIntoAtom, MAX_EXACT_INTEGER};
+///
+/// assert_eq!(60_i32.try_into_atom().unwrap(), Atom::from(60));
+/// assert!(MAX_EXACT_INTEGER.try_into_atom().is_ok());
+/// assert!((MAX_EXACT_INTEGER + 1).try_into_atom().is_err());
+/// ```
+pub trait TryIntoAtom {
+    /// Converts the integer to an atom if a pd float represents it exactly.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`NotExactInteger`](crate::error::AtomConversionError::NotExactInteger)
+    fn try_into_atom(self) -> Result<Atom, AtomConversionError>;
+}
+
+macro_rules! try_into_atom_for_integer {
+    ($type:ty) => {
+        impl TryIntoAtom for $type {
+            // This is fine since the value is checked to be in the range which pd floats represent exactly.
+            #[allow(clippy::cast_precision_loss)]
+            #[allow(clippy::cast_lossless)]
+            fn try_into_atom(self) -> Result<Atom, AtomConversionError> {
+                let value = i128::try_from(self)
+                    .map_err(|_| AtomConversionError::NotExactInteger(self.to_string()))?;
+                if value.unsigned_abs() <= MAX_EXACT_INTEGER.unsigned_abs() as u128 {
+                    Ok(Atom::Float(value as f64))
+                } else {
+                    Err(AtomConversionError::NotExactInteger(self.to_string()))
+                }
+            }
+        }
+    };
+}
+
+try_into_atom_for_integer!(i8);
+try_into_atom_for_integer!(i16);
+try_into_atom_for_integer!(i32);
+try_into_atom_for_integer!(i64);
+try_into_atom_for_integer!(i128);
+try_into_atom_for_integer!(isize);
+try_into_atom_for_integer!(u8);
+try_into_atom_for_integer!(u16);
+try_into_atom_for_integer!(u32);
+try_into_atom_for_integer!(u64);
+try_into_atom_for_integer!(u128);
+try_into_atom_for_integer!(usize);
+
+impl TryFrom<&Atom> for String {
+    type Error = AtomConversionError;
+
+    fn try_from(atom: &Atom) -> Result<Self, Self::Error> {
+        <&str>::try_from(atom).map(ToOwned::to_owned)
+    }
+}
+
+impl<'a> TryFrom<&'a Atom> for &'a str {
+    type Error = AtomConversionError;
+
+    fn try_from(atom: &'a Atom) -> Result<Self, Self::Error> {
+        match atom {
+            Atom::Float(value) => Err(AtomConversionError::ExpectedSymbol(*value)),
+            Atom::Symbol(symbol) => Ok(symbol),
+            Atom::Interned(symbol) => Ok(symbol.as_str()),
+            Atom::Pointer(_) => Err(AtomConversionError::Pointer),
+        }
+    }
+}
+
+/// A tuple of values which could be converted from a list of atoms, see [`ExtractAtoms`].
+///
+/// It is implemented for tuples of up to 8 elements of types which implement
+/// `TryFrom<&Atom, Error = AtomConversionError>`, like `f32`, `f64`, the integer types, `String` and `&str`.
+/// Floats are converted to integers only if they are whole numbers in the range of the integer type.
+pub trait FromAtoms<'a>: Sized {
+    /// The number of atoms which are converted.
+    const ARITY: usize;
+
+    /// Converts the atoms positionally to the values of the tuple.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`WrongArity`](crate::error::AtomConversionError::WrongArity)
+    /// - [`Element`](crate::error::AtomConversionError::Element)
+    fn from_atoms(atoms: &'a [Atom]) -> Result<Self, AtomConversionError>;
+}
+
+fn convert_element<'a, T: TryFrom<&'a Atom, Error = AtomConversionError>>(
+    atoms: &'a [Atom],
+    index: usize,
+) -> Result<T, AtomConversionError> {
+    let atom = atoms.get(index).ok_or(AtomConversionError::WrongArity {
+        expected: index + 1,
+        found: atoms.len(),
+    })?;
+    T::try_from(atom).map_err(|error| AtomConversionError::Element {
+        index,
+        source: Box::new(error),
+    })
+}
+
+macro_rules! from_atoms_for_tuple {
+    ($arity:literal; $($index:tt $type:ident),+) => {
+        impl<'a, $($type: TryFrom<&'a Atom, Error = AtomConversionError>),+> FromAtoms<'a> for ($($type,)+) {
+            const ARITY: usize = $arity;
+
+            fn from_atoms(atoms: &'a [Atom]) -> Result<Self, AtomConversionError> {
+                if atoms.len() != Self::ARITY {
+                    return Err(AtomConversionError::WrongArity {
+                        expected: Self::ARITY,
+                        found: atoms.len(),
+                    });
+                }
+                Ok(($(convert_element::<$type>(atoms, $index)?,)+))
+            }
+        }
+    };
+}
+
+from_atoms_for_tuple!(1; 0 A);
+from_atoms_for_tuple!(2; 0 A, 1 B);
+from_atoms_for_tuple!(3; 0 A, 1 B, 2 C);
+from_atoms_for_tuple!(4; 0 A, 1 B, 2 C, 3 D);
+from_atoms_for_tuple!(5; 0 A, 1 B, 2 C, 3 D, 4 E);
+from_atoms_for_tuple!(6; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+from_atoms_for_tuple!(7; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+from_atoms_for_tuple!(8; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+
+/// Extracts typed values from a list of atoms, like one which is received in [`on_list`](crate::receive::on_list).
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::types::{Atom, ExtractAtoms};
+///
+/// let list = vec![Atom::from(440), Atom::from("sine"), Atom::from(0.5)];
+///
+/// let (frequency, shape, gain): (f32, String, f32) = list.extract().unwrap();
+/// assert_eq!((frequency, shape.as_str(), gain), (440.0, "sine", 0.5));
+///
+/// // Symbols could be borrowed from the list.
+/// let ((frequency, shape), rest) = list.extract_prefix::<(i32, &str)>().unwrap();
+/// assert_eq!((frequency, shape), (440, "sine"));
+/// assert_eq!(rest, &[Atom::from(0.5)]);
+/// ```
+pub trait ExtractAtoms {
+    /// Converts all of the atoms positionally to the values of a tuple.
+    ///
+    /// Trailing atoms which do not have a place in the tuple are an error,
+    /// use [`extract_prefix`](ExtractAtoms::extract_prefix) to ignore them.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`WrongArity`](crate::error::AtomConversionError::WrongArity)
+    /// - [`Element`](crate::error::AtomConversionError::Element)
+    fn extract<'a, T: FromAtoms<'a>>(&'a self) -> Result<T, AtomConversionError>;
+
+    /// Converts the first atoms positionally to the values of a tuple and returns the rest of the atoms.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`WrongArity`](crate::error::AtomConversionError::WrongArity) if there are less atoms than the tuple has
+    /// - [`Element`](crate::error::AtomConversionError::Element)
+    fn extract_prefix<'a, T: FromAtoms<'a>>(
+        &'a self,
+    ) -> Result<(T, &'a [Atom]), AtomConversionError>;
+}
+
+impl ExtractAtoms for [Atom] {
+    fn extract<'a, T: FromAtoms<'a>>(&'a self) -> Result<T, AtomConversionError> {
+        T::from_atoms(self)
+    }
+
+    fn extract_prefix<'a, T: FromAtoms<'a>>(
+        &'a self,
+    ) -> Result<(T, &'a [Atom]), AtomConversionError> {
+        match (self.get(..T::ARITY), self.get(T::ARITY..)) {
+            (Some(prefix), Some(rest)) => Ok((T::from_atoms(prefix)?, rest)),
+            _ => Err(AtomConversionError::WrongArity {
+                expected: T::ARITY,
+                found: self.len(),
+            }),
+        }
+    }
+}
+
+/// Values which could be sent as a list of atoms, like with [`send_list_to`](crate::send::send_list_to).
+///
+/// It is implemented for slices, arrays and vectors of atoms, slices of `f32` and `f64`,
+/// arrays of values which convert to atoms and tuples of up to 8 of them.
+/// Atoms of arrays and tuples are converted on the stack, so sending them does not build a `Vec<Atom>`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::types::{Atom, IntoAtoms};
+///
+/// assert_eq!(("env", 0.0, 50).len_hint(), 3);
+/// assert_eq!(
+///     ("env", 0.0, 50).into_atoms(),
+///     vec![Atom::from("env"), Atom::from(0.0), Atom::from(50)]
+/// );
+/// assert_eq!([1.0_f32, 2.0].as_slice().into_atoms(), vec![Atom::from(1.0), Atom::from(2.0)]);
+/// ```
+pub trait IntoAtoms {
+    /// The number of atoms which the value converts to.
+    fn len_hint(&self) -> usize;
+
+    /// Converts the value to atoms and calls `operation` with them.
+    fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R;
+
+    /// Converts the value to a vector of atoms.
+    fn into_atoms(self) -> Vec<Atom>
+    where
+        Self: Sized,
+    {
+        self.with_atoms(<[Atom]>::to_vec)
+    }
+}
+
+impl IntoAtoms for &[Atom] {
+    fn len_hint(&self) -> usize {
+        self.len()
+    }
+
+    fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R {
+        operation(self)
+    }
+}
+
+impl IntoAtoms for &Vec<Atom> {
+    fn len_hint(&self) -> usize {
+        self.len()
+    }
+
+    fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R {
+        operation(self)
+    }
+}
+
+impl IntoAtoms for Vec<Atom> {
+    fn len_hint(&self) -> usize {
+        self.len()
+    }
+
+    fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R {
+        operation(&self)
+    }
+
+    fn into_atoms(self) -> Vec<Atom> {
+        self
+    }
+}
+
+impl<const N: usize> IntoAtoms for &[Atom; N] {
+    fn len_hint(&self) -> usize {
+        N
+    }
+
+    fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R {
+        operation(self)
+    }
+}
+
+impl<T: Into<Atom>, const N: usize> IntoAtoms for [T; N] {
+    fn len_hint(&self) -> usize {
+        N
+    }
+
+    fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R {
+        operation(&self.map(Into::into))
+    }
+}
+
+impl IntoAtoms for &[f32] {
+    fn len_hint(&self) -> usize {
+        self.len()
+    }
+
+    fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R {
+        operation(&self.iter().map(Atom::from).collect::<Vec<_>>())
+    }
+}
+
+impl IntoAtoms for &[f64] {
+    fn len_hint(&self) -> usize {
+        self.len()
+    }
+
+    fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R {
+        operation(&self.iter().map(Atom::from).collect::<Vec<_>>())
+    }
+}
+
+macro_rules! into_atoms_for_tuple {
+    ($arity:literal; $($index:tt $type:ident),+) => {
+        impl<$($type: Into<Atom>),+> IntoAtoms for ($($type,)+) {
+            fn len_hint(&self) -> usize {
+                $arity
+            }
+
+            fn with_atoms<R, O: FnOnce(&[Atom]) -> R>(self, operation: O) -> R {
+                operation(&[$(self.$index.into()),+])
+            }
+        }
+    };
+}
+
+into_atoms_for_tuple!(1; 0 A);
+into_atoms_for_tuple!(2; 0 A, 1 B);
+into_atoms_for_tuple!(3; 0 A, 1 B, 2 C);
+into_atoms_for_tuple!(4; 0 A, 1 B, 2 C, 3 D);
+into_atoms_for_tuple!(5; 0 A, 1 B, 2 C, 3 D, 4 E);
+into_atoms_for_tuple!(6; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+into_atoms_for_tuple!(7; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+into_atoms_for_tuple!(8; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+
+// Float equality is not reflexive for `NaN`, which only makes a `NaN` atom unreachable as a key.
+impl Eq for Atom {}
+
+impl core::hash::Hash for Atom {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // Interned symbols are equal to symbols, so they need to have the same hash.
+        self.variant_rank().hash(state);
+        match self {
+            Self::Float(float) => {
+                // `0.0` and `-0.0` are equal so they need to have the same hash.
+                let float = if *float == 0.0 { 0.0 } else { *float };
+                float.to_bits().hash(state);
+            }
+            Self::Symbol(s) => s.hash(state),
+            Self::Interned(symbol) => symbol.as_str().hash(state),
+            Self::Pointer(pointer) => pointer.hash(state),
+        }
+    }
+}
+
+/// An atom which is compared and hashed with the total order of [`Atom::total_cmp`].
+///
+/// It could be used as a key of a [`BTreeMap`](std::collections::BTreeMap),
+/// or of a [`HashMap`](std::collections::HashMap) where `NaN` keys should be found again and `-0.0` should not be `0.0`.
+/// Floats are equal when their bits are, so `NaN`s with different bits are different keys.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::types::{Atom, TotalAtom};
+/// use std::collections::{BTreeMap, HashMap};
+///
+/// let mut cache: HashMap<Vec<TotalAtom>, &str> = HashMap::new();
+/// let key = |list: &[Atom]| list.iter().cloned().map(TotalAtom).collect::<Vec<_>>();
+/// cache.insert(key(&[Atom::from("gain"), Atom::from(f64::NAN)]), "muted");
+/// assert_eq!(cache.get(&key(&[Atom::from("gain"), Atom::from(f64::NAN)])), Some(&"muted"));
+/// assert_eq!(cache.get(&key(&[Atom::from("gain"), Atom::from(-f64::NAN)])), None);
+///
+/// let mut sorted = BTreeMap::new();
+/// sorted.insert(TotalAtom(Atom::from(0.0)), "zero");
+/// sorted.insert(TotalAtom(Atom::from(-0.0)), "negative zero");
+/// assert_eq!(sorted.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TotalAtom(pub Atom);
+
+impl PartialEq for TotalAtom {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0).is_eq()
+    }
+}
+
+impl Eq for TotalAtom {}
+
+impl PartialOrd for TotalAtom {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalAtom {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl core::hash::Hash for TotalAtom {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.variant_rank().hash(state);
+        match &self.0 {
+            // Floats are equal in the total order when their bits are.
+            Atom::Float(float) => float.to_bits().hash(state),
+            Atom::Symbol(s) => s.hash(state),
+            Atom::Interned(symbol) => symbol.as_str().hash(state),
+            Atom::Pointer(pointer) => pointer.hash(state),
+        }
+    }
+}
+
+impl From<Atom> for TotalAtom {
+    fn from(atom: Atom) -> Self {
+        Self(atom)
+    }
+}
+
+impl From<TotalAtom> for Atom {
+    fn from(atom: TotalAtom) -> Self {
+        atom.0
+    }
+}
+
+// Atoms are formatted like pd prints them, so they could be parsed back with `parse_atoms`,
+// except for NaN and infinite floats which are read back as symbols.
+impl core::fmt::Display for Atom {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Float(float) => write_float(f, *float),
+            Self::Symbol(s) => write_symbol(f, s),
+            Self::Interned(symbol) => write_symbol(f, symbol.as_str()),
+            // Like pd prints it.
+            Self::Pointer(_) => f.write_str("(pointer)"),
+        }
+    }
+}
+
+impl core::str::FromStr for Atom {
+    type Err = AtomParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match <[Self; 1]>::try_from(parse_atoms(s)?) {
+            Ok([atom]) => Ok(atom),
+            Err(atoms) => Err(AtomParseError::NotOneAtom(atoms.len())),
+        }
+    }
+}
+
+/// Formats a float like `printf("%g")` which pd uses, with 6 significant digits.
+///
+/// NaN and infinities are written as `nan`, `inf` and `-inf`, which are symbols when they are read back.
+fn write_float(f: &mut std::fmt::Formatter, value: f64) -> Result<(), std::fmt::Error> {
+    if value.is_nan() {
+        return f.write_str("nan");
+    }
+    if value.is_infinite() {
+        return f.write_str(if value > 0.0 { "inf" } else { "-inf" });
+    }
+    if value == 0.0 {
+        return f.write_str(if value.is_sign_negative() { "-0" } else { "0" });
+    }
+    // The exponent is taken after rounding, `999999.5` is printed as `1e+06`.
+    let scientific = format!("{value:.5e}");
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or_default();
+    if (-4..6).contains(&exponent) {
+        let precision = usize::try_from(5 - exponent).unwrap_or_default();
+        f.write_str(trim_fraction(&format!("{value:.precision$}")))
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        write!(f, "{}e{sign}{:02}", trim_fraction(mantissa), exponent.abs())
+    }
+}
+
+fn trim_fraction(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
+
+/// Escapes the characters which pd would read as separators, whitespace or dollar arguments.
+///
+/// The leading character of a symbol which would be read as a number or as the empty symbol `""` is escaped too,
+/// and the empty symbol is written as `""`, so every symbol is read back by [`parse_atoms`] as it is.
+fn write_symbol(f: &mut std::fmt::Formatter, symbol: &str) -> Result<(), std::fmt::Error> {
+    if symbol.is_empty() {
+        return f.write_str(EMPTY_SYMBOL);
+    }
+    if reads_as_number(symbol) || symbol == EMPTY_SYMBOL {
+        f.write_char('\\')?;
+    }
+    let mut chars = symbol.chars().peekable();
+    while let Some(c) = chars.next() {
+        let dollar_argument = c == '$' && chars.peek().is_some_and(char::is_ascii_digit);
+        if matches!(c, ';' | ',' | '\\') || is_pd_whitespace(c) || dollar_argument {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// How the empty symbol is written in pd's text syntax by [`format_atoms`] and read by [`parse_atoms`].
+const EMPTY_SYMBOL: &str = "\"\"";
+
+/// Checks if an unescaped token is read as a float by [`parse_atoms`].
+fn reads_as_number(token: &str) -> bool {
+    token
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+        && token.parse::<f64>().is_ok()
+}
+
+const fn is_pd_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r')
+}
+
+/// Parses atoms from pd's text syntax, like a message box would.
+///
+/// Atoms are separated by whitespace. Numbers are read as floats and anything else as symbols.
+/// A backslash escapes the next character, so `\ `, `\;`, `\,`, `\\`, `\$` and escaped tabs and newlines could be a part of a symbol
+/// and an escaped number like `\1` is read as a symbol. The empty symbol is written as `""`.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::types::{parse_atoms, Atom};
+///
+/// let atoms = parse_atoms("440 0.5 sine my\\ voice").unwrap();
+/// assert_eq!(
+///     atoms,
+///     vec![
+///         Atom::from(440),
+///         Atom::from(0.5),
+///         Atom::from("sine"),
+///         Atom::from("my voice")
+///     ]
+/// );
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`TrailingBackslash`](crate::error::AtomParseError::TrailingBackslash)
+/// - [`Separator`](crate::error::AtomParseError::Separator)
+/// - [`DollarArgument`](crate::error::AtomParseError::DollarArgument)
+pub fn parse_atoms(text: &str) -> Result<Vec<Atom>, AtomParseError> {
+    let mut atoms = vec![];
+    let mut chars = text.chars().peekable();
+    loop {
+        while chars.next_if(|c| is_pd_whitespace(*c)).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(atoms);
+        }
+        let mut token = String::new();
+        let mut escaped = false;
+        while let Some(c) = chars.next_if(|c| !is_pd_whitespace(*c)) {
+            match c {
+                '\\' => {
+                    token.push(chars.next().ok_or(AtomParseError::TrailingBackslash)?);
+                    escaped = true;
+                }
+                ';' | ',' => return Err(AtomParseError::Separator(c)),
+                '$' if chars.peek().is_some_and(char::is_ascii_digit) => {
+                    let mut argument = String::from('$');
+                    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                        argument.push(digit);
+                    }
+                    return Err(AtomParseError::DollarArgument(argument));
+                }
+                c => token.push(c),
+            }
+        }
+        if escaped {
+            atoms.push(Atom::Symbol(token));
+        } else if token == EMPTY_SYMBOL {
+            atoms.push(Atom::Symbol(String::new()));
+        } else {
+            match token.parse::<f64>() {
+                Ok(float) if reads_as_number(&token) => atoms.push(Atom::Float(float)),
+                _ => atoms.push(Atom::Symbol(token)),
+            }
+        }
+    }
+}
+
+/// Formats atoms in pd's text syntax, the inverse of [`parse_atoms`].
+///
+/// Floats are printed like pd prints them, rounded to 6 significant digits.
+/// Symbols are escaped so they are parsed back as they are, see [`parse_atoms`],
+/// so lists which only have floats with at most 6 significant digits are parsed back to the same atoms.
+///
+/// NaN and infinite floats are printed as `nan`, `inf` and `-inf` like pd prints them.
+/// They do not round trip, pd and [`parse_atoms`] read them back as symbols.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::types::{format_atoms, parse_atoms, Atom};
+///
+/// let atoms = vec![Atom::from(440), Atom::from(0.5), Atom::from("my voice")];
+/// let text = format_atoms(&atoms);
+///
+/// assert_eq!(text, "440 0.5 my\\ voice");
+/// assert_eq!(parse_atoms(&text).unwrap(), atoms);
+/// ```
+#[must_use]
+pub fn format_atoms(atoms: &[Atom]) -> String {
+    let mut text = String::new();
+    for (index, atom) in atoms.iter().enumerate() {
+        if index > 0 {
+            text.push(' ');
+        }
+        // Writing to a string can not fail.
+        let _ = write!(text, "{atom}");
+    }
+    text
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Atom {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match (self, serializer.is_human_readable()) {
+            (Self::Float(float), true) => serializer.serialize_f64(*float),
+            (Self::Symbol(s), true) => serializer.serialize_str(s),
+            (Self::Float(float), false) => {
+                serde::Serialize::serialize(&TaggedAtom::<&str>::Float(*float), serializer)
+            }
+            (Self::Symbol(s), false) => {
+                serde::Serialize::serialize(&TaggedAtom::Symbol(s.as_str()), serializer)
+            }
+            (Self::Interned(symbol), true) => serializer.serialize_str(symbol.as_str()),
+            (Self::Interned(symbol), false) => {
+                serde::Serialize::serialize(&TaggedAtom::Symbol(symbol.as_str()), serializer)
+            }
+            (Self::Pointer(_), _) => Err(serde::ser::Error::custom(
+                "pointer atoms can not be serialized, they are only valid in pd",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Atom {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            Ok(
+                match <UntaggedAtom as serde::Deserialize>::deserialize(deserializer)? {
+                    UntaggedAtom::Float(float) => Self::Float(float),
+                    UntaggedAtom::Symbol(s) => Self::Symbol(s),
+                },
+            )
+        } else {
+            Ok(
+                match <TaggedAtom<String> as serde::Deserialize>::deserialize(deserializer)? {
+                    TaggedAtom::Float(float) => Self::Float(float),
+                    TaggedAtom::Symbol(s) => Self::Symbol(s),
+                },
+            )
+        }
+    }
+}
+
+/// Binary formats can not guess the type of a value, so they need to be told which variant follows.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Atom")]
+enum TaggedAtom<S> {
+    Float(f64),
+    Symbol(S),
+}
+
+/// Human readable formats tell numbers and strings apart, integers are read as floats too.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum UntaggedAtom {
+    Float(f64),
+    Symbol(String),
 }
 
-macro_rules! atom_from_number_type {
-    ($type:ty) => {
-        impl From<$type> for Atom {
-            fn from(value: $type) -> Self {
-                Self::Float(value.into())
+/// An opened pd patch which is returned from [`open_patch`](crate::open_patch).
+///
+/// It holds the raw handle of the patch, which is a [`c_void`](std::ffi::c_void) in the underlying sys crate
+/// but for convenience it is converted to `usize` and held here.
+/// Next to it, the location of the patch file and its `$0` are stored when it is opened.
+///
+/// The patch is closed when it is dropped.
+/// Use [`close`](Patch::close) to observe the errors of closing it
+/// or [`leak`](Patch::leak) to keep it open after the handle is gone.
+///
+/// Dropping it after [`terminate`](crate::terminate) or [`release_internal_queues`](crate::release_internal_queues) does nothing.
+#[derive(Debug)]
+pub struct Patch {
+    ptr: usize,
+    /// The number which the patch is listed with in [`open_patches`](crate::open_patches), `0` when it is not known.
+    registration: usize,
+    /// Shared with the destinations of the patch, it is cleared when the patch is closed.
+    open: Arc<AtomicBool>,
+    directory: PathBuf,
+    file_name: String,
+    dollar_zero: i32,
+    arguments: Vec<Atom>,
+    local_separator: String,
+    /// Tracks the calls to [`show_window`](Patch::show_window) and [`hide_window`](Patch::hide_window).
+    #[cfg(feature = "gui")]
+    window_visible: bool,
+    #[cfg(feature = "std")]
+    temporary_directory: Option<TempDir>,
+}
+
+/// The former name of [`Patch`].
+pub type PatchFileHandle = Patch;
+
+impl Patch {
+    pub(crate) const fn as_mut_ptr(&self) -> *mut std::ffi::c_void {
+        self.ptr as *mut std::ffi::c_void
+    }
+
+    pub(crate) fn new(
+        ptr: *mut std::ffi::c_void,
+        directory: PathBuf,
+        file_name: String,
+        dollar_zero: i32,
+    ) -> Self {
+        Self {
+            ptr: ptr as usize,
+            registration: 0,
+            open: Arc::new(AtomicBool::new(true)),
+            directory,
+            file_name,
+            dollar_zero,
+            arguments: vec![],
+            local_separator: "-".to_owned(),
+            #[cfg(feature = "gui")]
+            window_visible: false,
+            #[cfg(feature = "std")]
+            temporary_directory: None,
+        }
+    }
+
+    pub(crate) const fn registration(&self) -> usize {
+        self.registration
+    }
+
+    /// Lists the patch in [`open_patches`](crate::open_patches).
+    pub(crate) fn register(&mut self) {
+        let info = PatchInfo::new(self);
+        self.registration = info.registration;
+        crate::registry::registry()
+            .patches
+            .push(crate::registry::TrackedPatch {
+                info,
+                open: self.open.clone(),
+            });
+    }
+
+    /// Records the creation arguments which the patch is opened with.
+    pub(crate) fn set_arguments(&mut self, arguments: Vec<Atom>) {
+        self.arguments = arguments;
+    }
+
+    /// Keeps the directory which the patch is written to until the patch is dropped.
+    #[cfg(feature = "std")]
+    pub(crate) fn keep_temporary_directory(&mut self, directory: TempDir) {
+        self.temporary_directory = Some(directory);
+    }
+
+    /// Wraps a raw patch handle, e.g. one which is returned from [`into_raw`](Patch::into_raw)
+    /// or from calling the sys crate directly.
+    ///
+    /// The resulting patch has no metadata, its [`directory`](Patch::directory) and [`file_name`](Patch::file_name) are empty
+    /// and its [`dollar_zero`](Patch::dollar_zero) is `0`.
+    /// Use [`get_dollar_zero`](crate::get_dollar_zero) to ask pd for the `$0` of it.
+    #[must_use]
+    pub fn from_raw(ptr: *mut std::ffi::c_void) -> Self {
+        Self::new(ptr, PathBuf::new(), String::new(), 0)
+    }
+
+    /// Returns the raw patch handle to use it with the sys crate.
+    ///
+    /// The patch is not closed when the raw handle is dropped, it could be wrapped again with [`from_raw`](Patch::from_raw).
+    #[must_use]
+    pub fn into_raw(mut self) -> *mut std::ffi::c_void {
+        let ptr = self.as_mut_ptr();
+        // Nothing to close on drop.
+        self.ptr = 0;
+        ptr
+    }
+
+    /// Closes the patch.
+    ///
+    /// Same as calling [`close_patch`](crate::close_patch) with the patch.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`FailedToClosePatch`](crate::error::PatchLifeCycleError::FailedToClosePatch)
+    /// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+    pub fn close(self) -> Result<(), PatchLifeCycleError> {
+        crate::close_patch(self)
+    }
+
+    /// Closes the patch and opens the same file again, e.g. after it is edited.
+    ///
+    /// The patch gets a new [`dollar_zero`](Patch::dollar_zero) since pd assigns a new one to every opened patch.
+    /// It is opened with the same [`arguments`](Patch::arguments).
+    /// Subscriptions made with [`start_listening_from`](crate::receive::start_listening_from) and the closures
+    /// registered in the [`receive`](crate::receive) module are bound to symbols, not to the patch,
+    /// so they keep working with the reloaded patch.
+    ///
+    /// If the patch could not be opened again it is left closed, [`is_open`](Patch::is_open) returns `false`
+    /// and calling this function again retries opening it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use libpd_rs::open_patch;
+    ///
+    /// libpd_rs::init().unwrap();
+    ///
+    /// let mut patch = open_patch("tests/patches/sine.pd").unwrap();
+    /// // Edit the patch file..
+    /// patch.reload().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist) when the patch is created with
+    ///   [`from_raw`](Patch::from_raw) and has no path to reload from, the patch is left open
+    /// - [`FailedToReopenPatch`](crate::error::PatchLifeCycleError::FailedToReopenPatch)
+    /// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+    pub fn reload(&mut self) -> Result<(), PatchLifeCycleError> {
+        return_if_not_initialized!(PatchLifeCycleError::NotInitialized);
+        let path = self.path();
+        if self.file_name.is_empty() {
+            return Err(PatchLifeCycleError::PathDoesNotExist(
+                path.to_string_lossy().to_string(),
+            ));
+        }
+        if self.is_open() {
+            // Fails only when the patch is already closed by pd, it is closed either way.
+            let _ = crate::close_patch(Self::from_raw(self.as_mut_ptr()));
+            self.ptr = 0;
+            self.dollar_zero = 0;
+        }
+        let reopened = crate::open_patch_with_args(&path, &self.arguments).map_err(|err| {
+            PatchLifeCycleError::FailedToReopenPatch {
+                path: path.to_string_lossy().to_string(),
+                source: Box::new(err),
             }
+        })?;
+        self.dollar_zero = reopened.dollar_zero();
+        self.registration = reopened.registration();
+        self.open = reopened.open.clone();
+        self.ptr = reopened.into_raw() as usize;
+        #[cfg(feature = "gui")]
+        {
+            self.window_visible = false;
         }
-    };
-}
+        Ok(())
+    }
 
-macro_rules! atom_from_reference_number_type {
-    ($type:ty) => {
-        impl From<$type> for Atom {
-            fn from(value: $type) -> Self {
-                Self::Float((*value).into())
-            }
+    /// Opens the window of the patch in the gui, like `vis 1` would.
+    ///
+    /// The message is sent to the canvas of this patch directly instead of `pd-<file name>`,
+    /// which every patch opened from the same file listens to.
+    ///
+    /// This method is only available with the `gui` feature.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use libpd_rs::{gui::start_gui_auto, open_patch};
+    ///
+    /// libpd_rs::init().unwrap();
+    /// start_gui_auto().unwrap();
+    ///
+    /// let mut patch = open_patch("tests/patches/sine.pd").unwrap();
+    /// patch.show_window().unwrap();
+    /// assert!(patch.window_visible());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`NotRunning`](crate::error::GuiLifeCycleError::NotRunning)
+    /// - [`PatchClosed`](crate::error::GuiLifeCycleError::PatchClosed)
+    #[cfg(feature = "gui")]
+    pub fn show_window(&mut self) -> Result<(), GuiLifeCycleError> {
+        self.set_window_visible(true)
+    }
+
+    /// Closes the window of the patch in the gui, like `vis 0` would, the patch keeps running.
+    ///
+    /// See [`show_window`](Patch::show_window).
+    ///
+    /// This method is only available with the `gui` feature.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`NotRunning`](crate::error::GuiLifeCycleError::NotRunning)
+    /// - [`PatchClosed`](crate::error::GuiLifeCycleError::PatchClosed)
+    #[cfg(feature = "gui")]
+    pub fn hide_window(&mut self) -> Result<(), GuiLifeCycleError> {
+        self.set_window_visible(false)
+    }
+
+    /// Checks if the window of the patch is shown, as far as this crate knows.
+    ///
+    /// It is tracked from the calls to [`show_window`](Patch::show_window) and [`hide_window`](Patch::hide_window),
+    /// so it does not see the window being closed in the gui or opened from pd's window menu.
+    /// It is `false` after the patch is reloaded.
+    ///
+    /// This method is only available with the `gui` feature.
+    #[cfg(feature = "gui")]
+    #[must_use]
+    pub const fn window_visible(&self) -> bool {
+        self.window_visible
+    }
+
+    #[cfg(feature = "gui")]
+    fn set_window_visible(&mut self, visible: bool) -> Result<(), GuiLifeCycleError> {
+        if !crate::gui::gui_is_running() {
+            return Err(GuiLifeCycleError::NotRunning);
         }
-    };
-}
+        if !self.is_open() || !self.open.load(Ordering::Acquire) {
+            return Err(GuiLifeCycleError::PatchClosed(
+                self.path().to_string_lossy().into_owned(),
+            ));
+        }
+        // A float always converts.
+        let mut argument: Vec<libpd_sys::t_atom> =
+            crate::helpers::make_t_atom_list_from_atom_list!(&[Atom::from(i32::from(visible))])
+                .unwrap_or_default();
+        let selector = c"vis";
+        unsafe {
+            libpd_sys::sys_lock();
+            libpd_sys::pd_typedmess(
+                self.as_mut_ptr().cast(),
+                libpd_sys::gensym(selector.as_ptr()),
+                1,
+                argument.as_mut_ptr(),
+            );
+            libpd_sys::sys_unlock();
+        }
+        self.window_visible = visible;
+        Ok(())
+    }
 
-atom_from_number_type!(i8);
-atom_from_number_type!(i16);
-atom_from_number_type!(i32);
-atom_from_number_type!(u8);
-atom_from_number_type!(u16);
-atom_from_number_type!(u32);
-atom_from_number_type!(f32);
-atom_from_number_type!(f64);
-atom_from_reference_number_type!(&i8);
-atom_from_reference_number_type!(&i16);
-atom_from_reference_number_type!(&i32);
-atom_from_reference_number_type!(&u8);
-atom_from_reference_number_type!(&u16);
-atom_from_reference_number_type!(&u32);
-atom_from_reference_number_type!(&f32);
-atom_from_reference_number_type!(&f64);
+    /// Checks if the handle refers to an open patch.
+    ///
+    /// This is only `false` after a failed [`reload`](Patch::reload).
+    #[must_use]
+    pub const fn is_open(&self) -> bool {
+        self.ptr != 0
+    }
 
-impl From<String> for Atom {
-    fn from(s: String) -> Self {
-        Self::Symbol(s)
+    /// Drops the handle without closing the patch.
+    ///
+    /// The patch stays open until [`reset`](crate::reset) or [`terminate`](crate::terminate) is called.
+    /// Use [`into_raw`](Patch::into_raw) instead to keep a way to close it.
+    pub fn leak(self) {
+        let _ = self.into_raw();
+    }
+
+    /// The directory which the patch is opened from.
+    ///
+    /// This is the directory which [`open_patch`](crate::open_patch) resolves and passes to pd.
+    /// Pd resolves relative paths used inside the patch, e.g. for `[soundfiler]` or `[text define]`,
+    /// against it before trying the search paths.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use libpd_rs::open_patch;
+    ///
+    /// libpd_rs::init().unwrap();
+    ///
+    /// let patch = open_patch("tests/patches/sine.pd").unwrap();
+    /// println!("Opened from {}", patch.directory().display());
+    /// ```
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// The file name of the patch, e.g. `"sine.pd"`.
+    #[must_use]
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// The resolved path of the patch file which is the [`directory`](Patch::directory) joined with the [`file_name`](Patch::file_name).
+    #[must_use]
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(&self.file_name)
+    }
+
+    /// The `$0` of the patch.
+    ///
+    /// `$0` is a unique number which pd assigns to every opened patch.
+    /// It is used to address receivers which are local to a patch, like `[r $0-volume]`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use libpd_rs::{open_patch, send::send_float_to};
+    ///
+    /// libpd_rs::init().unwrap();
+    ///
+    /// let patch = open_patch("tests/patches/sine.pd").unwrap();
+    /// send_float_to(&format!("{}-volume", patch.dollar_zero()), 0.5).unwrap();
+    /// ```
+    #[must_use]
+    pub const fn dollar_zero(&self) -> i32 {
+        self.dollar_zero
+    }
+
+    /// The creation arguments which the patch is opened with by [`open_patch_with_args`](crate::open_patch_with_args).
+    ///
+    /// Empty for patches which are opened without arguments.
+    #[must_use]
+    pub fn arguments(&self) -> &[Atom] {
+        &self.arguments
+    }
+
+    /// The names which the patch receives from, e.g. `[r volume]`, `[r~ input]`,
+    /// the receive names of gui objects and number boxes.
+    ///
+    /// The names are found by reading the patch file, including its subpatches but not the abstractions in it.
+    /// `$0` and the creation arguments (`$1`, `$2`, …) in the names are resolved, so they could be passed
+    /// directly to the functions in the [`send`](crate::send) module.
+    /// Objects which are created after the patch is opened, e.g. with a [`CanvasEditor`](crate::dynamic::CanvasEditor),
+    /// and names which are set with messages like `[set $1(` to `[send]` objects are not known.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use libpd_rs::{open_patch, send::send_float_to};
+    ///
+    /// libpd_rs::init().unwrap();
+    ///
+    /// let patch = open_patch("tests/patches/voice.pd").unwrap();
+    /// for receiver in patch.receivers().unwrap() {
+    ///     // Make a slider for every receiver..
+    ///     send_float_to(&receiver, 0.0).unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+    pub fn receivers(&self) -> Result<Vec<String>, PatchLifeCycleError> {
+        Ok(self.endpoints()?.receivers)
+    }
+
+    /// The names which the patch sends to, e.g. `[s note]`, `[s~ bus]`,
+    /// the send names of gui objects and number boxes and the receivers in message boxes like `[; reverb 1(`.
+    ///
+    /// The limitations of [`receivers`](Patch::receivers) apply.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+    pub fn senders(&self) -> Result<Vec<String>, PatchLifeCycleError> {
+        Ok(self.endpoints()?.senders)
+    }
+
+    /// The arrays which the patch defines, in graphs, with `[table]` or with `[array define]`.
+    ///
+    /// The limitations of [`receivers`](Patch::receivers) apply.
+    /// The sizes are the ones in the file, use [`array_size`](crate::array::array_size) for the current size of an array.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+    pub fn arrays(&self) -> Result<Vec<ArrayInfo>, PatchLifeCycleError> {
+        Ok(self.endpoints()?.arrays)
+    }
+
+    fn endpoints(&self) -> Result<Endpoints, PatchLifeCycleError> {
+        let path = self.path();
+        let contents =
+            std::fs::read_to_string(&path).map_err(|source| PatchLifeCycleError::NotReadable {
+                path: path.to_string_lossy().to_string(),
+                source,
+            })?;
+        Ok(Endpoints::find(
+            &contents,
+            self.dollar_zero,
+            &self.arguments,
+        ))
+    }
+
+    /// The string which is put between the `$0` and the name of a local receiver, `"-"` by default.
+    #[must_use]
+    pub fn local_separator(&self) -> &str {
+        &self.local_separator
+    }
+
+    /// Sets the string which is put between the `$0` and the name of a local receiver.
+    pub fn set_local_separator<S: Into<String>>(&mut self, separator: S) {
+        self.local_separator = separator.into();
+    }
+
+    /// Expands the name of a receiver which is local to the patch, e.g. `"freq"` to `"1003-freq"`.
+    ///
+    /// Local receivers are named with the `$0` of the patch, like `[r $0-freq]`.
+    /// The name is prefixed with the [`dollar_zero`](Patch::dollar_zero) of the patch and the [`local_separator`](Patch::local_separator).
+    /// The `send_*_to_local` functions send to the expanded name and their errors contain it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use libpd_rs::types::PatchSet;
+    ///
+    /// libpd_rs::init().unwrap();
+    ///
+    /// let voices = PatchSet::open_copies("tests/patches/voice.pd", 3).unwrap();
+    /// for (voice, frequency) in voices.iter().zip([220.0, 330.0, 440.0]) {
+    ///     voice.send_float_to_local("freq", frequency).unwrap();
+    /// }
+    /// ```
+    #[must_use]
+    pub fn local_name<T: AsRef<str>>(&self, name: T) -> String {
+        format!(
+            "{}{}{}",
+            self.dollar_zero,
+            self.local_separator,
+            name.as_ref()
+        )
+    }
+
+    /// Makes a [`Destination`] which sends to a receiver which is local to the patch.
+    ///
+    /// The name is expanded once with [`local_name`](Patch::local_name),
+    /// so sending through the destination doesn't format or allocate the name again.
+    /// Sending fails with [`PatchClosed`](crate::error::SendError::PatchClosed)
+    /// after the patch is closed, dropped or reloaded, since a reloaded patch gets a new `$0`.
+    /// Patches which are made with [`from_raw`](Patch::from_raw) are not known to be closed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use libpd_rs::open_patch;
+    ///
+    /// libpd_rs::init().unwrap();
+    ///
+    /// let patch = open_patch("tests/patches/voice.pd").unwrap();
+    /// let frequency = patch.destination("freq");
+    /// for note in [220.0, 330.0, 440.0] {
+    ///     frequency.send_float(note).unwrap();
+    /// }
+    /// ```
+    ///
+    /// A name which contains a null byte makes a destination which fails to send with
+    /// [`InvalidString`](crate::error::SendError::InvalidString).
+    #[must_use]
+    pub fn destination<T: AsRef<str>>(&self, name: T) -> Destination {
+        let name = self.local_name(name);
+        Destination {
+            name: CString::new(name.as_str()).map_err(|_| name),
+            open: self.open.clone(),
+        }
+    }
+
+    /// Sends a `bang` to a receiver which is local to the patch.
+    ///
+    /// See [`send_bang_to`](crate::send::send_bang_to).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_bang_to_local<T: AsRef<str>>(&self, receiver: T) -> Result<(), SendError> {
+        crate::send::send_bang_to(self.local_name(receiver))
+    }
+
+    /// Sends an `f32` to a receiver which is local to the patch.
+    ///
+    /// See [`send_float_to`](crate::send::send_float_to).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_float_to_local<T: AsRef<str>>(
+        &self,
+        receiver: T,
+        value: f32,
+    ) -> Result<(), SendError> {
+        crate::send::send_float_to(self.local_name(receiver), value)
+    }
+
+    /// Sends an `f64` to a receiver which is local to the patch.
+    ///
+    /// See [`send_double_to`](crate::send::send_double_to).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_double_to_local<T: AsRef<str>>(
+        &self,
+        receiver: T,
+        value: f64,
+    ) -> Result<(), SendError> {
+        crate::send::send_double_to(self.local_name(receiver), value)
+    }
+
+    /// Sends a symbol to a receiver which is local to the patch.
+    ///
+    /// See [`send_symbol_to`](crate::send::send_symbol_to).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_symbol_to_local<T: AsRef<str>, S: AsRef<str>>(
+        &self,
+        receiver: T,
+        value: S,
+    ) -> Result<(), SendError> {
+        crate::send::send_symbol_to(self.local_name(receiver), value)
+    }
+
+    /// Sends a list to a receiver which is local to the patch.
+    ///
+    /// See [`send_list_to`](crate::send::send_list_to).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`MessageTooLong`](crate::error::SendError::MessageTooLong)
+    /// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_list_to_local<T: AsRef<str>>(
+        &self,
+        receiver: T,
+        list: &[Atom],
+    ) -> Result<(), SendError> {
+        crate::send::send_list_to(self.local_name(receiver), list)
+    }
+
+    /// Sends a typed message to a receiver which is local to the patch.
+    ///
+    /// See [`send_message_to`](crate::send::send_message_to).
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`MessageTooLong`](crate::error::SendError::MessageTooLong)
+    /// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_message_to_local<T: AsRef<str>, M: AsRef<str>>(
+        &self,
+        receiver: T,
+        message: M,
+        list: &[Atom],
+    ) -> Result<(), SendError> {
+        let receiver = self.local_name(receiver);
+        crate::send::send_message_to(receiver.as_str(), message.as_ref(), list)
     }
 }
-impl From<&String> for Atom {
-    fn from(s: &String) -> Self {
-        Self::Symbol(s.clone())
+
+impl Drop for Patch {
+    fn drop(&mut self) {
+        // Once the queues are released pd should not be called into.
+        if self.ptr != 0 && crate::is_initialized() {
+            let patch = Self::from_raw(self.as_mut_ptr());
+            self.ptr = 0;
+            // Closing fails when the patch is already closed, which is fine here.
+            let _ = crate::close_patch(patch);
+        }
     }
 }
-impl From<&str> for Atom {
-    fn from(s: &str) -> Self {
-        Self::Symbol(s.to_owned())
+
+impl From<*mut std::ffi::c_void> for Patch {
+    fn from(ptr: *mut std::ffi::c_void) -> Self {
+        Self::from_raw(ptr)
     }
 }
-impl From<char> for Atom {
-    fn from(c: char) -> Self {
-        Self::Symbol(c.to_string())
+
+// Needed in this case.
+#[allow(clippy::from_over_into)]
+impl Into<*mut std::ffi::c_void> for Patch {
+    fn into(self) -> *mut std::ffi::c_void {
+        self.into_raw()
     }
 }
-impl From<&char> for Atom {
-    fn from(c: &char) -> Self {
-        Self::Symbol(c.to_string())
+
+/// A receiver which is local to a patch, made with [`Patch::destination`].
+///
+/// It keeps the expanded name of the receiver, e.g. `"1003-freq"`, ready to be passed to pd.
+/// It is cheap to clone and could be kept next to the patch, e.g. in the struct of a synth voice.
+#[derive(Debug, Clone)]
+pub struct Destination {
+    /// The name is kept back if it contains a null byte, sending to it fails then.
+    name: Result<CString, String>,
+    open: Arc<AtomicBool>,
+}
+
+impl Destination {
+    /// The expanded name of the receiver.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match &self.name {
+            // The name is made from a string.
+            Ok(name) => name.to_str().unwrap_or_default(),
+            Err(name) => name,
+        }
+    }
+
+    /// Checks if the patch which the destination belongs to is still open.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Acquire)
+    }
+
+    /// Sends a `bang` to the receiver.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`PatchClosed`](crate::error::SendError::PatchClosed)
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_bang(&self) -> Result<(), SendError> {
+        let name = self.target()?;
+        let code = unsafe { libpd_sys::libpd_bang(name.as_ptr()) };
+        self.result(code)
+    }
+
+    /// Sends an `f32` to the receiver.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`PatchClosed`](crate::error::SendError::PatchClosed)
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_float(&self, value: f32) -> Result<(), SendError> {
+        let name = self.target()?;
+        let code = unsafe { libpd_sys::libpd_float(name.as_ptr(), value) };
+        self.result(code)
+    }
+
+    /// Sends an `f64` to the receiver.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`PatchClosed`](crate::error::SendError::PatchClosed)
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_double(&self, value: f64) -> Result<(), SendError> {
+        let name = self.target()?;
+        let code = unsafe { libpd_sys::libpd_double(name.as_ptr(), value) };
+        self.result(code)
+    }
+
+    /// Sends a list to the receiver.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`PatchClosed`](crate::error::SendError::PatchClosed)
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`MessageTooLong`](crate::error::SendError::MessageTooLong)
+    /// - [`PointerAtom`](crate::error::SendError::PointerAtom)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn send_list(&self, list: &[Atom]) -> Result<(), SendError> {
+        let name = self.target()?;
+        let length = message_length(list)?;
+        let code = with_t_atoms(list, |atom_list| unsafe {
+            libpd_sys::libpd_list(name.as_ptr(), length, atom_list.as_mut_ptr())
+        })
+        .map_err(invalid_atom)?;
+        self.result(code)
+    }
+
+    /// Checks if the destination could be sent to and returns its name to pass to pd.
+    fn target(&self) -> Result<&CStr, SendError> {
+        return_if_not_initialized!(SendError::NotInitialized);
+        if !self.is_open() {
+            return Err(SendError::PatchClosed(self.name().to_owned()));
+        }
+        self.name
+            .as_deref()
+            .map_err(|name| SendError::InvalidString {
+                context: "receiver name",
+                input: name.clone(),
+            })
+    }
+
+    fn result(&self, code: i32) -> Result<(), SendError> {
+        match code {
+            0 => Ok(()),
+            code => Err(SendError::from_receiver_code(code, self.name())),
+        }
     }
 }
 
-impl core::fmt::Display for Atom {
+/// A snapshot of the state of pd which is retrieved with [`status`](crate::status), e.g. to include it in bug reports.
+///
+/// It is printed on a few lines with [`Display`](core::fmt::Display).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdStatus {
+    /// The version of pd which libpd is built with, see [`pd_version`](crate::info::pd_version).
+    pub version: (i32, i32, i32),
+    /// Whether pd is initialized with [`init`](crate::init), see [`is_initialized`](crate::is_initialized).
+    pub initialized: bool,
+    /// Whether pd computes audio, it is turned on with [`dsp_on`](crate::convenience::dsp_on).
+    pub dsp_on: bool,
+    /// The audio configuration, `None` if audio is not initialized yet.
+    pub audio_config: Option<AudioConfig>,
+    /// The number of frames per pd tick, see [`block_size`](crate::block_size).
+    pub block_size: i32,
+    /// The patches which are opened with this crate, see [`open_patches`](crate::open_patches).
+    pub open_patches: Vec<PatchInfo>,
+    /// The sources which are listened from, see [`active_subscriptions`](crate::receive::active_subscriptions).
+    pub subscriptions: Vec<String>,
+}
+
+impl core::fmt::Display for PdStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
-        match self {
-            Self::Float(float) => write!(f, "{float}"),
-            Self::Symbol(s) => write!(f, "{s}"),
+        let (major, minor, bugfix) = self.version;
+        let initialized = if self.initialized {
+            "initialized"
+        } else {
+            "not initialized"
+        };
+        let dsp = if self.dsp_on { "on" } else { "off" };
+        writeln!(f, "pd {major}.{minor}.{bugfix}, {initialized}, dsp {dsp}")?;
+        match self.audio_config {
+            Some(config) => writeln!(
+                f,
+                "audio: {} inputs, {} outputs, {} Hz, block size {}",
+                config.input_channels, config.output_channels, config.sample_rate, self.block_size
+            )?,
+            None => writeln!(f, "audio: not initialized, block size {}", self.block_size)?,
+        }
+        write!(f, "patches:")?;
+        if self.open_patches.is_empty() {
+            write!(f, " none")?;
+        }
+        for patch in &self.open_patches {
+            write!(f, "\n  {} ($0 {})", patch.path.display(), patch.dollar_zero)?;
+        }
+        if self.subscriptions.is_empty() {
+            write!(f, "\nsubscriptions: none")
+        } else {
+            write!(f, "\nsubscriptions: {}", self.subscriptions.join(", "))
         }
     }
 }
 
-/// The handle which is returned from opening a patch.
+/// A patch which is opened with this crate and not closed yet, retrieved with [`open_patches`](crate::open_patches).
 ///
-/// This is a [`c_void`](std::ffi::c_void) in the underlying sys crate but for convenience it is converted to `usize` and held here.
+/// It describes the patch without owning it, the [`Patch`] which is returned when the patch is opened still closes it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PatchInfo {
+    /// The resolved path of the patch file, see [`Patch::path`].
+    pub path: PathBuf,
+    /// The `$0` of the patch.
+    pub dollar_zero: i32,
+    handle: usize,
+    /// Tells the patch apart from a patch which is opened later at the same address.
+    registration: usize,
+}
+
+impl PatchInfo {
+    fn new(patch: &Patch) -> Self {
+        Self {
+            path: patch.path(),
+            dollar_zero: patch.dollar_zero(),
+            handle: patch.as_mut_ptr() as usize,
+            registration: crate::registry::next_registration(),
+        }
+    }
+
+    pub(crate) const fn registration(&self) -> usize {
+        self.registration
+    }
+
+    /// The raw handle of the patch, which is the same as the one of the [`Patch`] it describes.
+    #[must_use]
+    pub const fn handle(&self) -> *mut std::ffi::c_void {
+        self.handle as *mut std::ffi::c_void
+    }
+}
+
+/// Copies of the same patch, e.g. the voices of a polyphonic instrument.
 ///
-/// This handle should be kept alive for the lifetime of the patch.
-#[derive(Debug)]
-pub struct PatchFileHandle(usize);
-impl PatchFileHandle {
-    pub(crate) const fn as_mut_ptr(&self) -> *mut std::ffi::c_void {
-        self.0 as *mut std::ffi::c_void
+/// Every copy has its own `$0`, use the functions which send to local receivers of [`Patch`] to address a single copy.
+/// The copies are closed when the set is dropped.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::types::PatchSet;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let voices = PatchSet::open_copies("tests/patches/voice.pd", 8).unwrap();
+/// assert_eq!(voices.len(), 8);
+/// voices[0].send_float_to_local("freq", 440.0).unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct PatchSet {
+    patches: Vec<Patch>,
+}
+
+impl PatchSet {
+    /// Opens the patch `count` times with [`open_patch`](crate::open_patch).
+    ///
+    /// The copies which are already opened are closed if opening one of them fails.
+    ///
+    /// # Errors
+    ///
+    /// A list of errors that can occur:
+    /// - [`FailedToOpenPatch`](crate::error::PatchLifeCycleError::FailedToOpenPatch)
+    /// - [`PathDoesNotExist`](crate::error::PatchLifeCycleError::PathDoesNotExist)
+    /// - [`InvalidPath`](crate::error::PatchLifeCycleError::InvalidPath)
+    /// - [`NotReadable`](crate::error::PatchLifeCycleError::NotReadable)
+    /// - [`EvaluationFailed`](crate::error::PatchLifeCycleError::EvaluationFailed)
+    /// - [`UnexpectedExtension`](crate::error::PatchLifeCycleError::UnexpectedExtension)
+    /// - [`NotInitialized`](crate::error::PatchLifeCycleError::NotInitialized)
+    pub fn open_copies<T: AsRef<Path>>(
+        path_to_patch: T,
+        count: usize,
+    ) -> Result<Self, PatchLifeCycleError> {
+        let patches = std::iter::repeat_with(|| crate::open_patch(path_to_patch.as_ref()))
+            .take(count)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patches })
+    }
+
+    /// Sets the local separator of every patch in the set, see [`Patch::set_local_separator`].
+    pub fn set_local_separator<S: AsRef<str>>(&mut self, separator: S) {
+        for patch in &mut self.patches {
+            patch.set_local_separator(separator.as_ref());
+        }
+    }
+
+    /// Takes the patches out of the set.
+    #[must_use]
+    pub fn into_patches(self) -> Vec<Patch> {
+        self.patches
     }
 }
 
-impl From<*mut std::ffi::c_void> for PatchFileHandle {
-    fn from(ptr: *mut std::ffi::c_void) -> Self {
-        Self(ptr as usize)
+impl From<Vec<Patch>> for PatchSet {
+    fn from(patches: Vec<Patch>) -> Self {
+        Self { patches }
     }
 }
 
-// Needed in this case.
-#[allow(clippy::from_over_into)]
-impl Into<*mut std::ffi::c_void> for PatchFileHandle {
-    fn into(self) -> *mut std::ffi::c_void {
-        self.0 as *mut std::ffi::c_void
+impl std::ops::Deref for PatchSet {
+    type Target = [Patch];
+
+    fn deref(&self) -> &Self::Target {
+        &self.patches
+    }
+}
+
+impl std::ops::DerefMut for PatchSet {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.patches
+    }
+}
+
+impl IntoIterator for PatchSet {
+    type Item = Patch;
+    type IntoIter = std::vec::IntoIter<Patch>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.patches.into_iter()
     }
 }
 
+impl<'a> IntoIterator for &'a PatchSet {
+    type Item = &'a Patch;
+    type IntoIter = std::slice::Iter<'a, Patch>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.patches.iter()
+    }
+}
+
+/// An array which is defined in a patch, returned from [`Patch::arrays`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrayInfo {
+    /// The name of the array with the dollar signs resolved.
+    pub name: String,
+    /// The size of the array which is written in the patch file.
+    pub size: usize,
+}
+
 /// The handle which is returned from subscribing to a sender.
 ///
 /// This is a [`c_void`](std::ffi::c_void) in the underlying sys crate but for convenience it is converted to `usize` and held here.
@@ -118,6 +2205,12 @@ impl Into<*mut std::ffi::c_void> for PatchFileHandle {
 #[derive(Debug)]
 pub struct ReceiverHandle(usize);
 
+impl ReceiverHandle {
+    pub(crate) const fn address(&self) -> usize {
+        self.0
+    }
+}
+
 impl From<*mut std::ffi::c_void> for ReceiverHandle {
     fn from(ptr: *mut std::ffi::c_void) -> Self {
         Self(ptr as usize)
@@ -132,6 +2225,269 @@ impl Into<*mut std::ffi::c_void> for ReceiverHandle {
     }
 }
 
+/// The audio configuration which pd is initialized with.
+///
+/// It is recorded by [`initialize_audio`](crate::initialize_audio) and [`reinitialize_audio`](crate::reinitialize_audio)
+/// and can be retrieved with [`audio_config`](crate::audio_config).
+///
+/// Named presets cover the common cases and could be passed to [`initialize_audio_with_config`](crate::initialize_audio_with_config).
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::types::AudioConfig;
+///
+/// assert_eq!(AudioConfig::default(), AudioConfig::stereo_44k());
+///
+/// let config = AudioConfig::stereo_output_48k().with_sample_rate(96000);
+/// assert_eq!(config, AudioConfig::new(0, 2, 96000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioConfig {
+    /// Number of input channels.
+    pub input_channels: i32,
+    /// Number of output channels.
+    pub output_channels: i32,
+    /// Sample rate in Hz.
+    pub sample_rate: i32,
+}
+
+impl AudioConfig {
+    /// Creates a configuration from raw channel counts and a sample rate.
+    ///
+    /// The values are validated when the configuration is passed to
+    /// [`initialize_audio_with_config`](crate::initialize_audio_with_config).
+    #[must_use]
+    pub const fn new(input_channels: i32, output_channels: i32, sample_rate: i32) -> Self {
+        Self {
+            input_channels,
+            output_channels,
+            sample_rate,
+        }
+    }
+
+    /// 2 input channels, 2 output channels and a sample rate of 44100 Hz.
+    #[must_use]
+    pub const fn stereo_44k() -> Self {
+        Self::new(2, 2, 44100)
+    }
+
+    /// 2 input channels, 2 output channels and a sample rate of 48000 Hz.
+    #[must_use]
+    pub const fn stereo_48k() -> Self {
+        Self::new(2, 2, 48000)
+    }
+
+    /// 1 input channel, 1 output channel and a sample rate of 44100 Hz.
+    #[must_use]
+    pub const fn mono_44k() -> Self {
+        Self::new(1, 1, 44100)
+    }
+
+    /// 1 input channel, 1 output channel and a sample rate of 48000 Hz.
+    #[must_use]
+    pub const fn mono_48k() -> Self {
+        Self::new(1, 1, 48000)
+    }
+
+    /// No input channels, 2 output channels and a sample rate of 44100 Hz.
+    ///
+    /// Useful for patches which only generate sound.
+    #[must_use]
+    pub const fn stereo_output_44k() -> Self {
+        Self::new(0, 2, 44100)
+    }
+
+    /// No input channels, 2 output channels and a sample rate of 48000 Hz.
+    ///
+    /// Useful for patches which only generate sound.
+    #[must_use]
+    pub const fn stereo_output_48k() -> Self {
+        Self::new(0, 2, 48000)
+    }
+
+    /// Returns the same configuration with a different sample rate.
+    #[must_use]
+    pub const fn with_sample_rate(self, sample_rate: i32) -> Self {
+        Self {
+            sample_rate,
+            ..self
+        }
+    }
+
+    /// Returns the same configuration with different channel counts.
+    #[must_use]
+    pub const fn with_channels(self, input_channels: i32, output_channels: i32) -> Self {
+        Self {
+            input_channels,
+            output_channels,
+            ..self
+        }
+    }
+}
+
+impl Default for AudioConfig {
+    /// Same as [`AudioConfig::stereo_44k`].
+    fn default() -> Self {
+        Self::stereo_44k()
+    }
+}
+
+/// Options for [`init_with_options`](crate::init_with_options).
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::{receive::MESSAGE_QUEUE_SIZE_IN_BYTES, types::InitOptions};
+///
+/// let options = InitOptions::default().with_midi_queue_bytes(4096);
+/// assert_eq!(options.message_queue_bytes, MESSAGE_QUEUE_SIZE_IN_BYTES);
+/// assert_eq!(options.midi_queue_bytes, 4096);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InitOptions {
+    /// The capacity of the queue which holds messages from pd until they are received, in bytes.
+    pub message_queue_bytes: usize,
+    /// The capacity of the queue which holds MIDI messages from pd until they are received, in bytes.
+    pub midi_queue_bytes: usize,
+}
+
+impl InitOptions {
+    /// Returns the same options with a different capacity of the message queue.
+    #[must_use]
+    pub const fn with_message_queue_bytes(self, message_queue_bytes: usize) -> Self {
+        Self {
+            message_queue_bytes,
+            ..self
+        }
+    }
+
+    /// Returns the same options with a different capacity of the MIDI queue.
+    #[must_use]
+    pub const fn with_midi_queue_bytes(self, midi_queue_bytes: usize) -> Self {
+        Self {
+            midi_queue_bytes,
+            ..self
+        }
+    }
+}
+
+impl Default for InitOptions {
+    /// Uses [`MESSAGE_QUEUE_SIZE_IN_BYTES`](crate::receive::MESSAGE_QUEUE_SIZE_IN_BYTES)
+    /// and [`MIDI_QUEUE_SIZE_IN_BYTES`](crate::receive::MIDI_QUEUE_SIZE_IN_BYTES).
+    fn default() -> Self {
+        Self {
+            message_queue_bytes: crate::receive::MESSAGE_QUEUE_SIZE_IN_BYTES,
+            midi_queue_bytes: crate::receive::MIDI_QUEUE_SIZE_IN_BYTES,
+        }
+    }
+}
+
+/// A panic which is caught in a closure registered with one of the `on_*` functions in the [`receive`](crate::receive) module.
+///
+/// Retrieved with [`take_last_callback_panic`](crate::receive::take_last_callback_panic).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallbackPanic {
+    /// Name of the function which registered the closure, e.g. `"on_float"`, or `"subscribe_with"` for a [`MessageSink`](crate::sink::MessageSink).
+    pub callback: &'static str,
+    /// The panic message or `"Box<dyn Any>"` if the panic payload is not a string.
+    pub message: String,
+}
+
+/// An error which pd writes to its console, received with [`on_pd_error`](crate::receive::on_pd_error).
+///
+/// Pd prefixes these lines with `error:` or with the log level, e.g. `verbose(4):`, the prefix is removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdConsoleError {
+    /// The log level of the line, `0` for fatal errors, `1` for errors and `4` for the verbose notes pd writes along with them.
+    pub level: i32,
+    /// The message of the error, e.g. `"couldn't create"`.
+    pub message: String,
+    /// The object which the error is about if pd describes it, e.g. `"foo 1 2"` for an object which couldn't be created.
+    ///
+    /// Pd writes the description to the console as a regular line before the error,
+    /// so the line still reaches the closure registered with [`on_print`](crate::receive::on_print).
+    pub object: Option<String>,
+}
+
+impl PdConsoleError {
+    /// Parses a line of the pd console, returns `None` if it is not an error.
+    ///
+    /// `previous_line` is the regular line before it which describes the object when the message starts with `...`.
+    pub(crate) fn from_line(line: &str, previous_line: &str) -> Option<Self> {
+        let (level, message) = if let Some(message) = line.strip_prefix("error: ") {
+            (1, message)
+        } else {
+            let (level, message) = line.strip_prefix("verbose(")?.split_once("): ")?;
+            let level = level
+                .parse::<i32>()
+                .ok()
+                .filter(|level| matches!(level, 0 | 1 | 4))?;
+            (level, message)
+        };
+        let (message, object) = match message.strip_prefix("... ") {
+            Some(message) if level != 4 && !previous_line.trim().is_empty() => {
+                (message, Some(previous_line.trim().to_owned()))
+            }
+            _ => (message, None),
+        };
+        Some(Self {
+            level,
+            message: message.to_owned(),
+            object,
+        })
+    }
+}
+
+/// The kind of a line of the pd console, received with [`on_print_with_kind`](crate::receive::on_print_with_kind).
+///
+/// Libpd writes errors and regular prints through the same hook, the kind is told from the prefix pd gives the line.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrintKind {
+    /// A regular line, like the version banner or the output of a `|print|` object.
+    Post,
+    /// A fatal error, the line starts with `verbose(0):`.
+    Fatal,
+    /// An error, the line starts with `error:` or `verbose(1):`.
+    Error,
+    /// A note with a higher log level, the line starts with `verbose(2):` to `verbose(4):`.
+    Verbose,
+}
+
+impl PrintKind {
+    /// Tells the kind of a line of the pd console from its prefix.
+    ///
+    /// # Example
+    /// ```rust
+    /// use libpd_rs::types::PrintKind;
+    ///
+    /// assert_eq!(PrintKind::of("error: foo: no method for 'bar'"), PrintKind::Error);
+    /// assert_eq!(PrintKind::of("verbose(4): ... you might be able to track this down"), PrintKind::Verbose);
+    /// assert_eq!(PrintKind::of("print: 1 2 3"), PrintKind::Post);
+    /// ```
+    #[must_use]
+    pub fn of(line: &str) -> Self {
+        if line.starts_with("error: ") {
+            return Self::Error;
+        }
+        let level = line
+            .strip_prefix("verbose(")
+            .and_then(|rest| rest.split_once("): "))
+            .and_then(|(level, _)| level.parse::<i32>().ok());
+        match level {
+            Some(0) => Self::Fatal,
+            Some(1) => Self::Error,
+            Some(2..=4) => Self::Verbose,
+            _ => Self::Post,
+        }
+    }
+
+    /// Checks if the line is a fatal error or an error.
+    #[must_use]
+    pub const fn is_error(self) -> bool {
+        matches!(self, Self::Fatal | Self::Error)
+    }
+}
+
 // pub const t_atomtype_A_SEMI: t_atomtype = 4;
 // pub const t_atomtype_A_COMMA: t_atomtype = 5;
 // pub const t_atomtype_A_DOLLAR: t_atomtype = 8;
@@ -188,3 +2544,19 @@ impl Into<*mut std::ffi::c_void> for ReceiverHandle {
 //     pub w_binbuf: *mut _binbuf,
 //     pub w_index: ::std::os::raw::c_int,
 // }
+
+/// How [`all_notes_off_with`](crate::send::all_notes_off_with) clears the notes of a MIDI channel.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotesOff {
+    /// The all notes off message, a control change of the controller `123` with the value `0`.
+    ///
+    /// It arrives at `|ctlin|` objects, so it only clears the notes of patches which handle it.
+    Controller,
+    /// A note off, a note on with velocity `0`, for every pitch `0-127`.
+    ///
+    /// These arrive at `|notein|` objects, so they clear the notes of patches which don't handle the controller.
+    NoteOffs,
+    /// The all notes off message followed by the note offs.
+    Both,
+}
@@ -0,0 +1,209 @@
+use crate::{error::SendError, send::send_list_to, types::Atom};
+
+/// Allocates voices to notes like the `[poly]` object of pd and sends them to a receiver in the format which `[poly]` outputs.
+///
+/// Every note on and note off is sent as a list of three floats, `voice note velocity`,
+/// where voices are numbered from `1` to the voice count like the outlets of `[poly]`.
+/// A note off is sent with a velocity of `0` to the voice which plays the note.
+/// The patch could then route the lists to its voices, e.g. with `[r voices]` into `[route 1 2 3 4]`.
+///
+/// A note on takes the voice which is released for the longest time.
+/// When every voice plays, the voice which plays for the longest time is stolen if stealing is enabled,
+/// a note off is sent for the note which it plays before the new note is sent to it.
+/// Otherwise the note is dropped and nothing is sent for it or its note off.
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::voice::VoiceAllocator;
+///
+/// libpd_rs::init().unwrap();
+/// let _receiver = libpd_rs::receive::start_listening_from("voices").unwrap();
+///
+/// let mut voices = VoiceAllocator::new("voices", 2, true);
+/// // Sends `1 60 100` and `2 64 100`.
+/// assert_eq!(voices.note_on(60, 100).unwrap(), Some(1));
+/// assert_eq!(voices.note_on(64, 100).unwrap(), Some(2));
+/// // Sends `1 60 0` to release the stolen note and `1 67 100`.
+/// assert_eq!(voices.note_on(67, 100).unwrap(), Some(1));
+/// assert_eq!(voices.stolen_count(), 1);
+/// // Sends `2 64 0`.
+/// assert_eq!(voices.note_off(64).unwrap(), Some(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct VoiceAllocator {
+    receiver: String,
+    voices: Vec<Voice>,
+    steal: bool,
+    /// Counts the note ons and note offs to order the voices by when they change last.
+    serial: u64,
+    stolen: usize,
+}
+
+/// A voice and the note which it plays.
+#[derive(Debug, Clone, Copy, Default)]
+struct Voice {
+    note: Option<u8>,
+    /// The serial of the last note on or note off of the voice.
+    changed_at: u64,
+}
+
+impl VoiceAllocator {
+    /// Creates an allocator of `voice_count` voices which sends the notes to `receiver`.
+    ///
+    /// The voice count is clipped to at least `1` like `[poly]` does.
+    pub fn new<T: AsRef<str>>(receiver: T, voice_count: usize, steal: bool) -> Self {
+        Self {
+            receiver: receiver.as_ref().to_owned(),
+            voices: vec![Voice::default(); voice_count.max(1)],
+            steal,
+            serial: 0,
+            stolen: 0,
+        }
+    }
+
+    /// The number of voices.
+    #[must_use]
+    pub const fn voice_count(&self) -> usize {
+        self.voices.len()
+    }
+
+    /// The number of voices which play a note.
+    #[must_use]
+    pub fn active_voices(&self) -> usize {
+        self.voices
+            .iter()
+            .filter(|voice| voice.note.is_some())
+            .count()
+    }
+
+    /// The number of notes which are stolen from their voices since the allocator is created.
+    #[must_use]
+    pub const fn stolen_count(&self) -> usize {
+        self.stolen
+    }
+
+    /// Allocates a voice to a note and sends the note to it, returns the number of the voice.
+    ///
+    /// A velocity of `0` is a note off like in MIDI, see [`note_off`](VoiceAllocator::note_off).
+    /// Returns `None` if every voice plays and stealing is disabled, nothing is sent then.
+    ///
+    /// # Errors
+    ///
+    /// Nothing is allocated if sending fails, a voice which is stolen already stays released.
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn note_on(&mut self, note: u8, velocity: u8) -> Result<Option<usize>, SendError> {
+        if velocity == 0 {
+            return self.note_off(note);
+        }
+        let free = self
+            .voices
+            .iter()
+            .enumerate()
+            .filter(|(_, voice)| voice.note.is_none())
+            .min_by_key(|(_, voice)| voice.changed_at)
+            .map(|(index, _)| index);
+        let index = match free {
+            Some(index) => index,
+            None if self.steal => {
+                let Some((index, stolen_note)) = self
+                    .voices
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, voice)| voice.changed_at)
+                    .and_then(|(index, voice)| Some((index, voice.note?)))
+                else {
+                    return Ok(None);
+                };
+                self.send(index, stolen_note, 0)?;
+                self.release(index);
+                self.stolen += 1;
+                index
+            }
+            None => return Ok(None),
+        };
+        self.send(index, note, velocity)?;
+        self.serial += 1;
+        self.voices[index] = Voice {
+            note: Some(note),
+            changed_at: self.serial,
+        };
+        Ok(Some(index + 1))
+    }
+
+    /// Sends a note off to the voice which plays the note and releases it, returns the number of the voice.
+    ///
+    /// Returns `None` if no voice plays the note, e.g. since it is dropped or stolen, nothing is sent then.
+    /// When the note plays in more than one voice the one which plays it for the longest time is released.
+    ///
+    /// # Errors
+    ///
+    /// The voice is not released if sending fails.
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn note_off(&mut self, note: u8) -> Result<Option<usize>, SendError> {
+        let Some(index) = self
+            .voices
+            .iter()
+            .enumerate()
+            .filter(|(_, voice)| voice.note == Some(note))
+            .min_by_key(|(_, voice)| voice.changed_at)
+            .map(|(index, _)| index)
+        else {
+            return Ok(None);
+        };
+        self.send(index, note, 0)?;
+        self.release(index);
+        Ok(Some(index + 1))
+    }
+
+    /// Sends a note off to every voice which plays a note and releases them, like the `stop` message of `[poly]`.
+    ///
+    /// # Errors
+    ///
+    /// The voices which are not released yet stay playing if sending fails.
+    ///
+    /// A list of errors that can occur:
+    /// - [`MissingDestination`](crate::error::SendError::MissingDestination)
+    /// - [`Unexpected`](crate::error::SendError::Unexpected)
+    /// - [`InvalidString`](crate::error::SendError::InvalidString)
+    /// - [`NotInitialized`](crate::error::SendError::NotInitialized)
+    pub fn all_notes_off(&mut self) -> Result<(), SendError> {
+        for index in 0..self.voices.len() {
+            if let Some(note) = self.voices[index].note {
+                self.send(index, note, 0)?;
+                self.release(index);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends `voice note velocity` for the voice at `index`.
+    fn send(&self, index: usize, note: u8, velocity: u8) -> Result<(), SendError> {
+        send_list_to(
+            &self.receiver,
+            &[
+                Atom::from(index + 1),
+                Atom::from(note),
+                Atom::from(velocity),
+            ],
+        )
+    }
+
+    /// Marks the voice at `index` as free.
+    fn release(&mut self, index: usize) {
+        self.serial += 1;
+        self.voices[index] = Voice {
+            note: None,
+            changed_at: self.serial,
+        };
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    error::{PatchLifeCycleError, WatchError},
+    types::Patch,
+};
+
+use notify::{
+    event::{MetadataKind, ModifyKind},
+    EventKind, RecursiveMode, Watcher as _,
+};
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Options for [`watch_patch`].
+///
+/// # Example
+/// ```rust
+/// use libpd_rs::watch::WatchOptions;
+/// use std::time::Duration;
+///
+/// let options = WatchOptions::default().with_debounce(Duration::from_millis(500));
+/// assert_eq!(options.debounce, Duration::from_millis(500));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// The time to wait after the last change of the file before reloading the patch.
+    ///
+    /// Editors usually write a file with more than one file system event.
+    pub debounce: Duration,
+}
+
+impl WatchOptions {
+    /// Returns the same options with a different debounce time.
+    #[must_use]
+    pub const fn with_debounce(self, debounce: Duration) -> Self {
+        Self { debounce }
+    }
+}
+
+impl Default for WatchOptions {
+    /// Debounces for 200 milliseconds.
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_millis(200),
+        }
+    }
+}
+
+enum Signal {
+    Changed,
+    Stop,
+}
+
+/// Keeps watching the patch which is opened with [`watch_patch`].
+///
+/// Dropping it stops watching and closes the patch.
+pub struct WatchHandle {
+    patch: Arc<Mutex<Patch>>,
+    signals: Sender<Signal>,
+    watcher: Option<notify::RecommendedWatcher>,
+    reloader: Option<JoinHandle<()>>,
+}
+
+impl WatchHandle {
+    /// Locks the patch which is watched, e.g. to read its [`dollar_zero`](Patch::dollar_zero).
+    ///
+    /// Reloading waits until the returned guard is dropped.
+    pub fn patch(&self) -> MutexGuard<'_, Patch> {
+        self.patch.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        // Stop the file system events first so the reloader is not woken up again.
+        drop(self.watcher.take());
+        let _ = self.signals.send(Signal::Stop);
+        if let Some(reloader) = self.reloader.take() {
+            let _ = reloader.join();
+        }
+    }
+}
+
+/// Opens a patch and reloads it with [`Patch::reload`] whenever its file changes.
+///
+/// Changes are debounced with [`WatchOptions::debounce`] and the patch is reloaded once for a burst of changes.
+/// `on_reload` is called after every reload with the patch or the error of reloading it,
+/// which is useful to send the initial values to the reloaded patch.
+/// A patch which failed to reload is closed and is opened again with the next change of the file.
+///
+/// The directory of the patch is watched, so editors which save by replacing the file are supported.
+/// Patches are reloaded and `on_reload` is called from a thread which is spawned for the watch.
+///
+/// # Example
+/// ```no_run
+/// use libpd_rs::watch::{watch_patch, WatchOptions};
+/// use libpd_rs::send::send_float_to;
+///
+/// libpd_rs::init().unwrap();
+///
+/// let handle = watch_patch("tests/patches/sine.pd", WatchOptions::default(), |result| {
+///     match result {
+///         Ok(_patch) => {
+///             let _ = send_float_to("frequency", 440.0);
+///         }
+///         Err(err) => eprintln!("{err}"),
+///     }
+/// })
+/// .unwrap();
+///
+/// // Edit the patch file..
+///
+/// // Stops watching and closes the patch.
+/// drop(handle);
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`Patch`](crate::error::WatchError::Patch) which contains the error of [`open_patch`](crate::open_patch)
+/// - [`FailedToWatch`](crate::error::WatchError::FailedToWatch)
+pub fn watch_patch<T, F>(
+    path_to_patch: T,
+    options: WatchOptions,
+    mut on_reload: F,
+) -> Result<WatchHandle, WatchError>
+where
+    T: AsRef<Path>,
+    F: FnMut(Result<&Patch, &PatchLifeCycleError>) + Send + 'static,
+{
+    let patch = crate::open_patch(path_to_patch).map_err(WatchError::Patch)?;
+    let failed_to_watch = {
+        let watched = patch.path().to_string_lossy().into_owned();
+        move |source| WatchError::FailedToWatch {
+            path: watched.clone(),
+            source,
+        }
+    };
+    let directory = patch.directory().to_path_buf();
+    let file_name = OsString::from(patch.file_name());
+    let patch = Arc::new(Mutex::new(patch));
+
+    let (signals, receiver) = mpsc::channel();
+    let change_signals = signals.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        // Reading the file while reloading should not trigger another reload.
+        let is_change = matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) && event.kind
+            != EventKind::Modify(ModifyKind::Metadata(MetadataKind::AccessTime));
+        let is_patch = event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == Some(file_name.as_os_str()));
+        if is_change && is_patch {
+            let _ = change_signals.send(Signal::Changed);
+        }
+    })
+    .map_err(&failed_to_watch)?;
+    watcher
+        .watch(&directory, RecursiveMode::NonRecursive)
+        .map_err(&failed_to_watch)?;
+
+    let watched_patch = Arc::clone(&patch);
+    let reloader = std::thread::spawn(move || {
+        while matches!(receiver.recv(), Ok(Signal::Changed)) {
+            // Wait until the file stops changing.
+            loop {
+                match receiver.recv_timeout(options.debounce) {
+                    Ok(Signal::Changed) => {}
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Ok(Signal::Stop) | Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            let mut patch = watched_patch.lock().unwrap_or_else(PoisonError::into_inner);
+            match patch.reload() {
+                Ok(()) => on_reload(Ok(&patch)),
+                Err(err) => on_reload(Err(&err)),
+            }
+        }
+    });
+
+    Ok(WatchHandle {
+        patch,
+        signals,
+        watcher: Some(watcher),
+        reloader: Some(reloader),
+    })
+}
@@ -0,0 +1,175 @@
+use crate::{
+    error::{ProcessError, WavError},
+    helpers::return_if_not_initialized,
+    process::render_offline_with_input,
+    types::Atom,
+};
+
+use std::path::Path;
+use std::time::Duration;
+
+/// Opens a patch, renders it offline for a duration and writes its output to a WAV file.
+///
+/// The WAV file has the output channels and the sample rate which audio is initialized with
+/// and its samples are 32 bit floats.
+/// The input of the patch is silent, use [`render_to_wav_with_input`] to feed it from another WAV file.
+///
+/// Dsp is turned on, the patch is rendered with [`render_offline`](crate::process::render_offline)
+/// and closed afterwards, so the duration is rounded up to whole blocks.
+/// Returns the number of frames which are written.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use libpd_rs::{init, initialize_audio, wav::render_to_wav};
+///
+/// init().unwrap();
+/// initialize_audio(0, 2, 48000).unwrap();
+///
+/// render_to_wav("tests/patches/sine.pd", Duration::from_secs(5), "sine.wav").unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`Patch`](crate::error::WavError::Patch)
+/// - [`Send`](crate::error::WavError::Send)
+/// - [`Process`](crate::error::WavError::Process)
+/// - [`Wav`](crate::error::WavError::Wav)
+/// - [`NoOutputChannels`](crate::error::WavError::NoOutputChannels)
+/// - [`NotInitialized`](crate::error::WavError::NotInitialized)
+pub fn render_to_wav<P: AsRef<Path>, W: AsRef<Path>>(
+    patch: P,
+    duration: Duration,
+    wav: W,
+) -> Result<usize, WavError> {
+    render(patch.as_ref(), &[], duration, wav.as_ref())
+}
+
+/// Opens a patch, renders it offline with the audio of a WAV file as its input and writes its output to a WAV file.
+///
+/// The input file needs to have the input channels and the sample rate which audio is initialized with,
+/// its samples could be integers or floats. When the input file is shorter than the duration the rest of the input is silent.
+///
+/// See [`render_to_wav`] for the output.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use libpd_rs::{init, initialize_audio, wav::render_to_wav_with_input};
+///
+/// init().unwrap();
+/// initialize_audio(2, 2, 44100).unwrap();
+///
+/// render_to_wav_with_input("reverb.pd", "dry.wav", Duration::from_secs(10), "wet.wav").unwrap();
+/// ```
+///
+/// # Errors
+///
+/// A list of errors that can occur:
+/// - [`Patch`](crate::error::WavError::Patch)
+/// - [`Send`](crate::error::WavError::Send)
+/// - [`Process`](crate::error::WavError::Process)
+/// - [`Wav`](crate::error::WavError::Wav)
+/// - [`InputChannelCount`](crate::error::WavError::InputChannelCount)
+/// - [`InputSampleRate`](crate::error::WavError::InputSampleRate)
+/// - [`NoOutputChannels`](crate::error::WavError::NoOutputChannels)
+/// - [`NotInitialized`](crate::error::WavError::NotInitialized)
+pub fn render_to_wav_with_input<P: AsRef<Path>, I: AsRef<Path>, W: AsRef<Path>>(
+    patch: P,
+    input: I,
+    duration: Duration,
+    wav: W,
+) -> Result<usize, WavError> {
+    let input = read_input(input.as_ref())?;
+    render(patch.as_ref(), &input, duration, wav.as_ref())
+}
+
+fn render(patch: &Path, input: &[f32], duration: Duration, wav: &Path) -> Result<usize, WavError> {
+    return_if_not_initialized!(WavError::NotInitialized);
+    let config =
+        crate::audio_config().ok_or(WavError::Process(ProcessError::AudioNotInitialized))?;
+    let channels = u16::try_from(config.output_channels)
+        .ok()
+        .filter(|channels| *channels > 0)
+        .ok_or(WavError::NoOutputChannels)?;
+    let spec = hound::WavSpec {
+        channels,
+        // The sample rate is validated to be positive when audio is initialized.
+        sample_rate: u32::try_from(config.sample_rate).unwrap_or_default(),
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+
+    let patch = crate::open_patch(patch).map_err(WavError::Patch)?;
+    crate::send::send_message_to("pd", "dsp", &[Atom::from(1)]).map_err(WavError::Send)?;
+    let wav_error = wav_error(wav);
+    let mut writer = hound::WavWriter::create(wav, spec).map_err(wav_error)?;
+
+    let mut remaining_input = input;
+    let mut write_result = Ok(());
+    let frames = render_offline_with_input(
+        duration,
+        |chunk| {
+            let length = chunk.len().min(remaining_input.len());
+            let (now, later) = remaining_input.split_at(length);
+            chunk[..length].copy_from_slice(now);
+            remaining_input = later;
+        },
+        |chunk| {
+            if write_result.is_ok() {
+                write_result = chunk
+                    .iter()
+                    .try_for_each(|sample| writer.write_sample(*sample));
+            }
+        },
+    )
+    .map_err(WavError::Process)?;
+    write_result.map_err(wav_error)?;
+    writer.finalize().map_err(wav_error)?;
+    patch.close().map_err(WavError::Patch)?;
+    Ok(frames)
+}
+
+/// Reads the interleaved samples of the input file as floats.
+fn read_input(path: &Path) -> Result<Vec<f32>, WavError> {
+    let wav_error = wav_error(path);
+    let mut reader = hound::WavReader::open(path).map_err(wav_error)?;
+    let spec = reader.spec();
+    let config =
+        crate::audio_config().ok_or(WavError::Process(ProcessError::AudioNotInitialized))?;
+    if i32::from(spec.channels) != config.input_channels {
+        return Err(WavError::InputChannelCount {
+            expected: config.input_channels,
+            actual: spec.channels,
+        });
+    }
+    if i64::from(spec.sample_rate) != i64::from(config.sample_rate) {
+        return Err(WavError::InputSampleRate {
+            expected: config.sample_rate,
+            actual: spec.sample_rate,
+        });
+    }
+    let samples: Result<Vec<f32>, hound::Error> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect(),
+        hound::SampleFormat::Int => {
+            // Integer samples are scaled to `-1` to `1`.
+            let scale = 2.0_f32.powi(i32::from(spec.bits_per_sample) - 1);
+            // This is fine since precision beyond a float sample is not audible.
+            #[allow(clippy::cast_precision_loss)]
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|sample| sample as f32 / scale))
+                .collect()
+        }
+    };
+    samples.map_err(wav_error)
+}
+
+/// Makes the errors of reading or writing a WAV file at the path.
+fn wav_error(path: &Path) -> impl Fn(hound::Error) -> WavError + Copy + '_ {
+    move |source| WavError::Wav {
+        path: path.to_string_lossy().into_owned(),
+        source,
+    }
+}
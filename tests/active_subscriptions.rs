@@ -0,0 +1,34 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    init,
+    receive::{active_subscriptions, start_listening_from, stop_listening_from},
+    reset,
+};
+
+#[test]
+fn active_subscriptions_are_listed() {
+    init().unwrap();
+    assert!(active_subscriptions().is_empty());
+
+    let zeta = start_listening_from("zeta").unwrap();
+    let alpha = start_listening_from("alpha").unwrap();
+    let alpha_again = start_listening_from("alpha").unwrap();
+    let _mid = start_listening_from("mid").unwrap();
+    assert_eq!(
+        active_subscriptions(),
+        vec!["alpha", "alpha", "mid", "zeta"]
+    );
+
+    stop_listening_from(alpha);
+    stop_listening_from(zeta);
+    assert_eq!(active_subscriptions(), vec!["alpha", "mid"]);
+
+    // Failed subscriptions are not listed.
+    assert!(start_listening_from("").is_err());
+    assert_eq!(active_subscriptions(), vec!["alpha", "mid"]);
+
+    stop_listening_from(alpha_again);
+    reset();
+    assert!(active_subscriptions().is_empty());
+}
@@ -0,0 +1,79 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{on_midi_control_change, on_midi_note_on, receive_midi_messages_from_pd},
+    send::{
+        all_notes_off, all_notes_off_all_channels, all_notes_off_with, ALL_NOTES_OFF_CONTROLLER,
+    },
+    types::NotesOff,
+};
+
+#[test]
+fn all_notes_off_clears_notes() {
+    init().unwrap();
+    // The patch echoes notes and control changes back.
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let notes: Arc<Mutex<Vec<(i32, i32, i32)>>> = Arc::new(Mutex::new(vec![]));
+    let notes_to_fill = notes.clone();
+    on_midi_note_on(move |channel, pitch, velocity| {
+        notes_to_fill
+            .lock()
+            .unwrap()
+            .push((channel, pitch, velocity));
+    });
+    let controls: Arc<Mutex<Vec<(i32, i32, i32)>>> = Arc::new(Mutex::new(vec![]));
+    let controls_to_fill = controls.clone();
+    on_midi_control_change(move |channel, controller, value| {
+        controls_to_fill
+            .lock()
+            .unwrap()
+            .push((channel, controller, value));
+    });
+
+    all_notes_off(3).unwrap();
+    receive_midi_messages_from_pd();
+    assert_eq!(
+        *controls.lock().unwrap(),
+        vec![(3, ALL_NOTES_OFF_CONTROLLER, 0)]
+    );
+    assert_eq!(
+        *notes.lock().unwrap(),
+        (0..=127).map(|pitch| (3, pitch, 0)).collect::<Vec<_>>()
+    );
+
+    controls.lock().unwrap().clear();
+    notes.lock().unwrap().clear();
+    all_notes_off_with(1, NotesOff::Controller).unwrap();
+    all_notes_off_with(2, NotesOff::NoteOffs).unwrap();
+    receive_midi_messages_from_pd();
+    assert_eq!(
+        *controls.lock().unwrap(),
+        vec![(1, ALL_NOTES_OFF_CONTROLLER, 0)]
+    );
+    assert_eq!(notes.lock().unwrap().len(), 128);
+    assert!(notes.lock().unwrap().iter().all(|note| note.0 == 2));
+
+    patch.close().unwrap();
+
+    // The note offs of every channel do not fit in the queue of the messages from pd, only control changes are echoed here.
+    let patch = open_patch("tests/patches/control_change_echo.pd").unwrap();
+    controls.lock().unwrap().clear();
+    notes.lock().unwrap().clear();
+    all_notes_off_all_channels().unwrap();
+    receive_midi_messages_from_pd();
+    assert_eq!(
+        *controls.lock().unwrap(),
+        (0..16)
+            .map(|channel| (channel, ALL_NOTES_OFF_CONTROLLER, 0))
+            .collect::<Vec<_>>()
+    );
+    assert!(notes.lock().unwrap().is_empty());
+
+    assert!(all_notes_off(-1).is_err());
+
+    patch.close().unwrap();
+}
@@ -0,0 +1,66 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    array::{read_float_array_from_clamped, resize_array, write_float_array_to},
+    close_patch,
+    error::ArrayError,
+};
+
+#[test]
+fn reads_near_the_end_return_the_count() {
+    libpd_rs::init().unwrap();
+    let handle = libpd_rs::open_patch("tests/patches/array_sketch_pad.pd").unwrap();
+    let sketch_pad = "sketch_pad";
+    resize_array(sketch_pad, 100).unwrap();
+    let source = (0..100).map(|i| i as f32).collect::<Vec<_>>();
+    write_float_array_to(sketch_pad, 0, &source, 100).unwrap();
+
+    // Reads which fit read everything.
+    let mut destination = [-1.0_f32; 64];
+    assert_eq!(
+        read_float_array_from_clamped(sketch_pad, 10, 64, &mut destination).unwrap(),
+        64
+    );
+    assert_eq!(&destination[..], &source[10..74]);
+
+    // Reads past the end are clamped and leave the tail as it is.
+    let mut destination = [-1.0_f32; 64];
+    let read = read_float_array_from_clamped(sketch_pad, 64, 64, &mut destination).unwrap();
+    assert_eq!(read, 36);
+    assert_eq!(&destination[..read], &source[64..]);
+    assert!(destination[read..].iter().all(|value| *value == -1.0));
+    assert_eq!(
+        read_float_array_from_clamped(sketch_pad, 100, 10, &mut destination).unwrap(),
+        0
+    );
+    assert_eq!(
+        read_float_array_from_clamped(sketch_pad, 200, 10, &mut destination).unwrap(),
+        0
+    );
+    assert_eq!(
+        read_float_array_from_clamped(sketch_pad, 0, 0, &mut destination).unwrap(),
+        0
+    );
+
+    assert!(matches!(
+        read_float_array_from_clamped(sketch_pad, -1, 10, &mut destination),
+        Err(ArrayError::NegativeOffset(-1))
+    ));
+    assert!(matches!(
+        read_float_array_from_clamped(sketch_pad, 0, -1, &mut destination),
+        Err(ArrayError::NegativeLength(-1))
+    ));
+    assert!(matches!(
+        read_float_array_from_clamped(sketch_pad, 90, 65, &mut destination),
+        Err(ArrayError::DestinationTooSmall {
+            needed: 65,
+            available: 64
+        })
+    ));
+    assert!(matches!(
+        read_float_array_from_clamped("not_exists", 0, 10, &mut destination),
+        Err(ArrayError::FailedToFindArray(name)) if name == "not_exists"
+    ));
+
+    close_patch(handle).unwrap();
+}
@@ -0,0 +1,70 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    array::{array_size, read_float_array_from, resize_array, write_float_array_to_resizing},
+    close_patch,
+    error::ArrayError,
+};
+
+#[test]
+fn arrays_are_grown_to_fit_the_written_values() {
+    libpd_rs::init().unwrap();
+    let handle = libpd_rs::open_patch("tests/patches/array_sketch_pad.pd").unwrap();
+    let sketch_pad = "sketch_pad";
+    resize_array(sketch_pad, 100).unwrap();
+
+    // Values which fit are written without resizing.
+    let source = (0..300).map(|i| i as f32).collect::<Vec<_>>();
+    assert_eq!(
+        write_float_array_to_resizing(sketch_pad, 10, &source, 50).unwrap(),
+        (100, false)
+    );
+    assert_eq!(array_size(sketch_pad).unwrap(), 100);
+
+    // The array grows to the end of the written values.
+    assert_eq!(
+        write_float_array_to_resizing(sketch_pad, 50, &source, 200).unwrap(),
+        (250, true)
+    );
+    assert_eq!(array_size(sketch_pad).unwrap(), 250);
+    let mut read = vec![0.0_f32; 250];
+    read_float_array_from(sketch_pad, 0, 250, &mut read).unwrap();
+    assert_eq!(&read[10..50], &source[..40]);
+    assert_eq!(&read[50..], &source[..200]);
+
+    // A larger array is not shrunk.
+    assert_eq!(
+        write_float_array_to_resizing(sketch_pad, 0, &source, 10).unwrap(),
+        (250, false)
+    );
+    assert_eq!(array_size(sketch_pad).unwrap(), 250);
+
+    // Nothing is resized if the write is invalid.
+    assert!(matches!(
+        write_float_array_to_resizing(sketch_pad, 100, &source, 301),
+        Err(ArrayError::SourceRangeOutOfBounds {
+            end: 301,
+            length: 300,
+            ..
+        })
+    ));
+    assert!(matches!(
+        write_float_array_to_resizing(sketch_pad, -1, &source, 10),
+        Err(ArrayError::NegativeOffset(-1))
+    ));
+    assert!(matches!(
+        write_float_array_to_resizing(sketch_pad, 0, &source, -1),
+        Err(ArrayError::NegativeLength(-1))
+    ));
+    assert!(matches!(
+        write_float_array_to_resizing(sketch_pad, i32::MAX, &source, 10),
+        Err(ArrayError::OutOfBounds { array_len: 250, .. })
+    ));
+    assert_eq!(array_size(sketch_pad).unwrap(), 250);
+    assert!(matches!(
+        write_float_array_to_resizing("not_exists", 0, &source, 10),
+        Err(ArrayError::FailedToFindArray(name)) if name == "not_exists"
+    ));
+
+    close_patch(handle).unwrap();
+}
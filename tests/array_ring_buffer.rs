@@ -0,0 +1,51 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "rtrb")]
+
+use libpd_rs::{
+    array::{read_float_array_into_producer, write_float_array_to},
+    error::ArrayError,
+    init, open_patch,
+};
+use rtrb::RingBuffer;
+
+#[test]
+fn arrays_are_read_into_ring_buffers() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/array_sketch_pad.pd").unwrap();
+    let values = (0..100).map(|value| value as f32).collect::<Vec<_>>();
+    write_float_array_to("sketch_pad", 0, &values, 100).unwrap();
+
+    // Everything fits.
+    let (mut producer, mut consumer) = RingBuffer::<f32>::new(256);
+    assert_eq!(
+        read_float_array_into_producer("sketch_pad", &mut producer).unwrap(),
+        100
+    );
+    let read = (0..100)
+        .map(|_| consumer.pop().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(read, values);
+    assert!(consumer.pop().is_err());
+
+    // What fits is pushed from the start of the array, a full ring buffer takes nothing.
+    let (mut producer, mut consumer) = RingBuffer::<f32>::new(30);
+    producer.push(-1.0).unwrap();
+    assert_eq!(
+        read_float_array_into_producer("sketch_pad", &mut producer).unwrap(),
+        29
+    );
+    assert_eq!(
+        read_float_array_into_producer("sketch_pad", &mut producer).unwrap(),
+        0
+    );
+    assert_eq!(consumer.pop(), Ok(-1.0));
+    let read = (0..29).map(|_| consumer.pop().unwrap()).collect::<Vec<_>>();
+    assert_eq!(read, values[..29]);
+
+    assert!(matches!(
+        read_float_array_into_producer("not_exists", &mut producer),
+        Err(ArrayError::FailedToFindArray(name)) if name == "not_exists"
+    ));
+
+    patch.close().unwrap();
+}
@@ -2,10 +2,11 @@
 
 use libpd_rs::{
     array::{
-        array_size, read_double_array_from, read_float_array_from, resize_array,
-        write_double_array_to, write_float_array_to,
+        array_exists, array_size, read_double_array_from, read_float_array_from, resize_array,
+        write_double_array_to, write_float_array_range, write_float_array_to,
     },
     close_patch,
+    error::{ArrayError, SizeError},
 };
 
 #[test]
@@ -16,24 +17,54 @@ fn apply_array_operations_in_a_row() {
     let bad_name = "not_exists";
     let sketch_pad = "sketch_pad";
 
-    assert!(array_size(bad_name).is_err());
+    assert!(matches!(array_size(bad_name), Err(SizeError::NonExistent(name)) if name == bad_name));
+    assert!(!array_exists(bad_name));
+    assert!(array_exists(sketch_pad));
 
     // Default
     let size = array_size(sketch_pad).unwrap();
     assert_eq!(size, 100);
 
-    assert!(resize_array(bad_name, 1024).is_err());
-
-    resize_array(sketch_pad, -1).unwrap();
+    assert!(matches!(
+        resize_array(bad_name, 1024),
+        Err(SizeError::NonExistent(_))
+    ));
+
+    // Clipped sizes resize the array and report the size it has.
+    assert!(matches!(
+        resize_array(sketch_pad, -1),
+        Err(SizeError::Clipped {
+            requested: -1,
+            actual: 1,
+            ..
+        })
+    ));
     let size = array_size(sketch_pad).unwrap();
     assert_eq!(size, 1);
 
-    resize_array(sketch_pad, 0).unwrap();
+    assert!(matches!(
+        resize_array(sketch_pad, 0),
+        Err(SizeError::Clipped {
+            requested: 0,
+            actual: 1,
+            ..
+        })
+    ));
     let size = array_size(sketch_pad).unwrap();
     assert_eq!(size, 1);
 
     // Protected with size limits. Over the limit is also 1.
-    resize_array(sketch_pad, i32::MAX).unwrap();
+    match resize_array(sketch_pad, i32::MAX) {
+        Err(SizeError::Clipped {
+            array,
+            requested,
+            actual,
+        }) => assert_eq!(
+            (array.as_str(), requested, actual),
+            (sketch_pad, i32::MAX, 1)
+        ),
+        other => panic!("{other:?}"),
+    }
     let size = array_size(sketch_pad).unwrap();
     assert_eq!(size, 1);
 
@@ -69,6 +100,70 @@ fn apply_array_operations_in_a_row() {
 
     assert_eq!(read_to, vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
 
+    // Ranges
+    let larger_buffer: Vec<f32> = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+    let mut read_to: Vec<f32> = vec![0.0; 8];
+    write_float_array_range(sketch_pad, 0, &larger_buffer, ..).unwrap();
+    read_float_array_from(sketch_pad, 0, 8, &mut read_to).unwrap();
+    assert_eq!(read_to, larger_buffer);
+
+    write_float_array_range(sketch_pad, 1, &[0.0, 9.0, 9.0, 0.0], 1..3).unwrap();
+    write_float_array_range(sketch_pad, 6, &larger_buffer, ..=1).unwrap();
+    read_float_array_from(sketch_pad, 0, 8, &mut read_to).unwrap();
+    assert_eq!(read_to, vec![1.0, 9.0, 9.0, 4.0, 5.0, 6.0, 1.0, 2.0]);
+
+    // Empty ranges write nothing.
+    write_float_array_range(sketch_pad, 8, &larger_buffer, 4..4).unwrap();
+
+    // Source range out of the source.
+    let result = write_float_array_range(sketch_pad, 0, &larger_buffer, 4..9);
+    assert!(matches!(
+        result,
+        Err(ArrayError::SourceRangeOutOfBounds {
+            start: 4,
+            end: 9,
+            length: 8
+        })
+    ));
+    #[allow(clippy::reversed_empty_ranges)]
+    let result = write_float_array_range(sketch_pad, 0, &larger_buffer, 4..2);
+    assert!(matches!(
+        result,
+        Err(ArrayError::SourceRangeOutOfBounds { .. })
+    ));
+    // Destination out of the array.
+    let result = write_float_array_range(sketch_pad, 7, &larger_buffer, 0..2);
+    assert!(matches!(
+        result,
+        Err(ArrayError::OutOfBounds {
+            offset: 7,
+            requested: 2,
+            array_len: 8,
+            ..
+        })
+    ));
+    let result = write_float_array_range(sketch_pad, -1, &larger_buffer, 0..2);
+    assert!(matches!(result, Err(ArrayError::NegativeOffset(-1))));
+    let result = write_float_array_range("not_exists", 0, &larger_buffer, 0..2);
+    assert!(matches!(result, Err(ArrayError::FailedToFindArray(name)) if name == "not_exists"));
+
+    // The destination needs to hold what is read.
+    let mut small = [0.0_f32; 2];
+    let result = read_float_array_from(sketch_pad, 0, 4, &mut small);
+    assert!(matches!(
+        result,
+        Err(ArrayError::DestinationTooSmall {
+            needed: 4,
+            available: 2
+        })
+    ));
+    let mut small = [0.0_f64; 2];
+    let result = read_double_array_from(sketch_pad, 0, 4, &mut small);
+    assert!(matches!(
+        result,
+        Err(ArrayError::DestinationTooSmall { .. })
+    ));
+
     // Clear float arrays.
     let mut read_to: Vec<f64> = vec![0.0; 6];
     write_double_array_to(sketch_pad, 0, &[0.0, 0.0, 0.0, 0.0, 0.0, 0.0], 6).unwrap();
@@ -96,7 +191,17 @@ fn apply_array_operations_in_a_row() {
 
     let mut read_to: Vec<f64> = vec![0.0; 1024];
     let result = read_double_array_from(sketch_pad, 0, -1, &mut read_to);
-    assert!(result.is_err());
+    assert!(matches!(result, Err(ArrayError::NegativeLength(-1))));
+
+    let result = read_double_array_from(sketch_pad, -4, 2, &mut read_to);
+    assert!(matches!(result, Err(ArrayError::NegativeOffset(-4))));
+
+    let will_write: Vec<f64> = vec![0.0; 4];
+    let result = write_double_array_to(sketch_pad, 0, &will_write, -2);
+    assert!(matches!(result, Err(ArrayError::NegativeLength(-2))));
+
+    let result = write_double_array_to(sketch_pad, -1, &will_write, 4);
+    assert!(matches!(result, Err(ArrayError::NegativeOffset(-1))));
 
     // Float
     let will_write_long_array: Vec<f32> = vec![0.0; 1024];
@@ -113,7 +218,17 @@ fn apply_array_operations_in_a_row() {
 
     let mut read_to: Vec<f32> = vec![0.0; 1024];
     let result = read_float_array_from(sketch_pad, 0, -1, &mut read_to);
-    assert!(result.is_err());
+    assert!(matches!(result, Err(ArrayError::NegativeLength(-1))));
+
+    let result = read_float_array_from(sketch_pad, -4, 2, &mut read_to);
+    assert!(matches!(result, Err(ArrayError::NegativeOffset(-4))));
+
+    let will_write: Vec<f32> = vec![0.0; 4];
+    let result = write_float_array_to(sketch_pad, 0, &will_write, -2);
+    assert!(matches!(result, Err(ArrayError::NegativeLength(-2))));
+
+    let result = write_float_array_to(sketch_pad, -1, &will_write, 4);
+    assert!(matches!(result, Err(ArrayError::NegativeOffset(-1))));
 
     close_patch(handle).unwrap();
 }
@@ -0,0 +1,26 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::types::Atom;
+
+#[test]
+fn atom_accessors() {
+    let float = Atom::from(0.5);
+    let symbol = Atom::from("foo");
+
+    assert_eq!(float.as_float(), Some(0.5));
+    assert_eq!(float.as_f32(), Some(0.5_f32));
+    assert_eq!(float.as_symbol(), None);
+    assert!(float.is_float());
+    assert!(!float.is_symbol());
+    assert_eq!(float.unwrap_float(), 0.5);
+
+    assert_eq!(symbol.as_float(), None);
+    assert_eq!(symbol.as_f32(), None);
+    assert_eq!(symbol.as_symbol(), Some("foo"));
+    assert!(symbol.is_symbol());
+    assert!(!symbol.is_float());
+    assert_eq!(symbol.unwrap_symbol(), "foo");
+
+    assert!(std::panic::catch_unwind(|| symbol.unwrap_float()).is_err());
+    assert!(std::panic::catch_unwind(|| float.unwrap_symbol().to_owned()).is_err());
+}
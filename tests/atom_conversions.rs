@@ -0,0 +1,41 @@
+#![allow(clippy::restriction)]
+
+use std::borrow::Cow;
+
+use libpd_rs::types::Atom;
+
+#[test]
+fn atoms_from_rust_types() {
+    assert_eq!(Atom::from(true), Atom::Float(1.0));
+    assert_eq!(Atom::from(&false), Atom::Float(0.0));
+
+    assert_eq!(Atom::from(-3_i64), Atom::Float(-3.0));
+    assert_eq!(Atom::from(7_usize), Atom::Float(7.0));
+    assert_eq!(Atom::from(&12_u64), Atom::Float(12.0));
+    assert_eq!(Atom::from(5_i128), Atom::Float(5.0));
+    // Integers which need more than 53 bits are rounded.
+    assert_eq!(
+        Atom::from(u64::MAX),
+        Atom::Float(18_446_744_073_709_551_616.0)
+    );
+
+    assert_eq!(Atom::from('x'), Atom::Symbol("x".to_owned()));
+    assert_eq!(
+        Atom::from(&"saw".to_owned()),
+        Atom::Symbol("saw".to_owned())
+    );
+    assert_eq!(
+        Atom::from(Cow::Borrowed("sine")),
+        Atom::Symbol("sine".to_owned())
+    );
+    assert_eq!(
+        Atom::from(Cow::<str>::Owned("square".to_owned())),
+        Atom::Symbol("square".to_owned())
+    );
+
+    let list: Vec<Atom> = (0_usize..3).map(Atom::from).collect();
+    assert_eq!(list, vec![Atom::from(0), Atom::from(1), Atom::from(2)]);
+
+    assert_eq!(Atom::from("sine").as_str(), Some("sine"));
+    assert_eq!(Atom::from(1).as_str(), None);
+}
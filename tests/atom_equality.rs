@@ -0,0 +1,42 @@
+#![allow(clippy::restriction)]
+
+use std::collections::{HashMap, HashSet};
+
+use libpd_rs::types::Atom;
+
+#[test]
+fn atoms_as_keys() {
+    let mut routes: HashMap<Atom, &str> = HashMap::new();
+    routes.insert(Atom::from("play"), "transport");
+    routes.insert(Atom::from(1.0), "one");
+    routes.insert(Atom::from(0.0), "zero");
+
+    assert_eq!(routes.get(&Atom::from("play")), Some(&"transport"));
+    assert_eq!(routes.get(&Atom::from(1)), Some(&"one"));
+    assert_eq!(routes.get(&Atom::from(-0.0)), Some(&"zero"));
+    assert_eq!(routes.get(&Atom::from("stop")), None);
+    // A symbol and a float with the same text are different atoms.
+    assert_eq!(routes.get(&Atom::from("1")), None);
+
+    routes.insert(Atom::from(f64::NAN), "nan");
+    assert_eq!(routes.get(&Atom::from(f64::NAN)), None);
+    assert_ne!(Atom::from(f64::NAN), Atom::from(f64::NAN));
+}
+
+#[test]
+fn deduplicating_atoms() {
+    let list = vec![
+        Atom::from("a"),
+        Atom::from(2.0),
+        Atom::from("a"),
+        Atom::from(2.0),
+        Atom::from(-0.0),
+        Atom::from(0.0),
+    ];
+    let unique: HashSet<Atom> = list.into_iter().collect();
+    assert_eq!(unique.len(), 3);
+
+    let mut list = vec![Atom::from("a"), Atom::from("a"), Atom::from(3)];
+    list.dedup();
+    assert_eq!(list, vec![Atom::from("a"), Atom::from(3)]);
+}
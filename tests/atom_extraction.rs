@@ -0,0 +1,86 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    error::AtomConversionError,
+    types::{Atom, ExtractAtoms},
+};
+
+#[test]
+fn atoms_convert_to_rust_types() {
+    let float = Atom::from(440);
+    let half = Atom::from(0.5);
+    let symbol = Atom::from("sine");
+
+    assert_eq!(f64::try_from(&half).unwrap(), 0.5);
+    assert_eq!(f32::try_from(&half).unwrap(), 0.5);
+    assert_eq!(i32::try_from(&float).unwrap(), 440);
+    assert_eq!(i64::try_from(&Atom::from(-3)).unwrap(), -3);
+    assert_eq!(String::try_from(&symbol).unwrap(), "sine");
+    assert_eq!(<&str>::try_from(&symbol).unwrap(), "sine");
+
+    assert!(matches!(
+        f32::try_from(&symbol),
+        Err(AtomConversionError::ExpectedFloat(symbol)) if symbol == "sine"
+    ));
+    assert!(matches!(
+        <&str>::try_from(&float),
+        Err(AtomConversionError::ExpectedSymbol(value)) if value == 440.0
+    ));
+    assert!(matches!(
+        i32::try_from(&half),
+        Err(AtomConversionError::NotAnInteger(_))
+    ));
+    assert!(matches!(
+        i32::try_from(&Atom::from(f64::from(i32::MAX) + 1.0)),
+        Err(AtomConversionError::NotAnInteger(_))
+    ));
+    assert_eq!(i32::try_from(&Atom::from(i32::MIN)).unwrap(), i32::MIN);
+    assert!(matches!(
+        i64::try_from(&Atom::from(9_223_372_036_854_775_808.0)),
+        Err(AtomConversionError::NotAnInteger(_))
+    ));
+    assert!(matches!(
+        i64::try_from(&Atom::from(f64::NAN)),
+        Err(AtomConversionError::NotAnInteger(_))
+    ));
+}
+
+#[test]
+fn lists_are_extracted_positionally() {
+    let list = [Atom::from(440), Atom::from("sine"), Atom::from(0.5)];
+
+    let (frequency, shape, gain): (f32, String, f32) = list.extract().unwrap();
+    assert_eq!(frequency, 440.0);
+    assert_eq!(shape, "sine");
+    assert_eq!(gain, 0.5);
+
+    let (single,): (i64,) = list[..1].extract().unwrap();
+    assert_eq!(single, 440);
+
+    // Trailing atoms are an error unless a prefix is extracted.
+    assert!(matches!(
+        list.extract::<(f32, &str)>(),
+        Err(AtomConversionError::WrongArity {
+            expected: 2,
+            found: 3
+        })
+    ));
+    let ((frequency, shape), rest) = list.extract_prefix::<(i32, &str)>().unwrap();
+    assert_eq!((frequency, shape), (440, "sine"));
+    assert_eq!(rest, &[Atom::from(0.5)]);
+
+    assert!(matches!(
+        list.extract_prefix::<(f32, String, f32, f32)>(),
+        Err(AtomConversionError::WrongArity {
+            expected: 4,
+            found: 3
+        })
+    ));
+    match list.extract::<(f32, f32, f32)>() {
+        Err(AtomConversionError::Element { index, source }) => {
+            assert_eq!(index, 1);
+            assert!(matches!(*source, AtomConversionError::ExpectedFloat(_)));
+        }
+        other => panic!("{other:?}"),
+    }
+}
@@ -0,0 +1,139 @@
+#![allow(clippy::restriction)]
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::{Hash, Hasher};
+
+use libpd_rs::types::{Atom, TotalAtom};
+
+mod common;
+use common::Random;
+
+const FLOATS: &[f64] = &[
+    0.0,
+    -0.0,
+    1.0,
+    -1.0,
+    0.5,
+    f64::MIN_POSITIVE,
+    f64::MAX,
+    f64::MIN,
+    f64::INFINITY,
+    f64::NEG_INFINITY,
+    f64::NAN,
+    -f64::NAN,
+];
+
+fn random_atom(rng: &mut Random) -> Atom {
+    const SYMBOLS: &[&str] = &["", "a", "b", "ab", "B", "0", "nan"];
+    if rng.below(3) == 0 {
+        Atom::from(SYMBOLS[rng.below(SYMBOLS.len())])
+    } else {
+        Atom::from(FLOATS[rng.below(FLOATS.len())])
+    }
+}
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn atoms_compare_like_floats_and_strings() {
+    // `==` is the float equality: signed zeros are equal, `NaN` is not equal to itself.
+    assert_eq!(Atom::from(0.0), Atom::from(-0.0));
+    assert_eq!(hash_of(&Atom::from(0.0)), hash_of(&Atom::from(-0.0)));
+    assert_ne!(Atom::from(f64::NAN), Atom::from(f64::NAN));
+    assert_eq!(Atom::from(f64::NAN).partial_cmp(&Atom::from(1.0)), None);
+    // Symbols are plain string equality and never equal to floats.
+    assert_eq!(Atom::from("a"), Atom::from(String::from("a")));
+    assert_ne!(Atom::from("a"), Atom::from("A"));
+    assert_ne!(Atom::from("0"), Atom::from(0.0));
+}
+
+#[test]
+fn total_order_of_atoms() {
+    // Signed zeros and NaNs are told apart, a NaN equals itself.
+    assert_eq!(Atom::from(-0.0).total_cmp(&Atom::from(0.0)), Ordering::Less);
+    assert_eq!(
+        Atom::from(f64::NAN).total_cmp(&Atom::from(f64::NAN)),
+        Ordering::Equal
+    );
+    assert_eq!(
+        Atom::from(f64::NAN).total_cmp(&Atom::from(f64::INFINITY)),
+        Ordering::Greater
+    );
+    assert_eq!(
+        Atom::from(-f64::NAN).total_cmp(&Atom::from(f64::NEG_INFINITY)),
+        Ordering::Less
+    );
+    // Floats come before symbols.
+    assert_eq!(
+        Atom::from(f64::NAN).total_cmp(&Atom::from("")),
+        Ordering::Less
+    );
+
+    let zeros: BTreeSet<TotalAtom> = [0.0, -0.0, 0.0]
+        .into_iter()
+        .map(|float| TotalAtom(float.into()))
+        .collect();
+    assert_eq!(zeros.len(), 2);
+    let nans: HashSet<TotalAtom> = [f64::NAN, f64::NAN, -f64::NAN]
+        .into_iter()
+        .map(|float| TotalAtom(float.into()))
+        .collect();
+    assert_eq!(nans.len(), 2);
+    assert!(nans.contains(&TotalAtom(Atom::from(f64::NAN))));
+    assert_eq!(
+        Atom::from(TotalAtom::from(Atom::from("a"))),
+        Atom::from("a")
+    );
+}
+
+#[test]
+fn total_order_properties() {
+    let mut rng = Random(0x2545_f491_4f6c_dd1d);
+    for _ in 0..2000 {
+        let (a, b, c) = (
+            random_atom(&mut rng),
+            random_atom(&mut rng),
+            random_atom(&mut rng),
+        );
+        let (ta, tb, tc) = (
+            TotalAtom(a.clone()),
+            TotalAtom(b.clone()),
+            TotalAtom(c.clone()),
+        );
+
+        // Reflexive and antisymmetric.
+        assert_eq!(a.total_cmp(&a), Ordering::Equal);
+        assert_eq!(a.total_cmp(&b), b.total_cmp(&a).reverse());
+        // Transitive.
+        if ta <= tb && tb <= tc {
+            assert!(ta <= tc, "{a:?} {b:?} {c:?}");
+        }
+        // Equal keys hash the same.
+        if ta == tb {
+            assert_eq!(hash_of(&ta), hash_of(&tb));
+        }
+        // The total order agrees with `==` except for signed zeros and NaNs.
+        let special = |atom: &Atom| {
+            atom.as_float()
+                .is_some_and(|float| float == 0.0 || float.is_nan())
+        };
+        if !special(&a) && !special(&b) {
+            assert_eq!(a == b, ta == tb, "{a:?} {b:?}");
+            if a == b {
+                assert_eq!(hash_of(&a), hash_of(&b));
+            }
+        }
+        // The derived partial order agrees with the total order where it is defined.
+        if let Some(ordering) = a.partial_cmp(&b) {
+            if !special(&a) && !special(&b) {
+                assert_eq!(ordering, a.total_cmp(&b), "{a:?} {b:?}");
+            }
+        }
+    }
+}
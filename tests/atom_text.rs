@@ -0,0 +1,175 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    error::AtomParseError,
+    types::{format_atoms, parse_atoms, Atom},
+};
+
+mod common;
+use common::Random;
+
+#[test]
+fn atoms_are_formatted_like_pd() {
+    assert_eq!(Atom::from(440).to_string(), "440");
+    assert_eq!(Atom::from(0.5).to_string(), "0.5");
+    assert_eq!(Atom::from(-0.25).to_string(), "-0.25");
+    assert_eq!(Atom::from(1.0 / 3.0).to_string(), "0.333333");
+    assert_eq!(Atom::from(123_456).to_string(), "123456");
+    assert_eq!(Atom::from(1_234_567).to_string(), "1.23457e+06");
+    assert_eq!(Atom::from(999_999.5).to_string(), "1e+06");
+    assert_eq!(Atom::from(0.0001).to_string(), "0.0001");
+    assert_eq!(Atom::from(0.000_015).to_string(), "1.5e-05");
+    assert_eq!(Atom::from(0).to_string(), "0");
+    // Floats which are not finite are printed like pd prints them, but they are read back as symbols.
+    let not_finite = [
+        Atom::from(f64::NAN),
+        Atom::from(f64::INFINITY),
+        Atom::from(f64::NEG_INFINITY),
+    ];
+    let text = format_atoms(&not_finite);
+    assert_eq!(text, "nan inf -inf");
+    assert_eq!(
+        parse_atoms(&text).unwrap(),
+        vec![Atom::from("nan"), Atom::from("inf"), Atom::from("-inf")]
+    );
+
+    assert_eq!(Atom::from("sine").to_string(), "sine");
+    assert_eq!(Atom::from("my voice").to_string(), "my\\ voice");
+    assert_eq!(Atom::from("a;b,c\\").to_string(), r"a\;b\,c\\");
+    assert_eq!(Atom::from("$1-freq").to_string(), "\\$1-freq");
+    assert_eq!(Atom::from("$ sign").to_string(), "$\\ sign");
+    assert_eq!(
+        Atom::from("tab\tnew\nline\r").to_string(),
+        "tab\\\tnew\\\nline\\\r"
+    );
+    // Symbols which would be read as numbers or as the empty symbol are escaped.
+    assert_eq!(Atom::from("440").to_string(), "\\440");
+    assert_eq!(Atom::from("-1e3").to_string(), "\\-1e3");
+    assert_eq!(Atom::from("").to_string(), "\"\"");
+    assert_eq!(Atom::from("\"\"").to_string(), "\\\"\"");
+
+    assert_eq!(
+        format_atoms(&[Atom::from(440), Atom::from(0.5), Atom::from("sine")]),
+        "440 0.5 sine"
+    );
+    assert_eq!(format_atoms(&[]), "");
+}
+
+#[test]
+fn atoms_are_parsed_like_pd() {
+    assert_eq!(
+        parse_atoms("  440 0.5\tsine\n-1e3 .5 +2 ").unwrap(),
+        vec![
+            Atom::from(440),
+            Atom::from(0.5),
+            Atom::from("sine"),
+            Atom::from(-1000),
+            Atom::from(0.5),
+            Atom::from(2),
+        ]
+    );
+    assert!(parse_atoms("").unwrap().is_empty());
+    assert!(parse_atoms(" \t\n").unwrap().is_empty());
+
+    // Things which are not numbers are symbols.
+    assert_eq!(
+        parse_atoms("- 1e inf nan 12abc").unwrap(),
+        vec![
+            Atom::from("-"),
+            Atom::from("1e"),
+            Atom::from("inf"),
+            Atom::from("nan"),
+            Atom::from("12abc"),
+        ]
+    );
+    // Escaped characters are a part of the symbol.
+    assert_eq!(
+        parse_atoms(r"my\ voice a\;b \$1 \\ \1").unwrap(),
+        vec![
+            Atom::from("my voice"),
+            Atom::from("a;b"),
+            Atom::from("$1"),
+            Atom::from("\\"),
+            Atom::from("1"),
+        ]
+    );
+
+    // The empty symbol is written as two quotes.
+    assert_eq!(
+        parse_atoms("\"\" \\\"\" a\"\"").unwrap(),
+        vec![Atom::from(""), Atom::from("\"\""), Atom::from("a\"\"")]
+    );
+
+    assert!(matches!(
+        parse_atoms("foo\\"),
+        Err(AtomParseError::TrailingBackslash)
+    ));
+    assert!(matches!(
+        parse_atoms("foo; bar"),
+        Err(AtomParseError::Separator(';'))
+    ));
+    assert!(matches!(
+        parse_atoms("foo , bar"),
+        Err(AtomParseError::Separator(','))
+    ));
+    assert!(matches!(
+        parse_atoms("$12-freq"),
+        Err(AtomParseError::DollarArgument(argument)) if argument == "$12"
+    ));
+    // A dollar sign which is not followed by a digit is a regular character.
+    assert_eq!(
+        parse_atoms("$ a$").unwrap(),
+        vec![Atom::from("$"), Atom::from("a$")]
+    );
+
+    assert_eq!("440".parse::<Atom>().unwrap(), Atom::from(440));
+    assert_eq!(
+        "my\\ voice".parse::<Atom>().unwrap(),
+        Atom::from("my voice")
+    );
+    assert!(matches!(
+        "".parse::<Atom>(),
+        Err(AtomParseError::NotOneAtom(0))
+    ));
+    assert!(matches!(
+        "440 sine".parse::<Atom>(),
+        Err(AtomParseError::NotOneAtom(2))
+    ));
+}
+
+fn random_symbol(rng: &mut Random) -> String {
+    const CHARACTERS: &[char] = &[
+        'a', 'z', 'Q', '-', '+', '.', 'e', '_', ' ', '\t', '\n', '\r', ';', ',', '\\', '$', '"',
+        '1', '9', 'é',
+    ];
+    let length = rng.below(7);
+    (0..length)
+        .map(|_| CHARACTERS[rng.below(CHARACTERS.len())])
+        .collect()
+}
+
+fn random_float(rng: &mut Random) -> f64 {
+    // Floats with at most 6 significant digits are printed without losing precision.
+    let mantissa = rng.below(1_999_999) as i64 - 999_999;
+    let exponent = rng.below(24) as i64 - 12;
+    format!("{mantissa}e{exponent}").parse().unwrap()
+}
+
+#[test]
+fn parsing_formatted_atoms_round_trips() {
+    let mut rng = Random(136);
+    for _ in 0..2000 {
+        let length = rng.below(10);
+        let atoms: Vec<Atom> = (0..length)
+            .map(|_| {
+                if rng.below(2) == 0 {
+                    Atom::Float(random_float(&mut rng))
+                } else {
+                    Atom::Symbol(random_symbol(&mut rng))
+                }
+            })
+            .collect();
+        let text = format_atoms(&atoms);
+        assert_eq!(parse_atoms(&text).unwrap(), atoms, "{text}");
+    }
+}
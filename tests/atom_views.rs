@@ -0,0 +1,74 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{
+        on_list_borrowed, on_message_borrowed, receive_messages_from_pd, start_listening_from,
+    },
+    send::{send_list_to, send_message_to},
+    types::{Atom, AtomView},
+};
+
+type Messages = Arc<Mutex<Vec<(String, Vec<Atom>)>>>;
+
+#[test]
+fn lists_are_received_as_views() {
+    let view = AtomView::Symbol("saw");
+    assert_eq!(view.as_symbol(), Some("saw"));
+    assert_eq!(view.as_float(), None);
+    assert_eq!(AtomView::Float(1.5).as_float(), Some(1.5));
+    assert_eq!(Atom::from(view), Atom::from("saw"));
+    assert_eq!(AtomView::Float(1.0), Atom::from(1));
+    assert_ne!(AtomView::Symbol("1"), Atom::from(1));
+
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list_borrowed(move |source, list| {
+        assert_eq!(source, "list_from_pd");
+        lists_to_fill
+            .lock()
+            .unwrap()
+            .push(list.iter().map(AtomView::to_atom).collect());
+    });
+    let messages: Messages = Arc::new(Mutex::new(vec![]));
+    let messages_to_fill = messages.clone();
+    on_message_borrowed(move |_source, message, values| {
+        let values = values.iter().map(Atom::from).collect();
+        messages_to_fill
+            .lock()
+            .unwrap()
+            .push((message.to_owned(), values));
+    });
+    let _handle = start_listening_from("list_from_pd").unwrap();
+
+    // Lists longer than what is viewed on the stack are received too.
+    let long = (0..100)
+        .map(|index| {
+            if index % 3 == 0 {
+                Atom::from(format!("s{index}"))
+            } else {
+                Atom::from(index)
+            }
+        })
+        .collect::<Vec<_>>();
+    send_list_to("list_from_rust", ("freq", 440)).unwrap();
+    send_list_to("list_from_rust", &long).unwrap();
+    send_message_to("list_from_rust", "set", (1, "a")).unwrap();
+    receive_messages_from_pd();
+
+    assert_eq!(
+        *lists.lock().unwrap(),
+        vec![vec![Atom::from("freq"), Atom::from(440)], long]
+    );
+    assert_eq!(
+        *messages.lock().unwrap(),
+        vec![("set".to_owned(), vec![Atom::from(1), Atom::from("a")])]
+    );
+
+    patch.close().unwrap();
+}
@@ -0,0 +1,27 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{atoms, types::Atom};
+
+#[test]
+fn atoms_macro() {
+    assert_eq!(
+        atoms![440.0, "sine", 0.5],
+        vec![Atom::from(440.0), Atom::from("sine"), Atom::from(0.5)]
+    );
+    // Trailing commas and expressions.
+    let shape = String::from("saw");
+    assert_eq!(
+        atoms![1 + 2, &shape, true, 'x',],
+        vec![
+            Atom::from(3),
+            Atom::from("saw"),
+            Atom::from(1),
+            Atom::from("x")
+        ]
+    );
+    assert_eq!(atoms![atoms![1, 2].len()], vec![Atom::from(2)]);
+    assert_eq!(atoms![], Vec::<Atom>::new());
+    assert_eq!(atoms![0.0; 4], vec![Atom::from(0.0); 4]);
+    assert_eq!(atoms!["a"; 2], vec![Atom::from("a"), Atom::from("a")]);
+    assert!(atoms![1; 0].is_empty());
+}
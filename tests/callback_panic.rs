@@ -0,0 +1,64 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    close_patch, init, open_patch,
+    receive::{
+        on_float, on_midi_byte, receive_messages_from_pd, receive_midi_messages_from_pd,
+        start_listening_from, stop_listening_from, take_last_callback_panic,
+    },
+    send::{send_float_to, send_midi_byte},
+};
+
+#[test]
+fn callback_panic() {
+    let floats: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![]));
+
+    init().unwrap();
+
+    let patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+    let receiver_handle = start_listening_from("float_from_pd").unwrap();
+
+    let floats_to_fill = floats.clone();
+    on_float(move |_, value| {
+        assert!(value > 2.0, "Unexpected float");
+        floats_to_fill.lock().unwrap().push(value);
+    });
+
+    assert!(take_last_callback_panic().is_none());
+
+    // The first float panics and disables the closure.
+    send_float_to("float_from_rust", 2.0).unwrap();
+    send_float_to("float_from_rust", 4.0).unwrap();
+    receive_messages_from_pd();
+
+    assert!(floats.lock().unwrap().is_empty());
+    let callback_panic = take_last_callback_panic().unwrap();
+    assert_eq!(callback_panic.callback, "on_float");
+    assert_eq!(callback_panic.message, "Unexpected float");
+    assert!(take_last_callback_panic().is_none());
+
+    // Registering again enables it.
+    let floats_to_fill = floats.clone();
+    on_float(move |_, value| {
+        floats_to_fill.lock().unwrap().push(value);
+    });
+    send_float_to("float_from_rust", 8.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![8.0]);
+
+    // Panic payloads which are formatted.
+    on_midi_byte(|port, byte| {
+        panic!("Byte {byte} from port {port}");
+    });
+    send_midi_byte(1, 0xF8).unwrap();
+    receive_midi_messages_from_pd();
+
+    let callback_panic = take_last_callback_panic().unwrap();
+    assert_eq!(callback_panic.callback, "on_midi_byte");
+    assert_eq!(callback_panic.message, "Byte 248 from port 1");
+
+    stop_listening_from(receiver_handle);
+    close_patch(patch_handle).unwrap();
+}
@@ -0,0 +1,50 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    close_patch,
+    context::{clear_context, context_mut, set_context, take_context},
+    init, open_patch,
+    receive::{on_float, receive_messages_from_pd, start_listening_from},
+    send::send_float_to,
+};
+
+#[derive(Debug, Default, PartialEq)]
+struct State {
+    floats: Vec<f32>,
+}
+
+#[test]
+fn callbacks_borrow_the_context() {
+    init().unwrap();
+    assert!(context_mut::<State>().is_none());
+
+    set_context(State::default());
+    on_float(|source, value| {
+        assert_eq!(source, "float_from_pd");
+        context_mut::<State>().unwrap().floats.push(value);
+    });
+
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    start_listening_from("float_from_pd").unwrap();
+    send_float_to("float_from_rust", 1.0).unwrap();
+    send_float_to("float_from_rust", 2.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(context_mut::<State>().unwrap().floats, vec![1.0, 2.0]);
+
+    // A context of another type is not borrowed or taken.
+    assert!(context_mut::<u32>().is_none());
+    assert!(take_context::<u32>().is_none());
+
+    // Setting a context replaces the previous one.
+    set_context(7_u32);
+    assert!(context_mut::<State>().is_none());
+    *context_mut::<u32>().unwrap() += 1;
+    assert_eq!(take_context::<u32>(), Some(8));
+    assert!(context_mut::<u32>().is_none());
+
+    set_context(State::default());
+    clear_context();
+    assert!(context_mut::<State>().is_none());
+
+    close_patch(patch).unwrap();
+}
@@ -0,0 +1,67 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{
+        drain_all_messages, drain_all_midi_messages, on_float, on_midi_note_on,
+        receive_messages_from_pd, start_listening_from,
+    },
+    send::{send_float_to, send_note_on},
+};
+
+#[test]
+fn drain_messages() {
+    init().unwrap();
+    let _patch = open_patch("tests/patches/echo.pd").unwrap();
+    let _receiver_handle = start_listening_from("float_from_pd").unwrap();
+
+    let floats = Arc::new(AtomicUsize::new(0));
+    let floats_to_count = floats.clone();
+    on_float(move |_, _| {
+        floats_to_count.fetch_add(1, Ordering::SeqCst);
+    });
+    let notes = Arc::new(AtomicUsize::new(0));
+    let notes_to_count = notes.clone();
+    on_midi_note_on(move |_, _, _| {
+        notes_to_count.fetch_add(1, Ordering::SeqCst);
+    });
+
+    assert_eq!(drain_all_messages(), 0);
+    assert_eq!(drain_all_midi_messages(), 0);
+
+    for value in 0..100 {
+        send_float_to("float_from_rust", value as f32).unwrap();
+    }
+    for pitch in 0..10 {
+        send_note_on(0, pitch, 64).unwrap();
+    }
+
+    assert_eq!(drain_all_messages(), 100);
+    assert_eq!(floats.load(Ordering::SeqCst), 100);
+    assert_eq!(drain_all_messages(), 0);
+
+    assert_eq!(drain_all_midi_messages(), 10);
+    assert_eq!(notes.load(Ordering::SeqCst), 10);
+    assert_eq!(drain_all_midi_messages(), 0);
+
+    // Receiving in a closure delivers nothing, what the closure sends waits for the next call.
+    let echoes = Arc::new(AtomicUsize::new(0));
+    let echoes_to_count = echoes.clone();
+    on_float(move |_, value| {
+        echoes_to_count.fetch_add(1, Ordering::SeqCst);
+        if value < 3.0 {
+            send_float_to("float_from_rust", value + 1.0).unwrap();
+            receive_messages_from_pd();
+        }
+    });
+    send_float_to("float_from_rust", 0.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(echoes.load(Ordering::SeqCst), 1);
+    assert_eq!(drain_all_messages(), 3);
+    assert_eq!(echoes.load(Ordering::SeqCst), 4);
+}
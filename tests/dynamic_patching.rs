@@ -0,0 +1,102 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    dynamic::CanvasEditor,
+    error::DynamicPatchingError,
+    init, open_patch,
+    receive::{
+        on_float, on_list, receive_messages_from_pd, source_to_listen_from_exists,
+        start_listening_from,
+    },
+    send::send_float_to,
+    types::{Atom, Patch},
+};
+
+#[test]
+fn dynamic_patching() {
+    init().unwrap();
+
+    let floats: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![]));
+    let floats_to_fill = floats.clone();
+    on_float(move |_, value| {
+        floats_to_fill.lock().unwrap().push(value);
+    });
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_, list| {
+        lists_to_fill.lock().unwrap().push(list.to_vec());
+    });
+    let _float_receiver = start_listening_from("dynamic_out").unwrap();
+    let _list_receiver = start_listening_from("voice_freq").unwrap();
+
+    // Building a patch from scratch.
+    let mut patch = open_patch("tests/patches/empty.pd").unwrap();
+    let mut editor = CanvasEditor::new(&mut patch).unwrap();
+    assert!(editor.objects().is_empty());
+
+    let receiver = editor.add_object(20, 20, "r dynamic_in");
+    let multiplier = editor.add_object(20, 60, "* 2");
+    let sender = editor.add_object(20, 100, "s dynamic_out");
+    editor.connect(receiver, 0, multiplier, 0).unwrap();
+    editor.connect(multiplier, 0, sender, 0).unwrap();
+    assert_eq!(editor.objects(), vec![receiver, multiplier, sender]);
+
+    send_float_to("dynamic_in", 21.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![42.0]);
+
+    editor.disconnect(multiplier, 0, sender, 0).unwrap();
+    send_float_to("dynamic_in", 1.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![42.0]);
+
+    // Deleting the first object moves the others up in pd.
+    editor.delete(receiver).unwrap();
+    assert!(!source_to_listen_from_exists("dynamic_in"));
+    assert_eq!(editor.objects(), vec![multiplier, sender]);
+    assert!(matches!(
+        editor.delete(receiver),
+        Err(DynamicPatchingError::UnknownObject)
+    ));
+    assert!(matches!(
+        editor.connect(receiver, 0, sender, 0),
+        Err(DynamicPatchingError::UnknownObject)
+    ));
+
+    let new_receiver = editor.add_object(120, 20, "r dynamic_in");
+    editor.connect(new_receiver, 0, multiplier, 0).unwrap();
+    editor.connect(multiplier, 0, sender, 0).unwrap();
+    send_float_to("dynamic_in", 5.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![42.0, 10.0]);
+
+    // Editing a patch which already has objects in its file.
+    let mut voice = open_patch("tests/patches/voice.pd").unwrap();
+    let dollar_zero = voice.dollar_zero();
+    let mut editor = CanvasEditor::new(&mut voice).unwrap();
+    let existing = editor.objects();
+    assert_eq!(existing.len(), 3);
+
+    let voice_receiver = editor.add_object(120, 20, "r dynamic_voice_in");
+    editor.connect(voice_receiver, 0, existing[1], 0).unwrap();
+    editor.delete(existing[0]).unwrap();
+    assert!(!source_to_listen_from_exists(format!("{dollar_zero}-freq")));
+
+    send_float_to("dynamic_voice_in", 220.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(
+        *lists.lock().unwrap(),
+        vec![vec![
+            Atom::Float(220.0),
+            Atom::Float(f64::from(dollar_zero))
+        ]]
+    );
+
+    let mut closed = Patch::from_raw(std::ptr::null_mut());
+    assert!(matches!(
+        CanvasEditor::new(&mut closed),
+        Err(DynamicPatchingError::PatchNotOpen)
+    ));
+}
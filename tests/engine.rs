@@ -0,0 +1,115 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    engine::PdEngine,
+    error::{EngineError, ProcessError},
+    receive::on_float,
+    receive::receive_messages_from_pd,
+    receive::source_to_listen_from_exists,
+    reinitialize_audio,
+    types::AudioConfig,
+};
+
+#[test]
+fn engine() {
+    let mut engine = PdEngine::new(AudioConfig::stereo_output_44k()).unwrap();
+    assert_eq!(engine.config(), AudioConfig::stereo_output_44k());
+    assert_eq!(engine.output_len(8), 1024);
+    assert_eq!(engine.input_len(8), 0);
+
+    engine.load("tests/patches/sine.pd").unwrap();
+    assert!(engine.patch().is_some());
+
+    // Audio is off until the engine is started.
+    let mut output = vec![1.0_f32; engine.output_len(8)];
+    engine.render(&[], &mut output).unwrap();
+    assert!(output.iter().all(|sample| *sample == 0.0));
+
+    engine.start().unwrap();
+    assert!(engine.is_running());
+    for _ in 0..4 {
+        engine.render(&[], &mut output).unwrap();
+    }
+    assert!(output.iter().any(|sample| *sample != 0.0));
+
+    // The renderer works from another thread.
+    let renderer = engine.renderer();
+    let rendered = std::thread::spawn(move || {
+        let mut output = vec![0.0_f32; 256];
+        renderer.render(&[], &mut output).unwrap();
+        output
+    })
+    .join()
+    .unwrap();
+    assert!(rendered.iter().any(|sample| *sample != 0.0));
+
+    // Buffers which are not whole blocks are refused.
+    let mut output = vec![1.0_f32; 100];
+    assert!(matches!(
+        engine.render(&[], &mut output),
+        Err(ProcessError::OutputBufferLength {
+            expected: 128,
+            actual: 100
+        })
+    ));
+    assert!(output.iter().all(|sample| *sample == 0.0));
+    let mut output = vec![0.0_f32; 128];
+    assert!(matches!(
+        engine.render(&[0.0; 64], &mut output),
+        Err(ProcessError::InputBufferLength {
+            expected: 0,
+            actual: 64
+        })
+    ));
+
+    // Loading replaces the patch and messages are received apart from rendering.
+    engine.load("tests/patches/echo.pd").unwrap();
+    let floats: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![]));
+    let floats_to_fill = floats.clone();
+    on_float(move |source, value| {
+        assert_eq!(source, "float_from_pd");
+        floats_to_fill.lock().unwrap().push(value);
+    });
+    engine.listen("float_from_pd").unwrap();
+    engine.listen("float_from_pd").unwrap();
+    engine.send_float("float_from_rust", 3.0).unwrap();
+    engine.render(&[], &mut output).unwrap();
+    assert!(floats.lock().unwrap().is_empty());
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![3.0]);
+
+    engine.stop_listening("float_from_pd");
+    engine.send_float("float_from_rust", 4.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![3.0]);
+
+    // Another engine is only made with the configuration of audio, which it leaves as it is.
+    assert!(matches!(
+        PdEngine::new(AudioConfig::stereo_48k()),
+        Err(EngineError::AudioConfigMismatch { configured, requested })
+            if configured == AudioConfig::stereo_output_44k() && requested == AudioConfig::stereo_48k()
+    ));
+    drop(PdEngine::new(AudioConfig::stereo_output_44k()).unwrap());
+    assert_eq!(
+        libpd_rs::audio_config(),
+        Some(AudioConfig::stereo_output_44k())
+    );
+
+    // Renderers refuse to render after audio is reinitialized with more output channels.
+    reinitialize_audio(0, 8, 44100).unwrap();
+    let mut output = vec![1.0_f32; 128];
+    assert!(matches!(
+        renderer.render(&[], &mut output),
+        Err(ProcessError::AudioConfigChanged)
+    ));
+    assert!(output.iter().all(|sample| *sample == 0.0));
+    reinitialize_audio(0, 2, 44100).unwrap();
+    renderer.render(&[], &mut output).unwrap();
+
+    // Dropping the engine closes the patch.
+    assert!(source_to_listen_from_exists("float_from_rust"));
+    drop(engine);
+    assert!(!source_to_listen_from_exists("float_from_rust"));
+}
@@ -0,0 +1,86 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::error::{ArrayError, InitializationError, SendError, SizeError};
+
+#[test]
+fn error_codes() {
+    // Does not need pd to be initialized.
+    match ArrayError::from_code(-1, "foo", 0, 4, 8) {
+        ArrayError::FailedToFindArray(array) => assert_eq!(array, "foo"),
+        _ => unreachable!(),
+    }
+    match ArrayError::from_code(-2, "foo", 6, 4, 8) {
+        ArrayError::OutOfBounds {
+            array,
+            offset,
+            requested,
+            array_len,
+        } => assert_eq!(
+            (array.as_str(), offset, requested, array_len),
+            ("foo", 6, 4, 8)
+        ),
+        _ => unreachable!(),
+    }
+    match ArrayError::from_code(-3, "foo", 0, 4, 8) {
+        ArrayError::Unexpected { array, code } => assert_eq!((array.as_str(), code), ("foo", -3)),
+        _ => unreachable!(),
+    }
+
+    match SizeError::from_code(-1, "foo") {
+        SizeError::NonExistent(array) => assert_eq!(array, "foo"),
+        _ => unreachable!(),
+    }
+    assert!(matches!(
+        SizeError::from_code(-3, "foo"),
+        SizeError::Unexpected {
+            code: -3,
+            array: Some(_)
+        }
+    ));
+    assert!(matches!(
+        SizeError::from_message_code(-1, 100_000),
+        SizeError::TooLarge { requested: 100_000 }
+    ));
+    assert!(matches!(
+        SizeError::from_message_code(-2, 100_000),
+        SizeError::Unexpected {
+            code: -2,
+            array: None
+        }
+    ));
+
+    // The context is in the messages.
+    let message = ArrayError::from_code(-2, "foo", 6, 4, 8).to_string();
+    assert!(message.contains("`foo`") && message.contains("`6`") && message.contains("`8`"));
+    assert!(SizeError::from_code(-1, "foo")
+        .to_string()
+        .contains("`foo`"));
+
+    assert!(matches!(SendError::from_code(-1), SendError::OutOfRange));
+    match SendError::from_receiver_code(-1, "foo") {
+        SendError::MissingDestination(receiver) => assert_eq!(receiver, "foo"),
+        _ => unreachable!(),
+    }
+
+    // Codes which libpd doesn't document are not mistaken for documented ones.
+    assert!(matches!(
+        SendError::from_code(-2),
+        SendError::Unexpected {
+            code: -2,
+            receiver: None
+        }
+    ));
+    match SendError::from_receiver_code(1, "foo") {
+        SendError::Unexpected { code, receiver } => {
+            assert_eq!((code, receiver.as_deref()), (1, Some("foo")));
+        }
+        _ => unreachable!(),
+    }
+    assert!(SendError::from_code(-2).to_string().contains("`-2`"));
+    assert!(SendError::from_receiver_code(1, "foo")
+        .to_string()
+        .contains("`foo`"));
+    assert!(InitializationError::Unexpected(-3)
+        .to_string()
+        .contains("`-3`"));
+}
@@ -0,0 +1,59 @@
+#![allow(clippy::restriction)]
+
+use std::error::Error as _;
+
+use libpd_rs::{
+    engine::PdEngine,
+    error::{EngineError, LibpdError, PatchLifeCycleError, SendError},
+    init, open_patch,
+    send::send_float_to,
+    types::AudioConfig,
+};
+
+fn open_and_send(path: &str) -> Result<(), LibpdError> {
+    let _ = init();
+    let patch = open_patch(path)?;
+    send_float_to("float_from_rust", 1.0)?;
+    patch.close()?;
+    Ok(())
+}
+
+#[test]
+fn errors_chain_their_sources() {
+    // Errors of different modules are collected with `?`.
+    open_and_send("tests/patches/echo.pd").unwrap();
+    let err = open_and_send("tests/patches/missing.pd").unwrap_err();
+    assert!(matches!(
+        err,
+        LibpdError::PatchLifeCycle(PatchLifeCycleError::PathDoesNotExist(_))
+    ));
+    // The collecting error is transparent.
+    assert!(err.to_string().contains("missing.pd"));
+    let from_send: LibpdError = SendError::MissingDestination("nowhere".to_owned()).into();
+    assert!(from_send.to_string().contains("nowhere"));
+
+    // Wrapped errors are sources.
+    let err = PdEngine::new(AudioConfig::new(0, 0, 44100)).unwrap_err();
+    assert!(matches!(err, EngineError::Audio(_)));
+    assert!(err.source().is_some());
+
+    let mut patch = open_patch("tests/patches/echo.pd").unwrap();
+    let copy = tempfile::tempdir().unwrap();
+    let moved = copy.path().join("echo.pd");
+    std::fs::copy("tests/patches/echo.pd", &moved).unwrap();
+    drop(patch);
+    patch = open_patch(&moved).unwrap();
+    std::fs::remove_file(&moved).unwrap();
+    let err = patch.reload().unwrap_err();
+    assert!(matches!(
+        err,
+        PatchLifeCycleError::FailedToReopenPatch { .. }
+    ));
+    let source = err.source().unwrap();
+    assert!(matches!(
+        source
+            .downcast_ref::<Box<PatchLifeCycleError>>()
+            .map(AsRef::as_ref),
+        Some(PatchLifeCycleError::PathDoesNotExist(_))
+    ));
+}
@@ -0,0 +1,125 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    error::AtomConversionError,
+    info::float_size,
+    init, open_patch,
+    receive::{on_list, receive_messages_from_pd, start_listening_from},
+    send::send_list_to,
+    types::{Atom, ExtractAtoms, TryIntoAtom, MAX_EXACT_INTEGER},
+};
+
+#[test]
+fn max_exact_integer_follows_the_float_size() {
+    if float_size() == 64 {
+        assert_eq!(MAX_EXACT_INTEGER, 9_007_199_254_740_992);
+    } else {
+        assert_eq!(MAX_EXACT_INTEGER, 16_777_216);
+    }
+}
+
+#[test]
+fn whole_floats_are_exact_integers() {
+    assert_eq!(Atom::from(0).as_exact_i64(), Some(0));
+    assert_eq!(Atom::from(-0.0).as_exact_i64(), Some(0));
+    assert_eq!(Atom::from(127).as_exact_i64(), Some(127));
+    assert_eq!(Atom::from(-1).as_exact_i64(), Some(-1));
+    assert_eq!(Atom::from(16_777_216).as_exact_i64(), Some(16_777_216));
+    assert_eq!(Atom::from(0.5).as_exact_i64(), None);
+    assert_eq!(Atom::from(-0.000_1).as_exact_i64(), None);
+    assert_eq!(Atom::from(f64::NAN).as_exact_i64(), None);
+    assert_eq!(Atom::from(f64::INFINITY).as_exact_i64(), None);
+    assert_eq!(Atom::from("1").as_exact_i64(), None);
+
+    // The bounds of `i64` are powers of two.
+    assert_eq!(
+        Atom::from(-(2.0_f64.powi(63))).as_exact_i64(),
+        Some(i64::MIN)
+    );
+    assert_eq!(Atom::from(2.0_f64.powi(63)).as_exact_i64(), None);
+}
+
+#[test]
+fn integers_are_extracted_in_their_range() {
+    let list = [Atom::from(60), Atom::from(127), Atom::from(3)];
+    let (pitch, velocity, voice): (u8, i8, usize) = list.extract().unwrap();
+    assert_eq!((pitch, velocity, voice), (60, 127, 3));
+
+    let (big, negative): (u64, i16) = [Atom::from(4_294_967_296_u64), Atom::from(-32768)]
+        .extract()
+        .unwrap();
+    assert_eq!((big, negative), (4_294_967_296, -32768));
+
+    assert!(matches!(
+        [Atom::from(256)].extract::<(u8,)>(),
+        Err(AtomConversionError::Element { index: 0, .. })
+    ));
+    assert!(matches!(
+        u32::try_from(&Atom::from(-1)),
+        Err(AtomConversionError::NotAnInteger(value)) if value == -1.0
+    ));
+    assert!(matches!(
+        i8::try_from(&Atom::from(-129)),
+        Err(AtomConversionError::NotAnInteger(_))
+    ));
+    assert!(matches!(
+        u16::try_from(&Atom::from(1.5)),
+        Err(AtomConversionError::NotAnInteger(_))
+    ));
+    assert_eq!(u16::try_from(&Atom::from(65535)).unwrap(), u16::MAX);
+}
+
+#[test]
+fn integers_are_converted_up_to_the_boundary() {
+    assert_eq!(0_u8.try_into_atom().unwrap(), Atom::from(0));
+    assert_eq!(i32::MIN.try_into_atom().is_ok(), float_size() == 64);
+    assert_eq!(
+        MAX_EXACT_INTEGER.try_into_atom().unwrap(),
+        Atom::Float(MAX_EXACT_INTEGER as f64)
+    );
+    assert_eq!(
+        (-MAX_EXACT_INTEGER).try_into_atom().unwrap(),
+        Atom::Float(-MAX_EXACT_INTEGER as f64)
+    );
+    assert!(matches!(
+        (MAX_EXACT_INTEGER + 1).try_into_atom(),
+        Err(AtomConversionError::NotExactInteger(value)) if value == (MAX_EXACT_INTEGER + 1).to_string()
+    ));
+    assert!((-MAX_EXACT_INTEGER - 1).try_into_atom().is_err());
+    assert!(u64::MAX.try_into_atom().is_err());
+    assert!(u128::MAX.try_into_atom().is_err());
+    assert!(i128::MIN.try_into_atom().is_err());
+
+    // The `2^24` boundary of 32 bit floats.
+    assert!(16_777_216_i32.try_into_atom().is_ok());
+    assert_eq!(16_777_217_i32.try_into_atom().is_ok(), float_size() == 64);
+    assert_eq!(
+        (-16_777_217_i64).try_into_atom().is_ok(),
+        float_size() == 64
+    );
+}
+
+#[test]
+fn exact_integers_arrive_unchanged() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    start_listening_from("list_from_pd").unwrap();
+    let received: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let received_to_fill = received.clone();
+    on_list(move |_, list| received_to_fill.lock().unwrap().push(list.to_vec()));
+
+    let boundary = [
+        MAX_EXACT_INTEGER.try_into_atom().unwrap(),
+        (1 - MAX_EXACT_INTEGER).try_into_atom().unwrap(),
+    ];
+    send_list_to("list_from_rust", &boundary).unwrap();
+    receive_messages_from_pd();
+
+    let received = received.lock().unwrap();
+    let (max, min): (i64, i64) = received.first().unwrap().extract().unwrap();
+    assert_eq!((max, min), (MAX_EXACT_INTEGER, 1 - MAX_EXACT_INTEGER));
+
+    patch.close().unwrap();
+}
@@ -0,0 +1,49 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "gui")]
+
+use libpd_rs::{
+    error::GuiLifeCycleError,
+    gui::{find_pd_gui, start_gui_auto},
+};
+
+#[test]
+fn find_pd_gui_in_path() {
+    let installation = tempfile::tempdir().unwrap();
+    let root = std::fs::canonicalize(installation.path()).unwrap();
+    let binary = if cfg!(windows) { "pd.exe" } else { "pd" };
+    std::fs::create_dir_all(root.join("bin")).unwrap();
+    std::fs::create_dir_all(root.join("tcl")).unwrap();
+    std::fs::write(root.join("bin").join(binary), "").unwrap();
+
+    // The binary without the gui script is not an installation.
+    let path = std::env::join_paths([root.join("bin")]).unwrap();
+    std::env::set_var("PATH", &path);
+    if let Some(found) = find_pd_gui() {
+        assert_ne!(found, root);
+    }
+
+    std::fs::write(root.join("tcl").join("pd-gui.tcl"), "").unwrap();
+    assert_eq!(find_pd_gui(), Some(root.clone()));
+
+    // Links to the binary are followed to the installation.
+    #[cfg(unix)]
+    {
+        let shims = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(root.join("bin").join("pd"), shims.path().join("pd")).unwrap();
+        std::env::set_var("PATH", std::env::join_paths([shims.path()]).unwrap());
+        assert_eq!(find_pd_gui(), Some(root.clone()));
+    }
+
+    // The probed locations are listed when nothing is found.
+    std::env::set_var("PATH", "");
+    if find_pd_gui().is_none() {
+        match start_gui_auto() {
+            Err(GuiLifeCycleError::PdNotFound(probed)) => {
+                #[cfg(target_os = "linux")]
+                assert!(probed.contains(&std::path::PathBuf::from("/usr/lib/puredata")));
+                assert!(!probed.contains(&root));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -2,8 +2,11 @@
 #![allow(clippy::unnecessary_cast)]
 
 use libpd_rs::{
-    add_to_search_paths, block_size, clear_search_paths, close_patch, get_dollar_zero, init,
-    initialize_audio, open_patch, release_internal_queues, types::PatchFileHandle,
+    add_to_search_paths, audio_config, block_size, clear_search_paths, close_patch,
+    error::AudioInitializationError,
+    get_dollar_zero, init, initialize_audio, initialize_audio_with_config, open_patch,
+    release_internal_queues,
+    types::{AudioConfig, Patch},
     verbose_print_state, verbose_print_state_active,
 };
 
@@ -18,6 +21,40 @@ fn all_main_functionality() {
     assert!(result.is_ok());
     let result = initialize_audio(0, 1, 44100);
     assert!(result.is_ok());
+    let result = initialize_audio(0, 2, 0);
+    assert!(matches!(
+        result,
+        Err(AudioInitializationError::InvalidSampleRate(0))
+    ));
+    let result = initialize_audio(0, 0, 44100);
+    assert!(matches!(
+        result,
+        Err(AudioInitializationError::InvalidChannelCount {
+            input: 0,
+            output: 0
+        })
+    ));
+    let result = initialize_audio(-1, 2, 44100);
+    assert!(matches!(
+        result,
+        Err(AudioInitializationError::InvalidChannelCount {
+            input: -1,
+            output: 2
+        })
+    ));
+    assert_eq!(audio_config().unwrap().output_channels, 1);
+
+    let result = initialize_audio_with_config(AudioConfig::default());
+    assert!(result.is_ok());
+    assert_eq!(audio_config(), Some(AudioConfig::stereo_44k()));
+    let result = initialize_audio_with_config(AudioConfig::mono_48k().with_sample_rate(-1));
+    assert!(matches!(
+        result,
+        Err(AudioInitializationError::InvalidSampleRate(-1))
+    ));
+    let result = initialize_audio_with_config(AudioConfig::stereo_output_48k());
+    assert!(result.is_ok());
+    assert_eq!(audio_config(), Some(AudioConfig::new(0, 2, 48000)));
 
     let result = open_patch("tests/patches/no_eresultistent.pd");
     assert!(result.is_err());
@@ -25,11 +62,25 @@ fn all_main_functionality() {
     assert!(result.is_err());
     let result = open_patch("tests/patches/simple.pd");
     assert!(result.is_ok());
+    let handle = result.unwrap();
+    assert!(handle.directory().ends_with("tests/patches"));
+    assert!(handle.directory().join("simple.pd").exists());
+    assert_eq!(handle.file_name(), "simple.pd");
+    assert!(handle.path().is_absolute());
+    assert!(handle.path().ends_with("tests/patches/simple.pd"));
+    assert_eq!(handle.dollar_zero(), get_dollar_zero(&handle).unwrap());
+    assert_ne!(handle.dollar_zero(), 0);
 
-    let result = close_patch(result.unwrap());
+    // Raw handles survive a round trip.
+    let handle = Patch::from_raw(handle.into_raw());
+    assert_eq!(handle.dollar_zero(), 0);
+    assert_ne!(get_dollar_zero(&handle).unwrap(), 0);
+
+    let result = close_patch(handle);
     assert!(result.is_ok());
 
-    let handle: PatchFileHandle = (std::ptr::null_mut() as *mut std::ffi::c_void).into();
+    let handle: Patch = (std::ptr::null_mut() as *mut std::ffi::c_void).into();
+    assert_eq!(handle.directory(), std::path::Path::new(""));
     let result = close_patch(handle);
     assert!(result.is_err());
 
@@ -47,7 +98,7 @@ fn all_main_functionality() {
     assert!(result.is_ok());
     assert_ne!(result.unwrap(), 0);
 
-    let handle: PatchFileHandle = (std::ptr::null_mut() as *mut std::ffi::c_void).into();
+    let handle: Patch = (std::ptr::null_mut() as *mut std::ffi::c_void).into();
     let result = get_dollar_zero(&handle);
     assert!(result.is_err());
 
@@ -0,0 +1,52 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "gui")]
+
+use std::time::{Duration, Instant};
+
+use libpd_rs::{
+    gui::{poll_gui_for, poll_gui_once, spawn_gui_poller, stop_gui},
+    with_pd_lock,
+};
+
+fn wait_until(condition: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    false
+}
+
+#[test]
+fn gui_poller() {
+    libpd_rs::init().unwrap();
+
+    // There is no gui work without a gui.
+    assert!(!poll_gui_once());
+    let started = Instant::now();
+    assert_eq!(poll_gui_for(Duration::from_secs(5)), 0);
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(poll_gui_for(Duration::ZERO), 0);
+
+    // Dropping the poller stops the thread.
+    let poller = spawn_gui_poller(Duration::from_millis(1));
+    assert!(poller.is_running());
+    std::thread::sleep(Duration::from_millis(10));
+    drop(poller);
+
+    // The poller pauses while pd is locked.
+    let poller = spawn_gui_poller(Duration::from_secs(60));
+    with_pd_lock(|| std::thread::sleep(Duration::from_millis(10)));
+    assert!(poller.is_running());
+    // Stopping wakes the thread up before the interval ends.
+    let started = Instant::now();
+    poller.stop();
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    // The poller stops with the gui.
+    let poller = spawn_gui_poller(Duration::from_millis(1));
+    stop_gui();
+    assert!(wait_until(|| !poller.is_running()));
+}
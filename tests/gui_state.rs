@@ -0,0 +1,26 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "gui")]
+
+use libpd_rs::{
+    error::GuiLifeCycleError,
+    gui::{gui_is_running, start_gui, stop_gui},
+};
+
+#[test]
+fn gui_state() {
+    libpd_rs::init().unwrap();
+    assert!(!gui_is_running());
+
+    // Stopping without a gui does nothing.
+    stop_gui();
+    assert!(!gui_is_running());
+
+    // Folders which are not pd installations are refused before launching anything.
+    for path in ["", "tests", "tests/patches/sine.pd"] {
+        match start_gui(path) {
+            Err(GuiLifeCycleError::NotFound(not_found)) => assert_eq!(not_found, path),
+            _ => unreachable!(),
+        }
+    }
+    assert!(!gui_is_running());
+}
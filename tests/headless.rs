@@ -0,0 +1,38 @@
+#![allow(clippy::restriction)]
+#![cfg(not(feature = "gui"))]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    block_size, close_patch, init, initialize_audio, open_patch,
+    process::process_float,
+    receive::{on_float, receive_messages_from_pd, start_listening_from, stop_listening_from},
+    send::{send_float_to, send_message_to},
+    types::Atom,
+};
+
+#[test]
+fn headless() {
+    init().unwrap();
+    initialize_audio(0, 2, 44100).unwrap();
+
+    // Audio is processed without a gui.
+    let sine = open_patch("tests/patches/sine.pd").unwrap();
+    send_message_to("pd", "dsp", &[Atom::from(1)]).unwrap();
+    let mut output = vec![0.0_f32; block_size() as usize * 2 * 4];
+    process_float(4, &[], &mut output);
+    assert!(output.iter().any(|sample| *sample != 0.0));
+    close_patch(sine).unwrap();
+
+    // Messages are exchanged without a gui.
+    let echo = open_patch("tests/patches/echo.pd").unwrap();
+    let floats: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![]));
+    let floats_to_fill = floats.clone();
+    on_float(move |_source, value| floats_to_fill.lock().unwrap().push(value));
+    let receiver = start_listening_from("float_from_pd").unwrap();
+    send_float_to("float_from_rust", 42.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![42.0]);
+    stop_listening_from(receiver);
+    close_patch(echo).unwrap();
+}
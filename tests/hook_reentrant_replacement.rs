@@ -0,0 +1,47 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{on_float, receive_messages_from_pd, start_listening_from},
+    send::send_float_to,
+};
+
+#[test]
+fn closures_could_replace_themselves() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    start_listening_from("float_from_pd").unwrap();
+    let received: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+    let replaced_while_running = Arc::new(Mutex::new(None));
+
+    let first = received.clone();
+    let replaced = replaced_while_running.clone();
+    on_float(move |_: &str, value: f32| {
+        let second = first.clone();
+        let previous = on_float(move |_: &str, value: f32| {
+            second.lock().unwrap().push(format!("second {value}"));
+        });
+        *replaced.lock().unwrap() = Some(previous.is_some());
+        // The closure is still alive after it is replaced.
+        first.lock().unwrap().push(format!("first {value}"));
+    });
+
+    send_float_to("float_from_rust", 1.0).unwrap();
+    send_float_to("float_from_rust", 2.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*received.lock().unwrap(), vec!["first 1", "second 2"]);
+    // The running closure is not handed back, it is dropped when receiving finishes.
+    assert_eq!(*replaced_while_running.lock().unwrap(), Some(false));
+    assert_eq!(Arc::strong_count(&replaced_while_running), 1);
+
+    send_float_to("float_from_rust", 3.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec!["first 1", "second 2", "second 3"]
+    );
+
+    patch.close().unwrap();
+}
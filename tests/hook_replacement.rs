@@ -0,0 +1,97 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{
+        on_bang, on_double, on_float, on_list, on_list_borrowed, receive_messages_from_pd,
+        start_listening_from,
+    },
+    send::{send_bang_to, send_float_to, send_list_to},
+    types::{Atom, AtomView},
+};
+
+type Received = Arc<Mutex<Vec<String>>>;
+
+fn recorder(received: &Received, name: &'static str) -> impl FnMut(&str, f32) + Send + Sync {
+    let received = received.clone();
+    move |_: &str, value: f32| received.lock().unwrap().push(format!("{name} {value}"))
+}
+
+#[test]
+fn replaced_closures_are_returned() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    start_listening_from("float_from_pd").unwrap();
+    start_listening_from("bang_from_pd").unwrap();
+    start_listening_from("list_from_pd").unwrap();
+    let received: Received = Arc::new(Mutex::new(vec![]));
+
+    // Nothing is replaced by the first closure.
+    assert!(on_float(recorder(&received, "first")).is_none());
+    let first = on_float(recorder(&received, "second")).expect("the first closure is replaced");
+    send_float_to("float_from_rust", 1.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*received.lock().unwrap(), vec!["second 1"]);
+
+    // The replaced closure could be restored.
+    let second = on_float(first).expect("the second closure is replaced");
+    send_float_to("float_from_rust", 2.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*received.lock().unwrap(), vec!["second 1", "first 2"]);
+
+    // Or be chained from the new closure.
+    let mut second = second;
+    let chained = received.clone();
+    let first = on_float(move |source: &str, value: f32| {
+        chained.lock().unwrap().push(format!("chained {value}"));
+        second(source, value);
+    })
+    .unwrap();
+    send_float_to("float_from_rust", 3.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec!["second 1", "first 2", "chained 3", "second 3"]
+    );
+
+    // The float and the double closures share a hook, the replaced one is converted.
+    let mut first = first;
+    let doubles = received.clone();
+    on_double(move |_: &str, value: f64| doubles.lock().unwrap().push(format!("double {value}")));
+    let double = on_float(move |source: &str, value: f32| first(source, value)).unwrap();
+    let mut double = double;
+    double("float_from_pd", 4.5);
+    assert_eq!(received.lock().unwrap().last().unwrap(), "double 4.5");
+    received.lock().unwrap().clear();
+
+    // Other hooks return their closures too.
+    let bangs = Arc::new(Mutex::new(0));
+    let bangs_to_count = bangs.clone();
+    on_bang(move |_| *bangs_to_count.lock().unwrap() += 1);
+    let mut counter = on_bang(|_| {}).unwrap();
+    counter("bang_from_pd");
+    send_bang_to("bang_from_rust").unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*bangs.lock().unwrap(), 1);
+
+    // The borrowed and owned list closures share a hook too.
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_, list| lists_to_fill.lock().unwrap().push(list.to_vec()));
+    let mut owned = on_list_borrowed(|_, _| {}).unwrap();
+    owned(
+        "list_from_pd",
+        &[AtomView::Float(1.0), AtomView::Symbol("a")],
+    );
+    assert!(on_list_borrowed(owned).is_some());
+    send_list_to("list_from_rust", &[Atom::from(3)]).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(
+        *lists.lock().unwrap(),
+        vec![vec![Atom::from(1), Atom::from("a")], vec![Atom::from(3)]]
+    );
+
+    patch.close().unwrap();
+}
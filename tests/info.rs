@@ -0,0 +1,21 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::info::{block_size, float_size, instance_count, pd_version, BLOCK_SIZE};
+
+#[test]
+fn info() {
+    // The version is known before pd is initialized.
+    let (major, minor, bugfix) = pd_version();
+    assert_eq!(major, 0);
+    assert!(minor >= 50);
+    assert!(bugfix >= 0);
+
+    assert!(float_size() == 32 || float_size() == 64);
+    // The block size is compiled in, it is never a default which changes after init.
+    assert_eq!(block_size() as usize, BLOCK_SIZE);
+
+    libpd_rs::init().unwrap();
+    assert_eq!(instance_count(), 1);
+    assert_eq!(block_size(), 64);
+    assert_eq!(block_size() as usize, BLOCK_SIZE);
+}
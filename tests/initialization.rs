@@ -0,0 +1,29 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{error::InitializationError, init, is_initialized, release_internal_queues};
+
+#[test]
+fn guarded_initialization() {
+    assert!(!is_initialized());
+
+    let handles: Vec<_> = (0..8).map(|_| std::thread::spawn(init)).collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+    assert!(results
+        .iter()
+        .filter_map(|result| result.as_ref().err())
+        .all(|err| matches!(err, InitializationError::AlreadyInitialized)));
+    assert!(is_initialized());
+
+    release_internal_queues();
+    assert!(!is_initialized());
+
+    // Initializing after a release re-creates the queues.
+    assert!(init().is_ok());
+    assert!(is_initialized());
+    assert!(matches!(
+        init(),
+        Err(InitializationError::AlreadyInitialized)
+    ));
+}
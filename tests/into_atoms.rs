@@ -0,0 +1,65 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{on_list, receive_messages_from_pd, start_listening_from},
+    send::{send_list_to, send_message_to},
+    types::{Atom, IntoAtoms},
+};
+
+#[test]
+fn values_are_sent_as_atoms() {
+    assert_eq!((1,).len_hint(), 1);
+    assert_eq!((0.0, 50.0, 1.0, 200.0).len_hint(), 4);
+    assert_eq!((1, 2, 3, 4, 5, 6, 7, "h").len_hint(), 8);
+    assert_eq!(["a", "b"].len_hint(), 2);
+    assert_eq!(vec![Atom::from(1)].len_hint(), 1);
+    assert_eq!(
+        ("saw", 440_f32, 0.5).into_atoms(),
+        vec![Atom::from("saw"), Atom::from(440), Atom::from(0.5)]
+    );
+    assert_eq!(
+        [1.5_f64, 2.5].as_slice().into_atoms(),
+        vec![Atom::from(1.5), Atom::from(2.5)]
+    );
+
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_source, list| lists_to_fill.lock().unwrap().push(list.to_vec()));
+    let _handle = start_listening_from("list_from_pd").unwrap();
+
+    let atoms = vec![Atom::from(1), Atom::from("a")];
+    let floats = [0.25_f32, 0.5];
+    let doubles = vec![0.75_f64];
+    send_list_to("list_from_rust", (0.0, 50.0, 1.0, 200.0)).unwrap();
+    send_list_to("list_from_rust", ("env", 1_i32, 2.5_f32)).unwrap();
+    send_list_to("list_from_rust", &atoms).unwrap();
+    send_list_to("list_from_rust", atoms.as_slice()).unwrap();
+    send_list_to("list_from_rust", atoms.clone()).unwrap();
+    send_list_to("list_from_rust", &[Atom::from(2)]).unwrap();
+    send_list_to("list_from_rust", [3, 4]).unwrap();
+    send_list_to("list_from_rust", floats.as_slice()).unwrap();
+    send_list_to("list_from_rust", doubles.as_slice()).unwrap();
+    send_message_to("list_from_rust", "set", (5, "b")).unwrap();
+    receive_messages_from_pd();
+
+    let expected: Vec<Vec<Atom>> = vec![
+        vec![0.into(), 50.into(), 1.into(), 200.into()],
+        vec!["env".into(), 1.into(), 2.5.into()],
+        atoms.clone(),
+        atoms.clone(),
+        atoms,
+        vec![2.into()],
+        vec![3.into(), 4.into()],
+        vec![0.25.into(), 0.5.into()],
+        vec![0.75.into()],
+    ];
+    assert_eq!(*lists.lock().unwrap(), expected);
+
+    patch.close().unwrap();
+}
@@ -4,6 +4,7 @@
 use libpd_rs::{
     close_patch,
     convenience::dsp_on,
+    error::SubscriptionError,
     init, initialize_audio, open_patch,
     receive::{source_to_listen_from_exists, start_listening_from, stop_listening_from},
     types::ReceiverHandle,
@@ -26,6 +27,10 @@ fn listening() {
     let result = start_listening_from("non_existent");
     assert!(result.is_ok());
 
+    // An empty name can not be bound to.
+    let result = start_listening_from("");
+    assert!(matches!(result, Err(SubscriptionError::EmptySourceName)));
+
     let handle_1 = start_listening_from("list_from_pd").unwrap();
     let handle_2 = start_listening_from("list_from_pd").unwrap();
 
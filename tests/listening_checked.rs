@@ -0,0 +1,34 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    error::SubscriptionError,
+    init, open_patch,
+    receive::{start_listening_from_checked, stop_listening_from},
+};
+
+#[test]
+fn listening_checked() {
+    init().unwrap();
+
+    let (handle, newly_bound) = start_listening_from_checked("checked_source").unwrap();
+    assert!(newly_bound);
+    let (other_handle, newly_bound) = start_listening_from_checked("checked_source").unwrap();
+    assert!(!newly_bound);
+    stop_listening_from(handle);
+    stop_listening_from(other_handle);
+    let (handle, newly_bound) = start_listening_from_checked("checked_source").unwrap();
+    assert!(newly_bound);
+    stop_listening_from(handle);
+
+    // A receiver in a patch is bound too.
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    let (handle, newly_bound) = start_listening_from_checked("float_from_rust").unwrap();
+    assert!(!newly_bound);
+    stop_listening_from(handle);
+    patch.close().unwrap();
+
+    assert!(matches!(
+        start_listening_from_checked(""),
+        Err(SubscriptionError::EmptySourceName)
+    ));
+}
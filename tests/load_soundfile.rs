@@ -0,0 +1,94 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    array::{array_size, load_soundfile_to_array, read_float_array_from},
+    close_patch,
+    error::{SendError, SoundfileError},
+    init, open_patch,
+    receive::{on_print, receive_messages_from_pd},
+};
+
+/// Writes a mono 16 bit WAV file.
+fn write_wav(path: &std::path::Path, samples: &[i16]) {
+    let data_length = (samples.len() * 2) as u32;
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_length).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16_u32.to_le_bytes());
+    bytes.extend_from_slice(&1_u16.to_le_bytes());
+    bytes.extend_from_slice(&1_u16.to_le_bytes());
+    bytes.extend_from_slice(&44100_u32.to_le_bytes());
+    bytes.extend_from_slice(&(44100_u32 * 2).to_le_bytes());
+    bytes.extend_from_slice(&2_u16.to_le_bytes());
+    bytes.extend_from_slice(&16_u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_length.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    std::fs::write(path, bytes).unwrap();
+}
+
+#[test]
+fn load_soundfile() {
+    init().unwrap();
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("half.wav");
+    write_wav(&file, &[16384; 300]);
+
+    let patch = open_patch("tests/patches/soundfiler.pd").unwrap();
+    assert_eq!(array_size("sample").unwrap(), 10);
+
+    // The array is resized and filled when the function returns.
+    assert_eq!(
+        load_soundfile_to_array(&patch, &file, "sample").unwrap(),
+        300
+    );
+    assert_eq!(array_size("sample").unwrap(), 300);
+    let mut samples = vec![0.0_f32; 300];
+    read_float_array_from("sample", 0, 300, &mut samples).unwrap();
+    assert!(samples.iter().all(|sample| *sample == 0.5));
+
+    assert!(matches!(
+        load_soundfile_to_array(&patch, directory.path().join("missing.wav"), "sample"),
+        Err(SoundfileError::FileNotFound(_))
+    ));
+    // The soundfiler can't read files which are not sound files.
+    let printed = Arc::new(Mutex::new(String::new()));
+    let printed_to_fill = printed.clone();
+    on_print(move |text| printed_to_fill.lock().unwrap().push_str(text));
+    let not_a_sound = directory.path().join("text.wav");
+    std::fs::write(&not_a_sound, "not a sound file").unwrap();
+    match load_soundfile_to_array(&patch, &not_a_sound, "sample") {
+        Err(SoundfileError::FailedToRead {
+            path,
+            console_output,
+        }) => {
+            assert!(path.ends_with("text.wav"));
+            assert!(!console_output.is_empty());
+        }
+        _ => unreachable!(),
+    }
+    // Loading does not receive the messages of the queue, the errors are printed when they are received.
+    assert!(printed.lock().unwrap().is_empty());
+    receive_messages_from_pd();
+    assert!(printed.lock().unwrap().contains("soundfiler read"));
+    assert_eq!(array_size("sample").unwrap(), 300);
+    match load_soundfile_to_array(&patch, &file, "missing") {
+        Err(SoundfileError::MissingArray(array)) => assert_eq!(array, "missing"),
+        _ => unreachable!(),
+    }
+
+    // Patches without a soundfiler can't load.
+    let sine = open_patch("tests/patches/sine.pd").unwrap();
+    assert!(matches!(
+        load_soundfile_to_array(&sine, &file, "sample"),
+        Err(SoundfileError::Send(SendError::MissingDestination(_)))
+    ));
+
+    close_patch(sine).unwrap();
+    close_patch(patch).unwrap();
+}
@@ -0,0 +1,130 @@
+#![allow(clippy::restriction)]
+
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{active_subscriptions, on_bang, receive_messages_from_pd, take_last_callback_panic},
+    send::{send_bang_to, send_float_to, send_list_to, send_message_to, send_symbol_to},
+    sink::{subscribe_with, unsubscribe_sink, ClosureSink, MessageSink, ReceivedMessage, VecSink},
+    types::Atom,
+};
+
+struct Bangs(Arc<Mutex<usize>>);
+
+impl MessageSink for Bangs {
+    fn on_bang(&mut self, _source: &str) {
+        *self.0.lock().unwrap() += 1;
+    }
+}
+
+#[test]
+fn messages_are_routed_to_sinks() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let collected = VecSink::new();
+    let collected_handle = subscribe_with("list_from_pd", collected.clone()).unwrap();
+    let (sender, receiver) = channel();
+    let _float_handle = subscribe_with("float_from_pd", sender).unwrap();
+    let symbols = Arc::new(Mutex::new(vec![]));
+    let symbols_to_fill = symbols.clone();
+    let _symbol_handle = subscribe_with(
+        "symbol_from_pd",
+        ClosureSink(move |source: &str, message| {
+            symbols_to_fill
+                .lock()
+                .unwrap()
+                .push((source.to_owned(), message));
+        }),
+    )
+    .unwrap();
+    let bangs = Arc::new(Mutex::new(0));
+    let _bang_handle = subscribe_with("bang_from_pd", Bangs(bangs.clone())).unwrap();
+    // A source could have many sinks.
+    let also_collected = VecSink::new();
+    let also_handle = subscribe_with("list_from_pd", also_collected.clone()).unwrap();
+
+    send_list_to("list_from_rust", &[Atom::from(1), Atom::from("a")]).unwrap();
+    send_message_to("list_from_rust", "set", &[Atom::from(2)]).unwrap();
+    send_float_to("float_from_rust", 42.0).unwrap();
+    send_symbol_to("symbol_from_rust", "saw").unwrap();
+    send_bang_to("bang_from_rust").unwrap();
+    receive_messages_from_pd();
+
+    let expected = vec![
+        (
+            "list_from_pd".to_owned(),
+            ReceivedMessage::List(vec![Atom::from(1), Atom::from("a")]),
+        ),
+        (
+            "list_from_pd".to_owned(),
+            ReceivedMessage::Message {
+                selector: "set".to_owned(),
+                arguments: vec![Atom::from(2)],
+            },
+        ),
+    ];
+    assert_eq!(collected.take(), expected);
+    assert_eq!(also_collected.take(), expected);
+    assert_eq!(
+        receiver.try_iter().collect::<Vec<_>>(),
+        vec![("float_from_pd".to_owned(), ReceivedMessage::Float(42.0))]
+    );
+    assert_eq!(
+        *symbols.lock().unwrap(),
+        vec![(
+            "symbol_from_pd".to_owned(),
+            ReceivedMessage::Symbol("saw".to_owned())
+        )]
+    );
+    assert_eq!(*bangs.lock().unwrap(), 1);
+
+    // Pd is bound to a source once whatever the number of its sinks is.
+    let bound = active_subscriptions();
+    assert_eq!(
+        bound.iter().filter(|name| *name == "list_from_pd").count(),
+        1
+    );
+
+    // Unsubscribing drops the sink, the source is unbound with its last sink.
+    unsubscribe_sink(collected_handle);
+    send_list_to("list_from_rust", &[Atom::from(3)]).unwrap();
+    receive_messages_from_pd();
+    assert!(collected.take().is_empty());
+    assert_eq!(also_collected.take().len(), 1);
+    unsubscribe_sink(also_handle);
+    assert!(!active_subscriptions().contains(&"list_from_pd".to_owned()));
+
+    // Closures which are registered before or after subscribing receive the messages along with the sinks.
+    let bangs_from_closure = Arc::new(Mutex::new(0));
+    let bangs_to_count = bangs_from_closure.clone();
+    on_bang(move |_| *bangs_to_count.lock().unwrap() += 1);
+    let _other_handle = subscribe_with("bang_from_pd", VecSink::new()).unwrap();
+    send_bang_to("bang_from_rust").unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*bangs_from_closure.lock().unwrap(), 1);
+    assert_eq!(*bangs.lock().unwrap(), 2);
+
+    // A sink which panics is not called again, the others still are.
+    struct Panicking;
+    impl MessageSink for Panicking {
+        fn on_bang(&mut self, _source: &str) {
+            panic!("sink panicked");
+        }
+    }
+    let _panicking_handle = subscribe_with("bang_from_pd", Panicking).unwrap();
+    send_bang_to("bang_from_rust").unwrap();
+    send_bang_to("bang_from_rust").unwrap();
+    receive_messages_from_pd();
+    assert_eq!(
+        take_last_callback_panic().unwrap().callback,
+        "subscribe_with"
+    );
+    assert_eq!(*bangs_from_closure.lock().unwrap(), 3);
+    assert_eq!(*bangs.lock().unwrap(), 4);
+    assert!(take_last_callback_panic().is_none());
+
+    patch.close().unwrap();
+}
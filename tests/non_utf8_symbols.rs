@@ -0,0 +1,73 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{
+        on_list, on_list_borrowed, on_message, on_message_borrowed, on_symbol,
+        receive_messages_from_pd, start_listening_from, take_last_callback_panic,
+    },
+    send::send_float_to,
+    types::{Atom, AtomView},
+};
+
+type Messages = Arc<Mutex<Vec<(String, Vec<Atom>)>>>;
+
+#[test]
+fn symbols_which_are_not_utf8_are_received_lossily() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/non_utf8_symbol.pd").unwrap();
+    start_listening_from("non_utf8_from_pd").unwrap();
+    let expected_list = vec![Atom::from("caf\u{fffd}"), Atom::from("na\u{fffd}ve")];
+    let expected_message = vec![("caf\u{fffd}".to_owned(), vec![Atom::from(1)])];
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_: &str, list: &[Atom]| {
+        lists_to_fill.lock().unwrap().push(list.to_vec());
+    });
+    let messages: Messages = Arc::new(Mutex::new(vec![]));
+    let messages_to_fill = messages.clone();
+    on_message(move |_: &str, selector: &str, arguments: &[Atom]| {
+        messages_to_fill
+            .lock()
+            .unwrap()
+            .push((selector.to_owned(), arguments.to_vec()));
+    });
+    let symbols: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+    let symbols_to_fill = symbols.clone();
+    on_symbol(move |_: &str, symbol: &str| {
+        symbols_to_fill.lock().unwrap().push(symbol.to_owned());
+    });
+    send_float_to("non_utf8_to_pd", 0.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*lists.lock().unwrap(), vec![expected_list.clone()]);
+    assert_eq!(*messages.lock().unwrap(), expected_message);
+    assert_eq!(*symbols.lock().unwrap(), vec!["na\u{fffd}ve".to_owned()]);
+
+    let views: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let views_to_fill = views.clone();
+    on_list_borrowed(move |_: &str, list: &[AtomView]| {
+        views_to_fill
+            .lock()
+            .unwrap()
+            .push(list.iter().map(AtomView::to_atom).collect());
+    });
+    let message_views: Messages = Arc::new(Mutex::new(vec![]));
+    let message_views_to_fill = message_views.clone();
+    on_message_borrowed(move |_: &str, selector: &str, arguments: &[AtomView]| {
+        message_views_to_fill.lock().unwrap().push((
+            selector.to_owned(),
+            arguments.iter().map(AtomView::to_atom).collect(),
+        ));
+    });
+    send_float_to("non_utf8_to_pd", 0.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*views.lock().unwrap(), vec![expected_list]);
+    assert_eq!(*message_views.lock().unwrap(), expected_message);
+    assert_eq!(symbols.lock().unwrap().len(), 2);
+    assert!(take_last_callback_panic().is_none());
+
+    patch.close().unwrap();
+}
@@ -0,0 +1,59 @@
+#![allow(clippy::restriction)]
+#![cfg(not(feature = "skip-init-checks"))]
+
+use libpd_rs::{
+    array::{array_exists, array_size, read_float_array_from},
+    close_patch,
+    error::{
+        ArrayError, AudioInitializationError, PatchLifeCycleError, SendError, SizeError,
+        SubscriptionError,
+    },
+    init, initialize_audio, open_patch,
+    process::process_float,
+    receive::{source_to_listen_from_exists, start_listening_from},
+    send::{send_float_to, send_note_on, start_message},
+};
+
+#[test]
+fn use_before_init() {
+    assert!(matches!(
+        send_float_to("foo", 1.0),
+        Err(SendError::NotInitialized)
+    ));
+    assert!(matches!(
+        send_note_on(0, 60, 64),
+        Err(SendError::NotInitialized)
+    ));
+    assert!(matches!(start_message(1), Err(SizeError::NotInitialized)));
+    assert!(matches!(array_size("foo"), Err(SizeError::NotInitialized)));
+    assert!(!array_exists("foo"));
+    let mut destination = [0.0_f32; 4];
+    assert!(matches!(
+        read_float_array_from("foo", 0, 4, &mut destination),
+        Err(ArrayError::NotInitialized)
+    ));
+    assert!(matches!(
+        start_listening_from("foo"),
+        Err(SubscriptionError::NotInitialized)
+    ));
+    assert!(!source_to_listen_from_exists("foo"));
+    assert!(matches!(
+        open_patch("tests/patches/simple.pd"),
+        Err(PatchLifeCycleError::NotInitialized)
+    ));
+    assert!(matches!(
+        initialize_audio(0, 2, 44100),
+        Err(AudioInitializationError::NotInitialized)
+    ));
+
+    let mut output_buffer = [1.0_f32; 128];
+    process_float(1, &[], &mut output_buffer);
+    assert!(output_buffer.iter().all(|sample| *sample == 0.0));
+
+    // Everything works as usual after initialization.
+    init().unwrap();
+    initialize_audio(0, 2, 44100).unwrap();
+    let patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+    assert!(send_float_to("float_from_rust", 1.0).is_ok());
+    close_patch(patch_handle).unwrap();
+}
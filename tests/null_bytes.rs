@@ -0,0 +1,131 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    add_to_search_paths,
+    array::{
+        array_exists, array_size, read_double_array_from, read_float_array_from, resize_array,
+        write_double_array_to, write_float_array_to,
+    },
+    dynamic::CanvasEditor,
+    error::{ArrayError, IoError, PatchLifeCycleError, SendError, SizeError, SubscriptionError},
+    init, open_patch, open_patch_with_args,
+    receive::{source_to_listen_from_exists, start_listening_from},
+    send::{
+        add_symbol_to_started_message, finish_message_as_list_and_send_to,
+        finish_message_as_typed_message_and_send_to, send_bang_to, send_double_to, send_float_to,
+        send_list_to, send_message_to, send_symbol_to, start_message,
+    },
+    types::Atom,
+};
+
+const NAMES: [&str; 4] = ["\0", "foo\0", "\0foo", "foo\0bar"];
+
+fn is_invalid_send(result: Result<(), SendError>) -> bool {
+    matches!(result, Err(SendError::InvalidString { .. }))
+}
+
+#[test]
+fn null_bytes_are_errors_instead_of_panics() {
+    init().unwrap();
+    let mut patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    for name in NAMES {
+        assert!(is_invalid_send(send_bang_to(name)));
+        assert!(is_invalid_send(send_float_to(name, 1.0)));
+        assert!(is_invalid_send(send_double_to(name, 1.0)));
+        assert!(is_invalid_send(send_symbol_to(name, "bar")));
+        assert!(is_invalid_send(send_symbol_to("foo", name)));
+        assert!(is_invalid_send(send_list_to(name, &[])));
+        assert!(is_invalid_send(send_list_to("foo", &[Atom::from(name)])));
+        assert!(is_invalid_send(send_message_to(name, "bar", &[])));
+        assert!(is_invalid_send(send_message_to("foo", name, &[])));
+        assert!(is_invalid_send(send_message_to(
+            "foo",
+            "bar",
+            &[Atom::from(name)]
+        )));
+
+        start_message(2).unwrap();
+        assert!(is_invalid_send(add_symbol_to_started_message(name)));
+        assert!(is_invalid_send(finish_message_as_list_and_send_to(name)));
+        start_message(2).unwrap();
+        assert!(is_invalid_send(
+            finish_message_as_typed_message_and_send_to(name, "bar")
+        ));
+        start_message(2).unwrap();
+        assert!(is_invalid_send(
+            finish_message_as_typed_message_and_send_to("foo", name)
+        ));
+
+        assert!(is_invalid_send(patch.send_bang_to_local(name)));
+        assert!(is_invalid_send(patch.send_float_to_local(name, 1.0)));
+        assert!(is_invalid_send(patch.send_symbol_to_local("foo", name)));
+        assert!(is_invalid_send(patch.send_message_to_local(
+            "foo",
+            name,
+            &[]
+        )));
+        let destination = patch.destination(name);
+        assert!(destination.name().contains('\0'));
+        assert!(is_invalid_send(destination.send_bang()));
+        assert!(is_invalid_send(destination.send_float(1.0)));
+        assert!(is_invalid_send(destination.send_double(1.0)));
+        assert!(is_invalid_send(destination.send_list(&[])));
+        assert!(is_invalid_send(
+            patch.destination("foo").send_list(&[Atom::from(name)])
+        ));
+
+        assert!(matches!(
+            array_size(name),
+            Err(SizeError::InvalidString { .. })
+        ));
+        assert!(matches!(
+            resize_array(name, 8),
+            Err(SizeError::InvalidString { .. })
+        ));
+        assert!(!array_exists(name));
+        let mut floats = [0.0_f32; 4];
+        let mut doubles = [0.0_f64; 4];
+        assert!(matches!(
+            read_float_array_from(name, 0, 4, &mut floats),
+            Err(ArrayError::InvalidString { .. })
+        ));
+        assert!(matches!(
+            write_float_array_to(name, 0, &floats, 4),
+            Err(ArrayError::InvalidString { .. })
+        ));
+        assert!(matches!(
+            read_double_array_from(name, 0, 4, &mut doubles),
+            Err(ArrayError::InvalidString { .. })
+        ));
+        assert!(matches!(
+            write_double_array_to(name, 0, &doubles, 4),
+            Err(ArrayError::InvalidString { .. })
+        ));
+
+        assert!(matches!(
+            start_listening_from(name),
+            Err(SubscriptionError::InvalidString { .. })
+        ));
+        assert!(!source_to_listen_from_exists(name));
+
+        assert!(open_patch(name).is_err());
+        assert!(open_patch(format!("tests/patches/{name}echo.pd")).is_err());
+        assert!(matches!(
+            open_patch_with_args("tests/patches/echo.pd", &[Atom::from(name)]),
+            Err(PatchLifeCycleError::InvalidString { .. })
+        ));
+        assert!(matches!(
+            add_to_search_paths(name),
+            Err(IoError::PathDoesNotExist(_))
+        ));
+
+        let mut editor = CanvasEditor::new(&mut patch).unwrap();
+        editor.add_object(10, 10, name);
+        editor.add_msg(10, 40, name);
+    }
+
+    // Nothing is sent when a string is rejected, so valid strings still go through.
+    assert!(send_float_to("float_from_rust", 1.0).is_ok());
+    patch.close().unwrap();
+}
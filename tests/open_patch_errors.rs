@@ -0,0 +1,89 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{error::PatchLifeCycleError, get_dollar_zero, init, open_patch, receive::on_print};
+
+#[test]
+fn open_patch_errors() {
+    init().unwrap();
+
+    let printed: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+    let printed_to_fill = printed.clone();
+    on_print(move |line| {
+        printed_to_fill.lock().unwrap().push(line.to_owned());
+    });
+
+    let result = open_patch("tests/patches/not_existent.pd");
+    assert!(matches!(
+        result,
+        Err(PatchLifeCycleError::PathDoesNotExist(_))
+    ));
+
+    let result = open_patch("tests/patches");
+    assert!(matches!(
+        result,
+        Err(PatchLifeCycleError::NotReadable { .. })
+    ));
+
+    // A directory which is named like a patch.
+    let directory = tempfile::tempdir().unwrap();
+    let patch_directory = directory.path().join("directory.pd");
+    std::fs::create_dir(&patch_directory).unwrap();
+    match open_patch(&patch_directory) {
+        Err(PatchLifeCycleError::NotReadable { path, source }) => {
+            assert_eq!(path, patch_directory.to_string_lossy());
+            assert_eq!(source.to_string(), "The path is a directory.");
+        }
+        other => panic!("{other:?}"),
+    }
+
+    // A file which is neither named as nor is a pd patch.
+    let text = directory.path().join("notes.txt");
+    std::fs::write(&text, "not_a_receiver hello;\n").unwrap();
+    match open_patch(&text) {
+        Err(PatchLifeCycleError::UnexpectedExtension { path, .. }) => {
+            assert_eq!(path, text.to_string_lossy());
+        }
+        other => panic!("{other:?}"),
+    }
+    // Not a pd patch.
+    let path = directory.path().join("not_a_patch.pd");
+    std::fs::write(&path, "not_a_receiver hello;\n").unwrap();
+    match open_patch(&path) {
+        Err(PatchLifeCycleError::EvaluationFailed {
+            path: failed_path,
+            console_output,
+        }) => {
+            assert_eq!(failed_path, path.to_string_lossy());
+            assert!(console_output
+                .iter()
+                .any(|line| line.contains("not_a_receiver")));
+        }
+        _ => unreachable!(),
+    }
+    // The lines still reach the print closure.
+    assert!(printed
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|line| line.contains("not_a_receiver")));
+
+    // Pd opens patches whatever they are named.
+    let named_as_text = directory.path().join("patch.txt");
+    std::fs::copy("tests/patches/echo.pd", &named_as_text).unwrap();
+    open_patch(&named_as_text).unwrap().close().unwrap();
+
+    // Reading `$0` leaves no dangling object in pd for the loadbang of a file which creates no canvas.
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    assert!(get_dollar_zero(&patch).unwrap() > 0);
+    patch.close().unwrap();
+    assert!(matches!(
+        open_patch(&text),
+        Err(PatchLifeCycleError::UnexpectedExtension { .. })
+    ));
+    assert!(matches!(
+        open_patch(&path),
+        Err(PatchLifeCycleError::EvaluationFailed { .. })
+    ));
+}
@@ -0,0 +1,60 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "std")]
+
+use std::path::Path;
+
+use libpd_rs::{
+    array::array_size, error::PatchLifeCycleError, init, open_patch_from_str,
+    receive::source_to_listen_from_exists,
+};
+
+#[test]
+fn open_patch_from_str_works() {
+    init().unwrap();
+
+    let patch = open_patch_from_str("echo", include_str!("patches/echo.pd"), None).unwrap();
+    assert_eq!(patch.file_name(), "echo.pd");
+    assert!(patch.path().exists());
+    assert_ne!(patch.dollar_zero(), 0);
+    assert!(source_to_listen_from_exists("float_from_rust"));
+
+    // The temporary directory is removed with the patch.
+    let directory = patch.directory().to_path_buf();
+    drop(patch);
+    assert!(!directory.exists());
+    assert!(!source_to_listen_from_exists("float_from_rust"));
+
+    let patch = open_patch_from_str("echo.pd", include_str!("patches/echo.pd"), None).unwrap();
+    assert_eq!(patch.file_name(), "echo.pd");
+    let directory = patch.directory().to_path_buf();
+    patch.close().unwrap();
+    assert!(!directory.exists());
+
+    // Names need to be file names.
+    let result = open_patch_from_str("patches/echo", include_str!("patches/echo.pd"), None);
+    assert!(matches!(
+        result,
+        Err(PatchLifeCycleError::FailedToEvaluateAsPatch { .. })
+    ));
+
+    // Abstractions are only found with the search path.
+    let uses_abstraction = "#N canvas 0 0 200 100 12;\n#X obj 20 20 array_sketch_pad;\n";
+    let patch = open_patch_from_str("uses_abstraction", uses_abstraction, None).unwrap();
+    assert!(array_size("sketch_pad").is_err());
+    drop(patch);
+
+    let result = open_patch_from_str(
+        "uses_abstraction",
+        uses_abstraction,
+        Some(Path::new("tests/not_existent")),
+    );
+    assert!(matches!(
+        result,
+        Err(PatchLifeCycleError::PathDoesNotExist(_))
+    ));
+
+    let patches = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/patches");
+    let patch = open_patch_from_str("uses_abstraction", uses_abstraction, Some(&patches)).unwrap();
+    assert_eq!(array_size("sketch_pad").unwrap(), 100);
+    drop(patch);
+}
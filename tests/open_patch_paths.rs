@@ -0,0 +1,51 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{close_patch, error::PatchLifeCycleError, init, open_patch};
+
+#[test]
+fn open_patch_paths() {
+    init().unwrap();
+
+    let patches = std::fs::canonicalize("tests/patches").unwrap();
+
+    // The directory is canonicalized before it is passed to pd.
+    let patch = open_patch("tests/../tests/patches/./simple.pd").unwrap();
+    assert_eq!(patch.directory(), patches);
+    close_patch(patch).unwrap();
+
+    // An absolute path is split to its directory and file name.
+    let patch = open_patch(patches.join("simple.pd")).unwrap();
+    assert_eq!(patch.directory(), patches);
+    close_patch(patch).unwrap();
+
+    // A single file name is looked for in the manifest directory after the executable directory.
+    match open_patch("simple.pd") {
+        Err(PatchLifeCycleError::PathDoesNotExist(path)) => {
+            assert_eq!(
+                std::path::Path::new(&path),
+                std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join(".")
+                    .join("simple.pd")
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    // Paths without a file name.
+    for path in ["/", "..", "tests/patches/.."] {
+        assert!(matches!(
+            open_patch(path),
+            Err(PatchLifeCycleError::InvalidPath(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let path = std::ffi::OsStr::from_bytes(b"tests/patches/\xFF.pd");
+        assert!(matches!(
+            open_patch(path),
+            Err(PatchLifeCycleError::InvalidPath(_))
+        ));
+    }
+}
@@ -0,0 +1,47 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch, open_patch_with_args,
+    receive::{on_print, receive_messages_from_pd},
+    send::send_bang_to,
+    types::Atom,
+};
+
+#[test]
+fn creation_arguments() {
+    init().unwrap();
+
+    let printed: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+    let printed_to_fill = printed.clone();
+    on_print(move |line| {
+        if line.starts_with("arguments") {
+            printed_to_fill
+                .lock()
+                .unwrap()
+                .push(line.trim_end().to_owned());
+        }
+    });
+    let take_printed = || std::mem::take(&mut *printed.lock().unwrap());
+
+    let arguments = [Atom::from(42), Atom::from("hello")];
+    let mut patch = open_patch_with_args("tests/patches/arguments.pd", &arguments).unwrap();
+    assert_eq!(patch.arguments(), &arguments);
+    receive_messages_from_pd();
+    assert_eq!(take_printed(), vec!["arguments: 42 hello"]);
+
+    // The arguments are passed again when the patch is reloaded.
+    patch.reload().unwrap();
+    receive_messages_from_pd();
+    assert_eq!(take_printed(), vec!["arguments: 42 hello"]);
+    send_bang_to(format!("{}-again", patch.dollar_zero())).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(take_printed(), vec!["arguments: 42 hello"]);
+
+    // The arguments are not left over for the next patch.
+    let plain = open_patch("tests/patches/arguments.pd").unwrap();
+    assert!(plain.arguments().is_empty());
+    receive_messages_from_pd();
+    assert_eq!(take_printed(), vec!["arguments: 0 0"]);
+}
@@ -0,0 +1,46 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{close_all_patches, close_patch, init, open_patch, open_patches};
+
+#[test]
+fn open_patches_and_close_all() {
+    init().unwrap();
+    assert!(open_patches().is_empty());
+
+    let sine = open_patch("tests/patches/sine.pd").unwrap();
+    let echo = open_patch("tests/patches/echo.pd").unwrap();
+    let open = open_patches();
+    assert_eq!(open.len(), 2);
+    assert_eq!(open[0].path, sine.path());
+    assert_eq!(open[0].dollar_zero, sine.dollar_zero());
+    assert_eq!(open[1].path, echo.path());
+
+    // Closing and dropping remove the patches.
+    close_patch(sine).unwrap();
+    assert_eq!(open_patches().len(), 1);
+    drop(echo);
+    assert!(open_patches().is_empty());
+
+    // A reloaded patch is listed with its new `$0`.
+    let mut reloaded = open_patch("tests/patches/sine.pd").unwrap();
+    reloaded.reload().unwrap();
+    let open = open_patches();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].dollar_zero, reloaded.dollar_zero());
+
+    // Leaked patches are still listed and closed.
+    open_patch("tests/patches/echo.pd").unwrap().leak();
+    let raw = open_patch("tests/patches/echo.pd").unwrap().into_raw();
+    assert_eq!(open_patches().len(), 3);
+    assert_eq!(open_patches()[2].handle(), raw);
+
+    assert_eq!(close_all_patches(), 3);
+    assert!(open_patches().is_empty());
+    assert_eq!(close_all_patches(), 0);
+
+    // The handles are stale, they don't close the patches which are opened later.
+    let opened_later = open_patch("tests/patches/sine.pd").unwrap();
+    assert!(reloaded.close().is_err());
+    assert_eq!(open_patches().len(), 1);
+    assert_eq!(open_patches()[0].dollar_zero, opened_later.dollar_zero());
+}
@@ -0,0 +1,97 @@
+#![allow(clippy::restriction)]
+
+use std::sync::Arc;
+
+use libpd_rs::{
+    init, open_patch,
+    params::{bind_param, flush_params, Param},
+    receive::{active_subscriptions, receive_messages_from_pd},
+    send::send_float_to,
+    sink::{subscribe_with, ReceivedMessage, VecSink},
+};
+
+const fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn params_are_mirrored_between_rust_and_the_patch() {
+    assert_send_sync::<Param>();
+    init().unwrap();
+    let patch = open_patch("tests/patches/params.pd").unwrap();
+    let doubled = VecSink::new();
+    let _doubled_handle = subscribe_with("doubled_cutoff", doubled.clone()).unwrap();
+
+    // The initial value is sent with the first flush.
+    let cutoff = Arc::new(bind_param("cutoff", 800.0).unwrap());
+    assert_eq!(cutoff.name(), "cutoff");
+    assert!(cutoff.is_dirty());
+    assert_eq!(flush_params(), 1);
+    assert!(!cutoff.is_dirty());
+    receive_messages_from_pd();
+    assert_eq!(
+        doubled.take(),
+        vec![("doubled_cutoff".to_owned(), ReceivedMessage::Float(1600.0))]
+    );
+
+    // Values which are set in between flushes are sent once.
+    let from_other_thread = Arc::clone(&cutoff);
+    std::thread::spawn(move || {
+        for step in 0..100 {
+            from_other_thread.set(step as f32);
+        }
+    })
+    .join()
+    .unwrap();
+    assert_eq!(cutoff.get(), 99.0);
+    assert!(cutoff.flush().unwrap());
+    assert!(!cutoff.flush().unwrap());
+    assert_eq!(flush_params(), 0);
+    receive_messages_from_pd();
+    assert_eq!(
+        doubled.take(),
+        vec![("doubled_cutoff".to_owned(), ReceivedMessage::Float(198.0))]
+    );
+
+    // Values which can not be sent stay marked.
+    let unbound = bind_param("nobody_receives_this", 1.0).unwrap();
+    assert!(unbound.flush().is_err());
+    assert!(unbound.is_dirty());
+    drop(unbound);
+
+    // A listening parameter follows the patch and skips its own values which pd sends back.
+    let mut resonance = bind_param("resonance", 0.5).unwrap();
+    resonance.listen_to_patch().unwrap();
+    resonance.listen_to_patch().unwrap();
+    assert_eq!(flush_params(), 1);
+    send_float_to("resonance_from_patch", 0.25).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(resonance.get(), 0.25);
+    assert!(!resonance.is_dirty());
+
+    resonance.set(0.75);
+    assert_eq!(flush_params(), 1);
+    // The value which is sent is received after a newer one is set.
+    resonance.set(0.125);
+    receive_messages_from_pd();
+    assert_eq!(resonance.get(), 0.125);
+    assert!(resonance.is_dirty());
+    assert_eq!(flush_params(), 1);
+    receive_messages_from_pd();
+    assert_eq!(resonance.get(), 0.125);
+    assert_eq!(flush_params(), 0);
+
+    // The patch does not overwrite a value which is not flushed yet.
+    resonance.set(0.5);
+    send_float_to("resonance_from_patch", 0.25).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(resonance.get(), 0.5);
+    assert!(resonance.is_dirty());
+    assert_eq!(flush_params(), 1);
+    receive_messages_from_pd();
+    assert_eq!(resonance.get(), 0.5);
+
+    // Dropping it unsubscribes from the patch.
+    drop(resonance);
+    assert!(!active_subscriptions().contains(&"resonance".to_owned()));
+
+    patch.close().unwrap();
+}
@@ -0,0 +1,72 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    close_all_patches,
+    error::SendError,
+    init, open_patch,
+    receive::{on_list, receive_messages_from_pd, start_listening_from},
+    types::Atom,
+};
+
+#[test]
+fn patch_destination() {
+    init().unwrap();
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_, list| {
+        lists_to_fill.lock().unwrap().push(list.to_vec());
+    });
+    let _receiver = start_listening_from("voice_freq").unwrap();
+
+    let mut voice = open_patch("tests/patches/voice.pd").unwrap();
+    let dollar_zero = voice.dollar_zero();
+    let freq = voice.destination("freq");
+    assert_eq!(freq.name(), format!("{dollar_zero}-freq"));
+    assert!(freq.is_open());
+
+    freq.send_float(220.0).unwrap();
+    freq.clone().send_double(330.0).unwrap();
+    freq.send_list(&[Atom::from(440.0)]).unwrap();
+    receive_messages_from_pd();
+    let dollar_zero_atom = Atom::Float(f64::from(dollar_zero));
+    assert_eq!(
+        *lists.lock().unwrap(),
+        vec![
+            vec![Atom::Float(220.0), dollar_zero_atom.clone()],
+            vec![Atom::Float(330.0), dollar_zero_atom.clone()],
+            vec![Atom::Float(440.0), dollar_zero_atom],
+        ]
+    );
+
+    // A receiver which is not in the patch.
+    match voice.destination("missing").send_bang() {
+        Err(SendError::MissingDestination(name)) => {
+            assert_eq!(name, format!("{dollar_zero}-missing"));
+        }
+        _ => unreachable!(),
+    }
+
+    // A reloaded patch has a new `$0`, so the destinations need to be made again.
+    voice.reload().unwrap();
+    assert!(!freq.is_open());
+    assert!(matches!(
+        freq.send_float(1.0),
+        Err(SendError::PatchClosed(name)) if name == format!("{dollar_zero}-freq")
+    ));
+    let freq = voice.destination("freq");
+    freq.send_float(550.0).unwrap();
+
+    drop(voice);
+    assert!(matches!(freq.send_bang(), Err(SendError::PatchClosed(_))));
+
+    let voice = open_patch("tests/patches/voice.pd").unwrap();
+    let freq = voice.destination("freq");
+    assert_eq!(close_all_patches(), 1);
+    assert!(matches!(
+        freq.send_list(&[]),
+        Err(SendError::PatchClosed(_))
+    ));
+}
@@ -0,0 +1,39 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    close_patch, init, open_patch, receive::source_to_listen_from_exists, reset, terminate,
+    types::Patch,
+};
+
+#[test]
+fn patch_drop() {
+    init().unwrap();
+
+    {
+        let _patch = open_patch("tests/patches/echo.pd").unwrap();
+        assert!(source_to_listen_from_exists("float_from_rust"));
+    }
+    // Closed on drop.
+    assert!(!source_to_listen_from_exists("float_from_rust"));
+
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    assert!(patch.close().is_ok());
+    assert!(!source_to_listen_from_exists("float_from_rust"));
+
+    // Raw handles do not close the patch.
+    let raw = open_patch("tests/patches/echo.pd").unwrap().into_raw();
+    assert!(source_to_listen_from_exists("float_from_rust"));
+    assert!(close_patch(Patch::from_raw(raw)).is_ok());
+    assert!(!source_to_listen_from_exists("float_from_rust"));
+
+    // Leaked patches stay open until reset.
+    open_patch("tests/patches/echo.pd").unwrap().leak();
+    assert!(source_to_listen_from_exists("float_from_rust"));
+    reset();
+    assert!(!source_to_listen_from_exists("float_from_rust"));
+
+    // Dropping after termination does nothing.
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    terminate();
+    drop(patch);
+}
@@ -0,0 +1,67 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    init, open_patch, open_patch_with_args,
+    receive::source_to_listen_from_exists,
+    types::{ArrayInfo, Atom},
+};
+
+#[test]
+fn patch_introspection() {
+    init().unwrap();
+
+    let patch =
+        open_patch_with_args("tests/patches/endpoints.pd", &[Atom::from("lowpass")]).unwrap();
+    let dollar_zero = patch.dollar_zero();
+
+    let receivers = patch.receivers().unwrap();
+    assert_eq!(
+        receivers,
+        vec![
+            format!("{dollar_zero}-freq"),
+            "volume".to_owned(),
+            "lowpass-cutoff".to_owned(),
+            "bang_in".to_owned(),
+            "number_in".to_owned(),
+            "sub_receiver".to_owned(),
+        ]
+    );
+    // The resolved names are the ones which pd binds.
+    for receiver in &receivers {
+        assert!(source_to_listen_from_exists(receiver), "{receiver}");
+    }
+
+    assert_eq!(
+        patch.senders().unwrap(),
+        vec![
+            "note".to_owned(),
+            "bus".to_owned(),
+            "bang_out".to_owned(),
+            "number_out".to_owned(),
+            "reverb".to_owned(),
+            format!("{dollar_zero}-delay"),
+        ]
+    );
+
+    assert_eq!(
+        patch.arrays().unwrap(),
+        vec![
+            ArrayInfo {
+                name: format!("{dollar_zero}-table"),
+                size: 64,
+            },
+            ArrayInfo {
+                name: "defined".to_owned(),
+                size: 16,
+            },
+            ArrayInfo {
+                name: "wave".to_owned(),
+                size: 32,
+            },
+        ]
+    );
+
+    // Without arguments `$1` is kept.
+    let patch = open_patch("tests/patches/endpoints.pd").unwrap();
+    assert!(patch.receivers().unwrap().contains(&"$1-cutoff".to_owned()));
+}
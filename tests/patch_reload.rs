@@ -0,0 +1,69 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    error::PatchLifeCycleError,
+    init, open_patch,
+    receive::{on_float, receive_messages_from_pd, start_listening_from},
+    send::send_float_to,
+    types::Patch,
+};
+
+#[test]
+fn patch_reload() {
+    init().unwrap();
+
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("echo.pd");
+    std::fs::write(&path, include_str!("patches/echo.pd")).unwrap();
+
+    let mut patch = open_patch(&path).unwrap();
+    let _receiver_handle = start_listening_from("float_from_pd").unwrap();
+    let floats: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![]));
+    let floats_to_fill = floats.clone();
+    on_float(move |_, value| {
+        floats_to_fill.lock().unwrap().push(value);
+    });
+
+    let dollar_zero = patch.dollar_zero();
+    patch.reload().unwrap();
+    assert!(patch.is_open());
+    assert_ne!(patch.dollar_zero(), dollar_zero);
+    assert_eq!(patch.path(), path);
+
+    // Subscriptions and closures survive the reload.
+    send_float_to("float_from_rust", 42.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![42.0]);
+
+    // Failing to reopen leaves the patch closed.
+    std::fs::remove_file(&path).unwrap();
+    let result = patch.reload();
+    assert!(matches!(
+        result,
+        Err(PatchLifeCycleError::FailedToReopenPatch { .. })
+    ));
+    assert!(!patch.is_open());
+    assert_eq!(patch.dollar_zero(), 0);
+    assert!(send_float_to("float_from_rust", 1.0).is_err());
+
+    // Retrying opens it again.
+    std::fs::write(&path, include_str!("patches/echo.pd")).unwrap();
+    patch.reload().unwrap();
+    assert!(patch.is_open());
+    send_float_to("float_from_rust", 2.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![42.0, 2.0]);
+    patch.close().unwrap();
+
+    // Patches without a path can not be reloaded and stay open.
+    let raw = open_patch(&path).unwrap().into_raw();
+    let mut patch = Patch::from_raw(raw);
+    assert!(matches!(
+        patch.reload(),
+        Err(PatchLifeCycleError::PathDoesNotExist(_))
+    ));
+    assert!(patch.is_open());
+    patch.close().unwrap();
+}
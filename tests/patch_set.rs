@@ -0,0 +1,75 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    error::{PatchLifeCycleError, SendError},
+    init,
+    receive::{
+        on_list, receive_messages_from_pd, source_to_listen_from_exists, start_listening_from,
+    },
+    types::{Atom, PatchSet},
+};
+
+#[test]
+fn patch_set() {
+    init().unwrap();
+
+    let _receiver_handle = start_listening_from("voice_freq").unwrap();
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_, list| {
+        lists_to_fill.lock().unwrap().push(list.to_vec());
+    });
+
+    let mut voices = PatchSet::open_copies("tests/patches/voice.pd", 3).unwrap();
+    assert_eq!(voices.len(), 3);
+    assert_ne!(voices[0].dollar_zero(), voices[1].dollar_zero());
+
+    let voice = &voices[1];
+    assert_eq!(
+        voice.local_name("freq"),
+        format!("{}-freq", voice.dollar_zero())
+    );
+    assert!(source_to_listen_from_exists(voice.local_name("freq")));
+
+    for (voice, frequency) in voices.iter().zip([220.0, 330.0, 440.0]) {
+        voice.send_float_to_local("freq", frequency).unwrap();
+    }
+    receive_messages_from_pd();
+    let expected: Vec<Vec<Atom>> = voices
+        .iter()
+        .zip([220.0, 330.0, 440.0])
+        .map(|(voice, frequency)| vec![Atom::from(frequency), Atom::from(voice.dollar_zero())])
+        .collect();
+    assert_eq!(*lists.lock().unwrap(), expected);
+
+    // Errors contain the expanded name.
+    let result = voices[0].send_float_to_local("gain", 0.5);
+    let expanded = format!("{}-gain", voices[0].dollar_zero());
+    assert!(matches!(result, Err(SendError::MissingDestination(name)) if name == expanded));
+
+    voices.set_local_separator("_");
+    assert_eq!(
+        voices[2].local_name("freq"),
+        format!("{}_freq", voices[2].dollar_zero())
+    );
+    assert!(voices[2].send_float_to_local("freq", 1.0).is_err());
+
+    // The copies are closed with the set.
+    let names: Vec<String> = voices
+        .iter()
+        .map(|voice| format!("{}-freq", voice.dollar_zero()))
+        .collect();
+    drop(voices);
+    assert!(names.iter().all(|name| !source_to_listen_from_exists(name)));
+
+    let result = PatchSet::open_copies("tests/patches/not_existent.pd", 2);
+    assert!(matches!(
+        result,
+        Err(PatchLifeCycleError::PathDoesNotExist(_))
+    ));
+    assert!(PatchSet::open_copies("tests/patches/voice.pd", 0)
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,23 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "gui")]
+
+use libpd_rs::{error::GuiLifeCycleError, open_patch};
+
+#[test]
+fn patch_window_without_gui() {
+    libpd_rs::init().unwrap();
+
+    let mut patch = open_patch("tests/patches/sine.pd").unwrap();
+    assert!(!patch.window_visible());
+
+    // Windows can only be shown or hidden while the gui is running.
+    assert!(matches!(
+        patch.show_window(),
+        Err(GuiLifeCycleError::NotRunning)
+    ));
+    assert!(matches!(
+        patch.hide_window(),
+        Err(GuiLifeCycleError::NotRunning)
+    ));
+    assert!(!patch.window_visible());
+}
@@ -0,0 +1,48 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    dynamic::CanvasEditor,
+    init, open_patch,
+    receive::{on_pd_error, on_print, receive_messages_from_pd},
+    types::PdConsoleError,
+};
+
+#[test]
+fn errors_are_split_from_prints() {
+    let lines: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+    let lines_to_fill = lines.clone();
+    on_print(move |line| lines_to_fill.lock().unwrap().push(line.to_owned()));
+    let errors: Arc<Mutex<Vec<PdConsoleError>>> = Arc::new(Mutex::new(vec![]));
+    let errors_to_fill = errors.clone();
+    on_pd_error(move |error| errors_to_fill.lock().unwrap().push(error));
+
+    init().unwrap();
+    let mut patch = open_patch("tests/patches/empty.pd").unwrap();
+    let mut editor = CanvasEditor::new(&mut patch).unwrap();
+    editor.add_object(10, 10, "this_object_does_not_exist 1 2");
+    receive_messages_from_pd();
+
+    // Regular prints still reach the print closure.
+    let (major, minor, bugfix) = libpd_rs::info::pd_version();
+    let banner = format!("pd {major}.{minor}.{bugfix}");
+    let lines = lines.lock().unwrap();
+    assert!(lines.contains(&banner));
+    assert!(lines.iter().all(|line| !line.starts_with("error:")));
+
+    let errors = errors.lock().unwrap();
+    let error = errors
+        .iter()
+        .find(|error| error.level == 1)
+        .expect("the object could not be created");
+    assert_eq!(error.message, "couldn't create");
+    assert_eq!(
+        error.object.as_deref(),
+        Some("this_object_does_not_exist 1 2")
+    );
+    // The description of the object is a regular line too.
+    assert!(lines
+        .iter()
+        .any(|line| line.trim() == "this_object_does_not_exist 1 2"));
+}
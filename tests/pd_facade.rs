@@ -0,0 +1,93 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    engine::Pd,
+    receive::{active_subscriptions, receive_messages_from_pd},
+    send::send_list_to,
+    sink::ReceivedMessage,
+    types::{Atom, AudioConfig},
+};
+
+#[test]
+fn pd_facade() {
+    let mut pd = Pd::new(AudioConfig::stereo_output_44k()).unwrap();
+    assert_eq!(pd.config(), AudioConfig::stereo_output_44k());
+    assert_eq!(pd.output_len(8), 1024);
+    assert_eq!(pd.input_len(8), 0);
+    let patch = pd.open("tests/patches/echo.pd").unwrap();
+
+    let floats = Arc::new(Mutex::new(vec![]));
+    let floats_to_fill = floats.clone();
+    pd.on("float_from_pd", move |value: f32| {
+        floats_to_fill.lock().unwrap().push(value);
+    })
+    .unwrap();
+    let symbols = Arc::new(Mutex::new(vec![]));
+    let symbols_to_fill = symbols.clone();
+    pd.on("symbol_from_pd", move |value: String| {
+        symbols_to_fill.lock().unwrap().push(value);
+    })
+    .unwrap();
+    let bangs = Arc::new(Mutex::new(0));
+    let bangs_to_fill = bangs.clone();
+    pd.on("bang_from_pd", move |()| {
+        *bangs_to_fill.lock().unwrap() += 1
+    })
+    .unwrap();
+    let lists = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    pd.on("list_from_pd", move |value: i32| {
+        lists_to_fill.lock().unwrap().push(value);
+    })
+    .unwrap();
+    let messages = Arc::new(Mutex::new(vec![]));
+    let messages_to_fill = messages.clone();
+    pd.on("list_from_pd", move |message: ReceivedMessage| {
+        messages_to_fill.lock().unwrap().push(message);
+    })
+    .unwrap();
+
+    let renderer = pd.audio().unwrap();
+    pd.send("float_from_rust", 440.0).unwrap();
+    pd.send("float_from_rust", 220).unwrap();
+    pd.send("symbol_from_rust", "saw").unwrap();
+    pd.send("nobody_receives_this", 1.0).unwrap_err();
+    // A list of a single atom is taken as the value, the others are skipped.
+    send_list_to("list_from_rust", [7]).unwrap();
+    send_list_to("list_from_rust", [1.5]).unwrap();
+    send_list_to("list_from_rust", [1, 2]).unwrap();
+    libpd_rs::send::send_bang_to("bang_from_rust").unwrap();
+
+    // The messages are delivered when they are received, not by rendering.
+    let mut output = vec![0.0_f32; pd.output_len(1)];
+    renderer.render(&[], &mut output).unwrap();
+    assert!(floats.lock().unwrap().is_empty());
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![440.0, 220.0]);
+    assert_eq!(*symbols.lock().unwrap(), vec!["saw".to_owned()]);
+    assert_eq!(*bangs.lock().unwrap(), 1);
+    assert_eq!(*lists.lock().unwrap(), vec![7]);
+    assert_eq!(
+        *messages.lock().unwrap(),
+        vec![
+            ReceivedMessage::List(vec![Atom::from(7)]),
+            ReceivedMessage::List(vec![Atom::from(1.5)]),
+            ReceivedMessage::List(vec![Atom::from(1), Atom::from(2)]),
+        ]
+    );
+
+    // Dropping it unsubscribes the closures, the patch is dropped before it.
+    drop(patch);
+    drop(pd);
+    let subscriptions = active_subscriptions();
+    for source in [
+        "float_from_pd",
+        "symbol_from_pd",
+        "bang_from_pd",
+        "list_from_pd",
+    ] {
+        assert!(!subscriptions.contains(&source.to_owned()));
+    }
+}
@@ -0,0 +1,26 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{convenience::PdGlobal, release_internal_queues};
+
+#[test]
+fn drop_after_release() {
+    // Dropping while pd is alive cleans up the handles.
+    let mut pd = PdGlobal::init_and_configure(0, 2, 44100).unwrap();
+    pd.open_patch("tests/patches/echo.pd").unwrap();
+    pd.subscribe_to_many(&["float_from_pd", "bang_from_pd"])
+        .unwrap();
+    drop(pd);
+
+    // Dropping after the queues are released should not call into pd.
+    let mut pd = PdGlobal::init_and_configure(0, 2, 44100).unwrap();
+    pd.open_patch("tests/patches/echo.pd").unwrap();
+    pd.subscribe_to("float_from_pd").unwrap();
+    release_internal_queues();
+    drop(pd);
+
+    // Initializing again brings pd back.
+    let mut pd = PdGlobal::init_and_configure(0, 2, 44100).unwrap();
+    pd.open_patch("tests/patches/echo.pd").unwrap();
+    pd.subscribe_to("float_from_pd").unwrap();
+    drop(pd);
+}
@@ -0,0 +1,67 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+use libpd_rs::{
+    array::{array_size, resize_array},
+    audio_config, close_patch,
+    convenience::dsp_on,
+    init, initialize_audio, open_patch,
+    process::process_float,
+    reinitialize_audio, with_pd_lock,
+};
+
+#[test]
+fn with_pd_lock_excludes_processing() {
+    init().unwrap();
+    initialize_audio(0, 2, 44100).unwrap();
+    dsp_on().unwrap();
+    let patch_handle = open_patch("tests/patches/array_sketch_pad.pd").unwrap();
+
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let running = Arc::new(AtomicBool::new(true));
+
+    let ticks_in_thread = Arc::clone(&ticks);
+    let running_in_thread = Arc::clone(&running);
+    let audio_thread = std::thread::spawn(move || {
+        let mut output_buffer = [0.0_f32; 128];
+        while running_in_thread.load(Ordering::SeqCst) {
+            process_float(1, &[], &mut output_buffer);
+            ticks_in_thread.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    // Wait for processing to start.
+    while ticks.load(Ordering::SeqCst) == 0 {
+        std::thread::yield_now();
+    }
+
+    let size = with_pd_lock(|| {
+        let before = ticks.load(Ordering::SeqCst);
+        resize_array("sketch_pad", 1024).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        // Nested calls and changing the configuration do not deadlock.
+        with_pd_lock(|| reinitialize_audio(0, 2, 48000)).unwrap();
+        let size = array_size("sketch_pad").unwrap();
+        // At most the tick which was running before locking finishes.
+        assert!(ticks.load(Ordering::SeqCst) <= before + 1);
+        size
+    });
+    assert_eq!(size, 1024);
+    assert_eq!(audio_config().unwrap().sample_rate, 48000);
+
+    // The lock is released when the closure panics.
+    let result = std::panic::catch_unwind(|| with_pd_lock(|| panic!("on purpose")));
+    assert!(result.is_err());
+    let after_panic = ticks.load(Ordering::SeqCst);
+    while ticks.load(Ordering::SeqCst) == after_panic {
+        std::thread::yield_now();
+    }
+
+    running.store(false, Ordering::SeqCst);
+    audio_thread.join().unwrap();
+    close_patch(patch_handle).unwrap();
+}
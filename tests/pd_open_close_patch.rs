@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use std::sync::mpsc;
 
 use libpd_rs::{block_size, convenience::PdGlobal, process::process_float};
@@ -10,6 +10,8 @@ fn subscribe_unsubscribe() {
     assert!(pd.subscriptions.get("a_source").is_some());
     assert!(pd.subscriptions.get("other").is_some());
     assert!(pd.subscriptions.get("another").is_some());
+    assert!(pd.subscribe_to("").is_err());
+    assert!(pd.subscriptions.get("").is_none());
     pd.unsubscribe_from("a_source");
     pd.unsubscribe_from("a_source");
     assert!(pd.subscriptions.get("a_source").is_none());
@@ -0,0 +1,55 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    error::{AtomConversionError, PatchLifeCycleError, SendError},
+    init, open_patch, open_patch_with_args,
+    receive::{on_list, receive_messages_from_pd, start_listening_from},
+    send::{send_bang_to, send_list_to},
+    types::Atom,
+};
+
+#[test]
+fn pointer_atoms_keep_lists_at_their_length() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/pointer.pd").unwrap();
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_source, list| lists_to_fill.lock().unwrap().push(list.to_vec()));
+    let _handle = start_listening_from("pointer_from_pd").unwrap();
+
+    send_bang_to("pointer_from_rust").unwrap();
+    receive_messages_from_pd();
+
+    let lists = lists.lock().unwrap();
+    let list = lists.first().expect("a list with a pointer is received");
+    assert_eq!(list.len(), 3);
+    assert_eq!(list[0], Atom::from(1));
+    assert_eq!(list[2], Atom::from(2));
+    let Atom::Pointer(pointer) = list[1] else {
+        panic!("{list:?}");
+    };
+    assert!(list[1].is_pointer());
+    assert_ne!(pointer.address(), 0);
+    assert_eq!(list[1], list[1].clone());
+    assert_eq!(list[1].to_string(), "(pointer)");
+    assert_eq!(list[1].as_float(), None);
+    assert!(matches!(
+        f64::try_from(&list[1]),
+        Err(AtomConversionError::Pointer)
+    ));
+
+    // Pointers are not sent back to pd.
+    assert!(matches!(
+        send_list_to("pointer_from_pd", list),
+        Err(SendError::PointerAtom)
+    ));
+    assert!(matches!(
+        open_patch_with_args("tests/patches/arguments.pd", &list[1..2]),
+        Err(PatchLifeCycleError::PointerArgument)
+    ));
+
+    patch.close().unwrap();
+}
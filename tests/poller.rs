@@ -0,0 +1,38 @@
+#![allow(clippy::restriction)]
+
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{on_float, spawn_poller, start_listening_from},
+    send::send_float_to,
+};
+
+#[test]
+fn poller_delivers_messages_in_the_background() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let (sender, receiver) = channel();
+    on_float(move |source: &str, value: f32| {
+        sender.send((source.to_owned(), value)).unwrap();
+    });
+    let _handle = start_listening_from("float_from_pd").unwrap();
+
+    let poller = spawn_poller(Duration::from_millis(1));
+    assert!(poller.is_running());
+    send_float_to("float_from_rust", 42.0).unwrap();
+    // Nothing is polled on this thread.
+    assert_eq!(
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap(),
+        ("float_from_pd".to_owned(), 42.0)
+    );
+
+    // The thread is stopped when the handle is dropped.
+    poller.stop();
+    send_float_to("float_from_rust", 1.0).unwrap();
+    assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+
+    patch.close().unwrap();
+}
@@ -0,0 +1,116 @@
+#![allow(clippy::restriction)]
+
+use std::time::Duration;
+
+use libpd_rs::{
+    init, open_patch,
+    params::{bind_param, morph, morph_tick, start_morph, Preset},
+    receive::receive_messages_from_pd,
+    sink::{subscribe_with, ReceivedMessage, VecSink},
+};
+
+fn preset(values: &[(&str, f32)]) -> Preset {
+    let mut preset = Preset::new();
+    for (name, value) in values {
+        preset.insert(name, *value);
+    }
+    preset
+}
+
+#[test]
+fn presets_are_interpolated() {
+    let from = preset(&[("cutoff", 400.0), ("drive", 0.2)]);
+    let to = preset(&[("cutoff", 800.0), ("resonance", 0.9)]);
+
+    assert_eq!(morph(&from, &to, 0.0), from);
+    assert_eq!(morph(&from, &to, 1.0), to);
+    // `t` is clipped.
+    assert_eq!(morph(&from, &to, -1.0), from);
+    assert_eq!(morph(&from, &to, 2.0), to);
+    // Parameters which are missing in either preset switch at the middle.
+    assert_eq!(
+        morph(&from, &to, 0.25),
+        preset(&[("cutoff", 500.0), ("drive", 0.2)])
+    );
+    assert_eq!(
+        morph(&from, &to, 0.5),
+        preset(&[("cutoff", 600.0), ("resonance", 0.9)])
+    );
+    // The ends are exact even when the values are far apart.
+    let from = preset(&[("cutoff", 1.0e8)]);
+    let to = preset(&[("cutoff", 0.3)]);
+    assert_eq!(morph(&from, &to, 1.0), to);
+    assert_eq!(morph(&from, &to, 0.0), from);
+}
+
+#[test]
+fn morphs_send_the_values_which_change() {
+    let _ = init();
+    let patch = open_patch("tests/patches/params.pd").unwrap();
+    let doubled = VecSink::new();
+    let _doubled_handle = subscribe_with("doubled_cutoff", doubled.clone()).unwrap();
+    let cutoff = bind_param("cutoff", 400.0).unwrap();
+    cutoff.flush().unwrap();
+    receive_messages_from_pd();
+    assert_eq!(doubled.take().len(), 1);
+
+    // Nothing is sent without a morph.
+    assert_eq!(morph_tick(Duration::from_millis(10)), 0);
+
+    let handle = start_morph(
+        &preset(&[("cutoff", 800.0), ("not_bound", 1.0)]),
+        Duration::from_secs(4),
+    );
+    assert!(handle.is_active());
+    assert_eq!(handle.progress(), 0.0);
+    assert_eq!(morph_tick(Duration::from_secs(1)), 1);
+    assert_eq!(cutoff.get(), 500.0);
+    assert_eq!(handle.progress(), 0.25);
+    assert!(!handle.is_finished());
+    // Values which do not change are not sent.
+    assert_eq!(morph_tick(Duration::ZERO), 0);
+    assert_eq!(morph_tick(Duration::from_secs(2)), 1);
+    assert_eq!(morph_tick(Duration::from_secs(2)), 1);
+    assert!(handle.is_finished());
+    assert!(!handle.is_active());
+    assert_eq!(handle.progress(), 1.0);
+    assert_eq!(cutoff.get(), 800.0);
+    assert_eq!(morph_tick(Duration::from_secs(1)), 0);
+    receive_messages_from_pd();
+    assert_eq!(
+        doubled
+            .take()
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect::<Vec<_>>(),
+        vec![
+            ReceivedMessage::Float(1000.0),
+            ReceivedMessage::Float(1400.0),
+            ReceivedMessage::Float(1600.0),
+        ]
+    );
+
+    // A morph stops where it is when it is replaced or cancelled.
+    let first = start_morph(&preset(&[("cutoff", 0.0)]), Duration::from_secs(8));
+    assert_eq!(morph_tick(Duration::from_secs(1)), 1);
+    assert_eq!(cutoff.get(), 700.0);
+    let second = start_morph(&preset(&[("cutoff", 1700.0)]), Duration::from_secs(1));
+    assert!(!first.is_active());
+    assert!(!first.is_finished());
+    first.cancel();
+    assert!(second.is_active());
+    assert_eq!(morph_tick(Duration::from_millis(500)), 1);
+    assert_eq!(cutoff.get(), 1200.0);
+    second.cancel();
+    assert!(!second.is_active());
+    assert_eq!(morph_tick(Duration::from_secs(1)), 0);
+    assert_eq!(cutoff.get(), 1200.0);
+
+    // A morph without a duration finishes with its first tick.
+    let instant = start_morph(&preset(&[("cutoff", 100.0)]), Duration::ZERO);
+    assert_eq!(morph_tick(Duration::ZERO), 1);
+    assert!(instant.is_finished());
+    assert_eq!(cutoff.get(), 100.0);
+
+    patch.close().unwrap();
+}
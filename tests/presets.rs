@@ -0,0 +1,93 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    init, open_patch,
+    params::{apply, bind_param, flush_params, snapshot, Preset, PRESET_FORMAT_VERSION},
+    receive::receive_messages_from_pd,
+    sink::{subscribe_with, ReceivedMessage, VecSink},
+};
+
+#[test]
+fn presets_capture_and_apply_bound_params() {
+    let _ = init();
+    let patch = open_patch("tests/patches/params.pd").unwrap();
+    let doubled = VecSink::new();
+    let _doubled_handle = subscribe_with("doubled_cutoff", doubled.clone()).unwrap();
+
+    let cutoff = bind_param("cutoff", 800.0).unwrap();
+    let mut resonance = bind_param("resonance", 0.5).unwrap();
+    resonance.listen_to_patch().unwrap();
+    assert_eq!(flush_params(), 2);
+    receive_messages_from_pd();
+    assert_eq!(doubled.take().len(), 1);
+
+    let preset = snapshot();
+    assert_eq!(preset.version(), PRESET_FORMAT_VERSION);
+    assert_eq!(preset.get("cutoff"), Some(800.0));
+    assert_eq!(preset.get("resonance"), Some(0.5));
+
+    cutoff.set(100.0);
+    resonance.set(0.9);
+    assert_eq!(flush_params(), 2);
+    receive_messages_from_pd();
+    assert_eq!(doubled.take().len(), 1);
+
+    // Every value is sent back, parameters which are not bound anymore are reported.
+    let mut older = preset.clone();
+    older.insert("removed_from_the_patch", 1.0);
+    assert_eq!(apply(&older), vec!["removed_from_the_patch".to_owned()]);
+    assert_eq!(cutoff.get(), 800.0);
+    assert_eq!(resonance.get(), 0.5);
+    assert!(!cutoff.is_dirty());
+    assert!(!resonance.is_dirty());
+    receive_messages_from_pd();
+    assert_eq!(
+        doubled.take(),
+        vec![("doubled_cutoff".to_owned(), ReceivedMessage::Float(1600.0))]
+    );
+    // The value which is sent back to the listening parameter is skipped.
+    assert_eq!(resonance.get(), 0.5);
+
+    // Parameters which are not in the preset keep their values.
+    let mut partial = Preset::new();
+    partial.insert("cutoff", 400.0);
+    assert!(apply(&partial).is_empty());
+    assert_eq!(cutoff.get(), 400.0);
+    assert_eq!(resonance.get(), 0.5);
+    receive_messages_from_pd();
+    assert_eq!(doubled.take().len(), 1);
+
+    // A dropped parameter is not captured anymore.
+    drop(resonance);
+    let preset = snapshot();
+    assert_eq!(preset.get("resonance"), None);
+    assert_eq!(preset.iter().collect::<Vec<_>>(), vec![("cutoff", 400.0)]);
+    assert_eq!(apply(&Preset::default()), Vec::<String>::new());
+
+    patch.close().unwrap();
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn presets_are_json_objects_of_values() {
+    let mut preset = Preset::new();
+    preset.insert("resonance", 0.5);
+    preset.insert("cutoff", 800.0);
+    let json = serde_json::to_string(&preset).unwrap();
+    assert_eq!(
+        json,
+        r#"{"version":1,"values":{"cutoff":800.0,"resonance":0.5}}"#
+    );
+    assert_eq!(serde_json::from_str::<Preset>(&json).unwrap(), preset);
+    assert!(serde_json::from_str::<Preset>(r#"{"values":{}}"#).is_err());
+    // Versions which are not known are rejected.
+    let newer = format!(
+        r#"{{"version":{},"values":{{}}}}"#,
+        PRESET_FORMAT_VERSION + 1
+    );
+    let error = serde_json::from_str::<Preset>(&newer).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unsupported preset format version"));
+    assert!(serde_json::from_str::<Preset>(r#"{"version":0,"values":{}}"#).is_err());
+}
@@ -0,0 +1,21 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{init, receive::on_print, receive::receive_messages_from_pd};
+
+#[test]
+fn print_hook_before_init() {
+    let lines: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+    let lines_to_fill = lines.clone();
+    on_print(move |line| lines_to_fill.lock().unwrap().push(line.to_owned()));
+
+    // What pd prints while initializing is queued until messages are received.
+    init().unwrap();
+    assert!(lines.lock().unwrap().is_empty());
+    receive_messages_from_pd();
+
+    let (major, minor, bugfix) = libpd_rs::info::pd_version();
+    let banner = format!("pd {major}.{minor}.{bugfix}");
+    assert!(lines.lock().unwrap().contains(&banner));
+}
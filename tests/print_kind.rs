@@ -0,0 +1,53 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    dynamic::CanvasEditor,
+    init, open_patch,
+    receive::{on_print_with_kind, receive_messages_from_pd},
+    types::PrintKind,
+};
+
+#[test]
+fn prints_are_told_apart_from_errors() {
+    assert_eq!(PrintKind::of("error: couldn't create"), PrintKind::Error);
+    assert_eq!(PrintKind::of("verbose(0): out of memory"), PrintKind::Fatal);
+    assert_eq!(
+        PrintKind::of("verbose(1): ... couldn't create"),
+        PrintKind::Error
+    );
+    assert_eq!(
+        PrintKind::of("verbose(3): tried foo.pd_linux"),
+        PrintKind::Verbose
+    );
+    assert_eq!(PrintKind::of("verbose(9): not a level"), PrintKind::Post);
+    assert_eq!(PrintKind::of("print: error: 1"), PrintKind::Post);
+    assert!(PrintKind::Fatal.is_error());
+    assert!(!PrintKind::Verbose.is_error());
+
+    let lines: Arc<Mutex<Vec<(PrintKind, String)>>> = Arc::new(Mutex::new(vec![]));
+    let lines_to_fill = lines.clone();
+    on_print_with_kind(move |kind, line| {
+        lines_to_fill.lock().unwrap().push((kind, line.to_owned()))
+    });
+
+    init().unwrap();
+    let mut patch = open_patch("tests/patches/empty.pd").unwrap();
+    let mut editor = CanvasEditor::new(&mut patch).unwrap();
+    editor.add_object(10, 10, "this_object_does_not_exist 1 2");
+    receive_messages_from_pd();
+
+    let (major, minor, bugfix) = libpd_rs::info::pd_version();
+    let banner = format!("pd {major}.{minor}.{bugfix}");
+    let lines = lines.lock().unwrap();
+    assert!(lines.contains(&(PrintKind::Post, banner)));
+    // The line keeps its prefix.
+    assert!(lines.iter().any(|(kind, line)| *kind == PrintKind::Error
+        && line.starts_with("error: ")
+        && line.ends_with("couldn't create")));
+    assert!(lines
+        .iter()
+        .filter(|(kind, _)| *kind == PrintKind::Post)
+        .all(|(_, line)| !line.starts_with("error:")));
+}
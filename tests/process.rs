@@ -4,7 +4,7 @@ use std::any::type_name;
 
 use libpd_rs::{
     block_size, close_patch,
-    convenience::dsp_on,
+    convenience::{allocate_io_buffers, dsp_on, input_buffer_len, output_buffer_len},
     init, initialize_audio, open_patch,
     process::{
         process_double, process_float, process_raw, process_raw_double, process_raw_short,
@@ -126,5 +126,15 @@ fn all_process_functions() {
     assert_eq!(type_of(output_buffer[0]), "i16");
     assert_ne!(sum, 0);
 
+    // Allocated buffers
+    assert_eq!(input_buffer_len(4, 0), 0);
+    assert_eq!(output_buffer_len(4, output_channels), 512);
+    let (input_buffer, mut output_buffer) = allocate_io_buffers(4).unwrap();
+    assert_eq!(input_buffer.len(), 0);
+    assert_eq!(output_buffer.len(), 512);
+
+    process_float(4, &input_buffer, &mut output_buffer);
+    assert!(output_buffer.iter().any(|sample| *sample != 0.0));
+
     close_patch(patch_handle).unwrap();
 }
@@ -0,0 +1,63 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    block_size,
+    convenience::dsp_on,
+    error::ProcessError,
+    init, initialize_audio, open_patch,
+    process::{try_process_raw, try_process_raw_double, try_process_raw_short},
+};
+
+#[test]
+fn validated_raw_process_functions() {
+    init().unwrap();
+
+    let mut output_buffer = [1.0_f32; 128];
+    assert!(matches!(
+        try_process_raw(&[], &mut output_buffer),
+        Err(ProcessError::AudioNotInitialized)
+    ));
+    assert!(output_buffer.iter().all(|sample| *sample == 0.0));
+
+    initialize_audio(1, 2, 44100).unwrap();
+    dsp_on().unwrap();
+    let _patch = open_patch("tests/patches/sine.pd").unwrap();
+
+    let block_size = block_size() as usize;
+    let input_buffer = vec![0.0_f32; block_size];
+
+    // Planar blocks, the first half is the left channel and the second half is the right channel.
+    let mut output_buffer = vec![0.0_f32; block_size * 2];
+    for _ in 0..100 {
+        try_process_raw(&input_buffer, &mut output_buffer).unwrap();
+    }
+    let (left, right) = output_buffer.split_at(block_size);
+    assert!(left.iter().any(|sample| *sample != 0.0));
+    assert!(right.iter().any(|sample| *sample != 0.0));
+
+    let mut output_buffer = vec![1.0_f32; block_size * 4];
+    assert!(matches!(
+        try_process_raw(&input_buffer, &mut output_buffer),
+        Err(ProcessError::OutputBufferLength { expected, actual })
+            if expected == block_size * 2 && actual == block_size * 4
+    ));
+    assert!(output_buffer.iter().all(|sample| *sample == 0.0));
+
+    let mut output_buffer = vec![0.0_f32; block_size * 2];
+    assert!(matches!(
+        try_process_raw(&[], &mut output_buffer),
+        Err(ProcessError::InputBufferLength { expected, actual })
+            if expected == block_size && actual == 0
+    ));
+
+    let mut output_buffer = vec![0.0_f64; block_size * 2];
+    try_process_raw_double(&vec![0.0_f64; block_size], &mut output_buffer).unwrap();
+    assert!(output_buffer.iter().any(|sample| *sample != 0.0));
+
+    let mut output_buffer = vec![0_i16; block_size * 2];
+    try_process_raw_short(&vec![0_i16; block_size], &mut output_buffer).unwrap();
+    assert!(matches!(
+        try_process_raw_short(&vec![0_i16; block_size * 2], &mut output_buffer),
+        Err(ProcessError::InputBufferLength { .. })
+    ));
+}
@@ -0,0 +1,63 @@
+#![allow(clippy::restriction)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use libpd_rs::{
+    init_with_options, open_patch,
+    receive::{
+        on_float, on_midi_note_on, queued_overflow_count, receive_messages_from_pd,
+        receive_midi_messages_from_pd, start_listening_from,
+    },
+    send::{send_float_to, send_note_on},
+    types::InitOptions,
+};
+
+#[test]
+fn messages_which_do_not_fit_into_the_queue_are_counted() {
+    let options = InitOptions::default()
+        .with_message_queue_bytes(256)
+        .with_midi_queue_bytes(64);
+    init_with_options(options).unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+    start_listening_from("float_from_pd").unwrap();
+    receive_messages_from_pd();
+
+    let floats = Arc::new(AtomicUsize::new(0));
+    let received = floats.clone();
+    on_float(move |_: &str, _: f32| {
+        received.fetch_add(1, Ordering::SeqCst);
+    });
+    let dropped = queued_overflow_count();
+    for value in 0..100 {
+        send_float_to("float_from_rust", value as f32).unwrap();
+    }
+    receive_messages_from_pd();
+    let floats = floats.load(Ordering::SeqCst);
+    assert!(floats > 0);
+    assert!(floats < 100);
+    assert!(queued_overflow_count() - dropped >= 100 - floats);
+
+    // The queue is empty again after receiving.
+    let dropped = queued_overflow_count();
+    send_float_to("float_from_rust", 1.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(queued_overflow_count(), dropped);
+
+    let notes = Arc::new(AtomicUsize::new(0));
+    let received = notes.clone();
+    on_midi_note_on(move |_: i32, _: i32, _: i32| {
+        received.fetch_add(1, Ordering::SeqCst);
+    });
+    let dropped = queued_overflow_count();
+    for pitch in 0..100 {
+        send_note_on(0, pitch, 64).unwrap();
+    }
+    receive_midi_messages_from_pd();
+    let notes = notes.load(Ordering::SeqCst);
+    assert!(notes > 0);
+    assert!(notes < 100);
+    assert_eq!(queued_overflow_count() - dropped, 100 - notes);
+
+    patch.close().unwrap();
+}
@@ -0,0 +1,88 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{mpsc, Arc, Mutex};
+
+use libpd_rs::{
+    block_size, close_patch,
+    convenience::dsp_on,
+    init, initialize_audio, open_patch,
+    process::process_float,
+    receive::{on_sysex, receive_midi_messages_from_pd},
+    send::{send_midi_byte, send_sysex},
+};
+
+type ReceivedMessages = Arc<Mutex<Vec<(i32, Vec<u8>)>>>;
+
+#[test]
+fn receive_whole_sysex_messages() {
+    let sample_rate = 44100;
+    let output_channels = 2;
+
+    let sysex_messages_received: ReceivedMessages = Arc::new(Mutex::new(vec![]));
+
+    init().unwrap();
+    initialize_audio(0, output_channels, sample_rate).unwrap();
+    dsp_on().unwrap();
+
+    let patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+
+    let messages_to_fill = sysex_messages_received.clone();
+    on_sysex(move |port, message| {
+        messages_to_fill
+            .lock()
+            .unwrap()
+            .push((port, message.to_vec()));
+    });
+
+    let (tx, rx) = mpsc::channel::<()>();
+
+    let handle = std::thread::spawn(move || {
+        // Mimic audio callback buffers.
+        let input_buffer = [0.0f32; 512];
+        let mut output_buffer = [0.0f32; 1024];
+
+        // Run pd
+        loop {
+            // Mimic an audio callback.
+            let approximate_buffer_duration =
+                (output_buffer.len() as f32 / sample_rate as f32) * 1000.0;
+            std::thread::sleep(std::time::Duration::from_millis(
+                approximate_buffer_duration as u64,
+            ));
+
+            receive_midi_messages_from_pd();
+            let ticks = output_buffer.len() as i32 / (block_size() * output_channels);
+            process_float(ticks, &input_buffer, &mut output_buffer);
+            match rx.try_recv() {
+                Ok(_) => break,
+                _ => continue,
+            }
+        }
+    });
+
+    // Two messages on different ports which are interleaved with bytes which should not end up in them.
+    send_sysex(0, &[0xF0, 0x7D, 0x01]).unwrap();
+    send_sysex(1, &[0xF0, 0x7D, 0x02, 0xF7]).unwrap();
+    send_midi_byte(0, 0xF8).unwrap();
+    send_sysex(0, &[0x02, 0xF7]).unwrap();
+    // Bytes outside of a message and an interrupted message.
+    send_sysex(2, &[0x7F, 0xF7, 0xF0, 0x7D, 0x90, 0x3C]).unwrap();
+    send_sysex(2, &[0xF0, 0x7D, 0x03, 0x04, 0xF7]).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    // Stop pd.
+    tx.send(()).unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(
+        *sysex_messages_received.lock().unwrap(),
+        vec![
+            (1, vec![0xF0, 0x7D, 0x02, 0xF7]),
+            (0, vec![0xF0, 0x7D, 0x01, 0x02, 0xF7]),
+            (2, vec![0xF0, 0x7D, 0x03, 0x04, 0xF7]),
+        ]
+    );
+
+    close_patch(patch_handle).unwrap();
+}
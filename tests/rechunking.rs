@@ -0,0 +1,80 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    block_size, convenience::dsp_on, error::ProcessError, init, initialize_audio, open_patch,
+    process::Rechunker,
+};
+
+#[test]
+fn rechunker_renders_buffers_of_any_length() {
+    let _ = init();
+    initialize_audio(1, 2, 44100).unwrap();
+    dsp_on().unwrap();
+    let patch = open_patch("tests/patches/passthrough.pd").unwrap();
+
+    let mut rechunker = Rechunker::new().unwrap();
+    let latency = rechunker.latency();
+    assert_eq!(latency, block_size() as usize);
+
+    // A ramp which is fed in buffers that do not line up with the blocks.
+    let total = 1000;
+    let input: Vec<f32> = (1..=total).map(|frame| frame as f32 / 1024.0).collect();
+    let mut output = Vec::new();
+    let mut fed = 0;
+    for length in [100, 37, 1, 0, 64, 200, 63].iter().cycle() {
+        if fed == total {
+            break;
+        }
+        let length = (*length).min(total - fed);
+        let mut chunk = vec![1.0_f32; length * 2];
+        rechunker
+            .render(&input[fed..fed + length], &mut chunk)
+            .unwrap();
+        output.extend(chunk);
+        fed += length;
+    }
+
+    // The output is the input delayed by one block, on both channels.
+    for (frame, samples) in output.chunks(2).enumerate() {
+        let expected = if frame < latency {
+            0.0
+        } else {
+            input[frame - latency]
+        };
+        assert_eq!(samples, [expected, expected], "frame {frame}");
+    }
+
+    // The kept frames are dropped with a reset.
+    rechunker.reset();
+    let mut chunk = vec![1.0_f32; 20];
+    rechunker.render(&[1.0; 10], &mut chunk).unwrap();
+    assert!(chunk.iter().all(|sample| *sample == 0.0));
+
+    patch.close().unwrap();
+}
+
+#[test]
+fn rechunker_checks_the_buffers() {
+    let _ = init();
+    initialize_audio(1, 2, 44100).unwrap();
+    let mut rechunker = Rechunker::new().unwrap();
+
+    let mut output = vec![1.0_f32; 21];
+    assert!(matches!(
+        rechunker.render(&[0.0; 10], &mut output),
+        Err(ProcessError::OutputBufferLength {
+            expected: 22,
+            actual: 21
+        })
+    ));
+    assert!(output.iter().all(|sample| *sample == 0.0));
+
+    let mut output = vec![1.0_f32; 20];
+    assert!(matches!(
+        rechunker.render(&[0.0; 9], &mut output),
+        Err(ProcessError::InputBufferLength {
+            expected: 10,
+            actual: 9
+        })
+    ));
+}
@@ -0,0 +1,28 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    error::ProcessError, init, initialize_audio, process::Rechunker, reinitialize_audio,
+};
+
+#[test]
+fn rechunker_refuses_to_render_after_audio_is_reinitialized() {
+    init().unwrap();
+    initialize_audio(1, 2, 44100).unwrap();
+    let mut rechunker = Rechunker::new().unwrap();
+    let mut output = vec![0.0_f32; 128];
+    rechunker.render(&[0.0; 64], &mut output).unwrap();
+
+    // The blocks are made for two output channels, processing them for more would overflow.
+    reinitialize_audio(1, 8, 44100).unwrap();
+    let mut output = vec![1.0_f32; 128];
+    assert!(matches!(
+        rechunker.render(&[0.0; 64], &mut output),
+        Err(ProcessError::AudioConfigChanged)
+    ));
+    assert!(output.iter().all(|sample| *sample == 0.0));
+
+    // A rechunker which is made again fits the new configuration.
+    let mut rechunker = Rechunker::new().unwrap();
+    let mut output = vec![0.0_f32; 64 * 8];
+    rechunker.render(&[0.0; 64], &mut output).unwrap();
+}
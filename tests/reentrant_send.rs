@@ -0,0 +1,80 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    close_patch,
+    error::{SendError, SizeError},
+    init, open_patch,
+    receive::{on_float, on_list, receive_messages_from_pd, start_listening_from},
+    send::{
+        add_float_to_started_message, cancel_message, finish_message_as_list_and_send_to,
+        send_float_to, send_list_to, start_message,
+    },
+    types::Atom,
+};
+
+type Results = Arc<Mutex<Vec<(bool, bool, bool)>>>;
+
+#[test]
+fn reentrant_send() {
+    init().unwrap();
+
+    let patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+    let _float_receiver = start_listening_from("float_from_pd").unwrap();
+    let _list_receiver = start_listening_from("list_from_pd").unwrap();
+
+    let results: Results = Arc::new(Mutex::new(vec![]));
+    let results_to_fill = results.clone();
+    on_float(move |_, _| {
+        let started = start_message(1);
+        let finished = finish_message_as_list_and_send_to("list_from_rust");
+        let sent = send_list_to("list_from_rust", &[Atom::from(7.0)]);
+        results_to_fill.lock().unwrap().push((
+            matches!(started, Err(SizeError::ReentrantSend)),
+            matches!(finished, Err(SendError::ReentrantSend)),
+            sent.is_ok(),
+        ));
+    });
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_, list| {
+        lists_to_fill.lock().unwrap().push(list.to_vec());
+    });
+
+    // A message is in composition while the callback runs.
+    start_message(1).unwrap();
+    add_float_to_started_message(1.0);
+    send_float_to("float_from_rust", 0.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*results.lock().unwrap(), vec![(true, true, true)]);
+
+    // The message in composition is intact.
+    finish_message_as_list_and_send_to("list_from_rust").unwrap();
+    receive_messages_from_pd();
+    assert_eq!(
+        *lists.lock().unwrap(),
+        vec![vec![Atom::from(7.0)], vec![Atom::from(1.0)]]
+    );
+
+    // Without a message in composition callbacks could compose messages.
+    results.lock().unwrap().clear();
+    send_float_to("float_from_rust", 0.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*results.lock().unwrap(), vec![(false, false, true)]);
+
+    // An abandoned message keeps the callbacks from composing messages until it is cancelled.
+    results.lock().unwrap().clear();
+    start_message(1).unwrap();
+    send_float_to("float_from_rust", 0.0).unwrap();
+    receive_messages_from_pd();
+    cancel_message();
+    send_float_to("float_from_rust", 0.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(
+        *results.lock().unwrap(),
+        vec![(true, true, true), (false, false, true)]
+    );
+
+    close_patch(patch_handle).unwrap();
+}
@@ -0,0 +1,67 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use libpd_rs::{
+    audio_config,
+    convenience::{dsp_off, dsp_on},
+    error::AudioInitializationError,
+    init, initialize_audio,
+    process::process_float,
+    reinitialize_audio,
+    types::AudioConfig,
+};
+
+#[test]
+fn reinitialize_audio_while_processing() {
+    init().unwrap();
+    assert!(audio_config().is_none());
+    assert!(matches!(
+        reinitialize_audio(0, 2, 48000),
+        Err(AudioInitializationError::AudioNotInitialized)
+    ));
+
+    initialize_audio(0, 2, 44100).unwrap();
+    assert_eq!(
+        audio_config(),
+        Some(AudioConfig {
+            input_channels: 0,
+            output_channels: 2,
+            sample_rate: 44100
+        })
+    );
+    dsp_on().unwrap();
+
+    let running = Arc::new(AtomicBool::new(true));
+    let running_in_thread = Arc::clone(&running);
+    let audio_thread = std::thread::spawn(move || {
+        // Size the buffer for the largest configuration used in this test.
+        let mut output = [0.0_f32; 64 * 2];
+        while running_in_thread.load(Ordering::SeqCst) {
+            process_float(1, &[], &mut output);
+        }
+    });
+
+    dsp_off().unwrap();
+    for sample_rate in [48000, 96000, 44100] {
+        reinitialize_audio(0, 2, sample_rate).unwrap();
+        assert_eq!(audio_config().unwrap().sample_rate, sample_rate);
+    }
+    reinitialize_audio(0, 1, 48000).unwrap();
+    dsp_on().unwrap();
+
+    running.store(false, Ordering::SeqCst);
+    audio_thread.join().unwrap();
+
+    assert_eq!(
+        audio_config(),
+        Some(AudioConfig {
+            input_channels: 0,
+            output_channels: 1,
+            sample_rate: 48000
+        })
+    );
+}
@@ -0,0 +1,75 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use libpd_rs::{
+    block_size,
+    convenience::dsp_on,
+    error::ProcessError,
+    init, initialize_audio, open_patch,
+    process::render_offline,
+    receive::{on_bang, start_listening_from},
+    reinitialize_audio,
+};
+
+#[test]
+fn render_offline_advances_logical_time() {
+    init().unwrap();
+    assert!(matches!(
+        render_offline(Duration::from_secs(1), |_| {}),
+        Err(ProcessError::AudioNotInitialized)
+    ));
+
+    initialize_audio(0, 2, 44100).unwrap();
+    dsp_on().unwrap();
+
+    let bangs = Arc::new(Mutex::new(0));
+    let bangs_to_count = bangs.clone();
+    on_bang(move |source| {
+        if source == "metro_tick" {
+            *bangs_to_count.lock().unwrap() += 1;
+        }
+    });
+    let _receiver = start_listening_from("metro_tick").unwrap();
+    let _metro = open_patch("tests/patches/metro.pd").unwrap();
+    let _sine = open_patch("tests/patches/sine.pd").unwrap();
+
+    let mut rendered = Vec::new();
+    let mut chunks = 0;
+    let started = Instant::now();
+    let frames = render_offline(Duration::from_secs(10), |chunk| {
+        assert_eq!(chunk.len() % (block_size() as usize * 2), 0);
+        rendered.extend_from_slice(chunk);
+        chunks += 1;
+    })
+    .unwrap();
+
+    // 10 seconds rounded up to whole blocks.
+    assert_eq!(frames, 6891 * 64);
+    assert_eq!(rendered.len(), frames * 2);
+    assert!(chunks > 1);
+    assert!(rendered.iter().any(|sample| *sample != 0.0));
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    // The metro bangs every 100 ms of logical time, starting at 0.
+    let bangs = *bangs.lock().unwrap();
+    assert!((100..=101).contains(&bangs), "{bangs}");
+
+    assert_eq!(
+        render_offline(Duration::ZERO, |_| unreachable!()).unwrap(),
+        0
+    );
+
+    // Rendering stops instead of writing past the chunks when audio is reinitialized meanwhile.
+    let mut chunks = 0;
+    assert!(matches!(
+        render_offline(Duration::from_secs(1), |_| {
+            chunks += 1;
+            reinitialize_audio(0, 8, 44100).unwrap();
+        }),
+        Err(ProcessError::AudioConfigChanged)
+    ));
+    assert_eq!(chunks, 1);
+    reinitialize_audio(0, 2, 44100).unwrap();
+}
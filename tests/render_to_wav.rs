@@ -0,0 +1,119 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "wav")]
+
+use std::time::Duration;
+
+use libpd_rs::{
+    error::WavError,
+    init, initialize_audio, open_patches,
+    wav::{render_to_wav, render_to_wav_with_input},
+};
+
+fn write_wav(path: &std::path::Path, channels: u16, sample_rate: u32, samples: &[i16]) {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec).unwrap();
+    for sample in samples {
+        writer.write_sample(*sample).unwrap();
+    }
+    writer.finalize().unwrap();
+}
+
+#[test]
+fn render_to_wav_files() {
+    init().unwrap();
+    initialize_audio(1, 2, 44100).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    // One second is rounded up to whole blocks.
+    let output = dir.path().join("sine.wav");
+    let frames = render_to_wav("tests/patches/sine.pd", Duration::from_secs(1), &output).unwrap();
+    assert_eq!(frames, 690 * 64);
+    assert!(open_patches().is_empty());
+
+    let mut reader = hound::WavReader::open(&output).unwrap();
+    let spec = reader.spec();
+    assert_eq!(spec.channels, 2);
+    assert_eq!(spec.sample_rate, 44100);
+    assert_eq!(spec.bits_per_sample, 32);
+    assert_eq!(spec.sample_format, hound::SampleFormat::Float);
+    let samples = reader
+        .samples::<f32>()
+        .collect::<Result<Vec<f32>, _>>()
+        .unwrap();
+    assert_eq!(samples.len(), frames * 2);
+    assert!(samples.iter().any(|sample| *sample != 0.0));
+
+    // The input is fed from the file and is silent after it ends.
+    let input = dir.path().join("input.wav");
+    write_wav(&input, 1, 44100, &[16384; 1000]);
+    let output = dir.path().join("passthrough.wav");
+    let frames = render_to_wav_with_input(
+        "tests/patches/passthrough.pd",
+        &input,
+        Duration::from_millis(100),
+        &output,
+    )
+    .unwrap();
+    assert_eq!(frames, 69 * 64);
+    let samples = hound::WavReader::open(&output)
+        .unwrap()
+        .samples::<f32>()
+        .collect::<Result<Vec<f32>, _>>()
+        .unwrap();
+    assert_eq!(samples.len(), frames * 2);
+    assert!(samples[..2000].iter().all(|sample| *sample == 0.5));
+    assert!(samples[2000..].iter().all(|sample| *sample == 0.0));
+
+    // The input file needs to match the audio configuration.
+    let stereo = dir.path().join("stereo.wav");
+    write_wav(&stereo, 2, 44100, &[0; 2]);
+    assert!(matches!(
+        render_to_wav_with_input(
+            "tests/patches/passthrough.pd",
+            &stereo,
+            Duration::from_millis(10),
+            &output
+        ),
+        Err(WavError::InputChannelCount {
+            expected: 1,
+            actual: 2
+        })
+    ));
+    let fast = dir.path().join("fast.wav");
+    write_wav(&fast, 1, 48000, &[0; 1]);
+    assert!(matches!(
+        render_to_wav_with_input(
+            "tests/patches/passthrough.pd",
+            &fast,
+            Duration::from_millis(10),
+            &output
+        ),
+        Err(WavError::InputSampleRate {
+            expected: 44100,
+            actual: 48000
+        })
+    ));
+    assert!(matches!(
+        render_to_wav_with_input(
+            "tests/patches/passthrough.pd",
+            dir.path().join("missing.wav"),
+            Duration::from_millis(10),
+            &output
+        ),
+        Err(WavError::Wav { .. })
+    ));
+    assert!(matches!(
+        render_to_wav(
+            "tests/patches/missing.pd",
+            Duration::from_millis(10),
+            &output
+        ),
+        Err(WavError::Patch(_))
+    ));
+    assert!(open_patches().is_empty());
+}
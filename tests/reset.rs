@@ -0,0 +1,50 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    add_to_search_paths, close_patch, init, initialize_audio, is_initialized, open_patch,
+    receive::{on_float, receive_messages_from_pd, start_listening_from, stop_listening_from},
+    reset,
+    send::send_float_to,
+};
+
+#[test]
+fn reset_to_fresh_state() {
+    let floats: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![]));
+
+    init().unwrap();
+    initialize_audio(0, 2, 44100).unwrap();
+    add_to_search_paths("tests/patches").unwrap();
+
+    let floats_to_fill = Arc::clone(&floats);
+    on_float(move |_, value| {
+        floats_to_fill.lock().unwrap().push(value);
+    });
+    let patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+    let other_patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+    let receiver_handle = start_listening_from("float_from_pd").unwrap();
+
+    send_float_to("float_from_rust", 1.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(floats.lock().unwrap().len(), 2);
+
+    reset();
+    assert!(is_initialized());
+
+    // The closure is dropped.
+    assert_eq!(Arc::strong_count(&floats), 1);
+
+    // Nothing is there to receive from anymore.
+    assert!(send_float_to("float_from_rust", 1.0).is_err());
+
+    // Stale handles are ignored.
+    assert!(close_patch(patch_handle).is_err());
+    assert!(close_patch(other_patch_handle).is_err());
+    stop_listening_from(receiver_handle);
+
+    // Pd is still usable.
+    let patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+    assert!(send_float_to("float_from_rust", 1.0).is_ok());
+    close_patch(patch_handle).unwrap();
+}
@@ -0,0 +1,56 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "std")]
+
+use std::path::PathBuf;
+
+use libpd_rs::{
+    add_to_search_paths, array::array_size, clear_search_paths, error::IoError, init,
+    open_patch_from_str, search_paths,
+};
+
+#[test]
+fn search_paths_are_tracked() {
+    init().unwrap();
+    assert!(search_paths().is_empty());
+
+    let patches = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/patches");
+
+    // Relative paths are resolved against the working directory.
+    add_to_search_paths("tests/patches").unwrap();
+    assert_eq!(
+        search_paths(),
+        vec![std::env::current_dir().unwrap().join("tests/patches")]
+    );
+
+    // Duplicates are ignored.
+    add_to_search_paths("./tests/patches/").unwrap();
+    add_to_search_paths(std::env::current_dir().unwrap().join("tests/patches")).unwrap();
+    assert_eq!(search_paths().len(), 1);
+
+    add_to_search_paths(env!("CARGO_MANIFEST_DIR")).unwrap();
+    assert_eq!(search_paths().len(), 2);
+    assert_eq!(search_paths()[1], PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+
+    assert!(matches!(
+        add_to_search_paths("does not exist"),
+        Err(IoError::PathDoesNotExist(_))
+    ));
+    assert_eq!(search_paths().len(), 2);
+
+    // Abstractions are found through the search paths.
+    let uses_abstraction = "#N canvas 0 0 200 100 12;\n#X obj 20 20 array_sketch_pad;\n";
+    let patch = open_patch_from_str("uses_abstraction", uses_abstraction, None).unwrap();
+    assert_eq!(array_size("sketch_pad").unwrap(), 100);
+    drop(patch);
+
+    clear_search_paths();
+    assert!(search_paths().is_empty());
+
+    let patch = open_patch_from_str("uses_abstraction", uses_abstraction, None).unwrap();
+    assert!(array_size("sketch_pad").is_err());
+    drop(patch);
+
+    // Could be added again after clearing.
+    add_to_search_paths(&patches).unwrap();
+    assert_eq!(search_paths(), vec![patches]);
+}
@@ -0,0 +1,46 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    add_search_paths_from_env, init,
+    receive::{on_print, receive_messages_from_pd},
+    search_paths,
+};
+
+#[test]
+fn search_paths_from_env() {
+    let printed: Arc<Mutex<String>> = Arc::new(Mutex::new(String::new()));
+    let printed_to_fill = printed.clone();
+    on_print(move |line| {
+        printed_to_fill.lock().unwrap().push_str(line);
+    });
+    init().unwrap();
+
+    let home = tempfile::tempdir().unwrap();
+    let externals = home.path().join("Documents/Pd/externals");
+    std::fs::create_dir_all(&externals).unwrap();
+    std::env::set_var("HOME", home.path());
+    std::env::set_var("USERPROFILE", home.path());
+
+    let patches = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/patches");
+    let pd_path = std::env::join_paths([
+        patches.clone(),
+        patches.join("does_not_exist"),
+        patches.clone(),
+    ])
+    .unwrap();
+    std::env::set_var("PD_PATH", pd_path);
+
+    // User directories need to be opted in.
+    assert_eq!(add_search_paths_from_env(false), vec![patches.clone()]);
+    receive_messages_from_pd();
+    assert!(printed.lock().unwrap().contains("does_not_exist"));
+
+    // Already added paths are not returned again.
+    assert_eq!(add_search_paths_from_env(true), vec![externals.clone()]);
+    assert_eq!(search_paths(), vec![patches, externals]);
+
+    std::env::remove_var("PD_PATH");
+    assert!(add_search_paths_from_env(true).is_empty());
+}
@@ -89,10 +89,10 @@ fn send_and_receive_list() {
     send_list_to("list_from_rust", &list_to_send).unwrap();
 
     start_message(list_to_send.len() as i32).unwrap();
-    add_symbol_to_started_message("daisy");
+    add_symbol_to_started_message("daisy").unwrap();
     add_double_to_started_message(33.5_f64);
     add_double_to_started_message(42_f64);
-    add_symbol_to_started_message("bang");
+    add_symbol_to_started_message("bang").unwrap();
     add_double_to_started_message(12.0_f64);
     add_double_to_started_message(0.0_f64);
     finish_message_as_list_and_send_to("list_from_rust").unwrap();
@@ -8,7 +8,7 @@ use libpd_rs::{
     init, initialize_audio, open_patch,
     process::process_float,
     receive::{on_midi_pitch_bend, receive_midi_messages_from_pd},
-    send::send_pitch_bend,
+    send::{send_pitch_bend, send_pitch_bend_normalized},
 };
 
 #[test]
@@ -96,5 +96,16 @@ fn send_and_receive_pitch_bend() {
             assert_eq!(p1, p2);
         });
 
+    // Range of raw and normalized values.
+    assert!(send_pitch_bend(0, -8192).is_ok());
+    assert!(send_pitch_bend(0, 8191).is_ok());
+    assert!(send_pitch_bend(0, 8192).is_err());
+    assert!(send_pitch_bend(-1, 0).is_err());
+    assert!(send_pitch_bend_normalized(0, -1.0).is_ok());
+    assert!(send_pitch_bend_normalized(0, 1.0).is_ok());
+    assert!(send_pitch_bend_normalized(0, 2.0).is_ok());
+    assert!(send_pitch_bend_normalized(0, f32::NAN).is_err());
+    assert!(send_pitch_bend_normalized(-1, 0.0).is_err());
+
     close_patch(patch_handle).unwrap();
 }
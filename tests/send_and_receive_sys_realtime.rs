@@ -16,7 +16,7 @@ fn send_and_receive_sys_realtime() {
     let sample_rate = 44100;
     let output_channels = 2;
 
-    let sys_realtime_messages_received: Arc<Mutex<Vec<(i32, i32)>>> = Arc::new(Mutex::new(vec![]));
+    let sys_realtime_messages_received: Arc<Mutex<Vec<(i32, u8)>>> = Arc::new(Mutex::new(vec![]));
 
     init().unwrap();
     initialize_audio(0, output_channels, sample_rate).unwrap();
@@ -71,7 +71,7 @@ fn send_and_receive_sys_realtime() {
     tx.send(()).unwrap();
     handle.join().unwrap();
 
-    let vales_to_compare: Vec<(i32, i32)> =
+    let vales_to_compare: Vec<(i32, u8)> =
         vec![(0, 0x7F), (0, 0x8F), (0, 0x9F), (0, 0xAF), (0, 0xBF)];
 
     assert_eq!(sys_realtime_messages_received.lock().unwrap().len(), 5);
@@ -8,7 +8,7 @@ use libpd_rs::{
     init, initialize_audio, open_patch,
     process::process_float,
     receive::{on_midi_byte, receive_midi_messages_from_pd},
-    send::send_sysex,
+    send::{send_sysex, send_sysex_byte},
 };
 
 #[test]
@@ -16,7 +16,7 @@ fn send_and_receive_sysex() {
     let sample_rate = 44100;
     let output_channels = 2;
 
-    let sysex_messages_received: Arc<Mutex<Vec<(i32, i32)>>> = Arc::new(Mutex::new(vec![]));
+    let sysex_messages_received: Arc<Mutex<Vec<(i32, u8)>>> = Arc::new(Mutex::new(vec![]));
 
     init().unwrap();
     initialize_audio(0, output_channels, sample_rate).unwrap();
@@ -56,15 +56,18 @@ fn send_and_receive_sysex() {
     });
 
     let mut port: i32 = 0;
-    let mut byte: i32 = 0x7F;
+    let mut byte: u8 = 0x7F;
 
     #[allow(clippy::explicit_counter_loop)]
     // Send 5 note on messages in sequence.
     for _ in 0..5 {
-        send_sysex(port, byte).unwrap();
+        send_sysex_byte(port, byte).unwrap();
         port += 1;
         byte += 0x10;
     }
+    // Send a whole message.
+    send_sysex(5, &[0xF0, 0x7D, 0x01, 0xF7]).unwrap();
+    assert!(send_sysex(-1, &[0xF0, 0xF7]).is_err());
 
     std::thread::sleep(std::time::Duration::from_millis(50));
 
@@ -72,10 +75,19 @@ fn send_and_receive_sysex() {
     tx.send(()).unwrap();
     handle.join().unwrap();
 
-    let vales_to_compare: Vec<(i32, i32)> =
-        vec![(0, 0x7F), (1, 0x8F), (2, 0x9F), (3, 0xAF), (4, 0xBF)];
-
-    assert_eq!(sysex_messages_received.lock().unwrap().len(), 5);
+    let vales_to_compare: Vec<(i32, u8)> = vec![
+        (0, 0x7F),
+        (1, 0x8F),
+        (2, 0x9F),
+        (3, 0xAF),
+        (4, 0xBF),
+        (5, 0xF0),
+        (5, 0x7D),
+        (5, 0x01),
+        (5, 0xF7),
+    ];
+
+    assert_eq!(sysex_messages_received.lock().unwrap().len(), 9);
 
     vales_to_compare
         .iter()
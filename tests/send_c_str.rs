@@ -0,0 +1,50 @@
+#![allow(clippy::restriction)]
+
+use std::ffi::CString;
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    error::SendError,
+    init, open_patch,
+    receive::{on_bang, on_float, on_symbol, receive_messages_from_pd, start_listening_from},
+    send::{send_bang_to_c_str, send_double_to_c_str, send_float_to_c_str, send_symbol_to_c_str},
+};
+
+#[test]
+fn send_to_c_str_receivers() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let bangs = Arc::new(Mutex::new(0));
+    let bangs_to_count = bangs.clone();
+    on_bang(move |_source| *bangs_to_count.lock().unwrap() += 1);
+    let floats = Arc::new(Mutex::new(vec![]));
+    let floats_to_fill = floats.clone();
+    on_float(move |_source, value| floats_to_fill.lock().unwrap().push(value));
+    let symbols = Arc::new(Mutex::new(vec![]));
+    let symbols_to_fill = symbols.clone();
+    on_symbol(move |_source, value| symbols_to_fill.lock().unwrap().push(value.to_owned()));
+    let _bang = start_listening_from("bang_from_pd").unwrap();
+    let _float = start_listening_from("float_from_pd").unwrap();
+    let _symbol = start_listening_from("symbol_from_pd").unwrap();
+
+    let receiver = CString::new("float_from_rust").unwrap();
+    for value in 0..3 {
+        send_float_to_c_str(&receiver, value as f32).unwrap();
+    }
+    send_double_to_c_str(&receiver, 3.0).unwrap();
+    send_bang_to_c_str(c"bang_from_rust").unwrap();
+    send_symbol_to_c_str(c"symbol_from_rust", c"saw").unwrap();
+    receive_messages_from_pd();
+
+    assert_eq!(*floats.lock().unwrap(), vec![0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(*bangs.lock().unwrap(), 1);
+    assert_eq!(*symbols.lock().unwrap(), vec!["saw".to_owned()]);
+
+    match send_float_to_c_str(c"nobody_listens", 1.0) {
+        Err(SendError::MissingDestination(receiver)) => assert_eq!(receiver, "nobody_listens"),
+        other => panic!("{other:?}"),
+    }
+
+    patch.close().unwrap();
+}
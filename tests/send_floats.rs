@@ -0,0 +1,37 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    error::SendError,
+    init, open_patch,
+    receive::{on_float, on_list, receive_messages_from_pd, start_listening_from},
+    send::send_floats_to,
+};
+
+#[test]
+fn send_floats_sends_separate_messages() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let floats = Arc::new(Mutex::new(vec![]));
+    let floats_to_fill = floats.clone();
+    on_float(move |_source, value| floats_to_fill.lock().unwrap().push(value));
+    let lists = Arc::new(Mutex::new(0));
+    let lists_to_count = lists.clone();
+    on_list(move |_source, _list| *lists_to_count.lock().unwrap() += 1);
+    let _receiver = start_listening_from("float_from_pd").unwrap();
+
+    send_floats_to("float_from_rust", &[1.0, 2.0, 3.0]).unwrap();
+    send_floats_to("float_from_rust", &[]).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![1.0, 2.0, 3.0]);
+    assert_eq!(*lists.lock().unwrap(), 0);
+
+    match send_floats_to("nobody_listens", &[1.0, 2.0]) {
+        Err(SendError::MissingDestination(receiver)) => assert_eq!(receiver, "nobody_listens"),
+        other => panic!("{other:?}"),
+    }
+
+    patch.close().unwrap();
+}
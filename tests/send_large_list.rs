@@ -0,0 +1,65 @@
+#![allow(clippy::restriction)]
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    array::read_float_array_from,
+    error::SendError,
+    init, open_patch,
+    receive::{on_list, receive_messages_from_pd, start_listening_from},
+    send::{send_large_list_to, MAX_MESSAGE_LENGTH},
+    types::Atom,
+};
+
+#[test]
+fn large_lists_are_sent_in_chunks() {
+    init().unwrap();
+    let echo = open_patch("tests/patches/echo.pd").unwrap();
+    let patch = open_patch("tests/patches/large_list.pd").unwrap();
+
+    assert_eq!(MAX_MESSAGE_LENGTH, i32::MAX as usize);
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_source, list| lists_to_fill.lock().unwrap().push(list.to_vec()));
+    let _handle = start_listening_from("list_from_pd").unwrap();
+
+    // Every chunk is led by the index of its first atom, the last one could be shorter.
+    let list = (0..5).map(Atom::from).collect::<Vec<_>>();
+    send_large_list_to("list_from_rust", &list, NonZeroUsize::new(2).unwrap()).unwrap();
+    send_large_list_to("list_from_rust", &[], NonZeroUsize::new(2).unwrap()).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(
+        *lists.lock().unwrap(),
+        vec![
+            vec![Atom::from(0), Atom::from(0), Atom::from(1)],
+            vec![Atom::from(2), Atom::from(2), Atom::from(3)],
+            vec![Atom::from(4), Atom::from(4)],
+        ]
+    );
+
+    // The patch reassembles the chunks into an array.
+    let waveform = (0..100)
+        .map(|index| Atom::from(f64::from(index) / 100.0))
+        .collect::<Vec<_>>();
+    send_large_list_to(
+        "large_list_from_rust",
+        &waveform,
+        NonZeroUsize::new(7).unwrap(),
+    )
+    .unwrap();
+    let mut table = [0.0_f32; 100];
+    read_float_array_from("large_list", 0, 100, &mut table).unwrap();
+    for (index, value) in table.iter().enumerate() {
+        assert_eq!(*value, index as f32 / 100.0);
+    }
+
+    assert!(matches!(
+        send_large_list_to("nobody_listens", &waveform, NonZeroUsize::new(7).unwrap()),
+        Err(SendError::MissingDestination(_))
+    ));
+
+    patch.close().unwrap();
+    echo.close().unwrap();
+}
@@ -0,0 +1,64 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    error::SendError,
+    init, open_patch,
+    receive::{on_list, receive_messages_from_pd, start_listening_from},
+    send::{send_list_to, send_message_to},
+    types::Atom,
+};
+
+fn mixed_list(length: usize) -> Vec<Atom> {
+    (0..length)
+        .map(|index| {
+            if index % 2 == 0 {
+                Atom::from(index as f64 + 0.5)
+            } else {
+                Atom::from(format!("symbol_{index}"))
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn lists_of_any_length_are_sent() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_source, list| lists_to_fill.lock().unwrap().push(list.to_vec()));
+    let _handle = start_listening_from("list_from_pd").unwrap();
+
+    // Lists around the length which is converted on the stack and a symbol too long to be terminated there.
+    let long_symbol = "s".repeat(1000);
+    let sent = vec![
+        mixed_list(1),
+        mixed_list(63),
+        mixed_list(64),
+        mixed_list(65),
+        mixed_list(500),
+        vec![Atom::from(long_symbol.as_str()), Atom::from(1)],
+        vec![Atom::from("s".repeat(127)), Atom::from("s".repeat(128))],
+    ];
+    for list in &sent {
+        send_list_to("list_from_rust", list).unwrap();
+    }
+    receive_messages_from_pd();
+    assert_eq!(*lists.lock().unwrap(), sent);
+
+    // A null byte is found wherever it is in the list.
+    let mut invalid = mixed_list(100);
+    invalid[80] = Atom::from("a\0b");
+    match send_list_to("list_from_rust", &invalid) {
+        Err(SendError::InvalidString { input, .. }) => assert_eq!(input, "a\0b"),
+        other => panic!("{other:?}"),
+    }
+    invalid[80] = Atom::from(1);
+    invalid[10] = Atom::from("a\0b");
+    assert!(send_message_to("list_from_rust", "set", &invalid).is_err());
+
+    patch.close().unwrap();
+}
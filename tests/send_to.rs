@@ -0,0 +1,46 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    error::SendError,
+    init, open_patch,
+    receive::{on_list, receive_messages_from_pd, start_listening_from},
+    send::send_to,
+    types::Atom,
+};
+
+#[test]
+fn send_lists_of_atoms() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_source, list| lists_to_fill.lock().unwrap().push(list.to_vec()));
+    let _list = start_listening_from("list_from_pd").unwrap();
+
+    let list = vec![Atom::from(1), Atom::from("b")];
+    send_to("list_from_rust", [440.0.into(), "on".into()]).unwrap();
+    send_to("list_from_rust", &list).unwrap();
+    send_to(String::from("list_from_rust"), &list[..1]).unwrap();
+    send_to("list_from_rust", list.clone()).unwrap();
+    receive_messages_from_pd();
+
+    assert_eq!(
+        *lists.lock().unwrap(),
+        vec![
+            vec![Atom::from(440), Atom::from("on")],
+            list.clone(),
+            vec![Atom::from(1)],
+            list,
+        ]
+    );
+
+    assert!(matches!(
+        send_to("nobody_listens", [Atom::from(1)]),
+        Err(SendError::MissingDestination(_))
+    ));
+
+    patch.close().unwrap();
+}
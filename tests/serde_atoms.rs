@@ -0,0 +1,75 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "serde")]
+
+use libpd_rs::{sink::ReceivedMessage, types::Atom};
+
+fn messages() -> Vec<(String, ReceivedMessage)> {
+    vec![
+        ("bang_from_pd".to_owned(), ReceivedMessage::Bang),
+        ("float_from_pd".to_owned(), ReceivedMessage::Float(440.0)),
+        (
+            "symbol_from_pd".to_owned(),
+            ReceivedMessage::Symbol("sine".to_owned()),
+        ),
+        (
+            "list_from_pd".to_owned(),
+            ReceivedMessage::List(vec![Atom::from(1), Atom::from("a")]),
+        ),
+        (
+            "list_from_pd".to_owned(),
+            ReceivedMessage::Message {
+                selector: "set".to_owned(),
+                arguments: vec![Atom::from(2), Atom::from("b")],
+            },
+        ),
+    ]
+}
+
+#[test]
+fn atoms_are_json_numbers_and_strings() {
+    let atoms = vec![Atom::from(440), Atom::from(0.5), Atom::from("sine")];
+    let json = serde_json::to_string(&atoms).unwrap();
+    assert_eq!(json, r#"[440.0,0.5,"sine"]"#);
+    assert_eq!(serde_json::from_str::<Vec<Atom>>(&json).unwrap(), atoms);
+
+    // Integers are read as floats.
+    assert_eq!(
+        serde_json::from_str::<Vec<Atom>>(r#"[440, -1, "saw"]"#).unwrap(),
+        vec![Atom::from(440), Atom::from(-1), Atom::from("saw")]
+    );
+    assert!(serde_json::from_str::<Atom>("true").is_err());
+}
+
+#[test]
+fn received_messages_are_tagged_in_json() {
+    let json = serde_json::to_string(&messages()).unwrap();
+    assert_eq!(
+        json,
+        concat!(
+            r#"[["bang_from_pd","bang"],"#,
+            r#"["float_from_pd",{"float":440.0}],"#,
+            r#"["symbol_from_pd",{"symbol":"sine"}],"#,
+            r#"["list_from_pd",{"list":[1.0,"a"]}],"#,
+            r#"["list_from_pd",{"message":{"selector":"set","arguments":[2.0,"b"]}}]]"#
+        )
+    );
+    assert_eq!(
+        serde_json::from_str::<Vec<(String, ReceivedMessage)>>(&json).unwrap(),
+        messages()
+    );
+}
+
+#[test]
+fn atoms_and_received_messages_round_trip_through_bincode() {
+    let atoms = vec![Atom::from(440), Atom::from("sine"), Atom::from(f64::NAN)];
+    let bytes = bincode::serialize(&atoms).unwrap();
+    let read: Vec<Atom> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(read[..2], atoms[..2]);
+    assert!(read[2].as_float().unwrap().is_nan());
+
+    let bytes = bincode::serialize(&messages()).unwrap();
+    assert_eq!(
+        bincode::deserialize::<Vec<(String, ReceivedMessage)>>(&bytes).unwrap(),
+        messages()
+    );
+}
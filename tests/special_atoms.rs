@@ -0,0 +1,47 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    init, open_patch,
+    receive::{on_list, on_list_borrowed, receive_messages_from_pd, start_listening_from},
+    send::send_float_to,
+    types::{Atom, AtomView},
+};
+
+#[test]
+fn dollars_are_received_as_symbols() {
+    init().unwrap();
+    let patch = open_patch("tests/patches/special_atoms.pd").unwrap();
+    start_listening_from("special_from_pd").unwrap();
+    let expected = vec![
+        Atom::from("dollars"),
+        Atom::from("$1"),
+        Atom::from("$12"),
+        Atom::from("$100000"),
+        Atom::from("$1-foo"),
+    ];
+
+    let lists: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let lists_to_fill = lists.clone();
+    on_list(move |_: &str, list: &[Atom]| {
+        lists_to_fill.lock().unwrap().push(list.to_vec());
+    });
+    send_float_to("special_to_pd", 0.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*lists.lock().unwrap(), vec![expected.clone()]);
+
+    let views: Arc<Mutex<Vec<Vec<Atom>>>> = Arc::new(Mutex::new(vec![]));
+    let views_to_fill = views.clone();
+    on_list_borrowed(move |_: &str, list: &[AtomView]| {
+        views_to_fill
+            .lock()
+            .unwrap()
+            .push(list.iter().map(AtomView::to_atom).collect());
+    });
+    send_float_to("special_to_pd", 0.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*views.lock().unwrap(), vec![expected]);
+
+    patch.close().unwrap();
+}
@@ -0,0 +1,49 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    convenience::{dsp_off, dsp_on},
+    info::BLOCK_SIZE,
+    init, initialize_audio, open_patch,
+    receive::{start_listening_from, stop_listening_from},
+    status,
+    types::AudioConfig,
+};
+
+#[test]
+fn status_snapshot() {
+    let before = status();
+    assert!(!before.initialized);
+    assert!(!before.dsp_on);
+    assert_eq!(before.audio_config, None);
+    assert!(before.open_patches.is_empty());
+    assert!(before.to_string().contains("not initialized"));
+
+    init().unwrap();
+    initialize_audio(0, 2, 44100).unwrap();
+    dsp_on().unwrap();
+    let patch = open_patch("tests/patches/sine.pd").unwrap();
+    let handle = start_listening_from("status_source").unwrap();
+
+    let status = status();
+    assert!(status.initialized);
+    assert!(status.dsp_on);
+    assert_eq!(status.audio_config, Some(AudioConfig::new(0, 2, 44100)));
+    assert_eq!(status.block_size as usize, BLOCK_SIZE);
+    assert_eq!(status.version, libpd_rs::info::pd_version());
+    assert_eq!(status.open_patches.len(), 1);
+    assert_eq!(status.open_patches[0].dollar_zero, patch.dollar_zero());
+    assert_eq!(status.subscriptions, vec!["status_source".to_owned()]);
+
+    let text = status.to_string();
+    assert!(text.contains(", initialized, dsp on"));
+    assert!(text.contains("audio: 0 inputs, 2 outputs, 44100 Hz, block size 64"));
+    assert!(text.contains("sine.pd"));
+    assert!(text.contains(&format!("($0 {})", patch.dollar_zero())));
+    assert!(text.ends_with("subscriptions: status_source"));
+
+    dsp_off().unwrap();
+    assert!(!libpd_rs::status().dsp_on);
+    stop_listening_from(handle);
+    patch.close().unwrap();
+    assert!(libpd_rs::status().to_string().contains("patches: none"));
+}
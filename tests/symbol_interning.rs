@@ -0,0 +1,158 @@
+#![allow(clippy::restriction)]
+
+use std::collections::HashSet;
+
+use libpd_rs::{
+    error::SendError,
+    init, open_patch,
+    receive::receive_messages_from_pd,
+    send::{send_bang_to, send_float_to, send_list_to, send_message_to, send_symbol_to},
+    sink::{subscribe_with, ReceivedMessage, VecSink},
+    symbol::{cached_symbol_count, clear_symbol_cache, intern, SYMBOL_CACHE_CAPACITY},
+    types::Atom,
+};
+
+#[test]
+fn interned_symbols_are_sent_like_names() {
+    let _ = init();
+    let patch = open_patch("tests/patches/echo.pd").unwrap();
+
+    let frequency = intern("float_from_rust").unwrap();
+    assert_eq!(frequency.as_str(), "float_from_rust");
+    assert_eq!(frequency.as_c_str(), c"float_from_rust");
+    assert_eq!(frequency.to_string(), "float_from_rust");
+    assert_eq!(frequency, "float_from_rust");
+    // Pd interns a name once.
+    assert_eq!(frequency, intern(String::from("float_from_rust")).unwrap());
+    assert_ne!(frequency, intern("symbol_from_rust").unwrap());
+    let mut symbols = HashSet::new();
+    symbols.insert(frequency.clone());
+    symbols.insert(intern("float_from_rust").unwrap());
+    assert_eq!(symbols.len(), 1);
+    // Atoms keep the interned symbol but behave like symbols of the name.
+    let atom = Atom::from(&frequency);
+    assert!(matches!(&atom, Atom::Interned(symbol) if *symbol == frequency));
+    assert_eq!(atom, Atom::from("float_from_rust"));
+    assert_eq!(atom.as_symbol(), Some("float_from_rust"));
+    assert!(atom.is_symbol());
+    assert_eq!(atom.to_string(), "float_from_rust");
+    let atoms: HashSet<Atom> = [atom, Atom::from("float_from_rust")].into_iter().collect();
+    assert_eq!(atoms.len(), 1);
+
+    let floats = VecSink::new();
+    let _floats_handle = subscribe_with("float_from_pd", floats.clone()).unwrap();
+    let symbols = VecSink::new();
+    let _symbols_handle = subscribe_with("symbol_from_pd", symbols.clone()).unwrap();
+    let lists = VecSink::new();
+    let _lists_handle = subscribe_with("list_from_pd", lists.clone()).unwrap();
+    let bangs = VecSink::new();
+    let _bangs_handle = subscribe_with("bang_from_pd", bangs.clone()).unwrap();
+
+    send_float_to(&frequency, 440.0).unwrap();
+    send_float_to(frequency, 220.0).unwrap();
+    let saw = intern("saw").unwrap();
+    send_symbol_to(intern("symbol_from_rust").unwrap(), &saw).unwrap();
+    let list_receiver = intern("list_from_rust").unwrap();
+    send_list_to(&list_receiver, &[Atom::from(saw.clone()), Atom::from(1)]).unwrap();
+    send_message_to(&list_receiver, intern("set").unwrap(), &[Atom::from(2)]).unwrap();
+    // Interned symbols and names could be mixed.
+    send_message_to("list_from_rust", intern("set").unwrap(), &[Atom::from(3)]).unwrap();
+    send_bang_to(intern("bang_from_rust").unwrap()).unwrap();
+    receive_messages_from_pd();
+
+    assert_eq!(
+        floats.take(),
+        vec![
+            ("float_from_pd".to_owned(), ReceivedMessage::Float(440.0)),
+            ("float_from_pd".to_owned(), ReceivedMessage::Float(220.0)),
+        ]
+    );
+    assert_eq!(
+        symbols.take(),
+        vec![(
+            "symbol_from_pd".to_owned(),
+            ReceivedMessage::Symbol("saw".to_owned())
+        )]
+    );
+    assert_eq!(
+        lists.take(),
+        vec![
+            (
+                "list_from_pd".to_owned(),
+                ReceivedMessage::List(vec![Atom::from("saw"), Atom::from(1)])
+            ),
+            (
+                "list_from_pd".to_owned(),
+                ReceivedMessage::Message {
+                    selector: "set".to_owned(),
+                    arguments: vec![Atom::from(2)],
+                }
+            ),
+            (
+                "list_from_pd".to_owned(),
+                ReceivedMessage::Message {
+                    selector: "set".to_owned(),
+                    arguments: vec![Atom::from(3)],
+                }
+            ),
+        ]
+    );
+    assert_eq!(bangs.take().len(), 1);
+
+    // Nothing is bound to an interned symbol which no object receives from.
+    let nobody = intern("nobody_receives_this").unwrap();
+    assert!(matches!(
+        send_bang_to(&nobody),
+        Err(SendError::MissingDestination(name)) if name == "nobody_receives_this"
+    ));
+
+    patch.close().unwrap();
+}
+
+#[test]
+fn names_with_null_bytes_are_not_interned() {
+    let _ = init();
+
+    assert!(matches!(
+        intern("foo\0bar"),
+        Err(SendError::InvalidString { context: "symbol", input }) if input == "foo\0bar"
+    ));
+    // The error tells what the name is passed as.
+    assert!(matches!(
+        send_bang_to("foo\0bar"),
+        Err(SendError::InvalidString {
+            context: "receiver name",
+            ..
+        })
+    ));
+    assert!(matches!(
+        send_message_to("foo", "foo\0bar", &[]),
+        Err(SendError::InvalidString {
+            context: "message selector",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn the_symbol_cache_is_bounded() {
+    let _ = init();
+    // The other tests could send in parallel, so the counts here hold whatever they send.
+    clear_symbol_cache();
+    assert!(cached_symbol_count() < SYMBOL_CACHE_CAPACITY);
+
+    let first = intern("cached_0").unwrap();
+    for index in 0..SYMBOL_CACHE_CAPACITY * 2 {
+        // Names which are sent to are cached even if nothing receives from them.
+        assert!(send_bang_to(format!("cached_{index}")).is_err());
+        assert!(cached_symbol_count() <= SYMBOL_CACHE_CAPACITY);
+    }
+    assert_eq!(cached_symbol_count(), SYMBOL_CACHE_CAPACITY);
+
+    // An evicted name is interned to the same symbol again.
+    assert!(send_bang_to("cached_0").is_err());
+    assert_eq!(first, intern("cached_0").unwrap());
+
+    clear_symbol_cache();
+    assert!(cached_symbol_count() < SYMBOL_CACHE_CAPACITY);
+}
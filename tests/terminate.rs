@@ -0,0 +1,69 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    audio_config, close_patch, init, initialize_audio, is_initialized, open_patch,
+    receive::{on_float, on_print, receive_messages_from_pd, start_listening_from},
+    send::send_float_to,
+    terminate,
+};
+
+#[test]
+fn terminate_and_start_again() {
+    let floats: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![]));
+
+    let floats_to_fill = Arc::clone(&floats);
+    on_float(move |_, value| {
+        floats_to_fill.lock().unwrap().push(value);
+    });
+    let printed = Arc::new(Mutex::new(vec![]));
+    let printed_to_fill = Arc::clone(&printed);
+    on_print(move |message| {
+        printed_to_fill.lock().unwrap().push(message.to_owned());
+    });
+
+    init().unwrap();
+    initialize_audio(0, 2, 44100).unwrap();
+    let patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+    let _receiver_handle = start_listening_from("float_from_pd").unwrap();
+
+    send_float_to("float_from_rust", 1.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats.lock().unwrap(), vec![1.0]);
+
+    terminate();
+    assert!(!is_initialized());
+    assert!(audio_config().is_none());
+
+    // The closures are dropped.
+    assert_eq!(Arc::strong_count(&floats), 1);
+    assert_eq!(Arc::strong_count(&printed), 1);
+
+    // The patch is closed already so the stale handle is ignored.
+    assert!(close_patch(patch_handle).is_err());
+
+    // Starting again does not carry anything from before.
+    init().unwrap();
+    initialize_audio(0, 2, 44100).unwrap();
+    let patch_handle = open_patch("tests/patches/echo.pd").unwrap();
+
+    let floats_after: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![]));
+    let floats_to_fill = Arc::clone(&floats_after);
+    on_float(move |_, value| {
+        floats_to_fill.lock().unwrap().push(value);
+    });
+
+    // Nothing is listened to after terminating.
+    send_float_to("float_from_rust", 2.0).unwrap();
+    receive_messages_from_pd();
+    assert!(floats_after.lock().unwrap().is_empty());
+
+    let _receiver_handle = start_listening_from("float_from_pd").unwrap();
+    send_float_to("float_from_rust", 3.0).unwrap();
+    receive_messages_from_pd();
+    assert_eq!(*floats_after.lock().unwrap(), vec![3.0]);
+
+    close_patch(patch_handle).unwrap();
+    terminate();
+}
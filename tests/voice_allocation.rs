@@ -0,0 +1,100 @@
+#![allow(clippy::restriction)]
+
+use libpd_rs::{
+    error::SendError,
+    init,
+    receive::receive_messages_from_pd,
+    sink::{subscribe_with, ReceivedMessage, VecSink},
+    types::Atom,
+    voice::VoiceAllocator,
+};
+
+fn sent(lists: &VecSink) -> Vec<[u8; 3]> {
+    receive_messages_from_pd();
+    lists
+        .take()
+        .into_iter()
+        .map(|(_, message)| match message {
+            ReceivedMessage::List(list) => {
+                let values = list
+                    .iter()
+                    .map(|atom| match atom {
+                        Atom::Float(value) => *value as u8,
+                        _ => panic!("Unexpected atom {atom:?}"),
+                    })
+                    .collect::<Vec<_>>();
+                values.try_into().unwrap()
+            }
+            message => panic!("Unexpected message {message:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn voices_are_allocated_like_poly() {
+    let _ = init();
+    let lists = VecSink::new();
+    let _handle = subscribe_with("poly_voices", lists.clone()).unwrap();
+
+    let mut voices = VoiceAllocator::new("poly_voices", 3, true);
+    assert_eq!(voices.voice_count(), 3);
+    assert_eq!(voices.note_on(60, 100).unwrap(), Some(1));
+    assert_eq!(voices.note_on(64, 90).unwrap(), Some(2));
+    assert_eq!(voices.note_on(67, 80).unwrap(), Some(3));
+    assert_eq!(voices.active_voices(), 3);
+    assert_eq!(sent(&lists), vec![[1, 60, 100], [2, 64, 90], [3, 67, 80]]);
+
+    // The voice which plays for the longest time is stolen.
+    assert_eq!(voices.note_on(72, 70).unwrap(), Some(1));
+    assert_eq!(voices.stolen_count(), 1);
+    assert_eq!(sent(&lists), vec![[1, 60, 0], [1, 72, 70]]);
+
+    // The stolen note is released already.
+    assert_eq!(voices.note_off(60).unwrap(), None);
+    assert_eq!(voices.note_off(64).unwrap(), Some(2));
+    // A velocity of 0 is a note off.
+    assert_eq!(voices.note_on(67, 0).unwrap(), Some(3));
+    assert_eq!(voices.active_voices(), 1);
+    assert_eq!(sent(&lists), vec![[2, 64, 0], [3, 67, 0]]);
+
+    // The voice which is released for the longest time is taken.
+    assert_eq!(voices.note_on(62, 100).unwrap(), Some(2));
+    assert_eq!(voices.note_on(65, 100).unwrap(), Some(3));
+    voices.all_notes_off().unwrap();
+    assert_eq!(voices.active_voices(), 0);
+    assert_eq!(
+        sent(&lists),
+        vec![
+            [2, 62, 100],
+            [3, 65, 100],
+            [1, 72, 0],
+            [2, 62, 0],
+            [3, 65, 0]
+        ]
+    );
+}
+
+#[test]
+fn notes_are_dropped_without_stealing() {
+    let _ = init();
+    let lists = VecSink::new();
+    let _handle = subscribe_with("poly_voices_without_stealing", lists.clone()).unwrap();
+
+    // The voice count is at least 1.
+    let mut voices = VoiceAllocator::new("poly_voices_without_stealing", 0, false);
+    assert_eq!(voices.voice_count(), 1);
+    assert_eq!(voices.note_on(60, 100).unwrap(), Some(1));
+    assert_eq!(voices.note_on(64, 100).unwrap(), None);
+    assert_eq!(voices.note_off(64).unwrap(), None);
+    assert_eq!(voices.stolen_count(), 0);
+    assert_eq!(voices.note_off(60).unwrap(), Some(1));
+    assert_eq!(sent(&lists), vec![[1, 60, 100], [1, 60, 0]]);
+
+    // Nothing is allocated if nothing receives the notes.
+    let mut unbound = VoiceAllocator::new("nobody_receives_this", 1, true);
+    assert!(matches!(
+        unbound.note_on(60, 100),
+        Err(SendError::MissingDestination(_))
+    ));
+    assert_eq!(unbound.active_voices(), 0);
+}
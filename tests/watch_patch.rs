@@ -0,0 +1,64 @@
+#![allow(clippy::restriction)]
+#![cfg(feature = "watch")]
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use libpd_rs::{
+    error::WatchError,
+    init,
+    receive::source_to_listen_from_exists,
+    watch::{watch_patch, WatchOptions},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[test]
+fn watch_patch_reloads() {
+    init().unwrap();
+
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("echo.pd");
+    std::fs::write(&path, include_str!("patches/echo.pd")).unwrap();
+
+    let (reloads, reloaded) = mpsc::channel();
+    let options = WatchOptions::default().with_debounce(Duration::from_millis(50));
+    let handle = watch_patch(&path, options, move |result| {
+        reloads
+            .send(result.map(|patch| patch.dollar_zero()).map_err(|_| ()))
+            .unwrap();
+    })
+    .unwrap();
+    let dollar_zero = handle.patch().dollar_zero();
+    assert!(source_to_listen_from_exists("float_from_rust"));
+
+    // A burst of writes is reloaded once.
+    std::fs::write(&path, include_str!("patches/echo.pd")).unwrap();
+    std::fs::write(&path, include_str!("patches/simple.pd")).unwrap();
+    let reloaded_dollar_zero = reloaded.recv_timeout(TIMEOUT).unwrap().unwrap();
+    assert_ne!(reloaded_dollar_zero, dollar_zero);
+    assert_eq!(handle.patch().dollar_zero(), reloaded_dollar_zero);
+    assert!(!source_to_listen_from_exists("float_from_rust"));
+    assert!(reloaded.recv_timeout(Duration::from_millis(300)).is_err());
+
+    // Removing the file fails to reload and leaves the patch closed.
+    std::fs::remove_file(&path).unwrap();
+    assert!(reloaded.recv_timeout(TIMEOUT).unwrap().is_err());
+    assert!(!handle.patch().is_open());
+
+    // The next change opens it again.
+    std::fs::write(&path, include_str!("patches/echo.pd")).unwrap();
+    assert!(reloaded.recv_timeout(TIMEOUT).unwrap().is_ok());
+    assert!(source_to_listen_from_exists("float_from_rust"));
+
+    // Dropping the handle closes the patch.
+    drop(handle);
+    assert!(!source_to_listen_from_exists("float_from_rust"));
+
+    let result = watch_patch(
+        "tests/patches/not_existent.pd",
+        WatchOptions::default(),
+        |_| {},
+    );
+    assert!(matches!(result, Err(WatchError::Patch(_))));
+}
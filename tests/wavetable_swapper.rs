@@ -0,0 +1,65 @@
+#![allow(clippy::restriction)]
+
+use std::sync::{Arc, Mutex};
+
+use libpd_rs::{
+    array::{read_float_array_from, WavetableSwapper},
+    close_patch,
+    error::{ArrayError, SendError},
+    init, open_patch,
+    receive::{on_symbol, receive_messages_from_pd, start_listening_from},
+};
+
+#[test]
+fn wavetable_swapper() {
+    init().unwrap();
+
+    let patch_handle = open_patch("tests/patches/wavetable_swap.pd").unwrap();
+    let _receiver_handle = start_listening_from("wavetable_switched").unwrap();
+    let switches: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+    let switches_to_fill = switches.clone();
+    on_symbol(move |_, value| {
+        switches_to_fill.lock().unwrap().push(value.to_owned());
+    });
+
+    let mut swapper = WavetableSwapper::new("wavetable_a", "wavetable_b", "wavetable_switch");
+    assert_eq!(swapper.active_array(), "wavetable_a");
+    assert_eq!(swapper.inactive_array(), "wavetable_b");
+
+    let mut read = [0.0_f32; 8];
+    swapper.write(&[0.5; 8]).unwrap();
+    assert_eq!(swapper.active_array(), "wavetable_b");
+    read_float_array_from("wavetable_b", 0, 8, &mut read).unwrap();
+    assert_eq!(read, [0.5; 8]);
+    read_float_array_from("wavetable_a", 0, 8, &mut read).unwrap();
+    assert_eq!(read, [0.0; 8]);
+
+    swapper.write(&[0.25; 4]).unwrap();
+    assert_eq!(swapper.active_array(), "wavetable_a");
+    read_float_array_from("wavetable_a", 0, 8, &mut read).unwrap();
+    assert_eq!(read, [0.25, 0.25, 0.25, 0.25, 0.0, 0.0, 0.0, 0.0]);
+
+    receive_messages_from_pd();
+    assert_eq!(
+        *switches.lock().unwrap(),
+        vec!["wavetable_b", "wavetable_a"]
+    );
+
+    // Nothing is switched when the write fails.
+    let err = swapper.write(&[0.0; 16]).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ArrayError>(),
+        Some(ArrayError::OutOfBounds { .. })
+    ));
+    assert_eq!(swapper.active_array(), "wavetable_a");
+
+    let mut swapper = WavetableSwapper::new("wavetable_a", "wavetable_b", "no_switch");
+    let err = swapper.write(&[0.0; 8]).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<SendError>(),
+        Some(SendError::MissingDestination(_))
+    ));
+    assert_eq!(swapper.active_array(), "wavetable_a");
+
+    close_patch(patch_handle).unwrap();
+}